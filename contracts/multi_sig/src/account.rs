@@ -0,0 +1,53 @@
+use soroban_sdk::auth::Context;
+use soroban_sdk::crypto::Hash;
+use soroban_sdk::{panic_with_error, symbol_short, BytesN, Env, Vec};
+
+use crate::errors::{GovernanceError, MultisigError};
+use crate::signers;
+use crate::storage;
+use crate::validation;
+
+// `__check_auth` lets the contract stand in as an M-of-N account for any
+// `Address::require_auth` on its address. The auth framework already binds the
+// payload to a nonce and an expiry ledger of its own, so these signatures are
+// taken as given and leave the per-signer nonces alone.
+//
+// None of the treasury controls can apply to a bare authorization: there is
+// no proposer to cap, no window for a guardian to veto in. So the account
+// never authorizes moving funds it holds, which stays with proposals, and
+// authorizes nothing at all while payments are paused.
+
+/// Accepts `signatures` over `payload` when they come from at least threshold
+/// distinct current signers and no call in `contexts` moves the contract's
+/// own funds. A signature that does not verify aborts the check in the host.
+pub fn check_auth(env: &Env, payload: &Hash<32>, signatures: &Vec<(BytesN<32>, BytesN<64>)>, contexts: &Vec<Context>) -> Result<(), MultisigError> {
+    validation::require_active(env);
+    validation::require_not_paused(env);
+    for context in contexts.iter() {
+        if let Context::Contract(call) = context {
+            if [symbol_short!("transfer"), symbol_short!("approve"), symbol_short!("burn")].contains(&call.fn_name) {
+                panic_with_error!(env, GovernanceError::AccountCallForbidden);
+            }
+        }
+    }
+
+    let payload = payload.to_bytes().into();
+    let mut signers = Vec::new(env);
+    for (signer, signature) in signatures.iter() {
+        if signers.contains(&signer) {
+            return Err(MultisigError::DuplicateSigner);
+        }
+        if !storage::is_signer(env, &signer) {
+            return Err(MultisigError::UnknownSigner);
+        }
+        signers::check_not_suspended(env, &signer)?;
+        signers::check_can_approve(env, &signer)?;
+        env.crypto().ed25519_verify(&signer, &payload, &signature);
+        signers.push_back(signer);
+    }
+
+    if signers.len() < storage::read_threshold(env) {
+        return Err(MultisigError::InsufficientApprovals);
+    }
+    Ok(())
+}
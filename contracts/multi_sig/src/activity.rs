@@ -0,0 +1,75 @@
+use soroban_sdk::{panic_with_error, BytesN, Env};
+
+use crate::clock;
+use crate::errors::MultisigError;
+use crate::history;
+use crate::storage;
+use crate::types::InactivityRemoval;
+
+// Every proposal and approval a signer makes is timestamped against their
+// key. Governance can use that to lower the bar for removing a signer who
+// has stopped taking part: a removal of someone inactive for longer than
+// `inactivity_seconds` needs only the configured threshold. Inactivity is
+// judged when the removal executes, so a signer who acts again while the vote
+// is open is back under the ordinary threshold.
+//
+// A signer with no record counts as active from when they joined, or from
+// when the rule was turned on if that is later, so no one can be caught out
+// by activity that predates tracking.
+
+/// Records that `signer` proposed or approved something now.
+pub fn record(env: &Env, signer: &BytesN<32>) {
+    storage::write_signer_last_active(env, signer, Some(clock::now(env)));
+}
+
+/// When `signer` last proposed or approved anything.
+pub fn last_active(env: &Env, signer: &BytesN<32>) -> u64 {
+    storage::read_signer_last_active(env, signer).unwrap_or_else(|| history::joined_at(env, signer))
+}
+
+/// Checks `config` for `SetInactivityRemoval`. Zero `inactivity_seconds` turns
+/// the rule off; otherwise its threshold must be at least one and no higher
+/// than the threshold it relaxes.
+pub fn validate(env: &Env, config: &InactivityRemoval) {
+    if config.inactivity_seconds == 0 {
+        return;
+    }
+    if config.threshold == 0 || config.threshold > storage::read_threshold(env) {
+        panic_with_error!(env, MultisigError::InvalidThreshold);
+    }
+}
+
+/// Applies `config`, stamping signers who have no activity on record as
+/// active now.
+pub fn configure(env: &Env, config: &InactivityRemoval) {
+    if config.inactivity_seconds == 0 {
+        storage::write_inactivity_removal(env, None);
+        return;
+    }
+    for signer in storage::read_signer_list(env).iter() {
+        if storage::read_signer_last_active(env, &signer).is_none() {
+            record(env, &signer);
+        }
+    }
+    storage::write_inactivity_removal(env, Some(config));
+}
+
+/// The reduced threshold for removing `signer`, if the rule is on and they
+/// have been inactive for longer than it allows.
+pub fn removal_threshold(env: &Env, signer: &BytesN<32>) -> Option<u32> {
+    let config = storage::read_inactivity_removal(env)?;
+    let inactive_for = clock::now(env).saturating_sub(last_active(env, signer));
+    (inactive_for > config.inactivity_seconds).then_some(config.threshold)
+}
+
+/// Moves `old_key`'s activity record to `new_key`. A rotated seat is the same
+/// member.
+pub fn transfer(env: &Env, old_key: &BytesN<32>, new_key: &BytesN<32>) {
+    let last_active = storage::read_signer_last_active(env, old_key);
+    storage::write_signer_last_active(env, old_key, None);
+    storage::write_signer_last_active(env, new_key, last_active);
+}
+
+pub fn clear(env: &Env, signer: &BytesN<32>) {
+    storage::write_signer_last_active(env, signer, None);
+}
@@ -0,0 +1,78 @@
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
+
+use crate::errors::{GovernanceError, MultisigError};
+use crate::features;
+use crate::storage;
+use crate::types::{AddressBookEntry, AddressLabel};
+
+/// Most entries the address book may hold at once.
+pub const MAX_ENTRIES: u32 = 50;
+
+/// Rejects a new entry whose label or address is already listed, or that would
+/// overflow the book.
+pub fn validate_new_entry(env: &Env, label: &AddressLabel) {
+    let ids = storage::read_address_book_ids(env);
+    if ids.len() >= MAX_ENTRIES {
+        panic_with_error!(env, GovernanceError::AddressBookFull);
+    }
+    if storage::read_address_book_id(env, &label.address).is_some() {
+        panic_with_error!(env, MultisigError::InvalidProposal);
+    }
+    for entry in entries(env).iter() {
+        if entry.label == label.label {
+            panic_with_error!(env, MultisigError::InvalidProposal);
+        }
+    }
+}
+
+pub fn add(env: &Env, label: &AddressLabel) {
+    // Checked again because other entries may have been added meanwhile
+    validate_new_entry(env, label);
+
+    let id = storage::read_address_book_next_id(env) + 1;
+    let entry = AddressBookEntry {
+        id,
+        label: label.label.clone(),
+        address: label.address.clone(),
+    };
+    storage::write_address_book_entry(env, &entry);
+    storage::write_address_book_next_id(env, id);
+
+    let mut ids = storage::read_address_book_ids(env);
+    ids.push_back(id);
+    storage::write_address_book_ids(env, &ids);
+}
+
+pub fn remove(env: &Env, entry_id: u32) {
+    let entry = load(env, entry_id);
+    storage::remove_address_book_entry(env, &entry);
+
+    let mut ids = storage::read_address_book_ids(env);
+    if let Some(index) = ids.first_index_of(entry_id) {
+        ids.remove(index);
+    }
+    storage::write_address_book_ids(env, &ids);
+}
+
+pub fn load(env: &Env, entry_id: u32) -> AddressBookEntry {
+    let Some(entry) = storage::read_address_book_entry(env, entry_id) else {
+        panic_with_error!(env, GovernanceError::AddressBookEntryNotFound);
+    };
+    entry
+}
+
+pub fn entries(env: &Env) -> Vec<AddressBookEntry> {
+    let mut entries = Vec::new(env);
+    for id in storage::read_address_book_ids(env).iter() {
+        entries.push_back(storage::read_address_book_entry(env, id).unwrap());
+    }
+    entries
+}
+
+/// Under `ADDRESS_BOOK_ONLY`, funds may only go to listed addresses.
+pub fn check_listed(env: &Env, address: &Address) -> Result<(), MultisigError> {
+    if features::is_enabled(env, &features::ADDRESS_BOOK_ONLY) && storage::read_address_book_id(env, address).is_none() {
+        return Err(MultisigError::RecipientNotInAddressBook);
+    }
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use soroban_sdk::{symbol_short, Env, Symbol, Vec};
+
+use crate::clock;
+use crate::pagination;
+use crate::payments;
+use crate::storage;
+use crate::types::{AuditEntry, ChangesPage};
+
+pub const CREATED: Symbol = symbol_short!("created");
+pub const APPROVED: Symbol = symbol_short!("approved");
+pub const REVOKED: Symbol = symbol_short!("revoked");
+pub const DELEGATED: Symbol = symbol_short!("delegated");
+pub const EXECUTED: Symbol = symbol_short!("executed");
+pub const EXPIRED: Symbol = symbol_short!("expired");
+pub const REJECTED: Symbol = symbol_short!("rejected");
+pub const CANCELED: Symbol = symbol_short!("canceled");
+pub const RESTORED: Symbol = symbol_short!("restored");
+pub const IMPORTED: Symbol = symbol_short!("imported");
+pub const VETOED: Symbol = symbol_short!("vetoed");
+
+// Every change to a payment proposal's state appends one entry here, so
+// clients can follow the contract by sequence number instead of re-reading
+// every proposal.
+
+pub fn record(env: &Env, proposal_id: u64, action: Symbol) {
+    let seq = storage::read_audit_count(env) + 1;
+    let entry = AuditEntry {
+        seq,
+        proposal_id,
+        action,
+        timestamp: clock::now(env),
+    };
+    storage::write_audit_entry(env, &entry);
+    storage::write_audit_count(env, seq);
+}
+
+/// Up to `limit` entries from `from_seq` on, with the current state of every
+/// proposal they name, each read once.
+pub fn changes_since(env: &Env, from_seq: u64, limit: u32) -> ChangesPage {
+    let window = pagination::id_window(env, from_seq, limit, storage::read_audit_count(env));
+
+    let mut entries = Vec::new(env);
+    let mut proposal_ids = Vec::new(env);
+    for seq in window.start..window.end {
+        let entry = storage::read_audit_entry(env, seq).unwrap();
+        if !proposal_ids.contains(entry.proposal_id) {
+            proposal_ids.push_back(entry.proposal_id);
+        }
+        entries.push_back(entry);
+    }
+
+    let mut proposals = Vec::new(env);
+    for proposal_id in proposal_ids.iter() {
+        proposals.push_back(payments::proposal_state(env, proposal_id));
+    }
+
+    ChangesPage {
+        entries,
+        proposals,
+        next_seq: window.end,
+        has_more: window.has_more,
+    }
+}
@@ -0,0 +1,101 @@
+use soroban_sdk::crypto::bls12_381::{G1Affine, G2Affine};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{bytesn, panic_with_error, symbol_short, vec, Bytes, BytesN, Env, Vec};
+
+use crate::errors::{MultisigError, SignerError};
+use crate::storage;
+
+/// Domain separation tag for aggregate execution signatures.
+pub const SIGNATURE_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Domain separation tag for the proof of possession given at registration.
+pub const POP_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+// Public keys live in G1 and signatures in G2. Aggregating public keys is only
+// safe against rogue keys, crafted to cancel out the others in a sum, when
+// every key has proven possession of its secret, so registration requires a
+// signature by the key over itself.
+
+/// What a signer signs with their ed25519 key to register `key`.
+pub fn registration_digest(env: &Env, key: &BytesN<96>) -> BytesN<32> {
+    env.crypto().sha256(&(symbol_short!("bls_key"), key.clone()).to_xdr(env)).into()
+}
+
+/// Registers `key` for `signer`, replacing any earlier one. Signers keep
+/// their bitmap position for as long as they stay registered.
+pub fn register(env: &Env, signer: &BytesN<32>, key: &BytesN<96>, proof: &BytesN<192>) {
+    let message = Bytes::from(key.clone());
+    if !verify(env, G1Affine::from_bytes(key.clone()), &message, POP_DST, proof) {
+        panic_with_error!(env, SignerError::InvalidBlsSignature);
+    }
+
+    storage::write_bls_key(env, signer, Some(key));
+    let mut signers = storage::read_bls_signers(env);
+    if !signers.contains(signer) {
+        signers.push_back(signer.clone());
+        storage::write_bls_signers(env, &signers);
+    }
+}
+
+/// Drops `signer`'s key. Signers registered after them move down one bit.
+pub fn unregister(env: &Env, signer: &BytesN<32>) {
+    let mut signers = storage::read_bls_signers(env);
+    if let Some(position) = signers.first_index_of(signer) {
+        signers.remove(position);
+        storage::write_bls_signers(env, &signers);
+        storage::write_bls_key(env, signer, None);
+    }
+}
+
+/// Signers selected by `bitmap`, where bit `i` (bit `i % 8` of byte `i / 8`)
+/// stands for the `i`th registered BLS signer. Selecting a position nobody is
+/// registered at fails with `BlsKeyNotRegistered`.
+pub fn selected_signers(env: &Env, bitmap: &Bytes) -> Vec<BytesN<32>> {
+    let registered = storage::read_bls_signers(env);
+    let mut selected = Vec::new(env);
+    for (byte_index, byte) in bitmap.iter().enumerate() {
+        for bit in 0..8u32 {
+            if byte & (1 << bit) == 0 {
+                continue;
+            }
+            let Some(signer) = registered.get(byte_index as u32 * 8 + bit) else {
+                panic_with_error!(env, SignerError::BlsKeyNotRegistered);
+            };
+            selected.push_back(signer);
+        }
+    }
+    selected
+}
+
+/// Checks `signature` as the aggregate of `signers`' signatures over
+/// `message`, failing with `InvalidBlsSignature` if it is not.
+pub fn verify_aggregate(env: &Env, signers: &Vec<BytesN<32>>, message: &Bytes, signature: &BytesN<192>) {
+    let bls = env.crypto().bls12_381();
+    let mut aggregate: Option<G1Affine> = None;
+    for signer in signers.iter() {
+        let key = G1Affine::from_bytes(storage::read_bls_key(env, &signer).unwrap());
+        aggregate = Some(match aggregate {
+            Some(sum) => bls.g1_add(&sum, &key),
+            None => key,
+        });
+    }
+
+    let Some(aggregate) = aggregate else {
+        panic_with_error!(env, MultisigError::InsufficientApprovals);
+    };
+    if !verify(env, aggregate, message, SIGNATURE_DST, signature) {
+        panic_with_error!(env, SignerError::InvalidBlsSignature);
+    }
+}
+
+// e(key, H(message)) == e(g1, signature), checked as a product of pairings
+// that must come out to the identity.
+fn verify(env: &Env, key: G1Affine, message: &Bytes, dst: &[u8], signature: &BytesN<192>) -> bool {
+    let bls = env.crypto().bls12_381();
+    let negated_generator = G1Affine::from_bytes(bytesn!(
+        env,
+        0x17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb114d1d6855d545a8aa7d76c8cf2e21f267816aef1db507c96655b9d5caac42364e6f38ba0ecb751bad54dcd6b939c2ca
+    ));
+    let hashed = bls.hash_to_g2(message, &Bytes::from_slice(env, dst));
+    bls.pairing_check(vec![env, key, negated_generator], vec![env, hashed, G2Affine::from_bytes(signature.clone())])
+}
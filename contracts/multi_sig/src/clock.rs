@@ -0,0 +1,10 @@
+use soroban_sdk::Env;
+
+/// The time the contract acts on: the ledger timestamp, moved forward by the
+/// staging offset in builds with the `testing-hooks` feature.
+pub fn now(env: &Env) -> u64 {
+    let timestamp = env.ledger().timestamp();
+    #[cfg(feature = "testing-hooks")]
+    let timestamp = timestamp.saturating_add(crate::hooks::timestamp_offset(env));
+    timestamp
+}
@@ -0,0 +1,69 @@
+use soroban_sdk::Env;
+
+use crate::pagination;
+use crate::schema;
+use crate::storage;
+
+// Proposal ids are shared by every family, so the highest id says nothing
+// about how many proposals of one family there are. Payment proposals and
+// signer changes are counted on their own as they are created. Deployments
+// from before the counts have none until `backfill` has walked their ids, and
+// until then nothing is counted live, so no proposal is counted twice.
+
+/// Starts both counts at zero. Called on initialization.
+pub fn open(env: &Env) {
+    storage::write_payment_proposal_count(env, 0);
+    storage::write_signer_change_count(env, 0);
+}
+
+pub fn record_payment(env: &Env) {
+    if let Some(count) = storage::read_payment_proposal_count(env) {
+        storage::write_payment_proposal_count(env, count + 1);
+    }
+}
+
+pub fn record_signer_change(env: &Env) {
+    if let Some(count) = storage::read_signer_change_count(env) {
+        storage::write_signer_change_count(env, count + 1);
+    }
+}
+
+pub fn payments(env: &Env) -> u64 {
+    storage::read_payment_proposal_count(env).unwrap_or(0)
+}
+
+pub fn signer_changes(env: &Env) -> u64 {
+    storage::read_signer_change_count(env).unwrap_or(0)
+}
+
+/// Counts the payment proposals and signer changes among the next `limit`
+/// ids of a deployment that predates the counts, picking up where the last
+/// call stopped. Returns the next id to visit; once it passes the last id the
+/// counts are written and kept live from then on.
+pub fn backfill(env: &Env, limit: u32) -> u64 {
+    schema::require_migratable(env);
+    let last_id = storage::read_proposal_count(env);
+    if storage::read_signer_change_count(env).is_some() {
+        pagination::check_limit(env, limit);
+        return last_id + 1;
+    }
+
+    let (cursor, mut payments, mut signer_changes) = storage::read_count_backfill(env).unwrap_or((1, 0, 0));
+    let window = pagination::id_window(env, cursor, limit, last_id);
+    for id in window.start..window.end {
+        if storage::has_signer_change(env, id) {
+            signer_changes += 1;
+        } else if storage::read_proposal_fields(env, id).is_some() {
+            payments += 1;
+        }
+    }
+
+    if window.has_more {
+        storage::write_count_backfill(env, Some((window.end, payments, signer_changes)));
+    } else {
+        storage::write_count_backfill(env, None);
+        storage::write_payment_proposal_count(env, payments);
+        storage::write_signer_change_count(env, signer_changes);
+    }
+    window.end
+}
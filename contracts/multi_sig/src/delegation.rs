@@ -0,0 +1,103 @@
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{panic_with_error, symbol_short, BytesN, Env};
+
+use crate::clock;
+use crate::errors::MultisigError;
+use crate::signatures;
+use crate::storage;
+use crate::types::Delegation;
+use crate::validation;
+
+// A standing delegation hands a signer's approvals to another key for a while,
+// unlike `delegate_vote`, which covers a single proposal. Approvals made with
+// the delegate's key are stored as the delegator's, and while the delegation
+// lasts the two of them share a single approval on any proposal, so a
+// delegate who is a signer too cannot add their own on top.
+
+/// What `signer` must sign to delegate to `delegate` until `expires_at` next.
+pub fn get_delegation_payload(env: &Env, signer: &BytesN<32>, delegate: &BytesN<32>, expires_at: u64) -> BytesN<32> {
+    validation::require_initialized(env);
+    signatures::next_payload(env, signer, &delegation_digest(env, delegate, expires_at))
+}
+
+/// What `signer` must sign to revoke their delegation next.
+pub fn get_revocation_payload(env: &Env, signer: &BytesN<32>) -> BytesN<32> {
+    validation::require_initialized(env);
+    signatures::next_payload(env, signer, &revocation_digest(env, signer))
+}
+
+fn delegation_digest(env: &Env, delegate: &BytesN<32>, expires_at: u64) -> BytesN<32> {
+    env.crypto().sha256(&(symbol_short!("delegate"), delegate.clone(), expires_at).to_xdr(env)).into()
+}
+
+fn revocation_digest(env: &Env, signer: &BytesN<32>) -> BytesN<32> {
+    env.crypto().sha256(&(symbol_short!("undeleg"), signer.clone()).to_xdr(env)).into()
+}
+
+/// Delegates `signer`'s approvals to `delegate` until `expires_at`, replacing
+/// any delegation they had. `signature` is `signer`'s over the delegation
+/// digest at their nonce `nonce`.
+pub fn delegate(env: &Env, signer: BytesN<32>, delegate: BytesN<32>, expires_at: u64, nonce: u64, signature: BytesN<64>) {
+    validation::require_active(env);
+
+    validation::require_signer(env, &signer);
+    if signer == delegate {
+        panic_with_error!(env, MultisigError::InvalidDelegation);
+    }
+    let now = clock::now(env);
+    if expires_at <= now || expires_at - now > validation::MAX_EXPIRY_SECONDS {
+        panic_with_error!(env, MultisigError::InvalidExpiryTime);
+    }
+    // One delegator per delegate, so that an approval with their key is never
+    // ambiguous
+    if acting_for(env, &delegate).is_some_and(|delegator| delegator != signer) {
+        panic_with_error!(env, MultisigError::InvalidDelegation);
+    }
+    signatures::consume(env, &signer, nonce, &delegation_digest(env, &delegate, expires_at), &signature);
+
+    clear(env, &signer);
+    let delegation = Delegation {
+        delegate: delegate.clone(),
+        delegated_at: now,
+        expires_at,
+    };
+    storage::write_delegation(env, &signer, Some(&delegation));
+    storage::write_delegator_of(env, &delegate, Some(&signer));
+}
+
+/// Ends `signer`'s delegation early. `signature` is `signer`'s over the
+/// revocation digest at their nonce `nonce`.
+pub fn revoke(env: &Env, signer: BytesN<32>, nonce: u64, signature: BytesN<64>) {
+    validation::require_active(env);
+
+    validation::require_signer(env, &signer);
+    if storage::read_delegation(env, &signer).is_none() {
+        panic_with_error!(env, MultisigError::InvalidDelegation);
+    }
+    signatures::consume(env, &signer, nonce, &revocation_digest(env, &signer), &signature);
+    clear(env, &signer);
+}
+
+/// Drops whatever delegation `signer` has, expired or not. Called when they
+/// stop being a signer.
+pub fn clear(env: &Env, signer: &BytesN<32>) {
+    let Some(delegation) = storage::read_delegation(env, signer) else {
+        return;
+    };
+    storage::write_delegation(env, signer, None);
+    if storage::read_delegator_of(env, &delegation.delegate).as_ref() == Some(signer) {
+        storage::write_delegator_of(env, &delegation.delegate, None);
+    }
+}
+
+/// `signer`'s delegation, if it has not expired and they are still a signer.
+pub fn active(env: &Env, signer: &BytesN<32>) -> Option<Delegation> {
+    let delegation = storage::read_delegation(env, signer)?;
+    (clock::now(env) <= delegation.expires_at && storage::is_signer(env, signer)).then_some(delegation)
+}
+
+/// The signer `key` currently approves for, if it holds an active delegation.
+pub fn acting_for(env: &Env, key: &BytesN<32>) -> Option<BytesN<32>> {
+    let delegator = storage::read_delegator_of(env, key)?;
+    active(env, &delegator).is_some_and(|delegation| delegation.delegate == *key).then_some(delegator)
+}
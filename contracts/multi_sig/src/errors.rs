@@ -1,5 +1,9 @@
 use soroban_sdk::contracterror;
 
+// A contract error enum holds at most 50 cases, so errors are split by area.
+// Codes are unique across all three enums and never reused.
+
+/// Setup, payment proposals and the checks every draft runs.
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -19,10 +23,104 @@ pub enum MultisigError {
     AlreadyApproved = 16,
     InsufficientApprovals = 17,
     InvalidProposal = 18,
+    InvalidExpiryTime = 24,
+    VoteDelegated = 25,
+    InvalidDelegation = 26,
+    TokenFrozen = 32,
+    PageTooLarge = 33,
+    InsecureConfiguration = 36,
+    RoleRequirementNotMet = 40,
+    ProposalClosed = 41,
+    ProposalNotExpired = 42,
+    SelfInvocationForbidden = 43,
+    ProposerCapExceeded = 44,
+    FinalApproverRequired = 47,
+    ArithmeticOverflow = 48,
+    AmountTooLarge = 49,
+    RecipientNotInAddressBook = 55,
+    TransferFailed = 56,
+    ReserveBreached = 59,
+    SameLedgerExecution = 60,
+    OlderProposalPending = 66,
+    MemoRequired = 70,
+    ProposalNotExecuted = 71,
+    DocumentHashRequired = 73,
+    ExecutionTierClosed = 74,
+    AlreadyRejected = 77,
+    NotProposer = 82,
+    ProposalCanceled = 83,
+    ProposalNotCanceled = 84,
+    RestoreWindowClosed = 85,
+    RestoreWindowOpen = 86,
+    StaleRate = 87,
+    ProposalVetoed = 95,
+    SignerSuspended = 99,
+    RoleNotPermitted = 102,
+}
+
+/// Signer changes, keys, signatures, signing sessions and notes.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SignerError {
     SignerChangeNotFound = 19,
     SignerChangeAlreadyExecuted = 20,
     SignerChangeExpired = 21,
     SignerChangeAlreadyApproved = 22,
     InsufficientSignerChangeApprovals = 23,
-    InvalidExpiryTime = 24,
+    RegistryUnavailable = 45,
+    RegistryManaged = 46,
+    SignerChangeApprovalClosed = 57,
+    SignerChangeNotYetEffective = 58,
+    InvalidNote = 61,
+    TooManyNotes = 62,
+    SessionNotFound = 68,
+    SessionExpired = 69,
+    RedundancyRequired = 72,
+    MalformedClientData = 75,
+    ChallengeMismatch = 76,
+    RecoveredKeyMismatch = 78,
+    InvalidBlsSignature = 79,
+    BlsKeyNotRegistered = 80,
+    RelayExpired = 81,
+    KeyNotInRing = 90,
+    RotationTooSoon = 91,
+    SignerNotInSnapshot = 92,
+    RecoveryDisabled = 97,
+    RecoveryNotDue = 98,
+    InvalidSignerLabel = 100,
+    SignerPolicyViolation = 101,
+    SignerUnbound = 103,
+}
+
+/// Governance proposals and the configuration they manage.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum GovernanceError {
+    GovernanceNotFound = 27,
+    GovernanceAlreadyExecuted = 28,
+    GovernanceExpired = 29,
+    GovernanceAlreadyApproved = 30,
+    InsufficientGovernanceApprovals = 31,
+    UnknownFeature = 34,
+    FeatureDisabled = 35,
+    WatcherAlreadyRegistered = 37,
+    WatcherNotFound = 38,
+    TooManyWatchers = 39,
+    TemplateNotFound = 50,
+    TemplateInactive = 51,
+    TooManyTemplates = 52,
+    AddressBookEntryNotFound = 53,
+    AddressBookFull = 54,
+    ObserverNotFound = 63,
+    TooManyObservers = 64,
+    ReadAccessDenied = 65,
+    SchemaMismatch = 67,
+    ImportClosed = 88,
+    AlreadyImported = 89,
+    ContractPaused = 93,
+    ContractRetired = 94,
+    NoGuardian = 96,
+    AccountCallForbidden = 104,
 }
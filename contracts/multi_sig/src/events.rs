@@ -0,0 +1,274 @@
+use soroban_sdk::{contractevent, Address, BytesN, String, Symbol, Vec};
+
+use crate::types::ExecutionTier;
+
+/// Emitted when an approval lifts a proposal to its effective requirement.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalReady {
+    #[topic]
+    pub proposal_id: u64,
+    pub required: u32,
+    pub approvals: u32,
+}
+
+/// Emitted when a revocation drops a ready proposal back below its effective
+/// requirement.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalUnready {
+    #[topic]
+    pub proposal_id: u64,
+    pub required: u32,
+    pub approvals: u32,
+}
+
+/// Emitted when initialization pulls in seed funds.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Funded {
+    #[topic]
+    pub token: Address,
+    pub funder: Address,
+    pub amount: i128,
+}
+
+/// Emitted whenever the contract draws tokens from an allowance. `purpose`
+/// names the feature that asked for them, such as `init_fund`.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FundsPulled {
+    #[topic]
+    pub purpose: Symbol,
+    #[topic]
+    pub token: Address,
+    pub from: Address,
+    pub amount: i128,
+}
+
+/// Published just before `execute_proposal` fails with `TransferFailed`, so it
+/// only reaches diagnostic output such as a simulation. `code` is the token's
+/// own contract error, or `None` when the token trapped without one.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenTransferFailed {
+    #[topic]
+    pub proposal_id: u64,
+    pub token: Address,
+    pub code: Option<u32>,
+}
+
+/// Emitted when a signer adds to a proposal's notes thread. `index` is the
+/// note's position in the thread, starting at 0.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NoteAdded {
+    #[topic]
+    pub proposal_id: u64,
+    pub author: BytesN<32>,
+    pub index: u32,
+    pub text: String,
+}
+
+/// Emitted when a transfer proposal executes, carrying the destination memo
+/// and document hash so off-chain reconciliation can match the payment to its
+/// deposit and invoice.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentExecuted {
+    #[topic]
+    pub proposal_id: u64,
+    #[topic]
+    pub recipient: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub destination_memo: Option<String>,
+    pub document_hash: Option<BytesN<32>>,
+}
+
+/// Emitted when a proposal with an execution schedule executes, naming the
+/// tier that executed it.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledExecution {
+    #[topic]
+    pub proposal_id: u64,
+    pub tier: ExecutionTier,
+}
+
+/// Published just before `execute_proposal` fails with `OlderProposalPending`,
+/// naming the older proposal that has to execute or expire first.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecutionBlocked {
+    #[topic]
+    pub proposal_id: u64,
+    pub blocking_id: u64,
+}
+
+/// Emitted when a watcher's `on_proposal_executed` call fails. The payment
+/// itself has still gone through.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WatcherFailed {
+    #[topic]
+    pub proposal_id: u64,
+    pub watcher: Address,
+}
+
+/// Emitted when a signer change leaves exactly as many signers as the
+/// threshold, so that losing any one key would deadlock the multisig.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RedundancyLost {
+    pub signer_count: u32,
+    pub threshold: u32,
+}
+
+/// Emitted when a signer change brings the signer count back above the
+/// threshold.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RedundancyRestored {
+    pub signer_count: u32,
+    pub threshold: u32,
+}
+
+/// Emitted when strict rejection closes a proposal that can no longer reach
+/// its requirement.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalRejected {
+    #[topic]
+    pub proposal_id: u64,
+    pub rejections: u32,
+}
+
+/// Emitted when a payment proposal is canceled. A proposer's cancellation can
+/// be restored until `restorable_until`; a final one carries the current time.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalCanceled {
+    #[topic]
+    pub proposal_id: u64,
+    pub restorable_until: u64,
+}
+
+/// Emitted when a proposer withdraws their signer change.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerChangeCanceled {
+    #[topic]
+    pub proposal_id: u64,
+    pub proposer: BytesN<32>,
+}
+
+/// Emitted when a signer sets or clears their label.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerLabelChanged {
+    #[topic]
+    pub signer: BytesN<32>,
+    pub label: Option<String>,
+}
+
+/// Emitted when the guardian vetoes a payment proposal, closing it.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalVetoed {
+    #[topic]
+    pub proposal_id: u64,
+    pub guardian: Address,
+}
+
+/// Emitted when the recovery key replaces the signer set after the committee
+/// went inactive.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryClaimed {
+    #[topic]
+    pub recovery_key: BytesN<32>,
+    pub signers: Vec<BytesN<32>>,
+    pub threshold: u32,
+}
+
+/// Emitted when the committee restores a proposal its proposer canceled.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalRestored {
+    #[topic]
+    pub proposal_id: u64,
+}
+
+/// Emitted when a signer rotates to a new key with `rotate_own_key`.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerKeyRotated {
+    #[topic]
+    pub old_key: BytesN<32>,
+    pub new_key: BytesN<32>,
+}
+
+/// Emitted when a signer switches to `new_key` from their key ring, leaving
+/// `remaining` keys in it.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RingKeyActivated {
+    #[topic]
+    pub old_key: BytesN<32>,
+    pub new_key: BytesN<32>,
+    pub remaining: u32,
+}
+
+/// Emitted for each signer carried over from a legacy multisig by
+/// `import_signers`.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerImported {
+    #[topic]
+    pub signer: BytesN<32>,
+}
+
+/// Emitted when `import_proposal` carries over legacy proposal
+/// `external_ref` as `proposal_id`, with `approvals` pre-recorded approvals.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalImported {
+    #[topic]
+    pub proposal_id: u64,
+    pub external_ref: u64,
+    pub approvals: u32,
+}
+
+/// Emitted when governance changes the threshold.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ThresholdChanged {
+    pub old_threshold: u32,
+    pub threshold: u32,
+}
+
+/// Emitted when governance pauses or unpauses payments.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseChanged {
+    pub paused: bool,
+}
+
+/// Emitted once, when the contract retires, with the balance of each listed
+/// token swept to `successor`.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Retired {
+    #[topic]
+    pub successor: Address,
+    pub swept: Vec<(Address, i128)>,
+}
+
+/// Emitted whenever a change to the signer set or a governance action moves
+/// `get_config_fingerprint`, and once at initialization.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigFingerprintChanged {
+    pub fingerprint: BytesN<32>,
+}
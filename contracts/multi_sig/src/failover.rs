@@ -0,0 +1,61 @@
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::clock;
+use crate::errors::MultisigError;
+use crate::events::ScheduledExecution;
+use crate::features;
+use crate::storage;
+use crate::types::{ExecutionSchedule, ExecutionTier};
+
+/// Most executors one proposal's schedule may name.
+pub const MAX_EXECUTORS: u32 = 10;
+
+// A proposal with an execution schedule opens up in tiers: signers may
+// execute it at any time, the named executors from `fallback_at` on, and
+// anyone from `public_at` on. Proposals without one stay executable by
+// anyone, as before schedules existed, unless `SIGNERS_EXECUTE` closes the
+// public tier for every proposal.
+
+pub fn validate(env: &Env, schedule: &ExecutionSchedule) {
+    let executors = &schedule.executors;
+    if executors.is_empty() || executors.len() > MAX_EXECUTORS || schedule.fallback_at > schedule.public_at {
+        panic_with_error!(env, MultisigError::InvalidProposal);
+    }
+    for (position, executor) in executors.iter().enumerate() {
+        if executors.first_index_of(&executor) != Some(position as u32) {
+            panic_with_error!(env, MultisigError::InvalidProposal);
+        }
+    }
+}
+
+/// Fails with `ExecutionTierClosed` unless `tier` may execute `proposal_id`
+/// now. `executor` is the caller for the `Executor` tier. With
+/// `SIGNERS_EXECUTE` on, the `Public` tier never opens.
+pub fn require_open(env: &Env, proposal_id: u64, tier: ExecutionTier, executor: Option<&Address>) {
+    if tier == ExecutionTier::Public && features::is_enabled(env, &features::SIGNERS_EXECUTE) {
+        panic_with_error!(env, MultisigError::ExecutionTierClosed);
+    }
+    let Some(schedule) = storage::read_execution_schedule(env, proposal_id) else {
+        if tier == ExecutionTier::Executor {
+            panic_with_error!(env, MultisigError::ExecutionTierClosed);
+        }
+        return;
+    };
+
+    let now = clock::now(env);
+    let open = match tier {
+        ExecutionTier::Committee => true,
+        ExecutionTier::Executor => now >= schedule.fallback_at && executor.is_some_and(|caller| schedule.executors.contains(caller)),
+        ExecutionTier::Public => now >= schedule.public_at,
+    };
+    if !open {
+        panic_with_error!(env, MultisigError::ExecutionTierClosed);
+    }
+}
+
+/// Announces which tier executed a scheduled proposal.
+pub fn record(env: &Env, proposal_id: u64, tier: ExecutionTier) {
+    if storage::read_execution_schedule(env, proposal_id).is_some() {
+        ScheduledExecution { proposal_id, tier }.publish(env);
+    }
+}
@@ -0,0 +1,91 @@
+use soroban_sdk::{map, panic_with_error, symbol_short, Env, Map, Symbol, Vec};
+
+use crate::errors::GovernanceError;
+use crate::storage;
+
+/// Per-proposal vote delegation (`delegate_vote`). Enabled by default.
+pub const DELEGATION: Symbol = symbol_short!("delegate");
+
+/// Per-role approval buckets on top of the threshold (`SetRoleRequirements`).
+/// Disabled by default.
+pub const STAGED_APPROVAL: Symbol = symbol_short!("staged");
+
+/// Transfers and allowances may only name address-book entries as recipient
+/// or spender. Disabled by default.
+pub const ADDRESS_BOOK_ONLY: Symbol = symbol_short!("book_only");
+
+/// A proposal may only execute in a later ledger than the approval that made
+/// it executable, so no single transaction can both approve and execute.
+/// Disabled by default.
+pub const SEPARATE_EXECUTION: Symbol = symbol_short!("sep_exec");
+
+/// Transfers to the same recipient in the same token execute in creation
+/// order: a proposal waits while an older one is ready to execute. Disabled
+/// by default.
+pub const FIFO_PER_RECIPIENT: Symbol = symbol_short!("fifo_rcpt");
+
+/// A rejection that leaves a proposal unable to reach its requirement closes
+/// it as `Rejected`. Without it rejections are only recorded. Disabled by
+/// default.
+pub const STRICT_REJECTION: Symbol = symbol_short!("strict_rj");
+
+/// Payment proposals are decided by the signers of the set they were created
+/// under: later signers cannot approve them. Not available to instances whose
+/// signers live in a registry. Disabled by default.
+pub const SNAPSHOT_SIGNERS: Symbol = symbol_short!("snapshot");
+
+/// Only signers, or a proposal's scheduled executors, may execute payment
+/// proposals: `execute_proposal` and the public tier of an execution schedule
+/// are closed. Disabled by default.
+pub const SIGNERS_EXECUTE: Symbol = symbol_short!("signer_ex");
+
+/// Every feature governance may toggle.
+pub fn known(env: &Env) -> Vec<Symbol> {
+    Vec::from_array(env, [DELEGATION, STAGED_APPROVAL, ADDRESS_BOOK_ONLY, SEPARATE_EXECUTION, FIFO_PER_RECIPIENT, STRICT_REJECTION, SNAPSHOT_SIGNERS, SIGNERS_EXECUTE])
+}
+
+// Flags used when governance has never toggled anything, so deployments that
+// predate the flag map keep their behaviour.
+fn defaults(env: &Env) -> Map<Symbol, bool> {
+    map![env, (DELEGATION, true)]
+}
+
+fn read_flags(env: &Env) -> Map<Symbol, bool> {
+    storage::read_feature_flags(env).unwrap_or_else(|| defaults(env))
+}
+
+pub fn is_known(env: &Env, feature: &Symbol) -> bool {
+    known(env).contains(feature)
+}
+
+pub fn is_enabled(env: &Env, feature: &Symbol) -> bool {
+    read_flags(env).get(feature.clone()).unwrap_or(false)
+}
+
+pub fn enabled(env: &Env) -> Vec<Symbol> {
+    let mut features = Vec::new(env);
+    for (feature, on) in read_flags(env).iter() {
+        if on {
+            features.push_back(feature);
+        }
+    }
+    features
+}
+
+pub fn set_enabled(env: &Env, feature: &Symbol, on: bool) {
+    let mut flags = read_flags(env);
+    flags.set(feature.clone(), on);
+    storage::write_feature_flags(env, &flags);
+}
+
+pub fn require_known(env: &Env, feature: &Symbol) {
+    if !is_known(env, feature) {
+        panic_with_error!(env, GovernanceError::UnknownFeature);
+    }
+}
+
+pub fn require_enabled(env: &Env, feature: &Symbol) {
+    if !is_enabled(env, feature) {
+        panic_with_error!(env, GovernanceError::FeatureDisabled);
+    }
+}
@@ -0,0 +1,47 @@
+use soroban_sdk::{Env, Vec};
+
+use crate::index;
+use crate::pagination;
+use crate::storage;
+use crate::types::{IndexKey, UnifiedProposal, UnifiedProposalPage};
+
+/// Open payment and signer-change proposals merged into one list, oldest
+/// first. Both families draw ids from the same counter, so merging the two open
+/// indexes by id orders the feed by creation time.
+pub fn open_items(env: &Env, cursor: u64, limit: u32) -> UnifiedProposalPage {
+    pagination::check_limit(env, limit);
+
+    let mut payments = index::iter(env, &IndexKey::OpenProposals).filter(|id| *id >= cursor).peekable();
+    let mut signer_changes = index::iter(env, &IndexKey::OpenSignerChanges).filter(|id| *id >= cursor).peekable();
+
+    let mut items: Vec<UnifiedProposal> = Vec::new(env);
+    let mut next_cursor = cursor;
+    let mut has_more = false;
+
+    loop {
+        let payment_first = match (payments.peek(), signer_changes.peek()) {
+            (None, None) => break,
+            (Some(payment), Some(change)) => payment < change,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+        };
+        if items.len() == limit {
+            has_more = true;
+            break;
+        }
+
+        let item = if payment_first {
+            UnifiedProposal::Payment(storage::read_proposal(env, payments.next().unwrap()).unwrap())
+        } else {
+            UnifiedProposal::SignerChange(storage::read_signer_change(env, signer_changes.next().unwrap()).unwrap())
+        };
+        next_cursor = item.id() + 1;
+        items.push_back(item);
+    }
+
+    UnifiedProposalPage {
+        items,
+        next_cursor,
+        has_more,
+    }
+}
@@ -0,0 +1,89 @@
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{BytesN, Env, Vec};
+
+use crate::events::ConfigFingerprintChanged;
+use crate::features;
+use crate::pagination;
+use crate::payments;
+use crate::signers;
+use crate::storage;
+use crate::types::{ConfigSigner, ConfigSnapshot};
+
+/// Version of the `ConfigSnapshot` encoding. Bump it whenever the snapshot
+/// gains a field or a field changes meaning, so old fingerprints can never
+/// match new ones by accident.
+pub const FINGERPRINT_VERSION: u32 = 3;
+
+// The fingerprint is recomputed from live state on every read; the stored copy
+// only exists to tell whether a change moved it.
+
+pub fn snapshot(env: &Env) -> ConfigSnapshot {
+    ConfigSnapshot {
+        version: FINGERPRINT_VERSION,
+        signers: config_signers(env),
+        threshold: storage::read_threshold(env),
+        signer_registry: storage::read_signer_registry(env),
+        allow_low_security: storage::allows_low_security(env),
+        protected_quorum: signers::protected_quorum(env),
+        final_approver: storage::read_final_approver(env),
+        role_requirements: storage::read_role_requirements(env),
+        features: features::enabled(env),
+        execution_grace: storage::read_execution_grace(env),
+        cancel_grace: payments::cancel_grace(env),
+        redundancy_policy: storage::read_redundancy_policy(env),
+    }
+}
+
+pub fn fingerprint(env: &Env) -> BytesN<32> {
+    env.crypto().sha256(&snapshot(env).to_xdr(env)).into()
+}
+
+/// Publishes `config_fingerprint_changed` if the fingerprint moved since it
+/// was last published. Called after every change that can move it.
+pub fn refresh(env: &Env) {
+    let fingerprint = fingerprint(env);
+    if storage::read_config_fingerprint(env).as_ref() == Some(&fingerprint) {
+        return;
+    }
+    storage::write_config_fingerprint(env, &fingerprint);
+    ConfigFingerprintChanged { fingerprint }.publish(env);
+}
+
+/// Every signer sorted by id, with what the configuration ties to their key.
+/// Empty when a signer registry holds the signer set.
+pub fn config_signers(env: &Env) -> Vec<ConfigSigner> {
+    describe(env, sorted_signers(env))
+}
+
+/// Up to `limit` entries of `config_signers` from index `start`.
+pub fn config_signers_page(env: &Env, start: u32, limit: u32) -> Vec<ConfigSigner> {
+    describe(env, pagination::page(env, sorted_signers(env), start, limit))
+}
+
+fn describe(env: &Env, ids: Vec<BytesN<32>>) -> Vec<ConfigSigner> {
+    let mut signers = Vec::new(env);
+    if storage::read_signer_registry(env).is_some() {
+        return signers;
+    }
+    let suspended = storage::read_suspended_signers(env);
+    for id in ids.iter() {
+        signers.push_back(ConfigSigner {
+            address: storage::read_signer_address(env, &id),
+            role: storage::read_signer_role(env, &id),
+            protected: storage::is_protected_signer(env, &id),
+            suspended: suspended.contains(&id),
+            id,
+        });
+    }
+    signers
+}
+
+fn sorted_signers(env: &Env) -> Vec<BytesN<32>> {
+    let mut sorted = Vec::new(env);
+    for id in storage::read_signer_list(env).iter() {
+        if let Err(position) = sorted.binary_search(&id) {
+            sorted.insert(position, id);
+        }
+    }
+    sorted
+}
@@ -0,0 +1,33 @@
+use soroban_sdk::{panic_with_error, symbol_short, Address, Env, Symbol};
+
+use crate::errors::MultisigError;
+use crate::events::FundsPulled;
+use crate::validation;
+
+/// Purpose recorded for the seed funds pulled by `initialize_funded`.
+pub const INITIAL_FUNDING: Symbol = symbol_short!("init_fund");
+
+// Every pull of tokens into the contract goes through here, so each one checks
+// the amount, reports a token failure as our own error and announces itself
+// the same way whatever feature asked for it.
+
+/// Draws `amount` of `token` from `from` through the allowance `from` granted
+/// this contract. A failure inside the token fails the caller with
+/// `TransferFailed`.
+pub fn pull_funds(env: &Env, from: &Address, token: &Address, amount: i128, purpose: Symbol) {
+    validation::raise(env, validation::check_amount(amount));
+
+    let this = env.current_contract_address();
+    let result = soroban_sdk::token::Client::new(env, token).try_transfer_from(&this, from, &this, &amount);
+    if result.is_err() {
+        panic_with_error!(env, MultisigError::TransferFailed);
+    }
+
+    FundsPulled {
+        purpose,
+        token: token.clone(),
+        from: from.clone(),
+        amount,
+    }
+    .publish(env);
+}
@@ -0,0 +1,367 @@
+use soroban_sdk::{panic_with_error, Address, BytesN, Env, Vec};
+
+use crate::activity;
+use crate::address_book;
+use crate::clock;
+use crate::errors::{GovernanceError, MultisigError, SignerError};
+use crate::events::PauseChanged;
+use crate::fingerprint;
+use crate::history;
+use crate::keyring;
+use crate::observers;
+use crate::outcomes;
+use crate::payments;
+use crate::rates;
+use crate::recovery;
+use crate::retirement;
+use crate::features;
+use crate::roles;
+use crate::signers;
+use crate::spending;
+use crate::storage;
+use crate::templates;
+use crate::treasury;
+use crate::types::{DocumentThreshold, GovernanceAction, GovernanceApproval, GovernanceProposal, PolicyAction, ProposalOutcome, SignerAction, TokenImplementation, TreasuryAction};
+use crate::validation;
+use crate::watchers;
+
+pub fn propose_governance(
+    env: &Env,
+    proposer: BytesN<32>,
+    action: GovernanceAction,
+    expires_in_seconds: u64,
+) -> u64 {
+    validation::require_active(env);
+
+    validation::validate_expiry(env, expires_in_seconds);
+    validation::require_signer(env, &proposer);
+    signers::require_not_suspended(env, &proposer);
+    signers::require_can_propose(env, &proposer);
+    signers::require_account_auth(env, &proposer);
+
+    if let GovernanceAction::Policy(PolicyAction::EnableFeature(feature)) | GovernanceAction::Policy(PolicyAction::DisableFeature(feature)) = &action {
+        features::require_known(env, feature);
+    }
+    // Join versions are kept where signers are added, which a registry does
+    // on its own instance
+    if let GovernanceAction::Policy(PolicyAction::EnableFeature(feature)) = &action {
+        if *feature == features::SNAPSHOT_SIGNERS && storage::read_signer_registry(env).is_some() {
+            panic_with_error!(env, SignerError::RegistryManaged);
+        }
+    }
+    if let GovernanceAction::Signers(SignerAction::SetRoleRequirements(requirements)) = &action {
+        roles::validate_requirements(env, requirements);
+    }
+    if let GovernanceAction::Signers(SignerAction::SetProtectedQuorum(quorum)) = &action {
+        signers::validate_protected_quorum(env, *quorum);
+    }
+    if let GovernanceAction::Treasury(TreasuryAction::SetProposerCap(cap)) = &action {
+        spending::validate_cap(env, cap);
+    }
+    if let GovernanceAction::Treasury(TreasuryAction::SetAllocationTargets(targets)) = &action {
+        treasury::validate_targets(env, targets);
+    }
+    if let GovernanceAction::Treasury(TreasuryAction::CreateTemplate(draft)) = &action {
+        templates::validate_draft(env, draft);
+    }
+    if let GovernanceAction::Treasury(TreasuryAction::DeactivateTemplate(template_id)) = &action {
+        templates::load(env, *template_id);
+    }
+    if let GovernanceAction::Treasury(TreasuryAction::SetTokenImplementation(entry)) = &action {
+        validate_token_implementation(env, entry);
+    }
+    if let GovernanceAction::Treasury(TreasuryAction::SetReserveFloor(floor)) = &action {
+        treasury::validate_reserve_floor(env, floor);
+    }
+    if let GovernanceAction::Treasury(TreasuryAction::SetDocumentThreshold(threshold)) = &action {
+        validate_document_threshold(env, threshold);
+    }
+    if let GovernanceAction::Policy(PolicyAction::SetExecutionGrace(grace_seconds)) = &action {
+        validation::validate_execution_grace(env, *grace_seconds);
+    }
+    if let GovernanceAction::Policy(PolicyAction::SetCancelGrace(grace_seconds)) = &action {
+        validation::validate_cancel_grace(env, *grace_seconds);
+    }
+    if let GovernanceAction::Policy(PolicyAction::CancelProposal(target)) = &action {
+        payments::load_cancelable(env, *target);
+    }
+    if let GovernanceAction::Treasury(TreasuryAction::SetExchangeRate(rate)) = &action {
+        rates::validate(env, rate);
+    }
+    if let GovernanceAction::Treasury(TreasuryAction::SetMaxRateAge(max_age_seconds)) = &action {
+        rates::validate_max_age(env, *max_age_seconds);
+    }
+    if let GovernanceAction::Treasury(TreasuryAction::SetValueCap(cap)) = &action {
+        spending::validate_value_cap(env, cap);
+    }
+    if let GovernanceAction::Treasury(TreasuryAction::SetDocumentValueThreshold(threshold)) = &action {
+        validate_document_value_threshold(env, *threshold);
+    }
+    if let GovernanceAction::Signers(SignerAction::SetKeyRing(ring)) = &action {
+        keyring::validate(env, ring);
+    }
+    if let GovernanceAction::Policy(PolicyAction::Retire(retirement)) = &action {
+        retirement::validate(env, retirement);
+    }
+    if let GovernanceAction::Policy(PolicyAction::SetGuardian(guardian)) = &action {
+        if *guardian == env.current_contract_address() {
+            panic_with_error!(env, MultisigError::InvalidProposal);
+        }
+    }
+    if let GovernanceAction::Signers(SignerAction::SetRecovery(config)) = &action {
+        recovery::validate(env, config);
+    }
+    if let GovernanceAction::Signers(SignerAction::SetThreshold(threshold)) = &action {
+        signers::validate_threshold(env, *threshold);
+    }
+    if let GovernanceAction::Signers(SignerAction::SetSignerPolicy(policy)) = &action {
+        signers::validate_signer_policy(env, policy);
+    }
+    if let GovernanceAction::Signers(SignerAction::SetSignerPermissions(signer, granted)) = &action {
+        signers::validate_permissions(env, signer, granted);
+    }
+    if let GovernanceAction::Signers(SignerAction::SetInactivityRemoval(config)) = &action {
+        activity::validate(env, config);
+    }
+    if let GovernanceAction::Signers(SignerAction::AddObserver(key)) = &action {
+        observers::validate_new_observer(env, key);
+    }
+    if let GovernanceAction::Signers(SignerAction::RemoveObserver(key)) = &action {
+        observers::require_observer(env, key);
+    }
+    if let GovernanceAction::Treasury(TreasuryAction::AddAddressBookEntry(label)) = &action {
+        address_book::validate_new_entry(env, label);
+    }
+    if let GovernanceAction::Treasury(TreasuryAction::RemoveAddressBookEntry(entry_id)) = &action {
+        address_book::load(env, *entry_id);
+    }
+
+    let current_time = clock::now(env);
+    let proposal_id = storage::next_proposal_id(env);
+
+    let proposal = GovernanceProposal {
+        id: proposal_id,
+        proposer,
+        action,
+        created_at: current_time,
+        expires_at: current_time + expires_in_seconds,
+        executed: false,
+    };
+
+    storage::write_governance(env, &proposal);
+    storage::write_governance_approvals(env, proposal_id, &Vec::new(env));
+    activity::record(env, &proposal.proposer);
+
+    proposal_id
+}
+
+pub fn approve_governance(env: &Env, proposal_id: u64, approver: BytesN<32>) {
+    validation::require_active(env);
+
+    validation::require_signer(env, &approver);
+    signers::require_not_suspended(env, &approver);
+    signers::require_can_approve(env, &approver);
+    signers::require_account_auth(env, &approver);
+
+    let proposal = load_open_governance(env, proposal_id);
+
+    if clock::now(env) > proposal.expires_at {
+        panic_with_error!(env, GovernanceError::GovernanceExpired);
+    }
+
+    let mut approvals = storage::read_governance_approvals(env, proposal_id);
+
+    // Check if already approved
+    for i in 0..approvals.len() {
+        let approval = approvals.get_unchecked(i);
+        if approval.signer == approver {
+            panic_with_error!(env, GovernanceError::GovernanceAlreadyApproved);
+        }
+    }
+
+    let approval = GovernanceApproval {
+        signer: approver,
+        approved_at: clock::now(env),
+    };
+
+    activity::record(env, &approval.signer);
+    approvals.push_back(approval);
+    storage::write_governance_approvals(env, proposal_id, &approvals);
+    recovery::touch(env);
+}
+
+pub fn execute_governance(env: &Env, proposal_id: u64) {
+    validation::require_active(env);
+
+    let proposal = load_open_governance(env, proposal_id);
+
+    if clock::now(env) > proposal.expires_at {
+        panic_with_error!(env, GovernanceError::GovernanceExpired);
+    }
+
+    // Approvals by signers removed, suspended or barred from approving since
+    // no longer count
+    let approvals = storage::read_governance_approvals(env, proposal_id);
+    let current = approvals
+        .iter()
+        .filter(|approval| {
+            storage::is_signer(env, &approval.signer) && !signers::is_suspended(env, &approval.signer) && signers::can_approve(env, &approval.signer)
+        })
+        .count() as u32;
+
+    if current < required_governance_approvals(env, &proposal.action) {
+        panic_with_error!(env, GovernanceError::InsufficientGovernanceApprovals);
+    }
+
+    // Apply the governed action
+    let version = history::version(env);
+    match &proposal.action {
+        GovernanceAction::Treasury(TreasuryAction::FreezeToken(token)) => storage::set_token_frozen(env, token, true),
+        GovernanceAction::Treasury(TreasuryAction::UnfreezeToken(token)) => storage::set_token_frozen(env, token, false),
+        GovernanceAction::Policy(PolicyAction::EnableFeature(feature)) => features::set_enabled(env, feature, true),
+        GovernanceAction::Policy(PolicyAction::DisableFeature(feature)) => features::set_enabled(env, feature, false),
+        GovernanceAction::Treasury(TreasuryAction::AddWatcher(registration)) => watchers::add_watcher(env, registration),
+        GovernanceAction::Treasury(TreasuryAction::RemoveWatcher(registration)) => watchers::remove_watcher(env, registration),
+        GovernanceAction::Signers(SignerAction::SetSignerRole(assignment)) => roles::assign_role(env, assignment),
+        GovernanceAction::Signers(SignerAction::ClearSignerRole(signer)) => roles::clear_role(env, signer),
+        GovernanceAction::Signers(SignerAction::SetRoleRequirements(requirements)) => storage::write_role_requirements(env, requirements),
+        GovernanceAction::Signers(SignerAction::ProtectSigner(signer)) => signers::set_protected(env, signer, true),
+        GovernanceAction::Signers(SignerAction::UnprotectSigner(signer)) => signers::set_protected(env, signer, false),
+        GovernanceAction::Signers(SignerAction::SetProtectedQuorum(quorum)) => {
+            signers::validate_protected_quorum(env, *quorum);
+            storage::write_protected_quorum(env, *quorum);
+        }
+        GovernanceAction::Treasury(TreasuryAction::SetProposerCap(cap)) => storage::write_proposer_cap(env, cap),
+        GovernanceAction::Treasury(TreasuryAction::RemoveProposerCap(token)) => storage::remove_proposer_cap(env, token),
+        GovernanceAction::Signers(SignerAction::SetFinalApprover(signer)) => {
+            validation::require_signer(env, signer);
+            storage::write_final_approver(env, Some(signer));
+        }
+        GovernanceAction::Signers(SignerAction::ClearFinalApprover) => storage::write_final_approver(env, None),
+        GovernanceAction::Treasury(TreasuryAction::SetAllocationTargets(targets)) => storage::write_allocation_targets(env, targets),
+        GovernanceAction::Treasury(TreasuryAction::CreateTemplate(draft)) => templates::create(env, draft),
+        GovernanceAction::Treasury(TreasuryAction::DeactivateTemplate(template_id)) => templates::deactivate(env, *template_id),
+        GovernanceAction::Treasury(TreasuryAction::AddAddressBookEntry(label)) => address_book::add(env, label),
+        GovernanceAction::Treasury(TreasuryAction::RemoveAddressBookEntry(entry_id)) => address_book::remove(env, *entry_id),
+        GovernanceAction::Treasury(TreasuryAction::SetTokenImplementation(entry)) => storage::write_token_implementation(env, entry),
+        GovernanceAction::Treasury(TreasuryAction::ClearTokenImplementation(token)) => storage::remove_token_implementation(env, token),
+        GovernanceAction::Treasury(TreasuryAction::SetReserveFloor(floor)) => storage::write_reserve_floor(env, floor),
+        GovernanceAction::Treasury(TreasuryAction::ClearReserveFloor(token)) => storage::remove_reserve_floor(env, token),
+        GovernanceAction::Treasury(TreasuryAction::RequireMemo(recipient)) => storage::set_memo_required(env, recipient, true),
+        GovernanceAction::Treasury(TreasuryAction::ClearMemoRequirement(recipient)) => storage::set_memo_required(env, recipient, false),
+        GovernanceAction::Policy(PolicyAction::SetExecutionGrace(grace_seconds)) => storage::write_execution_grace(env, *grace_seconds),
+        GovernanceAction::Signers(SignerAction::AddObserver(key)) => observers::add(env, key),
+        GovernanceAction::Signers(SignerAction::RemoveObserver(key)) => observers::remove(env, key),
+        GovernanceAction::Signers(SignerAction::SetRedundancyPolicy(policy)) => storage::write_redundancy_policy(env, *policy),
+        GovernanceAction::Treasury(TreasuryAction::SetDocumentThreshold(threshold)) => storage::write_document_threshold(env, threshold),
+        GovernanceAction::Treasury(TreasuryAction::ClearDocumentThreshold(token)) => storage::remove_document_threshold(env, token),
+        GovernanceAction::Policy(PolicyAction::SetCancelGrace(grace_seconds)) => storage::write_cancel_grace(env, *grace_seconds),
+        GovernanceAction::Policy(PolicyAction::CancelProposal(target)) => payments::cancel_by_committee(env, *target),
+        GovernanceAction::Treasury(TreasuryAction::SetExchangeRate(rate)) => storage::write_exchange_rate(env, rate),
+        GovernanceAction::Treasury(TreasuryAction::ClearExchangeRate(token)) => storage::remove_exchange_rate(env, token),
+        GovernanceAction::Treasury(TreasuryAction::SetMaxRateAge(max_age_seconds)) => storage::write_max_rate_age(env, *max_age_seconds),
+        GovernanceAction::Treasury(TreasuryAction::SetValueCap(cap)) => storage::write_value_cap(env, Some(cap)),
+        GovernanceAction::Treasury(TreasuryAction::RemoveValueCap) => storage::write_value_cap(env, None),
+        GovernanceAction::Treasury(TreasuryAction::SetDocumentValueThreshold(threshold)) => storage::write_document_value_threshold(env, Some(*threshold)),
+        GovernanceAction::Treasury(TreasuryAction::ClearDocumentValueThreshold) => storage::write_document_value_threshold(env, None),
+        GovernanceAction::Signers(SignerAction::SetKeyRing(ring)) => keyring::set(env, ring),
+        GovernanceAction::Policy(PolicyAction::Pause) => set_paused(env, true),
+        GovernanceAction::Policy(PolicyAction::Unpause) => set_paused(env, false),
+        GovernanceAction::Policy(PolicyAction::Retire(retirement)) => retirement::retire(env, retirement),
+        GovernanceAction::Policy(PolicyAction::SetGuardian(guardian)) => storage::write_guardian(env, Some(guardian)),
+        GovernanceAction::Policy(PolicyAction::ClearGuardian) => storage::write_guardian(env, None),
+        GovernanceAction::Signers(SignerAction::SetRecovery(config)) => storage::write_recovery(env, Some(config)),
+        GovernanceAction::Signers(SignerAction::ClearRecovery) => storage::write_recovery(env, None),
+        GovernanceAction::Signers(SignerAction::SetThreshold(threshold)) => signers::set_threshold(env, *threshold),
+        GovernanceAction::Signers(SignerAction::SetSignerPolicy(policy)) => {
+            // Signer changes executed since it was proposed may have moved the count
+            signers::validate_signer_policy(env, policy);
+            storage::write_signer_policy(env, Some(policy));
+        }
+        GovernanceAction::Signers(SignerAction::ClearSignerPolicy) => storage::write_signer_policy(env, None),
+        GovernanceAction::Signers(SignerAction::SetSignerPermissions(signer, granted)) => {
+            signers::validate_permissions(env, signer, granted);
+            signers::set_permissions(env, signer, granted);
+        }
+        GovernanceAction::Signers(SignerAction::SetInactivityRemoval(config)) => {
+            activity::validate(env, config);
+            activity::configure(env, config);
+        }
+    }
+    history::attribute(env, version, proposal_id);
+    fingerprint::refresh(env);
+    recovery::touch(env);
+
+    // Mark as executed
+    storage::set_proposal_flag(env, proposal_id, storage::FLAG_EXECUTED);
+    outcomes::record(env, proposal_id, ProposalOutcome::Executed, None);
+
+    let mut updated_proposal = proposal;
+    updated_proposal.executed = true;
+    storage::write_governance(env, &updated_proposal);
+}
+
+fn set_paused(env: &Env, paused: bool) {
+    if storage::is_paused(env) != paused {
+        storage::set_paused(env, paused);
+        PauseChanged { paused }.publish(env);
+    }
+}
+
+/// Approvals a governance action needs. Anything that changes signer
+/// protection is held to the same elevated quorum as removing a protected
+/// signer, so the bar cannot be lowered with the ordinary threshold.
+pub fn required_governance_approvals(env: &Env, action: &GovernanceAction) -> u32 {
+    match action {
+        GovernanceAction::Signers(SignerAction::ProtectSigner(_))
+        | GovernanceAction::Signers(SignerAction::UnprotectSigner(_))
+        | GovernanceAction::Signers(SignerAction::SetProtectedQuorum(_)) => signers::protected_quorum(env),
+        _ => storage::read_threshold(env),
+    }
+}
+
+pub fn check_token_not_frozen(env: &Env, token: &Address) -> Result<(), MultisigError> {
+    if storage::is_token_frozen(env, token) {
+        return Err(MultisigError::TokenFrozen);
+    }
+    Ok(())
+}
+
+pub fn require_token_not_frozen(env: &Env, token: &Address) {
+    validation::raise(env, check_token_not_frozen(env, token));
+}
+
+// Pre-authorizing calls on the multisig itself, or on the token it is already
+// calling directly, would be meaningless at best.
+fn validate_token_implementation(env: &Env, entry: &TokenImplementation) {
+    if entry.implementation == entry.token || entry.implementation == env.current_contract_address() {
+        panic_with_error!(env, MultisigError::InvalidProposal);
+    }
+}
+
+// A threshold of zero requires a document for every payment in the token.
+fn validate_document_threshold(env: &Env, threshold: &DocumentThreshold) {
+    if threshold.amount < 0 {
+        panic_with_error!(env, MultisigError::InvalidProposal);
+    }
+    validation::raise(env, validation::check_amount_ceiling(threshold.amount));
+}
+
+fn validate_document_value_threshold(env: &Env, threshold: i128) {
+    if threshold < 0 {
+        panic_with_error!(env, MultisigError::InvalidProposal);
+    }
+}
+
+// Loads a governance proposal that exists and has not been executed yet.
+fn load_open_governance(env: &Env, proposal_id: u64) -> GovernanceProposal {
+    let Some(proposal) = storage::read_governance(env, proposal_id) else {
+        panic_with_error!(env, GovernanceError::GovernanceNotFound);
+    };
+
+    if storage::has_proposal_flag(env, proposal_id, storage::FLAG_EXECUTED) {
+        panic_with_error!(env, GovernanceError::GovernanceAlreadyExecuted);
+    }
+
+    proposal
+}
@@ -0,0 +1,151 @@
+use soroban_sdk::{BytesN, Env, Vec};
+
+use crate::clock;
+use crate::errors::SignerError;
+use crate::features;
+use crate::pagination;
+use crate::storage;
+use crate::types::{SignerHistoryEntry, SignerSetChange, SignerSetSnapshot};
+
+/// Entries between signer-set checkpoints. `signer_set_at` replays at most
+/// this many entries past the checkpoint it starts from.
+pub const CHECKPOINT_INTERVAL: u64 = 32;
+
+/// Appends one signer-set mutation to the history. Every path that changes the
+/// signer set or threshold calls this.
+pub fn record(env: &Env, change: SignerSetChange) {
+    let seq = storage::read_signer_history_count(env) + 1;
+    let entry = SignerHistoryEntry {
+        seq,
+        timestamp: clock::now(env),
+        change,
+    };
+    storage::write_signer_history_entry(env, &entry);
+    storage::write_signer_history_count(env, seq);
+    if seq.is_multiple_of(CHECKPOINT_INTERVAL) {
+        storage::write_signer_set_checkpoint(env, seq, &replay(env, seq));
+    }
+}
+
+pub fn entries(env: &Env, from_seq: u64, limit: u32) -> Vec<SignerHistoryEntry> {
+    let window = pagination::id_window(env, from_seq, limit, storage::read_signer_history_count(env));
+
+    let mut entries = Vec::new(env);
+    for seq in window.start..window.end {
+        entries.push_back(storage::read_signer_history_entry(env, seq).unwrap());
+    }
+    entries
+}
+
+/// The signer set as of `timestamp`: every entry up to and including it
+/// applied. Entries are found by binary search on their timestamps, which
+/// never decrease, and replayed from the nearest checkpoint.
+pub fn signer_set_at(env: &Env, timestamp: u64) -> SignerSetSnapshot {
+    let (mut last, mut bound) = (0, storage::read_signer_history_count(env));
+    while last < bound {
+        let mid = last + (bound - last).div_ceil(2);
+        if storage::read_signer_history_entry(env, mid).unwrap().timestamp <= timestamp {
+            last = mid;
+        } else {
+            bound = mid - 1;
+        }
+    }
+    replay(env, last)
+}
+
+// The set after entry `last`, from the latest checkpoint at or before it.
+// Entries recorded before checkpoints were kept have none, so the search falls
+// back to earlier ones and, at worst, to the start of the history.
+fn replay(env: &Env, last: u64) -> SignerSetSnapshot {
+    let mut start = last - last % CHECKPOINT_INTERVAL;
+    let mut snapshot = loop {
+        if start == 0 {
+            break SignerSetSnapshot { signers: Vec::new(env), threshold: 0 };
+        }
+        if let Some(checkpoint) = storage::read_signer_set_checkpoint(env, start) {
+            break checkpoint;
+        }
+        start -= CHECKPOINT_INTERVAL;
+    };
+
+    for seq in start + 1..=last {
+        match storage::read_signer_history_entry(env, seq).unwrap().change {
+            SignerSetChange::Initialized(initial, initial_threshold) => {
+                snapshot.signers = initial;
+                snapshot.threshold = initial_threshold;
+            }
+            SignerSetChange::Added(signer) => snapshot.signers.push_back(signer),
+            SignerSetChange::Removed(signer) => {
+                if let Some(index) = snapshot.signers.first_index_of(&signer) {
+                    snapshot.signers.remove(index);
+                }
+            }
+            SignerSetChange::ThresholdChanged(changed) => snapshot.threshold = changed,
+        }
+    }
+    snapshot
+}
+
+// The history's length doubles as the signer set's version: every change to
+// the set appends to it. Each proposal is stamped with the version it was
+// created under and each seat with the version that added it, so whether a
+// signer belonged to a proposal's set is a comparison, not a replay.
+
+/// Current signer set version.
+pub fn version(env: &Env) -> u64 {
+    storage::read_signer_history_count(env)
+}
+
+/// Attributes the entries recorded since `since` to `proposal_id`, whose
+/// execution recorded them.
+pub fn attribute(env: &Env, since: u64, proposal_id: u64) {
+    for seq in since + 1..=version(env) {
+        storage::write_signer_history_proposal(env, seq, proposal_id);
+    }
+}
+
+/// Records that `signer` joined at the current version. Signers present at
+/// initialization carry no record and count as version 0.
+pub fn record_joined(env: &Env, signer: &BytesN<32>) {
+    storage::write_signer_joined_at(env, signer, Some(version(env)));
+}
+
+/// Moves `old_key`'s join record to `new_key`. A rotated seat is the same
+/// member.
+pub fn transfer_joined(env: &Env, old_key: &BytesN<32>, new_key: &BytesN<32>) {
+    let joined_at = storage::read_signer_joined_at(env, old_key);
+    storage::write_signer_joined_at(env, old_key, None);
+    storage::write_signer_joined_at(env, new_key, joined_at);
+}
+
+/// When `signer`'s seat was added, from the history entry its join record
+/// points at. Signers present at initialization joined with the first entry.
+pub fn joined_at(env: &Env, signer: &BytesN<32>) -> u64 {
+    let seq = storage::read_signer_joined_at(env, signer).unwrap_or(0).max(1);
+    storage::read_signer_history_entry(env, seq).map_or(0, |entry| entry.timestamp)
+}
+
+pub fn forget_joined(env: &Env, signer: &BytesN<32>) {
+    storage::write_signer_joined_at(env, signer, None);
+}
+
+/// Stamps proposal `proposal_id` with the current version.
+pub fn stamp_proposal(env: &Env, proposal_id: u64) {
+    storage::write_proposal_signer_set_version(env, proposal_id, version(env));
+}
+
+/// With `SNAPSHOT_SIGNERS` on, fails with `SignerNotInSnapshot` if `signer`
+/// joined after proposal `proposal_id` was created. Proposals from before
+/// stamping carry no version and are open to every signer.
+pub fn check_in_snapshot(env: &Env, proposal_id: u64, signer: &BytesN<32>) -> Result<(), SignerError> {
+    if !features::is_enabled(env, &features::SNAPSHOT_SIGNERS) {
+        return Ok(());
+    }
+    let Some(created_under) = storage::read_proposal_signer_set_version(env, proposal_id) else {
+        return Ok(());
+    };
+    if storage::read_signer_joined_at(env, signer).unwrap_or(0) > created_under {
+        return Err(SignerError::SignerNotInSnapshot);
+    }
+    Ok(())
+}
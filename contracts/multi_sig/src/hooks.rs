@@ -0,0 +1,61 @@
+use soroban_sdk::{contracttype, Address, BytesN, Env, Vec};
+
+use crate::storage;
+
+// Staging-only helpers, compiled in with the `testing-hooks` feature. What
+// they keep lives under its own key type, so production storage and the
+// schema describing it are the same with or without the feature.
+
+#[contracttype]
+#[derive(Clone)]
+enum HookKey {
+    TimestampOffset,
+    /// Every proposer and token a proposer cap usage counter was written for.
+    UsageKeys,
+    /// Every proposer a value cap usage counter was written for.
+    ValueUsageKeys,
+}
+
+pub fn timestamp_offset(env: &Env) -> u64 {
+    env.storage().instance().get(&HookKey::TimestampOffset).unwrap_or(0)
+}
+
+pub fn set_timestamp_offset(env: &Env, secs: u64) {
+    env.storage().instance().set(&HookKey::TimestampOffset, &secs);
+}
+
+/// Remembers that `proposer` has a usage counter for `token`, since counters
+/// cannot be enumerated otherwise.
+pub fn track_usage(env: &Env, proposer: &BytesN<32>, token: &Address) {
+    let mut keys: Vec<(BytesN<32>, Address)> = env.storage().instance().get(&HookKey::UsageKeys).unwrap_or(Vec::new(env));
+    let key = (proposer.clone(), token.clone());
+    if !keys.contains(&key) {
+        keys.push_back(key);
+        env.storage().instance().set(&HookKey::UsageKeys, &keys);
+    }
+}
+
+/// `track_usage` for the value cap counter, which is kept per proposer only.
+pub fn track_value_usage(env: &Env, proposer: &BytesN<32>) {
+    let mut proposers: Vec<BytesN<32>> = env.storage().instance().get(&HookKey::ValueUsageKeys).unwrap_or(Vec::new(env));
+    if !proposers.contains(proposer) {
+        proposers.push_back(proposer.clone());
+        env.storage().instance().set(&HookKey::ValueUsageKeys, &proposers);
+    }
+}
+
+/// Drops every proposer cap and value cap usage counter, so each proposer
+/// starts a fresh window on their next proposal.
+pub fn reset_rate_limits(env: &Env) {
+    let keys: Vec<(BytesN<32>, Address)> = env.storage().instance().get(&HookKey::UsageKeys).unwrap_or(Vec::new(env));
+    for (proposer, token) in keys.iter() {
+        storage::remove_proposer_usage(env, &proposer, &token);
+    }
+    env.storage().instance().remove(&HookKey::UsageKeys);
+
+    let proposers: Vec<BytesN<32>> = env.storage().instance().get(&HookKey::ValueUsageKeys).unwrap_or(Vec::new(env));
+    for proposer in proposers.iter() {
+        storage::remove_proposer_value_usage(env, &proposer);
+    }
+    env.storage().instance().remove(&HookKey::ValueUsageKeys);
+}
@@ -0,0 +1,95 @@
+use soroban_sdk::{panic_with_error, BytesN, Env, Vec};
+
+use crate::clock;
+use crate::errors::{GovernanceError, MultisigError, SignerError};
+use crate::events::{ProposalImported, SignerImported};
+use crate::fingerprint;
+use crate::history;
+use crate::payments;
+use crate::redundancy;
+use crate::signers;
+use crate::storage;
+use crate::types::{ImportedProposal, SignerSetChange};
+use crate::validation;
+
+/// How long after initialization state may be imported from a legacy
+/// multisig, unless `finish_import` closes the phase sooner (7 days).
+pub const IMPORT_WINDOW_SECONDS: u64 = 604_800;
+
+// A multisig replacing an older one starts in a bootstrap phase in which its
+// signers, acting together as the contract's own account, may carry over the
+// old signer set and open proposals. The phase ends for good when
+// `finish_import` is called or the window runs out; deployments initialized
+// before imports existed never had one.
+
+/// Starts the bootstrap phase. Called once, at initialization.
+pub fn open(env: &Env) {
+    storage::write_import_deadline(env, Some(clock::now(env) + IMPORT_WINDOW_SECONDS));
+}
+
+pub fn is_open(env: &Env) -> bool {
+    storage::read_import_deadline(env).is_some_and(|deadline| clock::now(env) <= deadline)
+}
+
+// Imports act for the whole committee, so they need the contract's own
+// authorization, which `__check_auth` grants on threshold signatures.
+fn require_import(env: &Env) {
+    validation::require_active(env);
+    if !is_open(env) {
+        panic_with_error!(env, GovernanceError::ImportClosed);
+    }
+    env.current_contract_address().require_auth();
+}
+
+/// Adds `signers` to the signer set, leaving the threshold alone.
+pub fn import_signers(env: &Env, signers: Vec<BytesN<32>>) {
+    require_import(env);
+    if storage::read_signer_registry(env).is_some() {
+        panic_with_error!(env, SignerError::RegistryManaged);
+    }
+    if signers.is_empty() {
+        panic_with_error!(env, MultisigError::EmptySignersList);
+    }
+
+    // A key listed twice is already a signer by its second appearance
+    for signer in signers.iter() {
+        if storage::is_signer(env, &signer) {
+            panic_with_error!(env, MultisigError::DuplicateSigner);
+        }
+        storage::add_signer(env, &signer);
+        history::record(env, SignerSetChange::Added(signer.clone()));
+        history::record_joined(env, &signer);
+        SignerImported { signer }.publish(env);
+    }
+    let count = storage::read_signer_count(env) + signers.len();
+    validation::raise(env, signers::check_signer_policy(env, count));
+    storage::write_signer_count(env, count);
+    redundancy::refresh(env);
+    fingerprint::refresh(env);
+}
+
+/// Creates a payment proposal from `legacy`, recording its legacy id as its
+/// import reference. Each legacy id can be imported once.
+pub fn import_proposal(env: &Env, legacy: ImportedProposal) -> u64 {
+    require_import(env);
+    if storage::read_imported_proposal(env, legacy.external_ref).is_some() {
+        panic_with_error!(env, GovernanceError::AlreadyImported);
+    }
+
+    let proposal_id = payments::create_imported(env, &legacy);
+    storage::write_import_ref(env, proposal_id, legacy.external_ref);
+    ProposalImported {
+        proposal_id,
+        external_ref: legacy.external_ref,
+        approvals: legacy.approvals.len(),
+    }
+    .publish(env);
+    proposal_id
+}
+
+/// Ends the bootstrap phase. Every import after this fails with
+/// `ImportClosed`.
+pub fn finish_import(env: &Env) {
+    require_import(env);
+    storage::write_import_deadline(env, None);
+}
@@ -0,0 +1,163 @@
+use soroban_sdk::{Env, Vec};
+
+use crate::storage;
+use crate::types::IndexKey;
+
+/// Most ids held by one chunk of an index. Appends past this open a new chunk
+/// under the next page number, so no single storage entry grows without bound.
+pub const CHUNK_SIZE: u32 = 32;
+
+// Every index is a list of ascending ids split across numbered chunks in
+// persistent storage, and paged views over one take the next id to visit as
+// their cursor. The rules that keep such pages from skipping or repeating an
+// id while items close between calls:
+//
+// - Ids newer than every listed one, as at creation, go to the last chunk.
+//   Older ids are inserted in order, carrying a full chunk's largest id into
+//   the next chunk.
+// - Removals are done in place so the remaining ids keep their order, which
+//   leaves earlier chunks under-full rather than shuffling ids between them.
+//   Nothing is ever swapped into a removed id's position.
+// - Positions are never handed out, only ids, so a removal anywhere in the
+//   index leaves every outstanding cursor valid.
+
+/// Adds `id` to the index in ascending position. Adding an id already listed
+/// does nothing.
+pub fn append(env: &Env, key: &IndexKey, id: u64) {
+    let chunks = storage::read_index_chunk_count(env, key);
+
+    let mut page = chunks.saturating_sub(1);
+    let mut chunk = if chunks == 0 {
+        Vec::new(env)
+    } else {
+        storage::read_index_chunk(env, key, page)
+    };
+    if chunk.last().is_some_and(|last| last >= id) {
+        insert_in_order(env, key, chunks, id);
+        return;
+    }
+    if chunk.len() >= CHUNK_SIZE {
+        page += 1;
+        chunk = Vec::new(env);
+    }
+
+    chunk.push_back(id);
+    storage::write_index_chunk(env, key, page, &chunk);
+    if page + 1 > chunks {
+        storage::write_index_chunk_count(env, key, page + 1);
+    }
+}
+
+/// Removes `id` from the index, returning whether it was present.
+pub fn remove(env: &Env, key: &IndexKey, id: u64) -> bool {
+    let chunks = storage::read_index_chunk_count(env, key);
+
+    for page in 0..chunks {
+        let mut chunk = storage::read_index_chunk(env, key, page);
+        match chunk.last() {
+            Some(last) if last >= id => {}
+            _ => continue,
+        }
+        let Some(position) = chunk.first_index_of(id) else {
+            return false;
+        };
+
+        chunk.remove(position);
+        if !chunk.is_empty() {
+            storage::write_index_chunk(env, key, page, &chunk);
+        } else {
+            storage::remove_index_chunk(env, key, page);
+            if page + 1 == chunks {
+                trim_empty_tail(env, key, page);
+            }
+        }
+        return true;
+    }
+    false
+}
+
+/// Visits every id in the index in ascending order, loading one chunk at a time.
+pub fn iter<'a>(env: &'a Env, key: &IndexKey) -> IndexIter<'a> {
+    IndexIter {
+        env,
+        key: key.clone(),
+        chunks: storage::read_index_chunk_count(env, key),
+        page: 0,
+        chunk: Vec::new(env),
+        position: 0,
+    }
+}
+
+pub struct IndexIter<'a> {
+    env: &'a Env,
+    key: IndexKey,
+    chunks: u32,
+    page: u32,
+    chunk: Vec<u64>,
+    position: u32,
+}
+
+impl Iterator for IndexIter<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        while self.position >= self.chunk.len() {
+            if self.page >= self.chunks {
+                return None;
+            }
+            self.chunk = storage::read_index_chunk(self.env, &self.key, self.page);
+            self.page += 1;
+            self.position = 0;
+        }
+
+        let id = self.chunk.get_unchecked(self.position);
+        self.position += 1;
+        Some(id)
+    }
+}
+
+// Inserts `id`, which is below the last chunk's largest id, into the first
+// chunk whose ids reach past it. A chunk pushed over `CHUNK_SIZE` hands its
+// largest id on to the next chunk, and so on, opening a new chunk at the end
+// if the last one overflows.
+fn insert_in_order(env: &Env, key: &IndexKey, chunks: u32, id: u64) {
+    let mut carry = id;
+    let mut placing = true;
+    for page in 0..chunks {
+        let mut chunk = storage::read_index_chunk(env, key, page);
+        if placing {
+            match chunk.last() {
+                Some(last) if last >= id => {}
+                _ => continue,
+            }
+            let position = match chunk.binary_search(id) {
+                Ok(_) => return,
+                Err(position) => position,
+            };
+            chunk.insert(position, id);
+            placing = false;
+        } else {
+            chunk.push_front(carry);
+        }
+
+        if chunk.len() <= CHUNK_SIZE {
+            storage::write_index_chunk(env, key, page, &chunk);
+            return;
+        }
+        carry = chunk.pop_back().unwrap();
+        storage::write_index_chunk(env, key, page, &chunk);
+    }
+
+    storage::write_index_chunk(env, key, chunks, &Vec::from_array(env, [carry]));
+    storage::write_index_chunk_count(env, key, chunks + 1);
+}
+
+// Drops the chunk count back past any empty chunks at the end, so the next
+// append reuses their page numbers.
+fn trim_empty_tail(env: &Env, key: &IndexKey, emptied_page: u32) {
+    let mut chunks = emptied_page;
+    while chunks > 0 && storage::read_index_chunk(env, key, chunks - 1).is_empty() {
+        chunks -= 1;
+    }
+    storage::write_index_chunk_count(env, key, chunks);
+}
@@ -0,0 +1,122 @@
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{panic_with_error, symbol_short, BytesN, Env, Vec};
+
+use crate::clock;
+use crate::errors::{MultisigError, SignerError};
+use crate::events::RingKeyActivated;
+use crate::fingerprint;
+use crate::signatures;
+use crate::signers;
+use crate::storage;
+use crate::types::KeyRing;
+use crate::validation;
+
+/// Most future keys one signer's ring may hold.
+pub const MAX_RING_KEYS: u32 = 5;
+
+/// Shortest time between two ring activations by the same signer (1 day).
+pub const MIN_ACTIVATION_INTERVAL_SECONDS: u64 = 86_400;
+
+// Hardware wallets that move to a new derivation path change their public key
+// while the person behind it stays the same. Governance vets the keys a signer
+// may move to in advance, as a key ring, after which the signer can switch to
+// any of them alone, with a signature from the key they are leaving. A switch
+// is a rotation in every other respect, and the rest of the ring follows the
+// seat to the new key. Switches are rate limited per seat, so a stolen active
+// key cannot cycle through the ring.
+
+/// Checks `ring` for `SetKeyRing`: the signer must be one, and the keys must
+/// be distinct, at most `MAX_RING_KEYS`, and not already signers. An empty
+/// ring clears the signer's ring.
+pub fn validate(env: &Env, ring: &KeyRing) {
+    validation::require_signer(env, &ring.signer);
+    if ring.keys.len() > MAX_RING_KEYS {
+        panic_with_error!(env, MultisigError::InvalidProposal);
+    }
+    for (position, key) in ring.keys.iter().enumerate() {
+        if ring.keys.first_index_of(&key) != Some(position as u32) || storage::is_signer(env, &key) {
+            panic_with_error!(env, MultisigError::DuplicateSigner);
+        }
+    }
+}
+
+/// Replaces `ring.signer`'s ring with `ring.keys`.
+pub fn set(env: &Env, ring: &KeyRing) {
+    validate(env, ring);
+    let keys = (!ring.keys.is_empty()).then_some(&ring.keys);
+    storage::write_key_ring(env, &ring.signer, keys);
+}
+
+/// Keys `signer` may switch to with `activate_ring_key`.
+pub fn keys(env: &Env, signer: &BytesN<32>) -> Vec<BytesN<32>> {
+    storage::read_key_ring(env, signer).unwrap_or(Vec::new(env))
+}
+
+/// Earliest time `signer` may activate another ring key.
+pub fn next_activation_at(env: &Env, signer: &BytesN<32>) -> u64 {
+    match storage::read_last_ring_activation(env, signer) {
+        Some(activated_at) => activated_at.saturating_add(MIN_ACTIVATION_INTERVAL_SECONDS),
+        None => 0,
+    }
+}
+
+/// What `active_key` must sign to switch to ring key `new_key` next.
+pub fn get_activation_payload(env: &Env, active_key: &BytesN<32>, new_key: &BytesN<32>) -> BytesN<32> {
+    validation::require_initialized(env);
+    signatures::next_payload(env, active_key, &activation_digest(env, active_key, new_key))
+}
+
+fn activation_digest(env: &Env, active_key: &BytesN<32>, new_key: &BytesN<32>) -> BytesN<32> {
+    let digest = (symbol_short!("ringkey"), env.current_contract_address(), active_key.clone(), new_key.clone());
+    env.crypto().sha256(&digest.to_xdr(env)).into()
+}
+
+/// Switches `active_key`'s seat to `new_key` from its ring. `signature` is
+/// `active_key`'s over `get_activation_payload` at its current nonce. A key
+/// outside the ring fails with `KeyNotInRing`, and a switch before
+/// `next_activation_at` with `RotationTooSoon`.
+pub fn activate(env: &Env, active_key: BytesN<32>, new_key: BytesN<32>, signature: BytesN<64>) {
+    validation::require_active(env);
+
+    signers::check_swap(env, &active_key, &new_key);
+    if !keys(env, &active_key).contains(&new_key) {
+        panic_with_error!(env, SignerError::KeyNotInRing);
+    }
+    let now = clock::now(env);
+    if now < next_activation_at(env, &active_key) {
+        panic_with_error!(env, SignerError::RotationTooSoon);
+    }
+    signers::require_auth(env, &active_key);
+    let nonce = storage::read_signer_nonce(env, &active_key);
+    signatures::consume(env, &active_key, nonce, &activation_digest(env, &active_key, &new_key), &signature);
+
+    signers::swap_key(env, &active_key, &new_key);
+    storage::write_last_ring_activation(env, &new_key, Some(now));
+    RingKeyActivated {
+        old_key: active_key,
+        new_key: new_key.clone(),
+        remaining: keys(env, &new_key).len(),
+    }
+    .publish(env);
+    fingerprint::refresh(env);
+}
+
+/// Moves `old_key`'s ring and activation record to `new_key` when the seat
+/// changes keys. The ring no longer lists the key now active.
+pub fn transfer(env: &Env, old_key: &BytesN<32>, new_key: &BytesN<32>) {
+    let mut ring = keys(env, old_key);
+    if let Some(position) = ring.first_index_of(new_key) {
+        ring.remove(position);
+    }
+    let last_activation = storage::read_last_ring_activation(env, old_key);
+    clear(env, old_key);
+    storage::write_key_ring(env, new_key, (!ring.is_empty()).then_some(&ring));
+    storage::write_last_ring_activation(env, new_key, last_activation);
+}
+
+/// Drops `signer`'s ring and activation record. Called when they stop being a
+/// signer.
+pub fn clear(env: &Env, signer: &BytesN<32>) {
+    storage::write_key_ring(env, signer, None);
+    storage::write_last_ring_activation(env, signer, None);
+}
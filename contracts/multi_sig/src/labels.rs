@@ -0,0 +1,67 @@
+use soroban_sdk::{panic_with_error, BytesN, Env, String, Vec};
+
+use crate::errors::SignerError;
+use crate::events::SignerLabelChanged;
+use crate::history;
+use crate::pagination;
+use crate::signers;
+use crate::storage;
+use crate::types::SignerInfo;
+use crate::validation;
+
+/// Longest signer label, in bytes.
+pub const MAX_LABEL_LEN: u32 = 64;
+
+// A label is a signer's own name for their seat, shown next to the key so
+// members can tell whose approval is missing. It means nothing to the
+// contract: it belongs to the key, goes with the seat when the key is rotated
+// and is dropped with it when the signer is removed, so a departed member's
+// name never lingers on a key.
+
+/// Sets `signer`'s label to `label`, or clears it when `label` is empty. Only
+/// the signer may label themselves.
+pub fn set(env: &Env, signer: BytesN<32>, label: String) {
+    validation::require_active(env);
+    if storage::read_signer_registry(env).is_some() {
+        panic_with_error!(env, SignerError::RegistryManaged);
+    }
+
+    validation::require_signer(env, &signer);
+    signers::require_account_auth(env, &signer);
+
+    if label.len() > MAX_LABEL_LEN {
+        panic_with_error!(env, SignerError::InvalidSignerLabel);
+    }
+    let label = (!label.is_empty()).then_some(label);
+    storage::write_signer_label(env, &signer, label.as_ref());
+    SignerLabelChanged { signer, label }.publish(env);
+}
+
+/// Current signers in the order they joined, with their labels and when they
+/// were added. Empty when a signer registry holds the signer set.
+pub fn signers_detailed(env: &Env, start: u32, limit: u32) -> Vec<SignerInfo> {
+    let mut signers = Vec::new(env);
+    if storage::read_signer_registry(env).is_some() {
+        return signers;
+    }
+    for key in pagination::page(env, storage::read_signer_list(env), start, limit).iter() {
+        signers.push_back(SignerInfo {
+            label: storage::read_signer_label(env, &key),
+            added_at: history::joined_at(env, &key),
+            key,
+        });
+    }
+    signers
+}
+
+/// Moves `old_key`'s label to `new_key` when the seat changes keys.
+pub fn transfer(env: &Env, old_key: &BytesN<32>, new_key: &BytesN<32>) {
+    let label = storage::read_signer_label(env, old_key);
+    storage::write_signer_label(env, old_key, None);
+    storage::write_signer_label(env, new_key, label.as_ref());
+}
+
+/// Drops `signer`'s label. Called when they stop being a signer.
+pub fn clear(env: &Env, signer: &BytesN<32>) {
+    storage::write_signer_label(env, signer, None);
+}
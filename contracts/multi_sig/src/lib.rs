@@ -1,8 +1,171 @@
 #![no_std]
 
+// Everything reachable from the crate root is the supported Rust API for
+// embedding this contract and follows semver. Modules stay private; storage
+// layouts (`DataKey` and the key enums nested in it, `IndexKey`, the legacy
+// proposal structs, proposer usage counters) are deliberately left out since
+// they change between releases.
+
+pub use errors::{GovernanceError, MultisigError, SignerError};
 pub use multisig::*;
+pub use registry::{SignerRegistry, SignerRegistryClient};
+pub use types::{
+    AddressBookEntry,
+    AddressLabel,
+    AllocationEntry,
+    AllocationReport,
+    AllocationTarget,
+    AllowanceAction,
+    ApprovalProgress,
+    AuditEntry,
+    ChangesPage,
+    ConfigSigner,
+    ConfigSnapshot,
+    Delegation,
+    Deposit,
+    DocumentThreshold,
+    ExchangeRate,
+    ExecutionReceipt,
+    ExecutionRecord,
+    ExecutionSchedule,
+    ExecutionTier,
+    ExecutionVerdict,
+    GovernanceAction,
+    GovernanceApproval,
+    GovernanceProposal,
+    ImportedProposal,
+    InactivityRemoval,
+    InitialFunding,
+    InvokeAction,
+    KeyRing,
+    MultisigConfig,
+    Obligation,
+    PaymentProof,
+    PendingCancellation,
+    PolicyAction,
+    PolicyCheck,
+    Proposal,
+    ProposalAction,
+    ProposalApproval,
+    ProposalNote,
+    ProposalOutcome,
+    ProposalPage,
+    ProposalState,
+    ProposalStatus,
+    ProposalTemplate,
+    ProposerCap,
+    RecoveryConfig,
+    RedundancyPolicy,
+    ReserveFloor,
+    Retirement,
+    RoleProgress,
+    RoleRequirement,
+    Secp256r1Key,
+    SessionApprovalReport,
+    Signer,
+    SignerAction,
+    SignerBatch,
+    SignerChangeApproval,
+    SignerChangeKind,
+    SignerChangeProposal,
+    SignerHistoryEntry,
+    SignerInfo,
+    SignerPermissions,
+    SignerPolicy,
+    SignerRoleAssignment,
+    SignerSetChange,
+    SignerSetSnapshot,
+    SigningSession,
+    StorageSchema,
+    TemplateDraft,
+    TerminalRecord,
+    TokenImplementation,
+    TransferAction,
+    TreasuryAction,
+    UnifiedProposal,
+    UnifiedProposalPage,
+    ValueCap,
+    VoteDelegation,
+    WatcherRegistration,
+};
+
+/// Events the contract publishes, for consumers decoding them off-chain or in
+/// tests.
+pub mod events;
+
+/// The handful of items most embedders need, under paths that only change in
+/// a major release: the client, the error enums and the proposal types.
+pub mod prelude {
+    pub use crate::{
+        ApprovalProgress,
+        GovernanceAction,
+        GovernanceError,
+        MultiSigContract,
+        MultiSigContractClient,
+        MultisigError,
+        Proposal,
+        ProposalAction,
+        ProposalOutcome,
+        SignerChangeProposal,
+        SignerError,
+        TransferAction,
+    };
+}
+
+// The hooks let anyone expire proposals and move the contract's clock, so a
+// build meant for deployment must not be able to carry them. Only builds with
+// debug assertions, such as tests or the `release-with-logs` profile staging
+// uses, may enable the feature.
+#[cfg(all(feature = "testing-hooks", not(debug_assertions)))]
+compile_error!("the `testing-hooks` feature is for staging builds only and cannot be used with debug assertions off");
 
+mod account;
+mod activity;
+mod address_book;
+mod audit;
+mod bls;
+mod clock;
+mod counts;
+mod delegation;
 mod errors;
+mod failover;
+mod features;
+mod feed;
+mod fingerprint;
+mod funding;
 mod types;
 mod multisig;
+mod notes;
+mod observers;
+mod obligations;
+mod outcomes;
+mod governance;
+mod history;
+#[cfg(feature = "testing-hooks")]
+mod hooks;
+mod import;
+mod index;
+mod keyring;
+mod labels;
+mod pagination;
+mod payments;
+mod proofs;
+mod rates;
+mod recovery;
+mod redundancy;
+mod registry;
+mod retirement;
+mod roles;
+mod schema;
+mod sessions;
+mod signatures;
+mod signers;
+mod simulation;
+mod spending;
+mod storage;
+mod templates;
+mod treasury;
+mod validation;
+mod watchers;
+mod webauthn;
 mod test;
\ No newline at end of file
@@ -1,245 +1,599 @@
-use soroban_sdk::{contract, contractimpl, panic_with_error, Address, BytesN, Env, String, Vec};
-
+use soroban_sdk::auth::{Context, CustomAccountInterface};
+use soroban_sdk::crypto::Hash;
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, Map, String, Symbol, Vec};
+
+use crate::account;
+use crate::activity;
+use crate::address_book;
+use crate::audit;
+use crate::counts;
+use crate::delegation;
+use crate::features;
+use crate::feed;
+use crate::fingerprint;
+use crate::governance;
+use crate::history;
+use crate::import;
+use crate::keyring;
+use crate::labels;
+#[cfg(feature = "testing-hooks")]
+use crate::hooks;
+use crate::notes;
+use crate::obligations;
+use crate::pagination;
+use crate::payments;
+use crate::proofs;
+use crate::rates;
+use crate::recovery;
+use crate::redundancy;
+use crate::schema;
+use crate::sessions;
+use crate::signers;
+use crate::simulation;
+use crate::spending;
+use crate::storage;
+use crate::templates;
+use crate::treasury;
+use crate::types::{
+    AddressBookEntry, AllocationReport, AllocationTarget, ApprovalProgress, ChangesPage, ConfigSigner, ConfigSnapshot, Delegation, Deposit, ExchangeRate, ExecutionReceipt, ExecutionRecord, ExecutionSchedule, ExecutionVerdict, GovernanceAction, GovernanceApproval,
+    GovernanceProposal, ImportedProposal, InactivityRemoval, InitialFunding, MultisigConfig, Obligation, PaymentProof, PendingCancellation, Proposal, ProposalTemplate, ProposalAction, ProposalApproval, ProposalNote, ProposalPage, ProposalStatus, RecoveryConfig, RoleRequirement, SignerChangeApproval,
+    SignerBatch, SignerChangeKind, SignerChangeProposal, SignerHistoryEntry, SignerInfo, SignerPermissions, SignerPolicy, Secp256r1Key, SessionApprovalReport, Signer, SignerSetSnapshot, SigningSession, StorageSchema, TerminalRecord, TransferAction, UnifiedProposalPage, ValueCap, VoteDelegation,
+};
 use crate::errors::MultisigError;
-use crate::types::{DataKey, Proposal, ProposalApproval, SignerChangeProposal, SignerChangeApproval};
+use crate::validation;
 
 #[contract]
 pub struct MultiSigContract;
 
 #[contractimpl]
 impl MultiSigContract {
-    pub fn initialize(env: Env, signers: Vec<BytesN<32>>, threshold: u32) {
-        if env.storage().instance().has(&DataKey::Initialized) {
-            panic_with_error!(&env, MultisigError::AlreadyInitialized);
-        }
+    /// Sets up the signer set and threshold. Unless `allow_low_security` is
+    /// set, fewer than two signers or a threshold below two is rejected, now and
+    /// in every later governance change.
+    pub fn initialize(env: Env, signers: Vec<BytesN<32>>, threshold: u32, allow_low_security: bool) {
+        signers::initialize(&env, signers, threshold, allow_low_security);
+        fingerprint::refresh(&env);
+    }
 
-        if signers.len() == 0 {
-            panic_with_error!(&env, MultisigError::EmptySignersList);
-        }
+    /// `initialize`, then pulls `funding.amount` of `funding.token` from
+    /// `funding.funder`, who must already have approved this contract as
+    /// spender. If the pull fails, nothing is initialized.
+    pub fn initialize_funded(
+        env: Env,
+        signers: Vec<BytesN<32>>,
+        threshold: u32,
+        allow_low_security: bool,
+        funding: InitialFunding,
+    ) {
+        signers::initialize(&env, signers, threshold, allow_low_security);
+        fingerprint::refresh(&env);
+        payments::pull_initial_funding(&env, &funding);
+    }
 
-        if threshold == 0 {
-            panic_with_error!(&env, MultisigError::InvalidThreshold);
-        }
+    /// `initialize`, then binds each listed signer key to an address. Calls
+    /// that create or approve proposals, revoke approvals or propose and
+    /// approve signer changes as a bound key need that address's
+    /// authorization. Unlisted keys act on the key alone.
+    pub fn initialize_with_addresses(
+        env: Env,
+        signers: Vec<BytesN<32>>,
+        threshold: u32,
+        allow_low_security: bool,
+        addresses: Vec<(BytesN<32>, Address)>,
+    ) {
+        signers::initialize(&env, signers, threshold, allow_low_security);
+        signers::bind_addresses(&env, addresses);
+        fingerprint::refresh(&env);
+    }
 
-        if threshold > signers.len() as u32 {
-            panic_with_error!(&env, MultisigError::ThresholdExceedsSigners);
-        }
+    /// `initialize` with signers that may be contracts or passkeys as well as
+    /// ed25519 keys. Everywhere else a signer is named by its `signer_id`; a
+    /// contract signer's calls need its address's authorization instead of a
+    /// signature. A `policy` holds the signer count within its bounds from
+    /// then on; the initial set must be within it, and only governance can
+    /// change it afterwards.
+    ///
+    /// Signers listed in `permissions` may only do what they are granted
+    /// there. A signer who may only propose drafts proposals but cannot
+    /// approve them, and does not count towards the threshold. Unlisted
+    /// signers may do both.
+    ///
+    /// With `only_signers_execute` the instance starts with `SIGNERS_EXECUTE`
+    /// on: `execute_proposal` is closed and only signers, or a proposal's
+    /// scheduled executors, may execute. Governance can turn it off later.
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize_with_signers(
+        env: Env,
+        signers: Vec<Signer>,
+        threshold: u32,
+        allow_low_security: bool,
+        policy: Option<SignerPolicy>,
+        permissions: Vec<(BytesN<32>, SignerPermissions)>,
+        only_signers_execute: bool,
+    ) {
+        signers::initialize_with_signers(&env, signers, threshold, allow_low_security, policy, permissions, only_signers_execute);
+        fingerprint::refresh(&env);
+    }
 
-        // Check for duplicate signers
-        for i in 0..signers.len() {
-            for j in (i + 1)..signers.len() {
-                if signers.get_unchecked(i) == signers.get_unchecked(j) {
-                    panic_with_error!(&env, MultisigError::DuplicateSigner);
-                }
-            }
-        }
+    /// Id that stands for `signer` in every call taking a signer key.
+    pub fn signer_id(env: Env, signer: Signer) -> BytesN<32> {
+        signer.id(&env)
+    }
 
-        env.storage().instance().set(&DataKey::Initialized, &true);
-        env.storage().instance().set(&DataKey::SignerCount, &(signers.len() as u32));
-        env.storage().instance().set(&DataKey::Threshold, &threshold);
-        env.storage().instance().set(&DataKey::Nonce, &0u64);
+    /// Address whose authorization `signer`'s calls need, if one is bound.
+    pub fn get_signer_address(env: Env, signer: BytesN<32>) -> Option<Address> {
+        validation::require_initialized(&env);
+        storage::read_signer_address(&env, &signer)
+    }
 
-        for signer in signers {
-            env.storage().instance().set(&DataKey::Signer(signer.clone()), &true);
-        }
+    /// The passkey `signer` stands for, if it is one.
+    pub fn get_passkey(env: Env, signer: BytesN<32>) -> Option<Secp256r1Key> {
+        validation::require_initialized(&env);
+        storage::read_passkey(&env, &signer)
+    }
+
+    /// Seed funds pulled by `initialize_funded`, if any.
+    pub fn get_initial_deposit(env: Env) -> Option<Deposit> {
+        validation::require_initialized(&env);
+        storage::read_initial_deposit(&env)
+    }
+
+    /// Alternative to `initialize` for an instance that shares its signer set
+    /// with others through `registry`. Local signer changes are disabled.
+    pub fn initialize_with_registry(env: Env, registry: Address) {
+        signers::initialize_with_registry(&env, registry);
+        fingerprint::refresh(&env);
+    }
+
+    /// Registry holding this instance's signers, if it delegates them.
+    pub fn get_signer_registry(env: Env) -> Option<Address> {
+        validation::require_initialized(&env);
+        storage::read_signer_registry(&env)
     }
 
+    /// Proposes a change to `signer`'s seat: `Add`, `Remove`, `Suspend` or
+    /// `Reinstate`. A suspended signer stays registered but can neither
+    /// propose nor approve until reinstated. Replacements and batches have
+    /// entry points of their own.
     pub fn propose_signer_change(
         env: Env,
         proposer: BytesN<32>,
-        change_type: String,
+        change_type: SignerChangeKind,
         signer: BytesN<32>,
         expires_in_seconds: u64,
     ) -> u64 {
-        Self::require_initialized(&env);
-        
-        // Validate expiry time (1 hour to 30 days)
-        const MIN_EXPIRY_SECONDS: u64 = 3600;      // 1 hour
-        const MAX_EXPIRY_SECONDS: u64 = 2_592_000; // 30 days
-        
-        if expires_in_seconds < MIN_EXPIRY_SECONDS {
-            panic_with_error!(&env, MultisigError::InvalidExpiryTime);
-        }
-        
-        if expires_in_seconds > MAX_EXPIRY_SECONDS {
-            panic_with_error!(&env, MultisigError::InvalidExpiryTime);
-        }
-        
-        // Verify proposer is a signer
-        if !env.storage().instance().has(&DataKey::Signer(proposer.clone())) {
-            panic_with_error!(&env, MultisigError::UnknownSigner);
-        }
-
-        // Validate change type
-        let add_type = String::from_str(&env, "add");
-        let remove_type = String::from_str(&env, "remove");
-        
-        if change_type != add_type && change_type != remove_type {
-            panic_with_error!(&env, MultisigError::InvalidProposal);
-        }
-
-        // For add: check if signer already exists
-        if change_type == add_type && env.storage().instance().has(&DataKey::Signer(signer.clone())) {
-            panic_with_error!(&env, MultisigError::DuplicateSigner);
-        }
-
-        // For remove: check if signer exists
-        if change_type == remove_type && !env.storage().instance().has(&DataKey::Signer(signer.clone())) {
-            panic_with_error!(&env, MultisigError::SignerNotFound);
-        }
+        signers::propose_signer_change(&env, proposer, change_type, signer, expires_in_seconds)
+    }
 
-        // For remove: check threshold constraint
-        if change_type == remove_type {
-            let current_count: u32 = env.storage().instance().get(&DataKey::SignerCount).unwrap();
-            let threshold: u32 = env.storage().instance().get(&DataKey::Threshold).unwrap();
-            
-            if current_count - 1 < threshold {
-                panic_with_error!(&env, MultisigError::ThresholdExceedsSigners);
-            }
-        }
+    /// `propose_signer_change` for any `Signer`, including contracts.
+    pub fn propose_signer_change_v2(
+        env: Env,
+        proposer: BytesN<32>,
+        change_type: SignerChangeKind,
+        signer: Signer,
+        expires_in_seconds: u64,
+    ) -> u64 {
+        signers::propose_signer_change_v2(&env, proposer, change_type, signer, expires_in_seconds)
+    }
 
-        let current_time = env.ledger().timestamp();
-        
-        // Get next proposal ID
-        let current_count: u64 = env.storage().instance()
-            .get(&DataKey::ProposalCount)
-            .unwrap_or(0u64);
-        let proposal_id = current_count + 1;
-        env.storage().instance().set(&DataKey::ProposalCount, &proposal_id);
-        
-        let proposal = SignerChangeProposal {
-            id: proposal_id,
-            proposer: proposer.clone(),
+    /// Signer change with its own approval window: approvals close
+    /// `approval_window_seconds` after creation, and the change cannot execute
+    /// before `execute_not_before` (if set) nor after it expires.
+    pub fn propose_scheduled_signer_change(
+        env: Env,
+        proposer: BytesN<32>,
+        change_type: SignerChangeKind,
+        signer: BytesN<32>,
+        approval_window_seconds: u64,
+        execute_not_before: Option<u64>,
+        expires_in_seconds: u64,
+    ) -> u64 {
+        signers::propose_scheduled_signer_change(
+            &env,
+            proposer,
             change_type,
             signer,
-            created_at: current_time,
-            expires_at: current_time + expires_in_seconds,
-            executed: false,
-        };
+            approval_window_seconds,
+            execute_not_before,
+            expires_in_seconds,
+        )
+    }
 
-        env.storage().instance().set(&DataKey::SignerChangeProposal(proposal_id), &proposal);
-        
-        let approvals: Vec<SignerChangeApproval> = Vec::new(&env);
-        env.storage().instance().set(&DataKey::SignerChangeApprovals(proposal_id), &approvals);
+    /// Proposes removing `signer`. Set `accept_no_redundancy` to allow the
+    /// removal to leave exactly as many signers as the threshold while the
+    /// redundancy policy is `Block`.
+    pub fn propose_signer_removal(
+        env: Env,
+        proposer: BytesN<32>,
+        signer: BytesN<32>,
+        expires_in_seconds: u64,
+        accept_no_redundancy: bool,
+    ) -> u64 {
+        signers::propose_signer_removal(&env, proposer, signer, expires_in_seconds, accept_no_redundancy)
+    }
 
-        proposal_id
+    /// Proposes replacing `old_signer` with `new_signer` in a single signer
+    /// change, so the signer count never changes. Nothing tied to the old key,
+    /// including its approvals, carries over to the new one.
+    pub fn propose_signer_replacement(
+        env: Env,
+        proposer: BytesN<32>,
+        old_signer: BytesN<32>,
+        new_signer: BytesN<32>,
+        expires_in_seconds: u64,
+    ) -> u64 {
+        signers::propose_signer_replacement(&env, proposer, old_signer, new_signer, expires_in_seconds)
+    }
+
+    /// Proposes adding `additions`, removing `removals` and, if given,
+    /// setting `new_threshold`, all approved and executed as one signer
+    /// change. The resulting set is checked as a whole at execution, and
+    /// nothing applies unless all of it does.
+    pub fn propose_signer_batch(
+        env: Env,
+        proposer: BytesN<32>,
+        additions: Vec<BytesN<32>>,
+        removals: Vec<BytesN<32>>,
+        new_threshold: Option<u32>,
+        expires_in_seconds: u64,
+    ) -> u64 {
+        signers::propose_signer_batch(&env, proposer, additions, removals, new_threshold, expires_in_seconds)
     }
 
     pub fn approve_signer_change(env: Env, proposal_id: u64, approver: BytesN<32>) {
-        Self::require_initialized(&env);
-        
-        if !env.storage().instance().has(&DataKey::Signer(approver.clone())) {
-            panic_with_error!(&env, MultisigError::UnknownSigner);
-        }
+        signers::approve_signer_change(&env, proposal_id, approver)
+    }
 
-        if !env.storage().instance().has(&DataKey::SignerChangeProposal(proposal_id)) {
-            panic_with_error!(&env, MultisigError::SignerChangeNotFound);
-        }
+    pub fn revoke_signer_change_approval(env: Env, proposal_id: u64, revoker: BytesN<32>) {
+        signers::revoke_signer_change_approval(&env, proposal_id, revoker)
+    }
 
-        if env.storage().instance().has(&DataKey::SignerChangeExecuted(proposal_id)) {
-            panic_with_error!(&env, MultisigError::SignerChangeAlreadyExecuted);
-        }
+    /// Withdraws signer change `proposal_id` as its proposer, closing it for
+    /// good.
+    pub fn cancel_signer_change(env: Env, proposal_id: u64, proposer: BytesN<32>) {
+        signers::cancel_signer_change(&env, proposal_id, proposer)
+    }
 
-        let proposal: SignerChangeProposal = env.storage().instance().get(&DataKey::SignerChangeProposal(proposal_id)).unwrap();
-        
-        if env.ledger().timestamp() > proposal.expires_at {
-            panic_with_error!(&env, MultisigError::SignerChangeExpired);
-        }
+    pub fn execute_signer_change(env: Env, proposal_id: u64) {
+        signers::execute_signer_change(&env, proposal_id)
+    }
 
-        let mut approvals: Vec<SignerChangeApproval> = env.storage().instance()
-            .get(&DataKey::SignerChangeApprovals(proposal_id)).unwrap_or(Vec::new(&env));
+    /// Swaps signer `old_key` for `new_key` without a signer change proposal.
+    /// `signature` is `old_key`'s over `get_rotation_payload`. The old key's
+    /// approvals on open payment proposals are dropped.
+    pub fn rotate_own_key(env: Env, old_key: BytesN<32>, new_key: BytesN<32>, signature: BytesN<64>) {
+        signers::rotate_own_key(&env, old_key, new_key, signature)
+    }
 
-        // Check if already approved
-        for i in 0..approvals.len() {
-            let approval = approvals.get_unchecked(i);
-            if approval.signer == approver {
-                panic_with_error!(&env, MultisigError::SignerChangeAlreadyApproved);
-            }
-        }
+    /// The hash `old_key` must sign to rotate to `new_key` next.
+    pub fn get_rotation_payload(env: Env, old_key: BytesN<32>, new_key: BytesN<32>) -> BytesN<32> {
+        signers::get_rotation_payload(&env, &old_key, &new_key)
+    }
 
-        let approval = SignerChangeApproval {
-            signer: approver,
-            approved_at: env.ledger().timestamp(),
-        };
+    /// Switches `active_key`'s seat to `new_key`, which governance must have
+    /// put in its key ring with `SetKeyRing`. `signature` is `active_key`'s
+    /// over `get_ring_activation_payload`. At most one switch per seat per
+    /// day; otherwise it works like `rotate_own_key`.
+    pub fn activate_ring_key(env: Env, active_key: BytesN<32>, new_key: BytesN<32>, signature: BytesN<64>) {
+        keyring::activate(&env, active_key, new_key, signature)
+    }
 
-        approvals.push_back(approval);
-        env.storage().instance().set(&DataKey::SignerChangeApprovals(proposal_id), &approvals);
+    /// The hash `active_key` must sign to switch to ring key `new_key` next.
+    pub fn get_ring_activation_payload(env: Env, active_key: BytesN<32>, new_key: BytesN<32>) -> BytesN<32> {
+        keyring::get_activation_payload(&env, &active_key, &new_key)
     }
 
-    pub fn execute_signer_change(env: Env, proposal_id: u64) {
-        Self::require_initialized(&env);
-        
-        if !env.storage().instance().has(&DataKey::SignerChangeProposal(proposal_id)) {
-            panic_with_error!(&env, MultisigError::SignerChangeNotFound);
-        }
+    /// Keys `signer` may still switch to, and the earliest time they may.
+    pub fn get_key_ring(env: Env, signer: BytesN<32>) -> (Vec<BytesN<32>>, u64) {
+        validation::require_initialized(&env);
+        (keyring::keys(&env, &signer), keyring::next_activation_at(&env, &signer))
+    }
 
-        if env.storage().instance().has(&DataKey::SignerChangeExecuted(proposal_id)) {
-            panic_with_error!(&env, MultisigError::SignerChangeAlreadyExecuted);
-        }
+    /// Replaces the whole signer set with `new_signers` and `new_threshold` on
+    /// the authority of the recovery key governance set with `SetRecovery`.
+    /// `signature` is the recovery key's over `get_recovery_payload`. Only
+    /// possible once more than the configured inactivity period has passed
+    /// without any approval or execution.
+    pub fn recovery_claim(env: Env, new_signers: Vec<BytesN<32>>, new_threshold: u32, signature: BytesN<64>) {
+        recovery::claim(&env, new_signers, new_threshold, signature)
+    }
 
-        let proposal: SignerChangeProposal = env.storage().instance().get(&DataKey::SignerChangeProposal(proposal_id)).unwrap();
-        
-        if env.ledger().timestamp() > proposal.expires_at {
-            panic_with_error!(&env, MultisigError::SignerChangeExpired);
-        }
+    /// The hash the recovery key must sign to install `new_signers` next.
+    pub fn get_recovery_payload(env: Env, new_signers: Vec<BytesN<32>>, new_threshold: u32) -> BytesN<32> {
+        recovery::get_claim_payload(&env, &new_signers, new_threshold)
+    }
 
-        let approvals: Vec<SignerChangeApproval> = env.storage().instance()
-            .get(&DataKey::SignerChangeApprovals(proposal_id)).unwrap_or(Vec::new(&env));
+    /// The recovery configuration, if any, and the time after which its key
+    /// may call `recovery_claim`.
+    pub fn get_recovery(env: Env) -> Option<(RecoveryConfig, u64)> {
+        validation::require_initialized(&env);
+        recovery::status(&env)
+    }
 
-        let threshold: u32 = env.storage().instance().get(&DataKey::Threshold).unwrap();
-        
-        if approvals.len() < threshold {
-            panic_with_error!(&env, MultisigError::InsufficientSignerChangeApprovals);
-        }
+    /// Adds signers carried over from a legacy multisig. Only possible while
+    /// `import_open`, and with the contract's own authorization, that is
+    /// threshold signatures through `__check_auth`.
+    pub fn import_signers(env: Env, signers: Vec<BytesN<32>>) {
+        import::import_signers(&env, signers)
+    }
 
-        // Execute the signer change
-        let add_type = String::from_str(&env, "add");
-        let remove_type = String::from_str(&env, "remove");
-        
-        if proposal.change_type == add_type {
-            env.storage().instance().set(&DataKey::Signer(proposal.signer.clone()), &true);
-            let current_count: u32 = env.storage().instance().get(&DataKey::SignerCount).unwrap();
-            env.storage().instance().set(&DataKey::SignerCount, &(current_count + 1));
-        } else if proposal.change_type == remove_type {
-            env.storage().instance().remove(&DataKey::Signer(proposal.signer.clone()));
-            let current_count: u32 = env.storage().instance().get(&DataKey::SignerCount).unwrap();
-            env.storage().instance().set(&DataKey::SignerCount, &(current_count - 1));
-        }
+    /// Carries over an open proposal from a legacy multisig, with its
+    /// approvals there pre-recorded, and returns its id here. Gated like
+    /// `import_signers`.
+    pub fn import_proposal(env: Env, legacy: ImportedProposal) -> u64 {
+        import::import_proposal(&env, legacy)
+    }
+
+    /// Closes the import phase for good. Gated like `import_signers`.
+    pub fn finish_import(env: Env) {
+        import::finish_import(&env)
+    }
+
+    /// Whether governance has paused payment proposals.
+    pub fn is_paused(env: Env) -> bool {
+        validation::require_initialized(&env);
+        storage::is_paused(&env)
+    }
 
-        // Mark as executed
-        env.storage().instance().set(&DataKey::SignerChangeExecuted(proposal_id), &true);
-        
-        let mut updated_proposal = proposal;
-        updated_proposal.executed = true;
-        env.storage().instance().set(&DataKey::SignerChangeProposal(proposal_id), &updated_proposal);
+    /// The address a retired contract swept its treasury to, or `None` while
+    /// it is active. Once set, every call that changes state fails with
+    /// `ContractRetired`.
+    pub fn get_successor(env: Env) -> Option<Address> {
+        validation::require_initialized(&env);
+        storage::read_successor(&env)
+    }
+
+    /// Whether `import_signers` and `import_proposal` are still available.
+    pub fn import_open(env: Env) -> bool {
+        validation::require_initialized(&env);
+        import::is_open(&env)
+    }
+
+    /// The legacy id `proposal_id` was imported from, or `None` if it was
+    /// created here.
+    pub fn get_import_ref(env: Env, proposal_id: u64) -> Option<u64> {
+        validation::require_initialized(&env);
+        storage::read_import_ref(&env, proposal_id)
+    }
+
+    /// The proposal legacy proposal `external_ref` was imported as.
+    pub fn find_imported_proposal(env: Env, external_ref: u64) -> Option<u64> {
+        validation::require_initialized(&env);
+        storage::read_imported_proposal(&env, external_ref)
+    }
+
+    pub fn propose_governance(
+        env: Env,
+        proposer: BytesN<32>,
+        action: GovernanceAction,
+        expires_in_seconds: u64,
+    ) -> u64 {
+        governance::propose_governance(&env, proposer, action, expires_in_seconds)
+    }
+
+    pub fn approve_governance(env: Env, proposal_id: u64, approver: BytesN<32>) {
+        governance::approve_governance(&env, proposal_id, approver)
+    }
+
+    pub fn execute_governance(env: Env, proposal_id: u64) {
+        governance::execute_governance(&env, proposal_id)
     }
 
     pub fn threshold(env: Env) -> u32 {
-        Self::require_initialized(&env);
-        env.storage().instance().get(&DataKey::Threshold).unwrap()
+        validation::require_initialized(&env);
+        storage::read_threshold(&env)
     }
 
     pub fn signer_count(env: Env) -> u32 {
-        Self::require_initialized(&env);
-        env.storage().instance().get(&DataKey::SignerCount).unwrap()
+        validation::require_initialized(&env);
+        storage::read_signer_count(&env)
+    }
+
+    /// Signer count, threshold and how the contract treats having no spare
+    /// signers, including whether it currently has none.
+    pub fn get_config(env: Env) -> MultisigConfig {
+        validation::require_initialized(&env);
+        redundancy::config(&env)
+    }
+
+    /// sha256 of the canonical encoding of `get_config_snapshot`: the signer
+    /// set, threshold and every other setting that decides who can move funds
+    /// and how. Counterparties compare it against what they were told.
+    pub fn get_config_fingerprint(env: Env) -> BytesN<32> {
+        validation::require_initialized(&env);
+        fingerprint::fingerprint(&env)
+    }
+
+    /// The configuration `get_config_fingerprint` hashes.
+    pub fn get_config_snapshot(env: Env) -> ConfigSnapshot {
+        validation::require_initialized(&env);
+        fingerprint::snapshot(&env)
     }
 
     pub fn nonce(env: Env) -> u64 {
-        Self::require_initialized(&env);
-        env.storage().instance().get(&DataKey::Nonce).unwrap()
+        validation::require_initialized(&env);
+        storage::read_nonce(&env)
     }
 
     pub fn is_signer(env: Env, signer: BytesN<32>) -> bool {
-        Self::require_initialized(&env);
-        env.storage().instance().has(&DataKey::Signer(signer))
+        validation::require_initialized(&env);
+        storage::is_signer(&env, &signer)
+    }
+
+    /// Whether `signer` is a signer and not suspended.
+    pub fn is_signer_active(env: Env, signer: BytesN<32>) -> bool {
+        validation::require_initialized(&env);
+        storage::is_signer(&env, &signer) && !signers::is_suspended(&env, &signer)
     }
 
-    fn require_initialized(env: &Env) {
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            panic_with_error!(env, MultisigError::NotInitialized);
+    /// Up to `limit` signers sorted by id, from index `start`, with their
+    /// bound address, role, protection and whether they are suspended. Empty
+    /// when a signer registry holds the signer set.
+    pub fn get_signers(env: Env, start: u32, limit: u32) -> Vec<ConfigSigner> {
+        validation::require_initialized(&env);
+        fingerprint::config_signers_page(&env, start, limit)
+    }
+
+    /// Up to `limit` keys of current signers in the order they joined, from
+    /// index `start`. Empty when a signer registry holds the signer set.
+    pub fn get_signer_keys(env: Env, start: u32, limit: u32) -> Vec<BytesN<32>> {
+        validation::require_initialized(&env);
+        if storage::read_signer_registry(&env).is_some() {
+            return Vec::new(&env);
         }
+        pagination::page(&env, storage::read_signer_list(&env), start, limit)
+    }
+
+    /// What `signer` may do. Signers never restricted may propose and approve.
+    pub fn get_signer_permissions(env: Env, signer: BytesN<32>) -> SignerPermissions {
+        validation::require_initialized(&env);
+        signers::permissions(&env, &signer)
+    }
+
+    /// When `signer` last proposed or approved anything, or joined if they
+    /// have not yet.
+    pub fn get_signer_last_active(env: Env, signer: BytesN<32>) -> u64 {
+        validation::require_initialized(&env);
+        validation::require_signer(&env, &signer);
+        activity::last_active(&env, &signer)
+    }
+
+    /// The reduced threshold for removing inactive signers, if governance
+    /// turned it on.
+    pub fn get_inactivity_removal(env: Env) -> Option<InactivityRemoval> {
+        validation::require_initialized(&env);
+        storage::read_inactivity_removal(&env)
+    }
+
+    /// Bounds on the signer count, if governance or initialization set any.
+    pub fn get_signer_policy(env: Env) -> Option<SignerPolicy> {
+        validation::require_initialized(&env);
+        storage::read_signer_policy(&env)
+    }
+
+    /// Up to `limit` signers in the order they joined, from index `start`,
+    /// with their label and when they were added. Empty when a signer
+    /// registry holds the signer set.
+    pub fn get_signers_detailed(env: Env, start: u32, limit: u32) -> Vec<SignerInfo> {
+        validation::require_initialized(&env);
+        labels::signers_detailed(&env, start, limit)
+    }
+
+    /// Sets `signer`'s label, at most `MAX_LABEL_LEN` bytes, so other members
+    /// can tell who holds the key. An empty label clears it. Only the signer
+    /// may label themselves, and the label is dropped when they are removed.
+    pub fn set_signer_label(env: Env, signer: BytesN<32>, label: String) {
+        labels::set(&env, signer, label)
+    }
+
+    pub fn get_signer_label(env: Env, signer: BytesN<32>) -> Option<String> {
+        validation::require_initialized(&env);
+        storage::read_signer_label(&env, &signer)
+    }
+
+    /// `is_signer` for any `Signer`, including contracts.
+    pub fn is_signer_v2(env: Env, signer: Signer) -> bool {
+        validation::require_initialized(&env);
+        storage::is_signer(&env, &signer.id(&env))
+    }
+
+    pub fn is_protected_signer(env: Env, signer: BytesN<32>) -> bool {
+        validation::require_initialized(&env);
+        storage::is_protected_signer(&env, &signer)
+    }
+
+    /// Approvals needed to remove a protected signer.
+    pub fn get_protected_quorum(env: Env) -> u32 {
+        validation::require_initialized(&env);
+        signers::protected_quorum(&env)
+    }
+
+    /// Appends `text` to the notes thread of a proposal of any family, open
+    /// or closed. Notes cannot be edited or removed. Returns the note's index.
+    pub fn add_note(env: Env, proposal_id: u64, author: BytesN<32>, text: String) -> u32 {
+        notes::add(&env, proposal_id, author, text)
+    }
+
+    /// Up to `limit` notes of `proposal_id`, oldest first, starting at index
+    /// `start`. Only signers and observers may read them.
+    pub fn get_notes(env: Env, reader: BytesN<32>, proposal_id: u64, start: u32, limit: u32) -> Vec<ProposalNote> {
+        validation::require_initialized(&env);
+        notes::page(&env, &reader, proposal_id, start, limit)
+    }
+
+    /// Read-only keys governance has registered for integrations. Observers
+    /// pass the same view gating as signers but cannot propose or approve.
+    pub fn get_observers(env: Env) -> Vec<BytesN<32>> {
+        validation::require_initialized(&env);
+        storage::read_observers(&env)
+    }
+
+    /// Payment-proposal changes from audit sequence `from_seq` on, at most
+    /// `limit` entries, with the current state of each proposal they touch.
+    /// Sequence numbers start at 1; a client that stores `next_seq` and
+    /// applies each page's proposal states stays in sync without re-reading
+    /// everything. `Ready` is evaluated at read time, so a threshold or
+    /// signer-set change can flip it without a new entry.
+    pub fn get_changes_since(env: Env, from_seq: u64, limit: u32) -> ChangesPage {
+        validation::require_initialized(&env);
+        audit::changes_since(&env, from_seq, limit)
     }
 
+    /// Signer-set mutations in order, at most `limit` per call. Sequence numbers
+    /// start at 1; pass 0 or 1 for the first page.
+    pub fn get_signer_history(env: Env, from_seq: u64, limit: u32) -> Vec<SignerHistoryEntry> {
+        validation::require_initialized(&env);
+        history::entries(&env, from_seq, limit)
+    }
+
+    /// The signer change or governance proposal whose execution recorded
+    /// history entry `seq`. Entries from initialization, recovery, key
+    /// rotations and imports have none.
+    pub fn get_signer_history_proposal(env: Env, seq: u64) -> Option<u64> {
+        validation::require_initialized(&env);
+        storage::read_signer_history_proposal(&env, seq)
+    }
+
+    /// The signer set and threshold in force at `timestamp`, rebuilt from the
+    /// history.
+    pub fn get_signer_set_at(env: Env, timestamp: u64) -> SignerSetSnapshot {
+        validation::require_initialized(&env);
+        history::signer_set_at(&env, timestamp)
+    }
+
+    /// Version of the signer set, bumped by every change to it.
+    pub fn get_signer_set_version(env: Env) -> u64 {
+        validation::require_initialized(&env);
+        history::version(&env)
+    }
+
+    /// Signer set version `proposal_id` was created under. With
+    /// `SNAPSHOT_SIGNERS` on, signers who joined after it cannot approve.
+    pub fn get_proposal_signer_set_version(env: Env, proposal_id: u64) -> Option<u64> {
+        validation::require_initialized(&env);
+        storage::read_proposal_signer_set_version(&env, proposal_id)
+    }
+
+    /// Signer who must give the last approval on every payment proposal, if
+    /// governance has named one.
+    pub fn get_final_approver(env: Env) -> Option<BytesN<32>> {
+        validation::require_initialized(&env);
+        storage::read_final_approver(&env)
+    }
+
+    pub fn get_signer_role(env: Env, signer: BytesN<32>) -> Option<Symbol> {
+        validation::require_initialized(&env);
+        storage::read_signer_role(&env, &signer)
+    }
+
+    /// Per-role approval counts required while staged approval is enabled.
+    pub fn get_role_requirements(env: Env) -> Vec<RoleRequirement> {
+        validation::require_initialized(&env);
+        storage::read_role_requirements(&env)
+    }
+
+    /// Creates a token transfer proposal. Kept for compatibility; equivalent to
+    /// `create_proposal_v2` with a `ProposalAction::Transfer`.
     pub fn create_proposal(
         env: Env,
         proposer: BytesN<32>,
@@ -249,237 +603,877 @@ impl MultiSigContract {
         reason: String,
         expires_in_seconds: u64,
     ) -> u64 {
-        Self::require_initialized(&env);
-        
-        // Validate expiry time (1 hour to 30 days)
-        const MIN_EXPIRY_SECONDS: u64 = 3600;      // 1 hour
-        const MAX_EXPIRY_SECONDS: u64 = 2_592_000; // 30 days
-        
-        if expires_in_seconds < MIN_EXPIRY_SECONDS {
-            panic_with_error!(&env, MultisigError::InvalidExpiryTime);
-        }
-        
-        if expires_in_seconds > MAX_EXPIRY_SECONDS {
-            panic_with_error!(&env, MultisigError::InvalidExpiryTime);
-        }
-        
-        // Verify proposer is a signer
-        if !env.storage().instance().has(&DataKey::Signer(proposer.clone())) {
-            panic_with_error!(&env, MultisigError::UnknownSigner);
-        }
-
-        if amount <= 0 {
-            panic_with_error!(&env, MultisigError::InvalidProposal);
-        }
-
-        let current_time = env.ledger().timestamp();
-        
-        // Get next proposal ID directly from storage
-        let current_count: u64 = env.storage().instance()
-            .get(&DataKey::ProposalCount)
-            .unwrap_or(0u64);
-        let proposal_id = current_count + 1;
-        env.storage().instance().set(&DataKey::ProposalCount, &proposal_id);
-        
-        let proposal = Proposal {
-            id: proposal_id,
-            proposer: proposer.clone(),
+        let action = ProposalAction::Transfer(TransferAction {
             token_address,
             recipient,
             amount,
-            reason,
-            created_at: current_time,
-            expires_at: current_time + expires_in_seconds,
-            executed: false,
-        };
+        });
+        payments::create_proposal(&env, proposer, action, reason, expires_in_seconds)
+    }
 
-        env.storage().instance().set(&DataKey::Proposal(proposal_id), &proposal);
-        
-        let approvals: Vec<ProposalApproval> = Vec::new(&env);
-        env.storage().instance().set(&DataKey::ProposalApprovals(proposal_id), &approvals);
+    pub fn create_proposal_v2(
+        env: Env,
+        proposer: BytesN<32>,
+        action: ProposalAction,
+        reason: String,
+        expires_in_seconds: u64,
+    ) -> u64 {
+        payments::create_proposal(&env, proposer, action, reason, expires_in_seconds)
+    }
 
-        proposal_id
+    /// `create_proposal_v2` for a transfer whose recipient needs a memo to
+    /// attribute it, such as an exchange deposit address. The memo is stored
+    /// on the proposal and published when it executes.
+    pub fn create_proposal_with_memo(
+        env: Env,
+        proposer: BytesN<32>,
+        action: ProposalAction,
+        reason: String,
+        destination_memo: String,
+        expires_in_seconds: u64,
+    ) -> u64 {
+        payments::create_proposal_with_details(&env, proposer, action, reason, Some(destination_memo), None, expires_in_seconds)
     }
 
-    pub fn approve_proposal(env: Env, proposal_id: u64, approver: BytesN<32>) {
-        Self::require_initialized(&env);
-        
-        if !env.storage().instance().has(&DataKey::Signer(approver.clone())) {
-            panic_with_error!(&env, MultisigError::UnknownSigner);
-        }
+    /// `create_proposal_v2` backed by a document, such as a signed invoice.
+    /// `document_hash` is stored on the proposal, covered by its content hash
+    /// so approvals commit to it, and published when it executes. Payments
+    /// above their token's `get_document_threshold` must be created this way.
+    pub fn create_proposal_with_document(
+        env: Env,
+        proposer: BytesN<32>,
+        action: ProposalAction,
+        reason: String,
+        destination_memo: Option<String>,
+        document_hash: BytesN<32>,
+        expires_in_seconds: u64,
+    ) -> u64 {
+        payments::create_proposal_with_details(&env, proposer, action, reason, destination_memo, Some(document_hash), expires_in_seconds)
+    }
 
-        if !env.storage().instance().has(&DataKey::Proposal(proposal_id)) {
-            panic_with_error!(&env, MultisigError::ProposalNotFound);
-        }
+    /// `create_proposal_v2` for a time-critical payment such as payroll.
+    /// Signers may execute it at any time, `schedule.executors` from
+    /// `schedule.fallback_at` and anyone from `schedule.public_at`, so it goes
+    /// out even when no signer is available.
+    pub fn create_proposal_with_schedule(
+        env: Env,
+        proposer: BytesN<32>,
+        action: ProposalAction,
+        reason: String,
+        expires_in_seconds: u64,
+        schedule: ExecutionSchedule,
+    ) -> u64 {
+        payments::create_proposal_with_schedule(&env, proposer, action, reason, expires_in_seconds, schedule)
+    }
 
-        if env.storage().instance().has(&DataKey::ProposalExecuted(proposal_id)) {
-            panic_with_error!(&env, MultisigError::ProposalAlreadyExecuted);
-        }
+    /// Ids of every payment proposal created against `document_hash`, oldest
+    /// first and including closed ones. More than one means the same document
+    /// has been proposed for payment again.
+    pub fn find_proposal_by_document(env: Env, document_hash: BytesN<32>) -> Vec<u64> {
+        payments::find_proposal_by_document(&env, &document_hash)
+    }
 
-        let proposal: Proposal = env.storage().instance().get(&DataKey::Proposal(proposal_id)).unwrap();
-        
-        if env.ledger().timestamp() > proposal.expires_at {
-            panic_with_error!(&env, MultisigError::ProposalExpired);
-        }
+    /// `create_proposal_v2` with a client-chosen `idempotency_key`. Retrying
+    /// with the same proposer and key returns the id of the proposal the first
+    /// attempt created, as long as that proposal is still open.
+    pub fn create_proposal_with_key(
+        env: Env,
+        proposer: BytesN<32>,
+        action: ProposalAction,
+        reason: String,
+        expires_in_seconds: u64,
+        idempotency_key: BytesN<32>,
+    ) -> u64 {
+        payments::create_proposal_with_key(&env, proposer, action, reason, expires_in_seconds, idempotency_key)
+    }
 
-        let mut approvals: Vec<ProposalApproval> = env.storage().instance()
-            .get(&DataKey::ProposalApprovals(proposal_id)).unwrap_or(Vec::new(&env));
+    /// Creates a transfer proposal from an active template, supplying only the
+    /// amount. The template's token and recipient are used as stored.
+    pub fn create_proposal_from_template(
+        env: Env,
+        proposer: BytesN<32>,
+        template_id: u32,
+        amount: i128,
+        reason: String,
+        expires_in_seconds: u64,
+    ) -> u64 {
+        payments::create_proposal_from_template(&env, proposer, template_id, amount, reason, expires_in_seconds)
+    }
 
-        // Check if already approved
-        for i in 0..approvals.len() {
-            let approval = approvals.get_unchecked(i);
-            if approval.signer == approver {
-                panic_with_error!(&env, MultisigError::AlreadyApproved);
-            }
-        }
+    /// Every template ever created, including deactivated ones, by id.
+    pub fn get_templates(env: Env) -> Vec<ProposalTemplate> {
+        validation::require_initialized(&env);
+        templates::all(&env)
+    }
 
-        let approval = ProposalApproval {
-            signer: approver,
-            approved_at: env.ledger().timestamp(),
-        };
+    /// Template a payment proposal was created from, if any.
+    pub fn get_proposal_template(env: Env, proposal_id: u64) -> Option<u32> {
+        validation::require_initialized(&env);
+        storage::read_proposal_template(&env, proposal_id)
+    }
 
-        approvals.push_back(approval);
-        env.storage().instance().set(&DataKey::ProposalApprovals(proposal_id), &approvals);
+    /// Creates a transfer proposal paying address-book entry `entry_id`. The
+    /// entry's label is recorded with the proposal.
+    pub fn create_proposal_to_entry(
+        env: Env,
+        proposer: BytesN<32>,
+        token_address: Address,
+        entry_id: u32,
+        amount: i128,
+        reason: String,
+        expires_in_seconds: u64,
+    ) -> u64 {
+        payments::create_proposal_to_entry(&env, proposer, token_address, entry_id, amount, reason, expires_in_seconds)
+    }
+
+    pub fn get_address_book(env: Env) -> Vec<AddressBookEntry> {
+        validation::require_initialized(&env);
+        address_book::entries(&env)
+    }
+
+    /// Label of the address-book entry a proposal was created against, if any.
+    pub fn get_recipient_label(env: Env, proposal_id: u64) -> Option<Symbol> {
+        validation::require_initialized(&env);
+        storage::read_recipient_label(&env, proposal_id)
+    }
+
+    /// Dry run of `create_proposal`: returns the name of every rule the draft
+    /// breaks (`invalid_expiry`, `unknown_proposer`, `invalid_amount`,
+    /// `amount_too_large`, `token_frozen`, `proposer_cap`, `unlisted_recipient`,
+    /// `memo_required`, `document_required`, `stale_rate`).
+    /// An empty list means it would be accepted.
+    pub fn validate_draft(
+        env: Env,
+        proposer: BytesN<32>,
+        token_address: Address,
+        recipient: Address,
+        amount: i128,
+        expires_in_seconds: u64,
+    ) -> Vec<Symbol> {
+        payments::validate_draft(&env, proposer, token_address, recipient, amount, expires_in_seconds)
+    }
+
+    /// Dry run of executing a transfer of `amount` of `token` to `recipient` at
+    /// `execute_at`, with no proposal needed. Reports each rule checked
+    /// (`amount`, `execute_at`, `token_frozen`, `unlisted_recipient`,
+    /// `memo_required`, `document_required`, `balance`, `reserve_floor`) with
+    /// the numbers behind it. Proposer caps are `validate_draft`'s to report.
+    pub fn simulate_draft_execution(env: Env, token: Address, recipient: Address, amount: i128, execute_at: u64) -> ExecutionVerdict {
+        simulation::simulate(&env, token, recipient, amount, execute_at)
+    }
+
+    /// How much more of `token` `proposer` may propose moving in the current
+    /// cap window, or `None` when the token has no proposer cap.
+    pub fn get_proposer_capacity(env: Env, proposer: BytesN<32>, token: Address) -> Option<i128> {
+        validation::require_initialized(&env);
+        spending::remaining(&env, &proposer, &token)
+    }
+
+    /// How much more value, in the reference unit, `proposer` may propose
+    /// moving across all tokens in the current window, or `None` when
+    /// governance has set no value cap.
+    pub fn get_proposer_value_capacity(env: Env, proposer: BytesN<32>) -> Option<i128> {
+        validation::require_initialized(&env);
+        spending::remaining_value(&env, &proposer)
+    }
+
+    /// Approves `proposal_id` as `approver`. `signature` is `approver`'s
+    /// ed25519 signature over `get_approval_payload`, made at their nonce
+    /// `nonce`, so anyone may relay it. A replayed signature fails with
+    /// `InvalidNonce`.
+    pub fn approve_proposal_signed(env: Env, proposal_id: u64, approver: BytesN<32>, nonce: u64, signature: BytesN<64>) {
+        payments::approve_proposal_signed(&env, proposal_id, approver, nonce, signature)
+    }
+
+    /// Approves `proposal_id` as the contract signer at `signer`, which must
+    /// authorize the call.
+    pub fn approve_proposal_as_contract(env: Env, proposal_id: u64, signer: Address) {
+        payments::approve_proposal_as_contract(&env, proposal_id, signer)
+    }
+
+    /// Approves `proposal_id` as the passkey signer `approver` from a WebAuthn
+    /// assertion whose challenge is `get_approval_payload` at their nonce
+    /// `nonce`. The authenticator data and client data JSON are relayed as
+    /// the authenticator produced them.
+    pub fn approve_proposal_webauthn(
+        env: Env,
+        proposal_id: u64,
+        approver: BytesN<32>,
+        nonce: u64,
+        authenticator_data: Bytes,
+        client_data_json: Bytes,
+        signature: BytesN<64>,
+    ) {
+        payments::approve_proposal_webauthn(&env, proposal_id, approver, nonce, authenticator_data, client_data_json, signature)
+    }
+
+    /// The hash `approver` must sign to approve `proposal_id` as it currently
+    /// stands, derived from `get_proposal_hash` and their `signer_nonce`. It
+    /// changes whenever either does. For secp256k1 signers both steps hash
+    /// with keccak256 instead, starting from `get_proposal_hash_keccak`.
+    pub fn get_approval_payload(env: Env, proposal_id: u64, approver: BytesN<32>) -> BytesN<32> {
+        payments::get_approval_payload(&env, proposal_id, &approver)
+    }
+
+    /// Approves `proposal_id` as the secp256k1 signer `approver` from a
+    /// recoverable signature over `get_approval_payload` at their nonce
+    /// `nonce`. A signature that recovers to any other key fails with
+    /// `RecoveredKeyMismatch`.
+    pub fn approve_proposal_secp256k1(env: Env, proposal_id: u64, approver: BytesN<32>, nonce: u64, signature: BytesN<64>, recovery_id: u32) {
+        payments::approve_proposal_secp256k1(&env, proposal_id, approver, nonce, signature, recovery_id)
+    }
+
+    /// Approves `proposal_id` for `approver` from their signature, submitted
+    /// by `relayer`, who authorizes the call and pays for it. The signature is
+    /// over `get_relay_payload` at their nonce `nonce` and is refused after
+    /// `valid_until`. The approval records `relayer`.
+    pub fn relay_approval(env: Env, proposal_id: u64, approver: BytesN<32>, nonce: u64, signature: BytesN<64>, valid_until: u64, relayer: Address) {
+        payments::relay_approval(&env, proposal_id, approver, nonce, signature, valid_until, relayer)
+    }
+
+    /// The hash `approver` must sign for a relayer to approve `proposal_id`
+    /// on their behalf until `valid_until`.
+    pub fn get_relay_payload(env: Env, proposal_id: u64, approver: BytesN<32>, valid_until: u64) -> BytesN<32> {
+        payments::get_relay_payload(&env, proposal_id, &approver, valid_until)
+    }
+
+    /// Rejects `proposal_id` as `rejecter`, who has not approved it.
+    /// `signature` is over `get_rejection_payload` at their nonce `nonce`.
+    /// With the `strict_rj` feature on, the rejection after which the proposal
+    /// can no longer reach its requirement closes it as `Rejected`.
+    pub fn reject_proposal_signed(env: Env, proposal_id: u64, rejecter: BytesN<32>, nonce: u64, signature: BytesN<64>) {
+        payments::reject_proposal_signed(&env, proposal_id, rejecter, nonce, signature)
+    }
+
+    /// The hash `rejecter` must sign to reject `proposal_id` as it currently
+    /// stands.
+    pub fn get_rejection_payload(env: Env, proposal_id: u64, rejecter: BytesN<32>) -> BytesN<32> {
+        payments::get_rejection_payload(&env, proposal_id, &rejecter)
+    }
+
+    /// Signers who have rejected `proposal_id`, in the order they did.
+    pub fn get_proposal_rejections(env: Env, proposal_id: u64) -> Vec<BytesN<32>> {
+        validation::require_initialized(&env);
+        storage::read_proposal_rejections(&env, proposal_id)
+    }
+
+    /// The nonce `signer`'s next signature must be made at. Every accepted
+    /// signature uses one up.
+    pub fn signer_nonce(env: Env, signer: BytesN<32>) -> u64 {
+        validation::require_initialized(&env);
+        storage::read_signer_nonce(&env, &signer)
+    }
+
+    /// Snapshots a set of open payment proposals for a signing ceremony and
+    /// returns the session id. Each signer then approves them all at once by
+    /// signing `get_session_payload` and passing the signature to
+    /// `approve_session`.
+    pub fn open_signing_session(env: Env, opener: BytesN<32>, proposal_ids: Vec<u64>, expires_in_seconds: u64) -> u64 {
+        sessions::open(&env, opener, proposal_ids, expires_in_seconds)
+    }
+
+    /// Approves, as `signer`, every proposal in the session that is still open,
+    /// unchanged since the snapshot and not yet approved by them. `signature`
+    /// is `signer`'s ed25519 signature over `get_session_payload`, made at
+    /// their nonce `nonce`. Skipped proposals are listed in the report rather
+    /// than failing the call.
+    pub fn approve_session(
+        env: Env,
+        session_id: u64,
+        signer: BytesN<32>,
+        nonce: u64,
+        signature: BytesN<64>,
+    ) -> SessionApprovalReport {
+        sessions::approve(&env, session_id, signer, nonce, signature)
+    }
+
+    /// The hash `signer` must sign to approve the session next: its
+    /// `session_hash` bound to their `signer_nonce`.
+    pub fn get_session_payload(env: Env, session_id: u64, signer: BytesN<32>) -> BytesN<32> {
+        sessions::next_payload(&env, session_id, &signer)
+    }
+
+    pub fn get_signing_session(env: Env, session_id: u64) -> SigningSession {
+        validation::require_initialized(&env);
+        sessions::load(&env, session_id)
     }
 
     pub fn revoke_approval(env: Env, proposal_id: u64, revoker: BytesN<32>) {
-        Self::require_initialized(&env);
-        
-        if !env.storage().instance().has(&DataKey::Signer(revoker.clone())) {
-            panic_with_error!(&env, MultisigError::UnknownSigner);
-        }
+        payments::revoke_approval(&env, proposal_id, revoker)
+    }
 
-        if !env.storage().instance().has(&DataKey::Proposal(proposal_id)) {
-            panic_with_error!(&env, MultisigError::ProposalNotFound);
-        }
+    /// Drops approvals on an open proposal by keys that are no longer signers
+    /// and returns how many went. Anyone may call it.
+    pub fn purge_stale_approvals(env: Env, proposal_id: u64) -> u32 {
+        payments::purge_stale_approvals(&env, proposal_id)
+    }
 
-        if env.storage().instance().has(&DataKey::ProposalExecuted(proposal_id)) {
-            panic_with_error!(&env, MultisigError::ProposalAlreadyExecuted);
-        }
+    /// Hands `delegator`'s vote on a single proposal to `delegate`. The delegate's
+    /// approval on that proposal then also counts as the delegator's, as long as
+    /// both are still signers when the proposal is executed.
+    pub fn delegate_vote(env: Env, proposal_id: u64, delegator: BytesN<32>, delegate: BytesN<32>) {
+        payments::delegate_vote(&env, proposal_id, delegator, delegate)
+    }
 
-        let mut approvals: Vec<ProposalApproval> = env.storage().instance()
-            .get(&DataKey::ProposalApprovals(proposal_id)).unwrap_or(Vec::new(&env));
-
-        let mut found = false;
-        for i in 0..approvals.len() {
-            let approval = approvals.get_unchecked(i);
-            if approval.signer == revoker {
-                approvals.remove(i);
-                found = true;
-                break;
-            }
-        }
+    /// Hands `signer`'s approvals to `delegate` until `expires_at`, at most
+    /// `MAX_EXPIRY_SECONDS` ahead, replacing any earlier delegation.
+    /// `signature` is `signer`'s over `get_delegation_payload` at their nonce
+    /// `nonce`. Until then, an approval signed by `delegate` is recorded as
+    /// `signer`'s, and the two share one approval on every proposal.
+    pub fn delegate_approval(env: Env, signer: BytesN<32>, delegate: BytesN<32>, expires_at: u64, nonce: u64, signature: BytesN<64>) {
+        delegation::delegate(&env, signer, delegate, expires_at, nonce, signature)
+    }
 
-        if !found {
-            panic_with_error!(&env, MultisigError::SignerNotFound);
-        }
+    /// The hash `signer` must sign to delegate to `delegate` until
+    /// `expires_at`.
+    pub fn get_delegation_payload(env: Env, signer: BytesN<32>, delegate: BytesN<32>, expires_at: u64) -> BytesN<32> {
+        delegation::get_delegation_payload(&env, &signer, &delegate, expires_at)
+    }
+
+    /// Ends `signer`'s delegation before it expires. `signature` is
+    /// `signer`'s over `get_revocation_payload` at their nonce `nonce`.
+    pub fn revoke_delegation(env: Env, signer: BytesN<32>, nonce: u64, signature: BytesN<64>) {
+        delegation::revoke(&env, signer, nonce, signature)
+    }
+
+    /// The hash `signer` must sign to revoke their delegation next.
+    pub fn get_revocation_payload(env: Env, signer: BytesN<32>) -> BytesN<32> {
+        delegation::get_revocation_payload(&env, &signer)
+    }
+
+    /// `signer`'s delegation while it is in force.
+    pub fn get_delegation(env: Env, signer: BytesN<32>) -> Option<Delegation> {
+        validation::require_initialized(&env);
+        delegation::active(&env, &signer)
+    }
 
-        env.storage().instance().set(&DataKey::ProposalApprovals(proposal_id), &approvals);
+    /// Withdraws a delegation previously made with `delegate_vote`, letting the
+    /// delegator vote directly again.
+    pub fn revoke_vote_delegation(env: Env, proposal_id: u64, delegator: BytesN<32>) {
+        payments::revoke_vote_delegation(&env, proposal_id, delegator)
     }
 
+    /// Executes `proposal_id` once it has its approvals. Anyone may call this,
+    /// except that a proposal with an execution schedule only opens to the
+    /// public at its `public_at`, and not at all while `SIGNERS_EXECUTE` is on.
     pub fn execute_proposal(env: Env, proposal_id: u64) {
-        Self::require_initialized(&env);
-        
-        // Check if proposal exists
-        if !env.storage().instance().has(&DataKey::Proposal(proposal_id)) {
-            panic_with_error!(&env, MultisigError::ProposalNotFound);
-        }
+        payments::execute_proposal(&env, proposal_id)
+    }
 
-        // Check if proposal is already executed
-        if env.storage().instance().has(&DataKey::ProposalExecuted(proposal_id)) {
-            panic_with_error!(&env, MultisigError::ProposalAlreadyExecuted);
-        }
+    /// `execute_proposal` as `signer`, which a proposal's execution schedule
+    /// allows at any time.
+    pub fn execute_as_signer(env: Env, proposal_id: u64, signer: BytesN<32>) {
+        payments::execute_as_signer(&env, proposal_id, signer)
+    }
 
-        let proposal: Proposal = env.storage().instance().get(&DataKey::Proposal(proposal_id)).unwrap();
-        
-        // Check if proposal is expired
-        if env.ledger().timestamp() > proposal.expires_at {
-            panic_with_error!(&env, MultisigError::ProposalExpired);
-        }
+    /// `execute_proposal` as one of the executors named in the proposal's
+    /// execution schedule, who must authorize the call. Allowed from the
+    /// schedule's `fallback_at`.
+    pub fn execute_as_executor(env: Env, proposal_id: u64, executor: Address) {
+        payments::execute_as_executor(&env, proposal_id, executor)
+    }
 
-        // Get approvals
-        let approvals: Vec<ProposalApproval> = env.storage().instance()
-            .get(&DataKey::ProposalApprovals(proposal_id)).unwrap_or(Vec::new(&env));
+    /// Who executed `proposal_id`, and when, if it has executed.
+    pub fn get_execution_record(env: Env, proposal_id: u64) -> Option<ExecutionRecord> {
+        validation::require_initialized(&env);
+        storage::read_execution_record(&env, proposal_id)
+    }
 
-        let threshold: u32 = env.storage().instance().get(&DataKey::Threshold).unwrap();
-        
-        if approvals.len() < threshold {
-            panic_with_error!(&env, MultisigError::InsufficientApprovals);
-        }
+    /// Tiered execution schedule of `proposal_id`, if it was created with one.
+    pub fn get_execution_schedule(env: Env, proposal_id: u64) -> Option<ExecutionSchedule> {
+        validation::require_initialized(&env);
+        storage::read_execution_schedule(&env, proposal_id)
+    }
+
+    /// Approves and executes `proposal_id` in one call from signatures
+    /// collected offline. Each entry is a signer key, the nonce it signed at
+    /// and its signature over `get_approval_payload`; duplicate keys,
+    /// non-signers, stale nonces and expired proposals are rejected before
+    /// anything is recorded.
+    pub fn execute_with_signatures(env: Env, proposal_id: u64, signatures: Vec<(BytesN<32>, u64, BytesN<64>)>) {
+        payments::execute_with_signatures(&env, proposal_id, signatures)
+    }
+
+    /// Registers `bls_key`, an uncompressed BLS12-381 G1 public key, for
+    /// `signer`. `proof` is the key's signature over its own bytes under the
+    /// proof-of-possession tag, and `signature` is `signer`'s ed25519
+    /// signature over `get_bls_registration_payload` at their nonce `nonce`.
+    pub fn register_bls_key(env: Env, signer: BytesN<32>, bls_key: BytesN<96>, proof: BytesN<192>, nonce: u64, signature: BytesN<64>) {
+        payments::register_bls_key(&env, signer, bls_key, proof, nonce, signature)
+    }
 
-        // Execute the token transfer first (external call)
-        Self::execute_token_transfer(&env, &proposal);
+    /// The hash `signer` must sign to register `bls_key` next.
+    pub fn get_bls_registration_payload(env: Env, signer: BytesN<32>, bls_key: BytesN<96>) -> BytesN<32> {
+        payments::get_bls_registration_payload(&env, &signer, &bls_key)
+    }
+
+    /// Signers with a registered BLS key, in bitmap order.
+    pub fn get_bls_signers(env: Env) -> Vec<BytesN<32>> {
+        validation::require_initialized(&env);
+        storage::read_bls_signers(&env)
+    }
+
+    pub fn get_bls_key(env: Env, signer: BytesN<32>) -> Option<BytesN<96>> {
+        validation::require_initialized(&env);
+        storage::read_bls_key(&env, &signer)
+    }
+
+    /// Approves and executes `proposal_id` from one aggregate BLS signature
+    /// over `get_proposal_hash`. Bit `i` of `signer_bitmap` (bit `i % 8` of
+    /// byte `i / 8`) selects the `i`th of `get_bls_signers`; at least the
+    /// threshold must be selected, and all must still be signers.
+    pub fn execute_with_aggregate_signature(env: Env, proposal_id: u64, aggregate_signature: BytesN<192>, signer_bitmap: Bytes) {
+        payments::execute_with_aggregate_signature(&env, proposal_id, aggregate_signature, signer_bitmap)
+    }
 
-        // Mark proposal as executed
-        env.storage().instance().set(&DataKey::ProposalExecuted(proposal_id), &true);
-        
-        // Update proposal status
-        let mut updated_proposal = proposal;
-        updated_proposal.executed = true;
-        env.storage().instance().set(&DataKey::Proposal(proposal_id), &updated_proposal);
+    /// Canonical digest of `proposal_id` for off-chain signing: sha256 over
+    /// this contract's address, the proposal's content hash and the current
+    /// nonce. Every signature over a single proposal is derived from it; see
+    /// `get_approval_payload`.
+    pub fn get_proposal_hash(env: Env, proposal_id: u64) -> BytesN<32> {
+        payments::get_proposal_hash(&env, proposal_id)
+    }
+
+    /// `get_proposal_hash` over the same contents with keccak256, the digest
+    /// secp256k1 signers' approvals are built from.
+    pub fn get_proposal_hash_keccak(env: Env, proposal_id: u64) -> BytesN<32> {
+        payments::get_proposal_hash_keccak(&env, proposal_id)
+    }
+
+    pub fn expire_proposal(env: Env, proposal_id: u64) {
+        payments::expire_proposal(&env, proposal_id)
+    }
+
+    /// Cancels `proposal_id` as its proposer. The committee can undo this with
+    /// `restore_proposal` for `get_cancel_grace` seconds, after which
+    /// `finalize_cancellation` makes it final.
+    pub fn cancel_proposal(env: Env, proposal_id: u64, proposer: BytesN<32>) {
+        payments::cancel_proposal(&env, proposal_id, proposer)
+    }
 
-        // Increment nonce
-        let current_nonce: u64 = env.storage().instance().get(&DataKey::Nonce).unwrap();
-        env.storage().instance().set(&DataKey::Nonce, &(current_nonce + 1));
+    /// Co-signs restoring `proposal_id`, canceled by its proposer, as
+    /// `signer`. Two co-signers (one under a threshold of 1) reopen it with
+    /// its approvals intact.
+    pub fn restore_proposal(env: Env, proposal_id: u64, signer: BytesN<32>) {
+        payments::restore_proposal(&env, proposal_id, signer)
+    }
+
+    /// Closes `proposal_id` as `Canceled` once its proposer's cancellation
+    /// can no longer be restored. Anyone may call this.
+    pub fn finalize_cancellation(env: Env, proposal_id: u64) {
+        payments::finalize_cancellation(&env, proposal_id)
+    }
+
+    /// Blocks payment proposal `proposal_id` for good. Only the guardian
+    /// governance named with `SetGuardian` may call this, and only on payment
+    /// proposals that have not closed; signer changes cannot be vetoed.
+    pub fn veto_proposal(env: Env, proposal_id: u64) {
+        payments::veto_proposal(&env, proposal_id)
+    }
+
+    /// The address that may veto payment proposals, if governance named one.
+    pub fn get_guardian(env: Env) -> Option<Address> {
+        validation::require_initialized(&env);
+        storage::read_guardian(&env)
+    }
+
+    /// The proposer's cancellation of `proposal_id` awaiting restore or
+    /// finalization, if any.
+    pub fn get_pending_cancellation(env: Env, proposal_id: u64) -> Option<PendingCancellation> {
+        validation::require_initialized(&env);
+        storage::read_pending_cancellation(&env, proposal_id)
     }
 
     pub fn get_proposal(env: Env, proposal_id: u64) -> Proposal {
-        Self::require_initialized(&env);
-        env.storage().instance().get(&DataKey::Proposal(proposal_id)).unwrap()
+        validation::require_initialized(&env);
+        storage::read_proposal(&env, proposal_id).unwrap()
+    }
+
+    /// Where payment proposal `proposal_id` stands, including whether the
+    /// guardian vetoed it.
+    pub fn get_proposal_status(env: Env, proposal_id: u64) -> ProposalStatus {
+        payments::get_proposal_status(&env, proposal_id)
+    }
+
+    /// Lists payment proposals by id, at most `limit` ids per call. Pass 0 as the
+    /// cursor for the first page and `next_cursor` for each following one.
+    pub fn get_proposals(env: Env, cursor: u64, limit: u32) -> ProposalPage {
+        payments::get_proposals(&env, cursor, limit)
+    }
+
+    /// Lists payment proposals created between `from_ts` and `to_ts` inclusive,
+    /// by day of creation and by id within a day, walking the daily creation
+    /// index. The range may span at
+    /// most a year; proposals created before the index existed are not listed.
+    /// Pass 0 as the cursor for the first page and `next_cursor` afterwards.
+    pub fn get_proposals_created_between(env: Env, from_ts: u64, to_ts: u64, cursor: u64, limit: u32) -> ProposalPage {
+        payments::get_proposals_created_between(&env, from_ts, to_ts, cursor, limit)
+    }
+
+    /// Lists payment proposals that are neither executed nor expired, oldest
+    /// first. Proposals created before the index existed are not listed. Pass 0
+    /// as the cursor for the first page and `next_cursor` afterwards. The cursor
+    /// is a proposal id, so proposals closing between calls never make a later
+    /// page skip or repeat one.
+    pub fn get_open_proposals(env: Env, cursor: u64, limit: u32) -> ProposalPage {
+        payments::get_open_proposals(&env, cursor, limit)
+    }
+
+    /// Open payment and signer-change proposals in one feed, oldest first.
+    /// Proposals created before the open indexes existed are not listed. Pass
+    /// 0 as the cursor for the first page and `next_cursor` afterwards.
+    pub fn get_all_open_items(env: Env, cursor: u64, limit: u32) -> UnifiedProposalPage {
+        validation::require_initialized(&env);
+        feed::open_items(&env, cursor, limit)
+    }
+
+    /// Committed outflows due between `from_ts` and `to_ts` inclusive, earliest
+    /// first. Currently these are transfer proposals that have gathered their
+    /// required approvals, due by their expiry.
+    pub fn get_upcoming_obligations(env: Env, from_ts: u64, to_ts: u64, limit: u32) -> Vec<Obligation> {
+        validation::require_initialized(&env);
+        obligations::upcoming(&env, from_ts, to_ts, limit)
+    }
+
+    /// Whether this build was compiled with the staging-only `testing-hooks`
+    /// feature. Auditors can call this on a deployed contract to confirm the
+    /// hooks are absent.
+    pub fn testing_hooks_enabled(_env: Env) -> bool {
+        cfg!(feature = "testing-hooks")
+    }
+
+    /// Storage layout version and `DataKey` families this deployment was
+    /// last stamped with. Version 0 means it predates the schema record.
+    pub fn get_storage_schema(env: Env) -> StorageSchema {
+        validation::require_initialized(&env);
+        schema::current(&env)
+    }
+
+    /// Rewrites payment proposals stored in the pre-action layout into the
+    /// current one, and folds the old per-family executed keys into packed
+    /// flags, visiting at most `limit` ids per call. Proposals already in
+    /// the current layout are left alone. Returns the cursor for the next call;
+    /// migration is complete once it exceeds `get_last_proposal_id`, at which
+    /// point the deployment is stamped with the current storage schema.
+    /// Refused if storage was written by a newer schema.
+    pub fn migrate_proposals(env: Env, cursor: u64, limit: u32) -> u64 {
+        payments::migrate_proposals(&env, cursor, limit)
+    }
+
+    /// Counts the payment proposals and signer changes of a deployment
+    /// created before they were counted separately, visiting at most `limit`
+    /// ids per call and resuming where the last call stopped. Returns the next
+    /// id to visit; once it exceeds `get_last_proposal_id` both counts are
+    /// complete and kept up to date from then on.
+    pub fn migrate_proposal_counts(env: Env, limit: u32) -> u64 {
+        counts::backfill(&env, limit)
     }
 
     pub fn get_proposal_approvals(env: Env, proposal_id: u64) -> Vec<ProposalApproval> {
-        Self::require_initialized(&env);
-        env.storage().instance()
-            .get(&DataKey::ProposalApprovals(proposal_id))
-            .unwrap_or(Vec::new(&env))
+        validation::require_initialized(&env);
+        storage::read_proposal_approvals(&env, proposal_id)
+    }
+
+    /// Approvals for each of `proposal_ids` that is a payment proposal, in the
+    /// order given. Other ids are left out. At most
+    /// `pagination::MAX_PAGE_SIZE` ids per call.
+    pub fn get_approvals_bulk(env: Env, proposal_ids: Vec<u64>) -> Vec<(u64, Vec<ProposalApproval>)> {
+        payments::approvals_bulk(&env, &proposal_ids)
+    }
+
+    /// Role each direct approver held when approving `proposal_id`.
+    pub fn get_approval_roles(env: Env, proposal_id: u64) -> Map<BytesN<32>, Symbol> {
+        validation::require_initialized(&env);
+        storage::read_approval_roles(&env, proposal_id)
+    }
+
+    /// Older proposal `proposal_id` is waiting on while the FIFO-per-recipient
+    /// feature is on, or `None` if nothing holds it up.
+    pub fn get_fifo_blocker(env: Env, proposal_id: u64) -> Option<u64> {
+        payments::get_fifo_blocker(&env, proposal_id)
+    }
+
+    /// Where `proposal_id` stands. `required` is the threshold in force when it
+    /// was created, so UIs can show "2 of 3 at creation".
+    pub fn get_approval_progress(env: Env, proposal_id: u64) -> ApprovalProgress {
+        payments::get_approval_progress(&env, proposal_id)
+    }
+
+    /// How a proposal of any family ended, or `None` while it is still open.
+    pub fn get_proposal_outcome(env: Env, proposal_id: u64) -> Option<TerminalRecord> {
+        validation::require_initialized(&env);
+        storage::read_outcome(&env, proposal_id)
+    }
+
+    pub fn get_execution_receipt(env: Env, proposal_id: u64) -> Option<ExecutionReceipt> {
+        validation::require_initialized(&env);
+        storage::read_execution_receipt(&env, proposal_id)
+    }
+
+    /// Proof that `proposal_id` was executed, for handing to a recipient or
+    /// auditor. Fails with `ProposalNotExecuted` until it has been.
+    pub fn get_payment_proof(env: Env, proposal_id: u64) -> PaymentProof {
+        proofs::build(&env, proposal_id)
+    }
+
+    /// Whether `proof` matches the proposal and execution receipt this
+    /// contract recorded. A tampered or fabricated proof returns false.
+    pub fn verify_payment_proof(env: Env, proof: PaymentProof) -> bool {
+        proofs::verify(&env, &proof)
     }
 
     pub fn is_proposal_executed(env: Env, proposal_id: u64) -> bool {
-        Self::require_initialized(&env);
-        env.storage().instance()
-            .get(&DataKey::ProposalExecuted(proposal_id))
-            .unwrap_or(false)
+        validation::require_initialized(&env);
+        storage::has_proposal_flag(&env, proposal_id, storage::FLAG_EXECUTED)
+    }
+
+    pub fn get_governance_proposal(env: Env, proposal_id: u64) -> GovernanceProposal {
+        validation::require_initialized(&env);
+        storage::read_governance(&env, proposal_id).unwrap()
+    }
+
+    pub fn get_governance_approvals(env: Env, proposal_id: u64) -> Vec<GovernanceApproval> {
+        validation::require_initialized(&env);
+        storage::read_governance_approvals(&env, proposal_id)
+    }
+
+    pub fn is_governance_executed(env: Env, proposal_id: u64) -> bool {
+        validation::require_initialized(&env);
+        storage::has_proposal_flag(&env, proposal_id, storage::FLAG_EXECUTED)
+    }
+
+    pub fn get_allocation_targets(env: Env) -> Vec<AllocationTarget> {
+        validation::require_initialized(&env);
+        storage::read_allocation_targets(&env)
+    }
+
+    /// Current holdings of each targeted token against its target share, in
+    /// basis points. Positive drift means the token is over-weight.
+    pub fn get_allocation_report(env: Env) -> AllocationReport {
+        validation::require_initialized(&env);
+        treasury::allocation_report(&env)
+    }
+
+    /// Balance of `token` above its governed reserve floor, free for proposals to spend.
+    pub fn get_free_balance(env: Env, token: Address) -> i128 {
+        validation::require_initialized(&env);
+        treasury::free_balance(&env, &token)
+    }
+
+    /// Seconds past `expires_at` that a proposal fully approved before its
+    /// expiry may still be executed.
+    pub fn get_execution_grace(env: Env) -> u64 {
+        validation::require_initialized(&env);
+        storage::read_execution_grace(&env)
+    }
+
+    /// Seconds a proposer's cancellation stays restorable.
+    pub fn get_cancel_grace(env: Env) -> u64 {
+        validation::require_initialized(&env);
+        payments::cancel_grace(&env)
+    }
+
+    /// When `proposal_id` last reached its approval requirement, or `None`
+    /// while it falls short.
+    pub fn get_fully_approved_at(env: Env, proposal_id: u64) -> Option<u64> {
+        validation::require_initialized(&env);
+        storage::read_fully_approved_at(&env, proposal_id)
+    }
+
+    /// Whether governance requires transfers to `recipient` to carry a memo.
+    pub fn is_memo_required(env: Env, recipient: Address) -> bool {
+        validation::require_initialized(&env);
+        storage::is_memo_required(&env, &recipient)
+    }
+
+    /// Amount of `token` above which payment proposals must reference a
+    /// document, or `None` when governance has set no threshold.
+    pub fn get_document_threshold(env: Env, token: Address) -> Option<i128> {
+        validation::require_initialized(&env);
+        storage::read_document_threshold(&env, &token)
+    }
+
+    /// Reference-unit value above which payment proposals in any token must
+    /// reference a document, or `None` when governance has set none.
+    pub fn get_document_value_threshold(env: Env) -> Option<i128> {
+        validation::require_initialized(&env);
+        storage::read_document_value_threshold(&env)
+    }
+
+    /// The rate governance last set for `token`, however old.
+    pub fn get_exchange_rate(env: Env, token: Address) -> Option<ExchangeRate> {
+        validation::require_initialized(&env);
+        storage::read_exchange_rate(&env, &token)
+    }
+
+    /// How old a rate may be before value limits fail with `StaleRate`.
+    pub fn get_max_rate_age(env: Env) -> u64 {
+        validation::require_initialized(&env);
+        rates::max_age(&env)
+    }
+
+    /// `amount` of `token` in the reference unit, rounded up, as value limits
+    /// see it. Fails with `StaleRate` without a fresh rate for `token`.
+    pub fn quote_value(env: Env, token: Address, amount: i128) -> i128 {
+        validation::require_initialized(&env);
+        rates::quote(&env, &token, amount)
     }
 
+    pub fn get_value_cap(env: Env) -> Option<ValueCap> {
+        validation::require_initialized(&env);
+        storage::read_value_cap(&env)
+    }
+
+    pub fn get_reserve_floor(env: Env, token: Address) -> i128 {
+        validation::require_initialized(&env);
+        storage::read_reserve_floor(&env, &token)
+    }
+
+    /// Implementation contract pre-authorized for forwarded calls of `token`,
+    /// if governance has named one.
+    pub fn get_token_implementation(env: Env, token: Address) -> Option<Address> {
+        validation::require_initialized(&env);
+        storage::read_token_implementation(&env, &token)
+    }
+
+    pub fn is_token_frozen(env: Env, token: Address) -> bool {
+        validation::require_initialized(&env);
+        storage::is_token_frozen(&env, &token)
+    }
+
+    /// Optional behaviours currently switched on for this deployment. Features
+    /// are toggled through `EnableFeature` / `DisableFeature` governance proposals.
+    pub fn get_enabled_features(env: Env) -> Vec<Symbol> {
+        validation::require_initialized(&env);
+        features::enabled(&env)
+    }
+
+    pub fn is_feature_enabled(env: Env, feature: Symbol) -> bool {
+        validation::require_initialized(&env);
+        features::is_enabled(&env, &feature)
+    }
+
+    /// Contracts notified after each payment in `token` executes.
+    pub fn get_token_watchers(env: Env, token: Address) -> Vec<Address> {
+        validation::require_initialized(&env);
+        storage::read_token_watchers(&env, &token)
+    }
+
+    pub fn get_vote_delegations(env: Env, proposal_id: u64) -> Vec<VoteDelegation> {
+        validation::require_initialized(&env);
+        storage::read_vote_delegations(&env, proposal_id)
+    }
+
+    /// Payment proposals created so far. Ids are shared with signer changes
+    /// and governance, so this is not the highest id; see
+    /// `get_last_proposal_id`.
     pub fn get_proposal_count(env: Env) -> u64 {
-        Self::require_initialized(&env);
-        env.storage().instance()
-            .get(&DataKey::ProposalCount)
-            .unwrap_or(0u64)
+        validation::require_initialized(&env);
+        counts::payments(&env)
+    }
+
+    /// Signer-change proposals created so far.
+    pub fn get_signer_change_count(env: Env) -> u64 {
+        validation::require_initialized(&env);
+        counts::signer_changes(&env)
+    }
+
+    /// Highest id handed to a proposal of any family, which is where paging
+    /// through ids ends.
+    pub fn get_last_proposal_id(env: Env) -> u64 {
+        validation::require_initialized(&env);
+        storage::read_proposal_count(&env)
     }
 
     pub fn get_signer_change_proposal(env: Env, proposal_id: u64) -> SignerChangeProposal {
-        Self::require_initialized(&env);
-        env.storage().instance().get(&DataKey::SignerChangeProposal(proposal_id)).unwrap()
+        validation::require_initialized(&env);
+        storage::read_signer_change(&env, proposal_id).unwrap()
+    }
+
+    /// Key a "replace" signer change puts in place of its `signer`.
+    pub fn get_signer_replacement(env: Env, proposal_id: u64) -> Option<BytesN<32>> {
+        validation::require_initialized(&env);
+        storage::read_signer_replacement(&env, proposal_id)
+    }
+
+    /// Changes a "batch" signer change applies.
+    pub fn get_signer_batch(env: Env, proposal_id: u64) -> Option<SignerBatch> {
+        validation::require_initialized(&env);
+        storage::read_signer_batch(&env, proposal_id)
     }
 
     pub fn get_signer_change_approvals(env: Env, proposal_id: u64) -> Vec<SignerChangeApproval> {
-        Self::require_initialized(&env);
-        env.storage().instance()
-            .get(&DataKey::SignerChangeApprovals(proposal_id))
-            .unwrap_or(Vec::new(&env))
+        validation::require_initialized(&env);
+        storage::read_signer_change_approvals(&env, proposal_id)
+    }
+
+    /// `get_approvals_bulk` for signer-change proposals.
+    pub fn get_signer_change_approvals_bulk(env: Env, proposal_ids: Vec<u64>) -> Vec<(u64, Vec<SignerChangeApproval>)> {
+        signers::approvals_bulk(&env, &proposal_ids)
+    }
+
+    /// Approvals `proposal_id` needs before it can execute: the threshold when
+    /// it was proposed, or the protected quorum when it removes a protected
+    /// signer.
+    pub fn get_signer_change_quorum(env: Env, proposal_id: u64) -> u32 {
+        validation::require_initialized(&env);
+        let proposal = storage::read_signer_change(&env, proposal_id).unwrap();
+        signers::required_signer_change_approvals(&env, &proposal)
     }
 
     pub fn is_signer_change_executed(env: Env, proposal_id: u64) -> bool {
-        Self::require_initialized(&env);
-        env.storage().instance()
-            .get(&DataKey::SignerChangeExecuted(proposal_id))
-            .unwrap_or(false)
-    }
-
-    fn execute_token_transfer(env: &Env, proposal: &Proposal) {
-        // Create a token client for the specified token
-        let token_client = soroban_sdk::token::Client::new(env, &proposal.token_address);
-        
-        // Get the multisig contract address as the sender
-        let multisig_address = env.current_contract_address();
-        
-        // Execute the transfer from multisig to recipient
-        token_client.transfer(
-            &multisig_address,
-            &proposal.recipient,
-            &proposal.amount,
-        );
+        validation::require_initialized(&env);
+        storage::has_proposal_flag(&env, proposal_id, storage::FLAG_EXECUTED)
+    }
+}
+
+#[contractimpl]
+impl CustomAccountInterface for MultiSigContract {
+    type Signature = Vec<(BytesN<32>, BytesN<64>)>;
+    type Error = MultisigError;
+
+    /// Authorizes `require_auth` on this contract's address when `signatures`
+    /// are valid ed25519 signatures over `signature_payload` from at least
+    /// threshold distinct signers, making the contract usable as an M-of-N
+    /// account or owner of other contracts. Token `transfer`, `approve` and
+    /// `burn` calls are refused: the treasury only pays out through proposals.
+    /// Nothing is authorized while payments are paused.
+    #[allow(non_snake_case)]
+    fn __check_auth(env: Env, signature_payload: Hash<32>, signatures: Self::Signature, auth_contexts: Vec<Context>) -> Result<(), MultisigError> {
+        account::check_auth(&env, &signature_payload, &signatures, &auth_contexts)
+    }
+}
+
+// Unsigned approvals let any caller vote with any signer's key, so they exist
+// only to keep tests that are not about signatures short.
+#[cfg(test)]
+#[contractimpl]
+impl MultiSigContract {
+    pub fn approve_proposal(env: Env, proposal_id: u64, approver: BytesN<32>) {
+        payments::approve_proposal(&env, proposal_id, approver)
+    }
+}
+
+// Staging-only entry points. They take no auth, which is why they cannot be
+// compiled into a build meant for deployment; see `testing_hooks_enabled`.
+#[cfg(feature = "testing-hooks")]
+#[contractimpl]
+impl MultiSigContract {
+    /// Closes `proposal_id` as `Expired` without waiting for its deadline.
+    pub fn force_expire(env: Env, proposal_id: u64) {
+        payments::force_expire(&env, proposal_id)
+    }
+
+    /// Makes the contract act as if `secs` more seconds had passed than the
+    /// ledger says, for every deadline, window and timestamp it records.
+    pub fn set_timestamp_offset(env: Env, secs: u64) {
+        validation::require_initialized(&env);
+        hooks::set_timestamp_offset(&env, secs)
+    }
+
+    /// Clears every proposer's usage against proposer caps.
+    pub fn reset_rate_limits(env: Env) {
+        validation::require_initialized(&env);
+        hooks::reset_rate_limits(&env)
     }
 }
@@ -0,0 +1,56 @@
+use soroban_sdk::{panic_with_error, BytesN, Env, String, Vec};
+
+use crate::clock;
+use crate::errors::{MultisigError, SignerError};
+use crate::events::NoteAdded;
+use crate::observers;
+use crate::pagination;
+use crate::storage;
+use crate::types::ProposalNote;
+use crate::validation;
+
+/// Longest note, in bytes.
+pub const MAX_NOTE_LEN: u32 = 280;
+/// Most notes a single proposal's thread may hold.
+pub const MAX_NOTES_PER_PROPOSAL: u32 = 50;
+
+pub fn add(env: &Env, proposal_id: u64, author: BytesN<32>, text: String) -> u32 {
+    validation::require_active(env);
+    validation::require_signer(env, &author);
+    require_proposal_exists(env, proposal_id);
+
+    if text.is_empty() || text.len() > MAX_NOTE_LEN {
+        panic_with_error!(env, SignerError::InvalidNote);
+    }
+
+    let mut notes = storage::read_proposal_notes(env, proposal_id);
+    if notes.len() >= MAX_NOTES_PER_PROPOSAL {
+        panic_with_error!(env, SignerError::TooManyNotes);
+    }
+
+    let index = notes.len();
+    notes.push_back(ProposalNote {
+        author: author.clone(),
+        text: text.clone(),
+        added_at: clock::now(env),
+    });
+    storage::write_proposal_notes(env, proposal_id, &notes);
+
+    NoteAdded { proposal_id, author, index, text }.publish(env);
+    index
+}
+
+pub fn page(env: &Env, reader: &BytesN<32>, proposal_id: u64, start: u32, limit: u32) -> Vec<ProposalNote> {
+    observers::require_reader(env, reader);
+    pagination::page(env, storage::read_proposal_notes(env, proposal_id), start, limit)
+}
+
+// Proposal ids are shared by every family, so a note may hang off any of them.
+fn require_proposal_exists(env: &Env, proposal_id: u64) {
+    let exists = storage::read_proposal(env, proposal_id).is_some()
+        || storage::read_signer_change(env, proposal_id).is_some()
+        || storage::read_governance(env, proposal_id).is_some();
+    if !exists {
+        panic_with_error!(env, MultisigError::ProposalNotFound);
+    }
+}
@@ -0,0 +1,67 @@
+use soroban_sdk::{Env, Vec};
+
+use crate::index;
+use crate::outcomes;
+use crate::pagination;
+use crate::storage;
+use crate::types::{IndexKey, Obligation, Proposal, ProposalAction};
+
+// Every kind of committed outflow registers the proposal behind it here and
+// deregisters it once the outflow happens or can no longer happen. Today that
+// is transfer proposals that have reached their approval requirement, due by
+// their expiry.
+
+pub fn register(env: &Env, proposal: &Proposal) {
+    if obligation(proposal).is_some() {
+        index::append(env, &IndexKey::Obligations, proposal.id);
+    }
+}
+
+pub fn deregister(env: &Env, proposal_id: u64) {
+    index::remove(env, &IndexKey::Obligations, proposal_id);
+}
+
+/// Obligations due between `from_ts` and `to_ts` inclusive, earliest first and
+/// by proposal id among equal timestamps, at most `limit` of them.
+pub fn upcoming(env: &Env, from_ts: u64, to_ts: u64, limit: u32) -> Vec<Obligation> {
+    pagination::check_limit(env, limit);
+
+    let mut due: Vec<Obligation> = Vec::new(env);
+    for id in index::iter(env, &IndexKey::Obligations) {
+        if outcomes::is_closed(env, id) {
+            continue;
+        }
+        let Some(entry) = storage::read_proposal(env, id).and_then(|proposal| obligation(&proposal)) else {
+            continue;
+        };
+        if entry.timestamp < from_ts || entry.timestamp > to_ts {
+            continue;
+        }
+
+        // Ids arrive in ascending order, so inserting after every entry with
+        // the same timestamp keeps ties ordered by id
+        let mut position = due.len();
+        while position > 0 && due.get_unchecked(position - 1).timestamp > entry.timestamp {
+            position -= 1;
+        }
+        if position < limit {
+            due.insert(position, entry);
+            if due.len() > limit {
+                due.pop_back();
+            }
+        }
+    }
+    due
+}
+
+fn obligation(proposal: &Proposal) -> Option<Obligation> {
+    let ProposalAction::Transfer(transfer) = &proposal.action else {
+        return None;
+    };
+    Some(Obligation {
+        timestamp: proposal.expires_at,
+        token: transfer.token_address.clone(),
+        amount: transfer.amount,
+        proposal_id: proposal.id,
+    })
+}
@@ -0,0 +1,59 @@
+use soroban_sdk::{panic_with_error, BytesN, Env};
+
+use crate::errors::{GovernanceError, MultisigError};
+use crate::storage;
+
+/// Most observer keys that may be registered at once.
+pub const MAX_OBSERVERS: u32 = 20;
+
+// Observers are kept apart from the signer set entirely: nothing that checks
+// for a signer ever sees them, so they can never propose, approve or count
+// towards a quorum. They only pass `require_reader`.
+
+/// Rejects a key that is already a signer or observer, or that would overflow
+/// the observer set.
+pub fn validate_new_observer(env: &Env, key: &BytesN<32>) {
+    let observers = storage::read_observers(env);
+    if observers.len() >= MAX_OBSERVERS {
+        panic_with_error!(env, GovernanceError::TooManyObservers);
+    }
+    if storage::is_signer(env, key) || observers.contains(key) {
+        panic_with_error!(env, MultisigError::DuplicateSigner);
+    }
+}
+
+pub fn require_observer(env: &Env, key: &BytesN<32>) {
+    if !is_observer(env, key) {
+        panic_with_error!(env, GovernanceError::ObserverNotFound);
+    }
+}
+
+pub fn add(env: &Env, key: &BytesN<32>) {
+    // Checked again because the signer or observer set may have changed meanwhile
+    validate_new_observer(env, key);
+
+    let mut observers = storage::read_observers(env);
+    observers.push_back(key.clone());
+    storage::write_observers(env, &observers);
+}
+
+pub fn remove(env: &Env, key: &BytesN<32>) {
+    let mut observers = storage::read_observers(env);
+    let Some(index) = observers.first_index_of(key) else {
+        panic_with_error!(env, GovernanceError::ObserverNotFound);
+    };
+    observers.remove(index);
+    storage::write_observers(env, &observers);
+}
+
+pub fn is_observer(env: &Env, key: &BytesN<32>) -> bool {
+    storage::read_observers(env).contains(key)
+}
+
+/// Gate for views restricted to the committee and its integrations: `key`
+/// must be a current signer or observer.
+pub fn require_reader(env: &Env, key: &BytesN<32>) {
+    if !storage::is_signer(env, key) && !is_observer(env, key) {
+        panic_with_error!(env, GovernanceError::ReadAccessDenied);
+    }
+}
@@ -0,0 +1,20 @@
+use soroban_sdk::{BytesN, Env};
+
+use crate::clock;
+use crate::storage;
+use crate::types::{ProposalOutcome, TerminalRecord};
+
+/// Records how a proposal of any family ended. Called from every transition
+/// that closes a proposal; `actor` is the signer who caused it, if any.
+pub fn record(env: &Env, proposal_id: u64, outcome: ProposalOutcome, actor: Option<BytesN<32>>) {
+    let record = TerminalRecord {
+        outcome,
+        actor,
+        timestamp: clock::now(env),
+    };
+    storage::write_outcome(env, proposal_id, &record);
+}
+
+pub fn is_closed(env: &Env, proposal_id: u64) -> bool {
+    storage::read_outcome(env, proposal_id).is_some()
+}
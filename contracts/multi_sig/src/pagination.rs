@@ -0,0 +1,56 @@
+use soroban_sdk::{panic_with_error, Env, IntoVal, TryFromVal, Val, Vec};
+
+use crate::errors::MultisigError;
+
+/// Largest page any listing view will return in a single call. Keeps each view
+/// comfortably inside the CPU budget however much data has accumulated.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+/// Width of one bucket in the proposal creation index. A day keeps each bucket
+/// to the handful of proposals a committee realistically creates in that time.
+pub const INDEX_BUCKET_SECONDS: u64 = 86_400;
+/// Most index buckets a single range query may span (one year of days).
+pub const MAX_INDEX_BUCKETS: u64 = 366;
+
+/// Window of sequential ids covered by one page of a listing view.
+pub struct IdWindow {
+    pub start: u64,
+    pub end: u64,
+    pub has_more: bool,
+}
+
+pub fn check_limit(env: &Env, limit: u32) {
+    if limit > MAX_PAGE_SIZE {
+        panic_with_error!(env, MultisigError::PageTooLarge);
+    }
+}
+
+/// Up to `limit` of `items`, starting at index `start`. Empty past the end.
+pub fn page<T: IntoVal<Env, Val> + TryFromVal<Env, Val>>(env: &Env, items: Vec<T>, start: u32, limit: u32) -> Vec<T> {
+    check_limit(env, limit);
+
+    if start >= items.len() {
+        return Vec::new(env);
+    }
+    let end = core::cmp::min(start.saturating_add(limit), items.len());
+    items.slice(start..end)
+}
+
+/// Returns the ids `[start, end)` a page starting at `cursor` should visit, given
+/// that ids run from 1 to `last_id` inclusive. A cursor of 0 starts at the first
+/// id; callers hand `end` back to clients as the next cursor.
+pub fn id_window(env: &Env, cursor: u64, limit: u32, last_id: u64) -> IdWindow {
+    check_limit(env, limit);
+
+    let start = if cursor == 0 { 1 } else { cursor };
+    if start > last_id {
+        return IdWindow { start, end: start, has_more: false };
+    }
+
+    let end = core::cmp::min(start + limit as u64, last_id + 1);
+    IdWindow {
+        start,
+        end,
+        has_more: end <= last_id,
+    }
+}
@@ -0,0 +1,1907 @@
+use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
+use soroban_sdk::xdr::{ScErrorType, ToXdr};
+use soroban_sdk::{
+    panic_with_error, symbol_short, vec, Address, Bytes, BytesN, Env, FromVal, IntoVal, InvokeError, String, Symbol, Val, Vec,
+};
+
+use crate::activity;
+use crate::address_book;
+use crate::audit;
+use crate::bls;
+use crate::clock;
+use crate::counts;
+use crate::delegation;
+use crate::errors::{GovernanceError, MultisigError, SignerError};
+use crate::obligations;
+use crate::outcomes;
+use crate::events::{
+    ExecutionBlocked, Funded, PaymentExecuted, ProposalCanceled, ProposalReady, ProposalRejected, ProposalRestored, ProposalUnready, ProposalVetoed, TokenTransferFailed,
+};
+use crate::failover;
+use crate::features;
+use crate::funding;
+use crate::governance;
+use crate::history;
+use crate::index;
+use crate::pagination;
+use crate::rates;
+use crate::recovery;
+use crate::roles;
+use crate::schema;
+use crate::sessions::SessionItem;
+use crate::signatures;
+use crate::signers;
+use crate::spending;
+use crate::storage;
+use crate::templates;
+use crate::treasury;
+use crate::types::{
+    AllowanceAction, ApprovalProgress, Deposit, ExecutionReceipt, ExecutionRecord, ExecutionSchedule, ExecutionTier, ImportedProposal, IndexKey, InitialFunding, InvokeAction, LegacyProposal, PendingCancellation, Proposal, ProposalAction,
+    ProposalApproval, ProposalOutcome, ProposalPage, ProposalState, ProposalStatus, RoleProgress, Signer, TransferAction, VoteDelegation,
+};
+use crate::validation;
+use crate::watchers;
+use crate::webauthn;
+
+/// Pulls seed funds into a freshly initialized multisig through the allowance
+/// `funding.funder` granted it. A failed pull fails the surrounding
+/// initialization with it.
+pub fn pull_initial_funding(env: &Env, funding: &InitialFunding) {
+    funding::pull_funds(env, &funding.funder, &funding.token, funding.amount, funding::INITIAL_FUNDING);
+
+    storage::write_initial_deposit(
+        env,
+        &Deposit {
+            funder: funding.funder.clone(),
+            token: funding.token.clone(),
+            amount: funding.amount,
+            deposited_at: clock::now(env),
+        },
+    );
+    Funded {
+        token: funding.token.clone(),
+        funder: funding.funder.clone(),
+        amount: funding.amount,
+    }
+    .publish(env);
+}
+
+pub fn create_proposal(
+    env: &Env,
+    proposer: BytesN<32>,
+    action: ProposalAction,
+    reason: String,
+    expires_in_seconds: u64,
+) -> u64 {
+    create_proposal_with_details(env, proposer, action, reason, None, None, expires_in_seconds)
+}
+
+/// `create_proposal` with the optional destination memo and document hash.
+pub fn create_proposal_with_details(
+    env: &Env,
+    proposer: BytesN<32>,
+    action: ProposalAction,
+    reason: String,
+    destination_memo: Option<String>,
+    document_hash: Option<BytesN<32>>,
+    expires_in_seconds: u64,
+) -> u64 {
+    validation::require_active(env);
+
+    for check in draft_checks(env, &proposer, &action, &destination_memo, &document_hash, expires_in_seconds) {
+        validation::raise(env, check);
+    }
+    signers::require_account_auth(env, &proposer);
+
+    if let Some((token, amount)) = action.value() {
+        spending::record_proposed(env, &proposer, &token, amount);
+    }
+
+    let current_time = clock::now(env);
+    let proposal_id = storage::next_proposal_id(env);
+
+    let proposal = Proposal {
+        id: proposal_id,
+        proposer,
+        action,
+        reason,
+        created_at: current_time,
+        expires_at: current_time + expires_in_seconds,
+        executed: false,
+        destination_memo,
+        document_hash,
+        executed_by: None,
+        executed_at: None,
+    };
+
+    insert_proposal(env, &proposal, audit::CREATED);
+    proposal_id
+}
+
+/// Creates a proposal carried over from a legacy multisig, with its original
+/// creator, creation time and expiry, and the approvals it had there. It must
+/// pass every rule a new proposal does, and its approvals must come from
+/// current signers.
+pub fn create_imported(env: &Env, legacy: &ImportedProposal) -> u64 {
+    let now = clock::now(env);
+    if legacy.created_at > now {
+        panic_with_error!(env, MultisigError::InvalidProposal);
+    }
+    let expires_in_seconds = legacy.expires_at.saturating_sub(now);
+    for check in draft_checks(env, &legacy.proposer, &legacy.action, &legacy.destination_memo, &legacy.document_hash, expires_in_seconds) {
+        validation::raise(env, check);
+    }
+    for approver in legacy.approvals.iter() {
+        validation::require_signer(env, &approver);
+    }
+
+    if let Some((token, amount)) = legacy.action.value() {
+        spending::record_proposed(env, &legacy.proposer, &token, amount);
+    }
+
+    let proposal = Proposal {
+        id: storage::next_proposal_id(env),
+        proposer: legacy.proposer.clone(),
+        action: legacy.action.clone(),
+        reason: legacy.reason.clone(),
+        created_at: legacy.created_at,
+        expires_at: legacy.expires_at,
+        executed: false,
+        destination_memo: legacy.destination_memo.clone(),
+        document_hash: legacy.document_hash.clone(),
+        executed_by: None,
+        executed_at: None,
+    };
+    insert_proposal(env, &proposal, audit::IMPORTED);
+    for approver in legacy.approvals.iter() {
+        record_approval(env, &proposal, approver, None);
+    }
+    proposal.id
+}
+
+fn insert_proposal(env: &Env, proposal: &Proposal, audit_action: Symbol) {
+    validation::require_not_paused(env);
+    let proposal_id = proposal.id;
+    storage::write_proposal(env, proposal);
+    storage::write_proposal_approvals(env, proposal_id, &Vec::new(env));
+    history::stamp_proposal(env, proposal_id);
+    storage::write_required_approvals(env, proposal_id, storage::read_threshold(env));
+    counts::record_payment(env);
+    activity::record(env, &proposal.proposer);
+
+    let day = proposal.created_at / pagination::INDEX_BUCKET_SECONDS;
+    index::append(env, &IndexKey::ProposalsByDay(day), proposal_id);
+    index::append(env, &IndexKey::OpenProposals, proposal_id);
+    if let Some(queue) = recipient_queue(proposal) {
+        index::append(env, &queue, proposal_id);
+    }
+    if let Some(document_hash) = &proposal.document_hash {
+        index::append(env, &IndexKey::ProposalsByDocument(document_hash.clone()), proposal_id);
+    }
+    audit::record(env, proposal_id, audit_action);
+}
+
+/// Every payment proposal that has referenced `document_hash`, oldest first,
+/// whatever became of it. More than one id means the document was proposed
+/// against more than once.
+pub fn find_proposal_by_document(env: &Env, document_hash: &BytesN<32>) -> Vec<u64> {
+    validation::require_initialized(env);
+
+    let mut ids = Vec::new(env);
+    for id in index::iter(env, &IndexKey::ProposalsByDocument(document_hash.clone())) {
+        ids.push_back(id);
+    }
+    ids
+}
+
+/// `create_proposal` that can safely be retried: while a proposal created by
+/// `proposer` under `idempotency_key` is still open, the same call returns its
+/// id instead of creating another. Once it closes the key may be used again.
+pub fn create_proposal_with_key(
+    env: &Env,
+    proposer: BytesN<32>,
+    action: ProposalAction,
+    reason: String,
+    expires_in_seconds: u64,
+    idempotency_key: BytesN<32>,
+) -> u64 {
+    validation::require_active(env);
+
+    let scoped_key = scope_idempotency_key(env, &proposer, &idempotency_key);
+    if let Some(existing) = storage::read_idempotency_key(env, &scoped_key) {
+        if !outcomes::is_closed(env, existing) {
+            return existing;
+        }
+    }
+
+    let proposal_id = create_proposal(env, proposer, action, reason, expires_in_seconds);
+    storage::write_idempotency_key(env, &scoped_key, proposal_id);
+    proposal_id
+}
+
+// Keys are chosen by clients, so each is stored hashed together with the
+// proposer to keep two signers' keys from colliding.
+fn scope_idempotency_key(env: &Env, proposer: &BytesN<32>, key: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = Bytes::from_slice(env, &proposer.to_array());
+    preimage.append(&Bytes::from_slice(env, &key.to_array()));
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Creates a transfer proposal paying the template's recipient in its token,
+/// and remembers which template it came from.
+pub fn create_proposal_from_template(
+    env: &Env,
+    proposer: BytesN<32>,
+    template_id: u32,
+    amount: i128,
+    reason: String,
+    expires_in_seconds: u64,
+) -> u64 {
+    validation::require_active(env);
+
+    let template = templates::load_active(env, template_id);
+    let action = ProposalAction::Transfer(TransferAction {
+        token_address: template.token,
+        recipient: template.recipient,
+        amount,
+    });
+
+    let proposal_id = create_proposal(env, proposer, action, reason, expires_in_seconds);
+    storage::write_proposal_template(env, proposal_id, template_id);
+    proposal_id
+}
+
+/// Creates a transfer proposal to an address-book entry. The resolved address
+/// goes into the proposal as usual and the entry's label is kept alongside it,
+/// so it still reads correctly if the entry is later removed.
+pub fn create_proposal_to_entry(
+    env: &Env,
+    proposer: BytesN<32>,
+    token_address: Address,
+    entry_id: u32,
+    amount: i128,
+    reason: String,
+    expires_in_seconds: u64,
+) -> u64 {
+    validation::require_active(env);
+
+    let entry = address_book::load(env, entry_id);
+    let action = ProposalAction::Transfer(TransferAction {
+        token_address,
+        recipient: entry.address,
+        amount,
+    });
+
+    let proposal_id = create_proposal(env, proposer, action, reason, expires_in_seconds);
+    storage::write_recipient_label(env, proposal_id, &entry.label);
+    proposal_id
+}
+
+/// Reports every rule a transfer proposal with these fields would break,
+/// without creating it. Empty means `create_proposal` would accept it.
+pub fn validate_draft(
+    env: &Env,
+    proposer: BytesN<32>,
+    token_address: Address,
+    recipient: Address,
+    amount: i128,
+    expires_in_seconds: u64,
+) -> Vec<Symbol> {
+    validation::require_initialized(env);
+
+    let action = ProposalAction::Transfer(TransferAction {
+        token_address,
+        recipient,
+        amount,
+    });
+
+    let mut violations = Vec::new(env);
+    for check in draft_checks(env, &proposer, &action, &None, &None, expires_in_seconds) {
+        // The cap and document checks can both fail on the same stale rate
+        if let Err(error) = check {
+            let name = violation_name(env, error);
+            if !violations.contains(&name) {
+                violations.push_back(name);
+            }
+        }
+    }
+    violations
+}
+
+/// Approves without any proof that the caller holds `approver`'s key, so it is
+/// only compiled into tests. Deployed contracts approve through
+/// `approve_proposal_signed` or a signing session.
+#[cfg(test)]
+pub fn approve_proposal(env: &Env, proposal_id: u64, approver: BytesN<32>) {
+    validation::require_active(env);
+
+    let slot = approval_slot(env, &approver);
+    signers::require_account_auth(env, &approver);
+
+    let proposal = load_open_proposal(env, proposal_id);
+    approve_open_proposal(env, &proposal, slot, None);
+}
+
+/// Approves `proposal_id` as the contract signer at `signer`, which authorizes
+/// with `require_auth` where a key would sign.
+pub fn approve_proposal_as_contract(env: &Env, proposal_id: u64, signer: Address) {
+    validation::require_active(env);
+
+    let approver = Signer::Contract(signer.clone()).id(env);
+    validation::require_signer(env, &approver);
+    signer.require_auth();
+
+    let proposal = load_open_proposal(env, proposal_id);
+    approve_open_proposal(env, &proposal, approver, None);
+}
+
+/// Approves `proposal_id` as `approver`, or as the signer who delegated to
+/// them. `signature` is `approver`'s ed25519 signature over the proposal hash
+/// at their nonce `nonce`, which advances on success so the same signature
+/// cannot be replayed.
+pub fn approve_proposal_signed(env: &Env, proposal_id: u64, approver: BytesN<32>, nonce: u64, signature: BytesN<64>) {
+    validation::require_active(env);
+
+    let slot = approval_slot(env, &approver);
+
+    let proposal = load_open_proposal(env, proposal_id);
+    signatures::consume(env, &approver, nonce, &proposal_hash(env, &proposal), &signature);
+
+    approve_open_proposal(env, &proposal, slot, None);
+}
+
+// The signer an approval made with `key` is recorded for: whoever delegated
+// to `key`, or else `key` itself, which must then be a signer.
+fn approval_slot(env: &Env, key: &BytesN<32>) -> BytesN<32> {
+    if let Some(delegator) = delegation::acting_for(env, key) {
+        return delegator;
+    }
+    validation::require_signer(env, key);
+    key.clone()
+}
+
+// Whether someone `approver` shares their approval with through an active
+// standing delegation, in either direction, has already approved `proposal`.
+fn partner_approved(env: &Env, proposal: &Proposal, approver: &BytesN<32>) -> bool {
+    let delegate = delegation::active(env, approver).map(|delegation| delegation.delegate);
+    let delegator = delegation::acting_for(env, approver);
+    current_approvals(env, proposal)
+        .0
+        .iter()
+        .any(|a| Some(&a.signer) == delegate.as_ref() || Some(&a.signer) == delegator.as_ref())
+}
+
+/// Approves `proposal_id` as the passkey signer `approver`. The WebAuthn
+/// assertion's challenge must be `approver`'s approval payload at their nonce
+/// `nonce`, which advances on success as for `approve_proposal_signed`.
+pub fn approve_proposal_webauthn(
+    env: &Env,
+    proposal_id: u64,
+    approver: BytesN<32>,
+    nonce: u64,
+    authenticator_data: Bytes,
+    client_data_json: Bytes,
+    signature: BytesN<64>,
+) {
+    validation::require_active(env);
+
+    validation::require_signer(env, &approver);
+    let Some(passkey) = storage::read_passkey(env, &approver) else {
+        panic_with_error!(env, MultisigError::UnknownSigner);
+    };
+
+    let proposal = load_open_proposal(env, proposal_id);
+    let challenge = signatures::payload(env, &proposal_hash(env, &proposal), nonce);
+    signatures::advance(env, &approver, nonce);
+    webauthn::verify(env, &passkey, &challenge, &authenticator_data, &client_data_json, &signature);
+
+    approve_open_proposal(env, &proposal, approver, None);
+}
+
+/// Approves `proposal_id` as the secp256k1 signer `approver`. The public key
+/// recovered from `signature` and `recovery_id` over their keccak256 approval
+/// payload at nonce `nonce` must be the one registered for them.
+pub fn approve_proposal_secp256k1(env: &Env, proposal_id: u64, approver: BytesN<32>, nonce: u64, signature: BytesN<64>, recovery_id: u32) {
+    validation::require_active(env);
+
+    validation::require_signer(env, &approver);
+    let Some(public_key) = storage::read_secp256k1_key(env, &approver) else {
+        panic_with_error!(env, MultisigError::UnknownSigner);
+    };
+
+    let proposal = load_open_proposal(env, proposal_id);
+    let payload = signatures::payload_keccak(env, &proposal_hash_keccak(env, &proposal), nonce);
+    signatures::advance(env, &approver, nonce);
+    if env.crypto().secp256k1_recover(&payload, &signature, recovery_id) != public_key {
+        panic_with_error!(env, SignerError::RecoveredKeyMismatch);
+    }
+
+    approve_open_proposal(env, &proposal, approver, None);
+}
+
+/// Approves `proposal_id` for `approver` from a signature a third party
+/// submits. `relayer` authorizes the call and is recorded on the approval;
+/// the signature is over the relay hash at `approver`'s nonce `nonce`, which
+/// commits to `valid_until` so it cannot be held back and used later.
+pub fn relay_approval(
+    env: &Env,
+    proposal_id: u64,
+    approver: BytesN<32>,
+    nonce: u64,
+    signature: BytesN<64>,
+    valid_until: u64,
+    relayer: Address,
+) {
+    validation::require_active(env);
+
+    relayer.require_auth();
+    validation::require_signer(env, &approver);
+    if clock::now(env) > valid_until {
+        panic_with_error!(env, SignerError::RelayExpired);
+    }
+
+    let proposal = load_open_proposal(env, proposal_id);
+    signatures::consume(env, &approver, nonce, &relay_hash(env, &proposal, valid_until), &signature);
+
+    approve_open_proposal(env, &proposal, approver, Some(relayer));
+}
+
+/// What `approver` must sign for `proposal_id` to be approved through
+/// `relay_approval` until `valid_until`.
+pub fn get_relay_payload(env: &Env, proposal_id: u64, approver: &BytesN<32>, valid_until: u64) -> BytesN<32> {
+    validation::require_initialized(env);
+
+    let proposal = load_open_proposal(env, proposal_id);
+    signatures::next_payload(env, approver, &relay_hash(env, &proposal, valid_until))
+}
+
+fn relay_hash(env: &Env, proposal: &Proposal, valid_until: u64) -> BytesN<32> {
+    env.crypto().sha256(&(symbol_short!("relay"), proposal_hash(env, proposal), valid_until).to_xdr(env)).into()
+}
+
+/// Rejects `proposal_id` as `rejecter`. `signature` is `rejecter`'s ed25519
+/// signature over the rejection hash at their nonce `nonce`. Under strict
+/// rejection, the rejection that leaves the proposal unable to pass closes it.
+pub fn reject_proposal_signed(env: &Env, proposal_id: u64, rejecter: BytesN<32>, nonce: u64, signature: BytesN<64>) {
+    validation::require_active(env);
+
+    validation::require_signer(env, &rejecter);
+
+    let proposal = load_open_proposal(env, proposal_id);
+    if clock::now(env) > proposal.expires_at {
+        panic_with_error!(env, MultisigError::ProposalExpired);
+    }
+    signatures::consume(env, &rejecter, nonce, &rejection_hash(env, &proposal), &signature);
+
+    let mut rejections = storage::read_proposal_rejections(env, proposal_id);
+    if rejections.contains(&rejecter) {
+        panic_with_error!(env, MultisigError::AlreadyRejected);
+    }
+    if current_approvals(env, &proposal).0.iter().any(|a| a.signer == rejecter) {
+        panic_with_error!(env, MultisigError::AlreadyApproved);
+    }
+    rejections.push_back(rejecter.clone());
+    storage::write_proposal_rejections(env, proposal_id, &rejections);
+    audit::record(env, proposal_id, audit::REJECTED);
+
+    if features::is_enabled(env, &features::STRICT_REJECTION) && !approval_possible(env, &proposal, &rejections) {
+        outcomes::record(env, proposal_id, ProposalOutcome::Rejected, Some(rejecter));
+        index::remove(env, &IndexKey::OpenProposals, proposal_id);
+        obligations::deregister(env, proposal_id);
+        leave_recipient_queue(env, &proposal);
+        ProposalRejected {
+            proposal_id,
+            rejections: rejections.len(),
+        }
+        .publish(env);
+    }
+}
+
+/// What `rejecter` must sign to reject `proposal_id` next.
+pub fn get_rejection_payload(env: &Env, proposal_id: u64, rejecter: &BytesN<32>) -> BytesN<32> {
+    validation::require_initialized(env);
+
+    let proposal = load_open_proposal(env, proposal_id);
+    signatures::next_payload(env, rejecter, &rejection_hash(env, &proposal))
+}
+
+// Rejections are signed over their own digest so that no approval signature
+// can be relayed as a rejection, or the other way round.
+fn rejection_hash(env: &Env, proposal: &Proposal) -> BytesN<32> {
+    env.crypto().sha256(&(symbol_short!("reject"), proposal_hash(env, proposal)).to_xdr(env)).into()
+}
+
+/// What `approver` must sign to approve `proposal_id` next, whether on its
+/// own or as part of `execute_with_signatures`.
+pub fn get_approval_payload(env: &Env, proposal_id: u64, approver: &BytesN<32>) -> BytesN<32> {
+    validation::require_initialized(env);
+
+    let proposal = load_open_proposal(env, proposal_id);
+    if storage::read_secp256k1_key(env, approver).is_some() {
+        let nonce = storage::read_signer_nonce(env, approver);
+        return signatures::payload_keccak(env, &proposal_hash_keccak(env, &proposal), nonce).into();
+    }
+    signatures::next_payload(env, approver, &proposal_hash(env, &proposal))
+}
+
+/// The digest clients show signers and every signature over a single proposal
+/// is built from: the proposal's content hash, this contract and the current
+/// nonce, so it changes with any edit and whenever a payment executes.
+pub fn proposal_hash(env: &Env, proposal: &Proposal) -> BytesN<32> {
+    env.crypto().sha256(&proposal_hash_contents(env, proposal)).into()
+}
+
+/// `proposal_hash` over the same contents with keccak256, which is what
+/// secp256k1 signers sign.
+pub fn proposal_hash_keccak(env: &Env, proposal: &Proposal) -> BytesN<32> {
+    env.crypto().keccak256(&proposal_hash_contents(env, proposal)).into()
+}
+
+fn proposal_hash_contents(env: &Env, proposal: &Proposal) -> Bytes {
+    let contents = (
+        env.current_contract_address(),
+        storage::read_proposal_content_hash(env, proposal),
+        storage::read_nonce(env),
+    );
+    contents.to_xdr(env)
+}
+
+pub fn get_proposal_hash(env: &Env, proposal_id: u64) -> BytesN<32> {
+    validation::require_initialized(env);
+
+    let Some(proposal) = storage::read_proposal(env, proposal_id) else {
+        panic_with_error!(env, MultisigError::ProposalNotFound);
+    };
+    proposal_hash(env, &proposal)
+}
+
+pub fn get_proposal_hash_keccak(env: &Env, proposal_id: u64) -> BytesN<32> {
+    validation::require_initialized(env);
+
+    let Some(proposal) = storage::read_proposal(env, proposal_id) else {
+        panic_with_error!(env, MultisigError::ProposalNotFound);
+    };
+    proposal_hash_keccak(env, &proposal)
+}
+
+// The checks every direct approval shares once the approver's identity is
+// settled.
+fn approve_open_proposal(env: &Env, proposal: &Proposal, approver: BytesN<32>, relayer: Option<Address>) {
+    let proposal_id = proposal.id;
+
+    if clock::now(env) > proposal.expires_at {
+        panic_with_error!(env, MultisigError::ProposalExpired);
+    }
+
+    // A signer who handed their vote to a delegate cannot also vote themselves
+    if features::is_enabled(env, &features::DELEGATION) && find_vote_delegation(env, proposal_id, &approver).is_some() {
+        panic_with_error!(env, MultisigError::VoteDelegated);
+    }
+
+    if storage::read_proposal_rejections(env, proposal_id).contains(&approver) {
+        panic_with_error!(env, MultisigError::AlreadyRejected);
+    }
+
+    if partner_approved(env, proposal, &approver) {
+        panic_with_error!(env, MultisigError::AlreadyApproved);
+    }
+
+    record_approval(env, proposal, approver, relayer);
+}
+
+pub fn approvals_bulk(env: &Env, proposal_ids: &Vec<u64>) -> Vec<(u64, Vec<ProposalApproval>)> {
+    validation::require_initialized(env);
+    pagination::check_limit(env, proposal_ids.len());
+
+    let mut found = Vec::new(env);
+    for proposal_id in proposal_ids.iter() {
+        if storage::has_proposal(env, proposal_id) {
+            found.push_back((proposal_id, storage::read_proposal_approvals(env, proposal_id)));
+        }
+    }
+    found
+}
+
+/// Approves `proposal_id` as part of a signing session that `approver` signed
+/// while the proposal hashed to `snapshot`. Where `approve_proposal` would
+/// fail, this reports why the proposal was skipped instead.
+pub fn approve_snapshot(env: &Env, proposal_id: u64, approver: &BytesN<32>, snapshot: &BytesN<32>) -> SessionItem {
+    let Some(proposal) = storage::read_proposal(env, proposal_id) else {
+        return SessionItem::Unavailable;
+    };
+    if outcomes::is_closed(env, proposal_id)
+        || storage::read_pending_cancellation(env, proposal_id).is_some()
+        || storage::has_proposal_flag(env, proposal_id, storage::FLAG_EXECUTED)
+        || clock::now(env) > proposal.expires_at
+    {
+        return SessionItem::Unavailable;
+    }
+    if features::is_enabled(env, &features::DELEGATION) && find_vote_delegation(env, proposal_id, approver).is_some() {
+        return SessionItem::Unavailable;
+    }
+    if storage::read_proposal_rejections(env, proposal_id).contains(approver) {
+        return SessionItem::Unavailable;
+    }
+    if storage::read_proposal_content_hash(env, &proposal) != *snapshot {
+        return SessionItem::Amended;
+    }
+    if current_approvals(env, &proposal).0.iter().any(|a| a.signer == *approver) || partner_approved(env, &proposal, approver) {
+        return SessionItem::AlreadyApproved;
+    }
+
+    record_approval(env, &proposal, approver.clone(), None);
+    SessionItem::Approved
+}
+
+// Adds `approver`'s approval of `proposal` as it currently stands. Callers
+// have already checked the proposal is open and the approver may vote.
+fn record_approval(env: &Env, proposal: &Proposal, approver: BytesN<32>, relayer: Option<Address>) {
+    let proposal_id = proposal.id;
+    validation::require_not_paused(env);
+    signers::require_not_suspended(env, &approver);
+    signers::require_can_approve(env, &approver);
+    validation::raise(env, history::check_in_snapshot(env, proposal_id, &approver));
+    let content_hash = storage::read_proposal_content_hash(env, proposal);
+    let mut approvals = storage::read_proposal_approvals(env, proposal_id);
+
+    // Check if already approved. An approval of an earlier version of the
+    // proposal is replaced rather than refused.
+    for i in 0..approvals.len() {
+        let approval = approvals.get_unchecked(i);
+        if approval.signer == approver {
+            if approval.content_hash == content_hash {
+                panic_with_error!(env, MultisigError::AlreadyApproved);
+            }
+            approvals = renumber_approvals(env, approvals.clone(), i);
+            break;
+        }
+    }
+
+    let was_ready = approval_progress(env, proposal).ready;
+
+    let approval = ProposalApproval {
+        signer: approver,
+        approved_at: clock::now(env),
+        ordinal: approvals.len() + 1,
+        content_hash,
+        relayer,
+    };
+
+    approvals.push_back(approval.clone());
+    storage::write_proposal_approvals(env, proposal_id, &approvals);
+    roles::record_approval_role(env, proposal_id, &approval.signer);
+    activity::record(env, &approval.signer);
+    audit::record(env, proposal_id, audit::APPROVED);
+    recovery::touch(env);
+
+    track_readiness_change(env, proposal, was_ready);
+}
+
+pub fn revoke_approval(env: &Env, proposal_id: u64, revoker: BytesN<32>) {
+    validation::require_active(env);
+
+    validation::require_signer(env, &revoker);
+    signers::require_account_auth(env, &revoker);
+
+    let proposal = load_open_proposal(env, proposal_id);
+
+    let approvals = storage::read_proposal_approvals(env, proposal_id);
+    let was_ready = approval_progress(env, &proposal).ready;
+
+    let Some(position) = approvals.iter().position(|a| a.signer == revoker) else {
+        panic_with_error!(env, MultisigError::SignerNotFound);
+    };
+    let renumbered = renumber_approvals(env, approvals, position as u32);
+
+    storage::write_proposal_approvals(env, proposal_id, &renumbered);
+    roles::forget_approval_role(env, proposal_id, &revoker);
+    audit::record(env, proposal_id, audit::REVOKED);
+
+    track_readiness_change(env, &proposal, was_ready);
+}
+
+/// Drops `signer`'s approvals from every open proposal, as when their key is
+/// rotated, so that approving again under the new key does not count twice.
+pub fn drop_approvals_by(env: &Env, signer: &BytesN<32>) {
+    for proposal_id in index::iter(env, &IndexKey::OpenProposals) {
+        strip_approvals(env, proposal_id, |approver| approver == signer);
+    }
+}
+
+/// Removes approvals on open proposal `proposal_id` by keys that are no longer
+/// signers, returning how many went. Removals through `execute_signer_change`
+/// purge eagerly; this catches signers dropped by the registry.
+pub fn purge_stale_approvals(env: &Env, proposal_id: u64) -> u32 {
+    validation::require_active(env);
+    load_open_proposal(env, proposal_id);
+    strip_approvals(env, proposal_id, |approver| !storage::is_signer(env, approver))
+}
+
+fn strip_approvals(env: &Env, proposal_id: u64, stale: impl Fn(&BytesN<32>) -> bool) -> u32 {
+    let mut approvals = storage::read_proposal_approvals(env, proposal_id);
+    let mut dropped: Vec<BytesN<32>> = Vec::new(env);
+    while let Some(position) = approvals.iter().position(|a| stale(&a.signer)) {
+        dropped.push_back(approvals.get_unchecked(position as u32).signer);
+        approvals = renumber_approvals(env, approvals, position as u32);
+    }
+    if dropped.is_empty() {
+        return 0;
+    }
+
+    let proposal = storage::read_proposal(env, proposal_id).unwrap();
+    let was_ready = approval_progress(env, &proposal).ready;
+
+    storage::write_proposal_approvals(env, proposal_id, &approvals);
+    for signer in dropped.iter() {
+        roles::forget_approval_role(env, proposal_id, &signer);
+        audit::record(env, proposal_id, audit::REVOKED);
+    }
+
+    track_readiness_change(env, &proposal, was_ready);
+    dropped.len()
+}
+
+pub fn delegate_vote(env: &Env, proposal_id: u64, delegator: BytesN<32>, delegate: BytesN<32>) {
+    validation::require_active(env);
+    features::require_enabled(env, &features::DELEGATION);
+
+    validation::require_signer(env, &delegator);
+    signers::require_account_auth(env, &delegator);
+    validation::require_signer(env, &delegate);
+
+    if delegator == delegate {
+        panic_with_error!(env, MultisigError::InvalidDelegation);
+    }
+
+    let proposal = load_open_proposal(env, proposal_id);
+    validation::require_not_paused(env);
+    validation::raise(env, history::check_in_snapshot(env, proposal_id, &delegator));
+
+    if clock::now(env) > proposal.expires_at {
+        panic_with_error!(env, MultisigError::ProposalExpired);
+    }
+
+    // The delegator must not have voted already
+    let approvals = storage::read_proposal_approvals(env, proposal_id);
+    for i in 0..approvals.len() {
+        if approvals.get_unchecked(i).signer == delegator {
+            panic_with_error!(env, MultisigError::AlreadyApproved);
+        }
+    }
+
+    let was_ready = approval_progress(env, &proposal).ready;
+    let mut delegations = storage::read_vote_delegations(env, proposal_id);
+
+    for i in 0..delegations.len() {
+        let existing = delegations.get_unchecked(i);
+        // One delegation per delegator, and no chains through a delegate who
+        // has already handed away their own vote
+        if existing.delegator == delegator || existing.delegator == delegate {
+            panic_with_error!(env, MultisigError::VoteDelegated);
+        }
+        // Nobody may delegate to a signer who has delegated to them
+        if existing.delegate == delegator {
+            panic_with_error!(env, MultisigError::InvalidDelegation);
+        }
+    }
+
+    delegations.push_back(VoteDelegation {
+        delegator,
+        delegate,
+        delegated_at: clock::now(env),
+    });
+    storage::write_vote_delegations(env, proposal_id, &delegations);
+    audit::record(env, proposal_id, audit::DELEGATED);
+
+    track_readiness_change(env, &proposal, was_ready);
+}
+
+pub fn revoke_vote_delegation(env: &Env, proposal_id: u64, delegator: BytesN<32>) {
+    validation::require_active(env);
+
+    validation::require_signer(env, &delegator);
+    signers::require_account_auth(env, &delegator);
+
+    let proposal = load_open_proposal(env, proposal_id);
+
+    let was_ready = approval_progress(env, &proposal).ready;
+    let mut delegations = storage::read_vote_delegations(env, proposal_id);
+
+    let mut found = false;
+    for i in 0..delegations.len() {
+        if delegations.get_unchecked(i).delegator == delegator {
+            delegations.remove(i);
+            found = true;
+            break;
+        }
+    }
+
+    if !found {
+        panic_with_error!(env, MultisigError::InvalidDelegation);
+    }
+
+    storage::write_vote_delegations(env, proposal_id, &delegations);
+    audit::record(env, proposal_id, audit::DELEGATED);
+
+    track_readiness_change(env, &proposal, was_ready);
+}
+
+/// Creates a payment proposal with an execution schedule, so that it can
+/// still be executed on time when no signer is around to do it.
+pub fn create_proposal_with_schedule(
+    env: &Env,
+    proposer: BytesN<32>,
+    action: ProposalAction,
+    reason: String,
+    expires_in_seconds: u64,
+    schedule: ExecutionSchedule,
+) -> u64 {
+    validation::require_active(env);
+    failover::validate(env, &schedule);
+
+    let proposal_id = create_proposal(env, proposer, action, reason, expires_in_seconds);
+    storage::write_execution_schedule(env, proposal_id, &schedule);
+    proposal_id
+}
+
+/// Executes as the public. A proposal with an execution schedule only allows
+/// this from its `public_at`.
+pub fn execute_proposal(env: &Env, proposal_id: u64) {
+    execute_in_tier(env, proposal_id, ExecutionTier::Public, None, None);
+}
+
+/// Executes as `signer`, which a proposal's execution schedule allows at any
+/// time. Unlike approving, this never acts on the key alone: `signer` must be
+/// able to authorize the call.
+pub fn execute_as_signer(env: &Env, proposal_id: u64, signer: BytesN<32>) {
+    validation::require_active(env);
+    validation::require_signer(env, &signer);
+    signers::require_not_suspended(env, &signer);
+    signers::require_account_auth(env, &signer);
+    execute_in_tier(env, proposal_id, ExecutionTier::Committee, Some(&signer), None);
+}
+
+/// Executes as one of the proposal's scheduled executors, from the
+/// schedule's `fallback_at`.
+pub fn execute_as_executor(env: &Env, proposal_id: u64, executor: Address) {
+    validation::require_active(env);
+    executor.require_auth();
+    execute_in_tier(env, proposal_id, ExecutionTier::Executor, None, Some(&executor));
+}
+
+// `signer` is the signer executing in the `Committee` tier, when one is
+// known, and `executor` the caller in the `Executor` tier.
+fn execute_in_tier(env: &Env, proposal_id: u64, tier: ExecutionTier, signer: Option<&BytesN<32>>, executor: Option<&Address>) {
+    validation::require_active(env);
+
+    let proposal = load_open_proposal(env, proposal_id);
+    validation::require_not_paused(env);
+
+    // Check if proposal is expired, allowing for the grace period
+    if clock::now(env) > execution_deadline(env, &proposal) {
+        panic_with_error!(env, MultisigError::ProposalExpired);
+    }
+    failover::require_open(env, proposal_id, tier, executor);
+
+    let (approvals, _) = current_approvals(env, &proposal);
+    let approvers = counted_approvers(env, proposal_id, &approvals);
+
+    let progress = approval_progress(env, &proposal);
+    if progress.approvals < progress.required {
+        panic_with_error!(env, MultisigError::InsufficientApprovals);
+    }
+    if !roles_fulfilled(&progress.roles) {
+        panic_with_error!(env, MultisigError::RoleRequirementNotMet);
+    }
+    if !final_approver_satisfied(env, &approvals) {
+        panic_with_error!(env, MultisigError::FinalApproverRequired);
+    }
+    if features::is_enabled(env, &features::SEPARATE_EXECUTION) {
+        require_later_ledger(env, proposal_id);
+    }
+    if let Some(blocking_id) = fifo_blocker(env, &proposal) {
+        ExecutionBlocked { proposal_id, blocking_id }.publish(env);
+        panic_with_error!(env, MultisigError::OlderProposalPending);
+    }
+
+    // Mark proposal as executed. Everything is recorded before the external
+    // call below, so whatever that call observes or attempts, the proposal
+    // is already closed; a failed call rolls all of it back.
+    storage::set_proposal_flag(env, proposal_id, storage::FLAG_EXECUTED);
+    outcomes::record(env, proposal_id, ProposalOutcome::Executed, None);
+    audit::record(env, proposal_id, audit::EXECUTED);
+    index::remove(env, &IndexKey::OpenProposals, proposal_id);
+    obligations::deregister(env, proposal_id);
+    leave_recipient_queue(env, &proposal);
+
+    // Update proposal status
+    let mut updated_proposal = proposal;
+    updated_proposal.executed = true;
+    updated_proposal.executed_by = signer.cloned();
+    updated_proposal.executed_at = Some(clock::now(env));
+    storage::write_proposal(env, &updated_proposal);
+
+    // Keep an immutable record of exactly who authorized the payment
+    let receipt = ExecutionReceipt {
+        proposal_id,
+        executed_at: clock::now(env),
+        approvals_at_execution: approvers.len(),
+        approvers,
+    };
+    storage::write_execution_receipt(env, &receipt);
+    let record = ExecutionRecord {
+        executed_at: receipt.executed_at,
+        tier,
+        executed_by: signer.cloned(),
+        executor: executor.cloned(),
+    };
+    storage::write_execution_record(env, proposal_id, &record);
+    recovery::touch(env);
+
+    // Increment nonce
+    storage::write_nonce(env, storage::read_nonce(env) + 1);
+
+    // Perform the proposed action (external call)
+    execute_action(env, proposal_id, &updated_proposal.action);
+
+    // Announce and notify watchers last, once the execution is fully recorded
+    failover::record(env, proposal_id, tier);
+    if let ProposalAction::Transfer(transfer) = &updated_proposal.action {
+        PaymentExecuted {
+            proposal_id,
+            recipient: transfer.recipient.clone(),
+            token: transfer.token_address.clone(),
+            amount: transfer.amount,
+            destination_memo: updated_proposal.destination_memo.clone(),
+            document_hash: updated_proposal.document_hash.clone(),
+        }
+        .publish(env);
+        watchers::notify_executed(env, proposal_id, transfer);
+    }
+}
+
+/// Approves `proposal_id` with signatures collected offline and executes it
+/// in the same call. Each entry is a signer key, the nonce it signed at and
+/// its ed25519 signature over `proposal_hash`, exactly as for
+/// `approve_proposal_signed`. The signatures alone must come from as many
+/// active signers who may approve as the proposal requires; approvals
+/// already recorded on-chain do not make up for missing ones.
+pub fn execute_with_signatures(env: &Env, proposal_id: u64, signatures: Vec<(BytesN<32>, u64, BytesN<64>)>) {
+    validation::require_active(env);
+
+    let proposal = load_open_proposal(env, proposal_id);
+    if clock::now(env) > proposal.expires_at {
+        panic_with_error!(env, MultisigError::ProposalExpired);
+    }
+
+    let digest = proposal_hash(env, &proposal);
+    let mut signers = Vec::new(env);
+    for (signer, nonce, signature) in signatures.iter() {
+        if signers.contains(&signer) {
+            panic_with_error!(env, MultisigError::DuplicateSigner);
+        }
+        validation::require_signer(env, &signer);
+        signers::require_not_suspended(env, &signer);
+        signers::require_can_approve(env, &signer);
+        signatures::consume(env, &signer, nonce, &digest, &signature);
+        signers.push_back(signer);
+    }
+    if signers.len() < required_approvals(env, &proposal) {
+        panic_with_error!(env, MultisigError::InsufficientApprovals);
+    }
+
+    for signer in signers.iter() {
+        let (approvals, _) = current_approvals(env, &proposal);
+        if !approvals.iter().any(|a| a.signer == signer) {
+            approve_open_proposal(env, &proposal, signer, None);
+        }
+    }
+
+    // The signatures prove the committee is executing
+    execute_in_tier(env, proposal_id, ExecutionTier::Committee, None, None);
+}
+
+/// Registers `bls_key` as `signer`'s BLS12-381 public key for
+/// `execute_with_aggregate_signature`. `proof` is the key's own signature over
+/// itself, and `signature` `signer`'s ed25519 signature over the registration
+/// digest at their nonce `nonce`.
+pub fn register_bls_key(env: &Env, signer: BytesN<32>, bls_key: BytesN<96>, proof: BytesN<192>, nonce: u64, signature: BytesN<64>) {
+    validation::require_active(env);
+
+    validation::require_signer(env, &signer);
+    signatures::consume(env, &signer, nonce, &bls::registration_digest(env, &bls_key), &signature);
+    bls::register(env, &signer, &bls_key, &proof);
+}
+
+/// What `signer` must sign to register `bls_key` next.
+pub fn get_bls_registration_payload(env: &Env, signer: &BytesN<32>, bls_key: &BytesN<96>) -> BytesN<32> {
+    validation::require_initialized(env);
+
+    signatures::next_payload(env, signer, &bls::registration_digest(env, bls_key))
+}
+
+/// `execute_with_signatures` with one aggregate BLS signature over the
+/// proposal hash, by the registered BLS signers `signer_bitmap` selects.
+/// Every selected signer's approval is recorded before executing.
+pub fn execute_with_aggregate_signature(env: &Env, proposal_id: u64, aggregate_signature: BytesN<192>, signer_bitmap: Bytes) {
+    validation::require_active(env);
+
+    let proposal = load_open_proposal(env, proposal_id);
+    if clock::now(env) > proposal.expires_at {
+        panic_with_error!(env, MultisigError::ProposalExpired);
+    }
+
+    let signers = bls::selected_signers(env, &signer_bitmap);
+    for signer in signers.iter() {
+        validation::require_signer(env, &signer);
+    }
+    if signers.len() < required_approvals(env, &proposal) {
+        panic_with_error!(env, MultisigError::InsufficientApprovals);
+    }
+    bls::verify_aggregate(env, &signers, &proposal_hash(env, &proposal).into(), &aggregate_signature);
+
+    for signer in signers.iter() {
+        let (approvals, _) = current_approvals(env, &proposal);
+        if !approvals.iter().any(|a| a.signer == signer) {
+            approve_open_proposal(env, &proposal, signer, None);
+        }
+    }
+
+    // As with individual signatures, the aggregate proves the committee is
+    // executing
+    execute_in_tier(env, proposal_id, ExecutionTier::Committee, None, None);
+}
+
+/// Closes a payment proposal whose expiry has passed, recording it as
+/// `Expired`. Anyone may call this; it only makes final what is already true.
+pub fn expire_proposal(env: &Env, proposal_id: u64) {
+    validation::require_active(env);
+
+    let proposal = load_open_proposal(env, proposal_id);
+
+    if clock::now(env) <= execution_deadline(env, &proposal) {
+        panic_with_error!(env, MultisigError::ProposalNotExpired);
+    }
+    close_expired(env, &proposal);
+}
+
+/// Closes `proposal_id` as `Expired` whether or not its deadline has passed.
+#[cfg(feature = "testing-hooks")]
+pub fn force_expire(env: &Env, proposal_id: u64) {
+    validation::require_active(env);
+
+    let proposal = load_open_proposal(env, proposal_id);
+    close_expired(env, &proposal);
+}
+
+fn close_expired(env: &Env, proposal: &Proposal) {
+    let proposal_id = proposal.id;
+    storage::set_proposal_flag(env, proposal_id, storage::FLAG_EXPIRED);
+    outcomes::record(env, proposal_id, ProposalOutcome::Expired, None);
+    audit::record(env, proposal_id, audit::EXPIRED);
+    index::remove(env, &IndexKey::OpenProposals, proposal_id);
+    obligations::deregister(env, proposal_id);
+    leave_recipient_queue(env, proposal);
+}
+
+// A proposer's cancellation only takes `proposal_id` out of play: while the
+// cancel grace runs it can neither be approved nor executed, but it keeps its
+// approvals, index entries and obligations so that a restore can put it back
+// exactly as it was. Cleanup waits until the cancellation is final.
+
+/// Cancels `proposal_id` as its proposer. It stays restorable for the cancel
+/// grace and is closed for good by `finalize_cancellation` after that, or at
+/// once when the grace is zero.
+pub fn cancel_proposal(env: &Env, proposal_id: u64, proposer: BytesN<32>) {
+    validation::require_active(env);
+
+    validation::require_signer(env, &proposer);
+    signers::require_account_auth(env, &proposer);
+
+    let proposal = load_open_proposal(env, proposal_id);
+    if proposal.proposer != proposer {
+        panic_with_error!(env, MultisigError::NotProposer);
+    }
+
+    let canceled_at = clock::now(env);
+    let restorable_until = canceled_at.saturating_add(cancel_grace(env));
+    audit::record(env, proposal_id, audit::CANCELED);
+    ProposalCanceled { proposal_id, restorable_until }.publish(env);
+
+    if restorable_until == canceled_at {
+        close(env, &proposal, ProposalOutcome::Canceled, Some(proposer));
+        return;
+    }
+    let pending = PendingCancellation {
+        canceled_by: proposer,
+        canceled_at,
+        restorable_until,
+        restore_votes: Vec::new(env),
+    };
+    storage::write_pending_cancellation(env, proposal_id, Some(&pending));
+}
+
+/// Co-signs restoring `proposal_id` within its cancel grace. The
+/// `RESTORE_COSIGNERS`th current signer to co-sign, or the threshold's if that
+/// is lower, reopens it with the approvals it had.
+pub fn restore_proposal(env: &Env, proposal_id: u64, signer: BytesN<32>) {
+    validation::require_active(env);
+
+    validation::require_signer(env, &signer);
+    signers::require_account_auth(env, &signer);
+
+    let mut pending = load_pending_cancellation(env, proposal_id);
+    if clock::now(env) > pending.restorable_until {
+        panic_with_error!(env, MultisigError::RestoreWindowClosed);
+    }
+    if pending.restore_votes.contains(&signer) {
+        panic_with_error!(env, MultisigError::AlreadyApproved);
+    }
+    pending.restore_votes.push_back(signer);
+
+    // Co-signers removed since they signed no longer count
+    let cosigners = pending.restore_votes.iter().filter(|voter| storage::is_signer(env, voter)).count() as u32;
+    if cosigners < validation::RESTORE_COSIGNERS.min(storage::read_threshold(env)) {
+        storage::write_pending_cancellation(env, proposal_id, Some(&pending));
+        return;
+    }
+
+    storage::write_pending_cancellation(env, proposal_id, None);
+    audit::record(env, proposal_id, audit::RESTORED);
+    ProposalRestored { proposal_id }.publish(env);
+}
+
+/// Makes the cancellation of `proposal_id` final once its cancel grace has
+/// run out. Anyone may call this.
+pub fn finalize_cancellation(env: &Env, proposal_id: u64) {
+    validation::require_active(env);
+
+    let pending = load_pending_cancellation(env, proposal_id);
+    if clock::now(env) <= pending.restorable_until {
+        panic_with_error!(env, MultisigError::RestoreWindowOpen);
+    }
+
+    let proposal = storage::read_proposal(env, proposal_id).unwrap();
+    close(env, &proposal, ProposalOutcome::Canceled, Some(pending.canceled_by));
+}
+
+/// Cancels `proposal_id` for good, as a governance action. A cancellation
+/// still pending from its proposer is overridden.
+pub fn cancel_by_committee(env: &Env, proposal_id: u64) {
+    let proposal = load_cancelable(env, proposal_id);
+
+    audit::record(env, proposal_id, audit::CANCELED);
+    ProposalCanceled {
+        proposal_id,
+        restorable_until: clock::now(env),
+    }
+    .publish(env);
+    close(env, &proposal, ProposalOutcome::Canceled, None);
+}
+
+/// Vetoes `proposal_id` as the guardian, closing it for good. Open and
+/// proposer-canceled payment proposals can be vetoed; signer changes and
+/// governance proposals cannot, so signers can always replace a guardian who
+/// oversteps.
+pub fn veto_proposal(env: &Env, proposal_id: u64) {
+    validation::require_active(env);
+
+    let Some(guardian) = storage::read_guardian(env) else {
+        panic_with_error!(env, GovernanceError::NoGuardian);
+    };
+    guardian.require_auth();
+
+    let proposal = load_cancelable(env, proposal_id);
+    storage::write_veto(env, proposal_id, &guardian);
+    audit::record(env, proposal_id, audit::VETOED);
+    ProposalVetoed { proposal_id, guardian }.publish(env);
+    close(env, &proposal, ProposalOutcome::Vetoed, None);
+}
+
+/// `proposal_id` if it is a payment proposal that has not closed, whether or
+/// not its proposer canceled it.
+pub fn load_cancelable(env: &Env, proposal_id: u64) -> Proposal {
+    let Some(proposal) = storage::read_proposal(env, proposal_id) else {
+        panic_with_error!(env, MultisigError::ProposalNotFound);
+    };
+    if storage::has_proposal_flag(env, proposal_id, storage::FLAG_EXECUTED) {
+        panic_with_error!(env, MultisigError::ProposalAlreadyExecuted);
+    }
+    if outcomes::is_closed(env, proposal_id) {
+        panic_with_error!(env, MultisigError::ProposalClosed);
+    }
+    proposal
+}
+
+pub fn cancel_grace(env: &Env) -> u64 {
+    storage::read_cancel_grace(env).unwrap_or(validation::DEFAULT_CANCEL_GRACE_SECONDS)
+}
+
+fn load_pending_cancellation(env: &Env, proposal_id: u64) -> PendingCancellation {
+    load_cancelable(env, proposal_id);
+    let Some(pending) = storage::read_pending_cancellation(env, proposal_id) else {
+        panic_with_error!(env, MultisigError::ProposalNotCanceled);
+    };
+    pending
+}
+
+fn close(env: &Env, proposal: &Proposal, outcome: ProposalOutcome, actor: Option<BytesN<32>>) {
+    let proposal_id = proposal.id;
+    storage::write_pending_cancellation(env, proposal_id, None);
+    outcomes::record(env, proposal_id, outcome, actor);
+    index::remove(env, &IndexKey::OpenProposals, proposal_id);
+    obligations::deregister(env, proposal_id);
+    leave_recipient_queue(env, proposal);
+}
+
+pub fn get_proposal_status(env: &Env, proposal_id: u64) -> ProposalStatus {
+    validation::require_initialized(env);
+
+    if storage::read_proposal(env, proposal_id).is_none() {
+        panic_with_error!(env, MultisigError::ProposalNotFound);
+    }
+    proposal_state(env, proposal_id).status
+}
+
+/// `proposal_id` as a `ChangesPage` reports it.
+pub fn proposal_state(env: &Env, proposal_id: u64) -> ProposalState {
+    let proposal = storage::read_proposal(env, proposal_id).unwrap();
+    let status = match storage::read_outcome(env, proposal_id).map(|record| record.outcome) {
+        Some(ProposalOutcome::Executed) => ProposalStatus::Executed,
+        Some(ProposalOutcome::Expired) => ProposalStatus::Expired,
+        Some(ProposalOutcome::Rejected) => ProposalStatus::Rejected,
+        Some(ProposalOutcome::Canceled) => ProposalStatus::Canceled,
+        Some(ProposalOutcome::Vetoed) => ProposalStatus::Vetoed,
+        None if storage::read_pending_cancellation(env, proposal_id).is_some() => ProposalStatus::CancelPending,
+        None if approval_progress(env, &proposal).ready => ProposalStatus::Ready,
+        None => ProposalStatus::Open,
+    };
+    ProposalState {
+        approvals: storage::read_proposal_approvals(env, proposal_id),
+        status,
+        proposal,
+    }
+}
+
+pub fn get_approval_progress(env: &Env, proposal_id: u64) -> ApprovalProgress {
+    validation::require_initialized(env);
+
+    let Some(proposal) = storage::read_proposal(env, proposal_id) else {
+        panic_with_error!(env, MultisigError::ProposalNotFound);
+    };
+
+    approval_progress(env, &proposal)
+}
+
+pub fn get_proposals(env: &Env, cursor: u64, limit: u32) -> ProposalPage {
+    validation::require_initialized(env);
+
+    let window = pagination::id_window(env, cursor, limit, storage::read_proposal_count(env));
+
+    let mut proposals: Vec<Proposal> = Vec::new(env);
+    for id in window.start..window.end {
+        // Signer-change ids share the counter and are skipped
+        if let Some(proposal) = storage::read_proposal(env, id) {
+            proposals.push_back(proposal);
+        }
+    }
+
+    ProposalPage {
+        proposals,
+        next_cursor: window.end,
+        has_more: window.has_more,
+    }
+}
+
+pub fn get_proposals_created_between(env: &Env, from_ts: u64, to_ts: u64, cursor: u64, limit: u32) -> ProposalPage {
+    validation::require_initialized(env);
+    pagination::check_limit(env, limit);
+
+    let from_day = from_ts / pagination::INDEX_BUCKET_SECONDS;
+    let to_day = to_ts / pagination::INDEX_BUCKET_SECONDS;
+    if from_ts > to_ts || to_day - from_day >= pagination::MAX_INDEX_BUCKETS {
+        panic_with_error!(env, MultisigError::PageTooLarge);
+    }
+
+    // Pages run day by day, and by id within a day. Imported proposals keep
+    // their original creation time, so an earlier day can hold later ids than
+    // the days after it. Resuming therefore picks up in the day of the last
+    // proposal returned and skips smaller ids in that day only.
+    let resume_day = cursor
+        .checked_sub(1)
+        .and_then(|last| storage::read_proposal(env, last))
+        .map(|last| last.created_at / pagination::INDEX_BUCKET_SECONDS);
+    let start_day = resume_day.map_or(from_day, |day| day.max(from_day));
+
+    let mut proposals: Vec<Proposal> = Vec::new(env);
+    let mut next_cursor = cursor;
+    let mut has_more = false;
+
+    'days: for day in start_day..=to_day {
+        for id in index::iter(env, &IndexKey::ProposalsByDay(day)) {
+            if resume_day == Some(day) && id < cursor {
+                continue;
+            }
+            let proposal = storage::read_proposal(env, id).unwrap();
+            if proposal.created_at < from_ts || proposal.created_at > to_ts {
+                continue;
+            }
+            if proposals.len() == limit {
+                has_more = true;
+                break 'days;
+            }
+            next_cursor = id + 1;
+            proposals.push_back(proposal);
+        }
+    }
+
+    ProposalPage {
+        proposals,
+        next_cursor,
+        has_more,
+    }
+}
+
+pub fn get_open_proposals(env: &Env, cursor: u64, limit: u32) -> ProposalPage {
+    validation::require_initialized(env);
+    pagination::check_limit(env, limit);
+
+    let mut proposals: Vec<Proposal> = Vec::new(env);
+    let mut next_cursor = cursor;
+    let mut has_more = false;
+
+    for id in index::iter(env, &IndexKey::OpenProposals) {
+        if id < cursor {
+            continue;
+        }
+        if proposals.len() == limit {
+            has_more = true;
+            break;
+        }
+        next_cursor = id + 1;
+        proposals.push_back(storage::read_proposal(env, id).unwrap());
+    }
+
+    ProposalPage {
+        proposals,
+        next_cursor,
+        has_more,
+    }
+}
+
+pub fn migrate_proposals(env: &Env, cursor: u64, limit: u32) -> u64 {
+    schema::require_migratable(env);
+
+    let window = pagination::id_window(env, cursor, limit, storage::read_proposal_count(env));
+
+    for id in window.start..window.end {
+        storage::migrate_legacy_flags(env, id);
+
+        // Structs are stored as symbol-keyed maps, so the layout can be told
+        // apart by the presence of the `action` field
+        let Some(stored) = storage::read_proposal_fields(env, id) else {
+            continue;
+        };
+        if stored.contains_key(symbol_short!("action")) {
+            continue;
+        }
+        let legacy = LegacyProposal::from_val(env, &stored.to_val());
+
+        let proposal = Proposal {
+            id: legacy.id,
+            proposer: legacy.proposer,
+            action: ProposalAction::Transfer(TransferAction {
+                token_address: legacy.token_address,
+                recipient: legacy.recipient,
+                amount: legacy.amount,
+            }),
+            reason: legacy.reason,
+            created_at: legacy.created_at,
+            expires_at: legacy.expires_at,
+            executed: legacy.executed,
+            destination_memo: None,
+            document_hash: None,
+            executed_by: None,
+            executed_at: None,
+        };
+        storage::write_proposal(env, &proposal);
+    }
+
+    // The last page brings the deployment up to the current layout
+    if !window.has_more {
+        schema::stamp(env);
+    }
+
+    window.end
+}
+
+// Loads a payment proposal that exists and has not been executed yet.
+fn load_open_proposal(env: &Env, proposal_id: u64) -> Proposal {
+    let Some(proposal) = storage::read_proposal(env, proposal_id) else {
+        panic_with_error!(env, MultisigError::ProposalNotFound);
+    };
+
+    if storage::has_proposal_flag(env, proposal_id, storage::FLAG_EXECUTED) {
+        panic_with_error!(env, MultisigError::ProposalAlreadyExecuted);
+    }
+
+    if storage::read_veto(env, proposal_id).is_some() {
+        panic_with_error!(env, MultisigError::ProposalVetoed);
+    }
+
+    if outcomes::is_closed(env, proposal_id) {
+        panic_with_error!(env, MultisigError::ProposalClosed);
+    }
+
+    if storage::read_pending_cancellation(env, proposal_id).is_some() {
+        panic_with_error!(env, MultisigError::ProposalCanceled);
+    }
+
+    proposal
+}
+
+fn find_vote_delegation(env: &Env, proposal_id: u64, delegator: &BytesN<32>) -> Option<VoteDelegation> {
+    storage::read_vote_delegations(env, proposal_id)
+        .iter()
+        .find(|d| d.delegator == *delegator)
+}
+
+// Keys whose votes count towards the threshold: every direct approver who is
+// still a signer plus each delegator whose delegate has approved. A
+// delegation only counts while both parties are still signers. Approvals by
+// removed signers are purged, and never count even before that.
+fn counted_approvers(env: &Env, proposal_id: u64, approvals: &Vec<ProposalApproval>) -> Vec<BytesN<32>> {
+    let mut counted: Vec<BytesN<32>> = Vec::new(env);
+    for approval in approvals.iter() {
+        if storage::is_signer(env, &approval.signer) {
+            counted.push_back(approval.signer);
+        }
+    }
+
+    // Delegations left over from before the feature was disabled no longer count
+    if !features::is_enabled(env, &features::DELEGATION) {
+        return counted;
+    }
+
+    for delegation in storage::read_vote_delegations(env, proposal_id).iter() {
+        if !storage::is_signer(env, &delegation.delegator) || !storage::is_signer(env, &delegation.delegate) {
+            continue;
+        }
+        if approvals.iter().any(|a| a.signer == delegation.delegate) {
+            counted.push_back(delegation.delegator);
+        }
+    }
+
+    counted
+}
+
+/// Last moment `proposal` may execute. Approvals stop at `expires_at`, but a
+/// proposal that was fully approved by then keeps the configured grace period
+/// on top to actually be executed.
+pub fn execution_deadline(env: &Env, proposal: &Proposal) -> u64 {
+    match storage::read_fully_approved_at(env, proposal.id) {
+        Some(approved_at) if approved_at <= proposal.expires_at => {
+            proposal.expires_at.saturating_add(storage::read_execution_grace(env))
+        }
+        _ => proposal.expires_at,
+    }
+}
+
+// With `FIFO_PER_RECIPIENT` on, the oldest open transfer to the same
+// recipient and token that is ready to execute ahead of `proposal`, if any.
+// Older proposals that are not ready, or past their execution deadline, do
+// not hold it up.
+fn fifo_blocker(env: &Env, proposal: &Proposal) -> Option<u64> {
+    if !features::is_enabled(env, &features::FIFO_PER_RECIPIENT) {
+        return None;
+    }
+    let queue = recipient_queue(proposal)?;
+
+    for id in index::iter(env, &queue) {
+        if id >= proposal.id {
+            break;
+        }
+        if outcomes::is_closed(env, id) || storage::read_pending_cancellation(env, id).is_some() {
+            continue;
+        }
+        let Some(older) = storage::read_proposal(env, id) else {
+            continue;
+        };
+        if clock::now(env) <= execution_deadline(env, &older) && approval_progress(env, &older).ready {
+            return Some(id);
+        }
+    }
+    None
+}
+
+pub fn get_fifo_blocker(env: &Env, proposal_id: u64) -> Option<u64> {
+    validation::require_initialized(env);
+    let proposal = load_open_proposal(env, proposal_id);
+    fifo_blocker(env, &proposal)
+}
+
+fn recipient_queue(proposal: &Proposal) -> Option<IndexKey> {
+    let ProposalAction::Transfer(transfer) = &proposal.action else {
+        return None;
+    };
+    Some(IndexKey::RecipientQueue(transfer.token_address.clone(), transfer.recipient.clone()))
+}
+
+fn leave_recipient_queue(env: &Env, proposal: &Proposal) {
+    if let Some(queue) = recipient_queue(proposal) {
+        index::remove(env, &queue, proposal.id);
+    }
+}
+
+// Proposals that became ready before the ledger was recorded carry no
+// sequence and are let through.
+fn require_later_ledger(env: &Env, proposal_id: u64) {
+    if let Some(sequence) = storage::read_fully_approved_ledger(env, proposal_id) {
+        if env.ledger().sequence() <= sequence {
+            panic_with_error!(env, MultisigError::SameLedgerExecution);
+        }
+    }
+}
+
+/// Number of counted approvals `proposal` needs before it may execute: the
+/// threshold in force when it was created, so later threshold changes leave
+/// it alone. Proposals from before that was recorded follow the current one.
+pub fn required_approvals(env: &Env, proposal: &Proposal) -> u32 {
+    storage::read_required_approvals(env, proposal.id).unwrap_or_else(|| storage::read_threshold(env))
+}
+
+/// Where `proposal` stands against its effective requirement: the threshold
+/// plus, in staged mode, every role bucket, and the final-approver rule. Every check of whether a proposal
+/// is executable goes through here.
+fn approval_progress(env: &Env, proposal: &Proposal) -> ApprovalProgress {
+    let (approvals, stale_approvals) = current_approvals(env, proposal);
+    let approvers = counted_approvers(env, proposal.id, &approvals);
+    let required = required_approvals(env, proposal);
+    let roles = roles::role_progress(env, proposal.id, &approvals, &approvers);
+
+    let ready = approvers.len() >= required && roles_fulfilled(&roles) && final_approver_satisfied(env, &approvals);
+
+    ApprovalProgress {
+        approvals: approvers.len(),
+        required,
+        roles,
+        ready,
+        stale_approvals,
+    }
+}
+
+// Splits the recorded approvals into those given to the proposal as it
+// stands now, and a count of those given to an earlier version.
+fn current_approvals(env: &Env, proposal: &Proposal) -> (Vec<ProposalApproval>, u32) {
+    let content_hash = storage::read_proposal_content_hash(env, proposal);
+    let mut current = Vec::new(env);
+    let mut stale = 0;
+    for approval in storage::read_proposal_approvals(env, proposal.id).iter() {
+        if approval.content_hash == content_hash {
+            current.push_back(approval);
+        } else {
+            stale += 1;
+        }
+    }
+    (current, stale)
+}
+
+// Drops the approval at `position` and closes the gap so ordinals keep
+// matching approval order.
+fn renumber_approvals(env: &Env, mut approvals: Vec<ProposalApproval>, position: u32) -> Vec<ProposalApproval> {
+    approvals.remove(position);
+    let mut renumbered = Vec::new(env);
+    for (i, mut approval) in approvals.iter().enumerate() {
+        approval.ordinal = i as u32 + 1;
+        renumbered.push_back(approval);
+    }
+    renumbered
+}
+
+// Whether `proposal` could still meet its requirement if every signer who has
+// not rejected it approved, under the current signer set, threshold, roles and
+// final approver. Rejections by former signers no longer count. Delegations
+// are left out: they can only take votes away, so ignoring them never closes a
+// proposal that could still pass.
+fn approval_possible(env: &Env, proposal: &Proposal, rejections: &Vec<BytesN<32>>) -> bool {
+    let rejected = rejections.iter().filter(|signer| storage::is_signer(env, signer)).count() as u32;
+    if storage::read_signer_count(env) - rejected < required_approvals(env, proposal) {
+        return false;
+    }
+
+    if storage::read_final_approver(env).is_some_and(|final_approver| rejections.contains(&final_approver)) {
+        return false;
+    }
+
+    if features::is_enabled(env, &features::STAGED_APPROVAL) {
+        let signers = storage::read_signer_list(env);
+        for requirement in storage::read_role_requirements(env).iter() {
+            let available = signers
+                .iter()
+                .filter(|signer| !rejections.contains(signer) && storage::read_signer_role(env, signer) == Some(requirement.role.clone()))
+                .count() as u32;
+            if available < requirement.count {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+fn roles_fulfilled(roles: &Vec<RoleProgress>) -> bool {
+    roles.iter().all(|r| r.approvals >= r.required)
+}
+
+// When governance names a final approver, that signer must hold the last
+// approval slot.
+fn final_approver_satisfied(env: &Env, approvals: &Vec<ProposalApproval>) -> bool {
+    let Some(final_approver) = storage::read_final_approver(env) else {
+        return true;
+    };
+    approvals.last().is_some_and(|last| last.signer == final_approver)
+}
+
+// Emits `proposal_ready` / `proposal_unready` when the proposal crosses its
+// effective requirement in either direction, keeping the obligations index
+// and the fully-approved timestamp in step: a proposal that can execute is a
+// committed outflow until it stops being one.
+fn track_readiness_change(env: &Env, proposal: &Proposal, was_ready: bool) {
+    let progress = approval_progress(env, proposal);
+
+    if !was_ready && progress.ready {
+        storage::write_fully_approved_at(env, proposal.id, Some(clock::now(env)));
+        storage::write_fully_approved_ledger(env, proposal.id, Some(env.ledger().sequence()));
+        obligations::register(env, proposal);
+        ProposalReady {
+            proposal_id: proposal.id,
+            required: progress.required,
+            approvals: progress.approvals,
+        }
+        .publish(env);
+    } else if was_ready && !progress.ready {
+        storage::write_fully_approved_at(env, proposal.id, None);
+        storage::write_fully_approved_ledger(env, proposal.id, None);
+        obligations::deregister(env, proposal.id);
+        ProposalUnready {
+            proposal_id: proposal.id,
+            required: progress.required,
+            approvals: progress.approvals,
+        }
+        .publish(env);
+    }
+}
+
+fn execute_action(env: &Env, proposal_id: u64, action: &ProposalAction) {
+    match action {
+        ProposalAction::Transfer(transfer) => execute_token_transfer(env, proposal_id, transfer),
+        ProposalAction::Approve(allowance) => execute_token_approval(env, allowance),
+        ProposalAction::Invoke(invocation) => execute_invocation(env, invocation),
+    }
+}
+
+fn execute_token_transfer(env: &Env, proposal_id: u64, transfer: &TransferAction) {
+    // Every outgoing transfer goes through here, so a frozen token can never
+    // leave the contract regardless of which path requested it
+    governance::require_token_not_frozen(env, &transfer.token_address);
+    treasury::require_within_reserve(env, &transfer.token_address, transfer.amount);
+
+    // Create a token client for the specified token
+    let token_client = soroban_sdk::token::Client::new(env, &transfer.token_address);
+
+    // Get the multisig contract address as the sender
+    let multisig_address = env.current_contract_address();
+
+    authorize_forwarded_call(
+        env,
+        &transfer.token_address,
+        symbol_short!("transfer"),
+        (multisig_address.clone(), transfer.recipient.clone(), transfer.amount).into_val(env),
+    );
+
+    // Execute the transfer from multisig to recipient. A token failure is
+    // reported as our own error, which rolls back the execution already
+    // recorded, so the proposal stays open and can be executed again once the
+    // cause is fixed.
+    let result = token_client.try_transfer(
+        &multisig_address,
+        &transfer.recipient,
+        &transfer.amount,
+    );
+    if let Err(failure) = result {
+        TokenTransferFailed {
+            proposal_id,
+            token: transfer.token_address.clone(),
+            code: token_error_code(failure),
+        }
+        .publish(env);
+        panic_with_error!(env, MultisigError::TransferFailed);
+    }
+}
+
+// The token's contract error code, when it failed with one rather than
+// trapping or hitting a host error.
+pub fn token_error_code(failure: Result<soroban_sdk::Error, InvokeError>) -> Option<u32> {
+    match failure {
+        Ok(error) if error.is_type(ScErrorType::Contract) => Some(error.get_code()),
+        Err(InvokeError::Contract(code)) => Some(code),
+        _ => None,
+    }
+}
+
+fn execute_token_approval(env: &Env, allowance: &AllowanceAction) {
+    governance::require_token_not_frozen(env, &allowance.token_address);
+    // The spender could draw the whole allowance at once
+    treasury::require_within_reserve(env, &allowance.token_address, allowance.amount);
+
+    authorize_forwarded_call(
+        env,
+        &allowance.token_address,
+        symbol_short!("approve"),
+        (
+            env.current_contract_address(),
+            allowance.spender.clone(),
+            allowance.amount,
+            allowance.expiration_ledger,
+        )
+            .into_val(env),
+    );
+
+    let token_client = soroban_sdk::token::Client::new(env, &allowance.token_address);
+    token_client.approve(
+        &env.current_contract_address(),
+        &allowance.spender,
+        &allowance.amount,
+        &allowance.expiration_ledger,
+    );
+}
+
+// The multisig's implicit authorization covers only the token call it makes
+// itself. A token that forwards to an implementation contract has the holder
+// authenticated there, one call deeper, so when governance has named that
+// implementation the identical call on it is pre-authorized here.
+pub fn authorize_forwarded_call(env: &Env, token: &Address, function: Symbol, args: Vec<Val>) {
+    let Some(implementation) = storage::read_token_implementation(env, token) else {
+        return;
+    };
+    env.authorize_as_current_contract(vec![
+        env,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: implementation,
+                fn_name: function,
+                args,
+            },
+            sub_invocations: Vec::new(env),
+        }),
+    ]);
+}
+
+// The target receives the multisig's authorization for this one direct call
+// only: `require_auth` on the multisig address succeeds inside `function`
+// itself, but nothing is pre-authorized with `authorize_as_current_contract`, so
+// any call the target makes onward in the multisig's name fails its auth check.
+fn execute_invocation(env: &Env, invocation: &InvokeAction) {
+    // Checked again here in case the proposal predates the ban
+    validation::raise(env, check_external_target(env, invocation));
+    env.invoke_contract::<Val>(&invocation.contract, &invocation.function, invocation.args.clone());
+}
+
+// Every creation-time rule, in the order `create_proposal` enforces them. Both
+// `create_proposal` and `validate_draft` run exactly this list.
+fn draft_checks(
+    env: &Env,
+    proposer: &BytesN<32>,
+    action: &ProposalAction,
+    destination_memo: &Option<String>,
+    document_hash: &Option<BytesN<32>>,
+    expires_in_seconds: u64,
+) -> [Result<(), MultisigError>; 11] {
+    let action_check = match action {
+        ProposalAction::Transfer(transfer) => validation::check_amount(transfer.amount),
+        // An allowance of zero is how an existing allowance is revoked
+        ProposalAction::Approve(allowance) if allowance.amount < 0 => Err(MultisigError::InvalidProposal),
+        ProposalAction::Approve(allowance) => validation::check_amount_ceiling(allowance.amount),
+        ProposalAction::Invoke(invocation) => check_external_target(env, invocation),
+    };
+
+    let token_check = match action.token() {
+        Some(token) => governance::check_token_not_frozen(env, &token),
+        None => Ok(()),
+    };
+
+    let (cap_check, value_cap_check) = match action.value() {
+        Some((token, amount)) => (
+            spending::check_within_cap(env, proposer, &token, amount),
+            spending::check_within_value_cap(env, proposer, &token, amount),
+        ),
+        None => (Ok(()), Ok(())),
+    };
+
+    let beneficiary_check = match action.beneficiary() {
+        Some(beneficiary) => address_book::check_listed(env, &beneficiary),
+        None => Ok(()),
+    };
+
+    [
+        validation::check_expiry(expires_in_seconds),
+        validation::check_signer(env, proposer),
+        signers::check_not_suspended(env, proposer),
+        signers::check_can_propose(env, proposer),
+        action_check,
+        token_check,
+        cap_check,
+        value_cap_check,
+        beneficiary_check,
+        check_memo(env, action, destination_memo),
+        check_document(env, action, document_hash, clock::now(env)),
+    ]
+}
+
+/// Only transfers carry a memo. Recipients governance has marked as needing
+/// one, such as exchange deposit addresses, must be given a non-empty memo.
+pub fn check_memo(env: &Env, action: &ProposalAction, destination_memo: &Option<String>) -> Result<(), MultisigError> {
+    let ProposalAction::Transfer(transfer) = action else {
+        return match destination_memo {
+            Some(_) => Err(MultisigError::InvalidProposal),
+            None => Ok(()),
+        };
+    };
+
+    let provided = destination_memo.as_ref().is_some_and(|memo| !memo.is_empty());
+    if !provided && storage::is_memo_required(env, &transfer.recipient) {
+        return Err(MultisigError::MemoRequired);
+    }
+    if destination_memo.as_ref().is_some_and(|memo| memo.is_empty() || memo.len() > validation::MAX_MEMO_LEN) {
+        return Err(MultisigError::InvalidProposal);
+    }
+    Ok(())
+}
+
+/// Payments above their token's document threshold, or worth more than the
+/// value threshold at time `at`, must name the document they settle. Any
+/// proposal may carry one below them.
+pub fn check_document(env: &Env, action: &ProposalAction, document_hash: &Option<BytesN<32>>, at: u64) -> Result<(), MultisigError> {
+    let Some((token, amount)) = action.value() else {
+        return Ok(());
+    };
+    if document_hash.is_some() {
+        return Ok(());
+    }
+    if storage::read_document_threshold(env, &token).is_some_and(|threshold| amount > threshold) {
+        return Err(MultisigError::DocumentHashRequired);
+    }
+    if let Some(threshold) = storage::read_document_value_threshold(env) {
+        if rates::to_reference_at(env, &token, amount, at)? > threshold {
+            return Err(MultisigError::DocumentHashRequired);
+        }
+    }
+    Ok(())
+}
+
+// Name `validate_draft` reports for a failed creation-time check.
+fn violation_name(env: &Env, error: MultisigError) -> Symbol {
+    let name = match error {
+        MultisigError::InvalidExpiryTime => "invalid_expiry",
+        MultisigError::UnknownSigner => "unknown_proposer",
+        MultisigError::TokenFrozen => "token_frozen",
+        MultisigError::SelfInvocationForbidden => "self_invocation",
+        MultisigError::ProposerCapExceeded => "proposer_cap",
+        MultisigError::AmountTooLarge => "amount_too_large",
+        MultisigError::RecipientNotInAddressBook => "unlisted_recipient",
+        MultisigError::MemoRequired => "memo_required",
+        MultisigError::DocumentHashRequired => "document_required",
+        MultisigError::StaleRate => "stale_rate",
+        MultisigError::ArithmeticOverflow => "amount_too_large",
+        _ => "invalid_amount",
+    };
+    Symbol::new(env, name)
+}
+
+// Calling the multisig through an approved invocation would let a payment
+// quorum reach entry points that are meant to be governed separately.
+fn check_external_target(env: &Env, invocation: &InvokeAction) -> Result<(), MultisigError> {
+    if invocation.contract == env.current_contract_address() {
+        return Err(MultisigError::SelfInvocationForbidden);
+    }
+    Ok(())
+}
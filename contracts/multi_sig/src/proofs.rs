@@ -0,0 +1,40 @@
+use soroban_sdk::{panic_with_error, Env};
+
+use crate::errors::MultisigError;
+use crate::storage;
+use crate::types::PaymentProof;
+use crate::validation;
+
+/// Assembles the proof that `proposal_id` was executed as approved.
+pub fn build(env: &Env, proposal_id: u64) -> PaymentProof {
+    validation::require_initialized(env);
+
+    let Some(proposal) = storage::read_proposal(env, proposal_id) else {
+        panic_with_error!(env, MultisigError::ProposalNotFound);
+    };
+    // Proposals executed before receipts were kept cannot be proven either
+    let Some(receipt) = storage::read_execution_receipt(env, proposal_id) else {
+        panic_with_error!(env, MultisigError::ProposalNotExecuted);
+    };
+
+    PaymentProof {
+        content_hash: storage::read_proposal_content_hash(env, &proposal),
+        proposal,
+        receipt,
+    }
+}
+
+/// Whether `proof` is internally consistent and matches what this contract
+/// recorded when it executed the proposal.
+pub fn verify(env: &Env, proof: &PaymentProof) -> bool {
+    validation::require_initialized(env);
+
+    if proof.proposal.content_hash(env) != proof.content_hash || proof.receipt.proposal_id != proof.proposal.id {
+        return false;
+    }
+    let Some(stored) = storage::read_proposal(env, proof.proposal.id) else {
+        return false;
+    };
+    storage::read_proposal_content_hash(env, &stored) == proof.content_hash
+        && storage::read_execution_receipt(env, proof.proposal.id).as_ref() == Some(&proof.receipt)
+}
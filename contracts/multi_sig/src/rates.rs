@@ -0,0 +1,59 @@
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::clock;
+use crate::errors::MultisigError;
+use crate::storage;
+use crate::types::ExchangeRate;
+
+/// How old a rate may be, in seconds, before value limits stop accepting it,
+/// unless governance has set an age of its own.
+pub const DEFAULT_MAX_RATE_AGE_SECONDS: u64 = 86_400;
+
+// Value limits are denominated in a reference unit, such as cents, that no
+// token is. Governance keeps one rate per token, and a payment is valued at
+// `amount * numerator / denominator`, rounded up so that no payment, however
+// small, is ever valued below what it is worth. Splitting a payment can then
+// only add to the value counted against a limit, never take from it.
+
+pub fn validate(env: &Env, rate: &ExchangeRate) {
+    if rate.numerator <= 0 || rate.denominator <= 0 || rate.as_of > clock::now(env) {
+        panic_with_error!(env, MultisigError::InvalidProposal);
+    }
+}
+
+pub fn validate_max_age(env: &Env, max_age_seconds: u64) {
+    if max_age_seconds == 0 {
+        panic_with_error!(env, MultisigError::InvalidProposal);
+    }
+}
+
+pub fn max_age(env: &Env) -> u64 {
+    storage::read_max_rate_age(env).unwrap_or(DEFAULT_MAX_RATE_AGE_SECONDS)
+}
+
+/// `amount` of `token` in the reference unit. Value limits fail closed: a
+/// token without a rate, or whose rate is older than `max_age`, fails with
+/// `StaleRate`.
+pub fn to_reference(env: &Env, token: &Address, amount: i128) -> Result<i128, MultisigError> {
+    to_reference_at(env, token, amount, clock::now(env))
+}
+
+/// `to_reference` with the rate's age judged at time `at`.
+pub fn to_reference_at(env: &Env, token: &Address, amount: i128, at: u64) -> Result<i128, MultisigError> {
+    let rate = storage::read_exchange_rate(env, token).ok_or(MultisigError::StaleRate)?;
+    if at.saturating_sub(rate.as_of) > max_age(env) {
+        return Err(MultisigError::StaleRate);
+    }
+
+    let scaled = amount.checked_mul(rate.numerator).ok_or(MultisigError::ArithmeticOverflow)?;
+    let value = scaled / rate.denominator;
+    Ok(if scaled % rate.denominator > 0 { value + 1 } else { value })
+}
+
+/// `to_reference`, failing the call on error.
+pub fn quote(env: &Env, token: &Address, amount: i128) -> i128 {
+    match to_reference(env, token, amount) {
+        Ok(value) => value,
+        Err(error) => panic_with_error!(env, error),
+    }
+}
@@ -0,0 +1,82 @@
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{panic_with_error, symbol_short, BytesN, Env, Vec};
+
+use crate::clock;
+use crate::errors::{MultisigError, SignerError};
+use crate::events::RecoveryClaimed;
+use crate::signatures;
+use crate::signers;
+use crate::storage;
+use crate::types::RecoveryConfig;
+use crate::validation;
+
+// Signers who lose enough keys strand the treasury for good. Governance can
+// name a recovery key to guard against that: once no approval or execution of
+// any proposal has happened for `inactivity_seconds`, the recovery key alone
+// may replace the whole signer set. Every approval and execution resets the
+// timer, so a committee that still works never sees the key act. Without a
+// configuration there is no recovery path at all.
+
+/// Checks `config` for `SetRecovery`.
+pub fn validate(env: &Env, config: &RecoveryConfig) {
+    if config.inactivity_seconds == 0 {
+        panic_with_error!(env, MultisigError::InvalidProposal);
+    }
+}
+
+/// Records signer activity now, restarting the inactivity timer.
+pub fn touch(env: &Env) {
+    storage::write_last_activity(env, clock::now(env));
+}
+
+/// The recovery configuration, if any, and the time after which its key may
+/// take over.
+pub fn status(env: &Env) -> Option<(RecoveryConfig, u64)> {
+    let config = storage::read_recovery(env)?;
+    let last_activity = storage::read_last_activity(env).unwrap_or(0);
+    let claimable_after = last_activity.saturating_add(config.inactivity_seconds);
+    Some((config, claimable_after))
+}
+
+/// What the recovery key must sign to install `signers` with `threshold`.
+pub fn get_claim_payload(env: &Env, signers: &Vec<BytesN<32>>, threshold: u32) -> BytesN<32> {
+    validation::require_initialized(env);
+    let Some(config) = storage::read_recovery(env) else {
+        panic_with_error!(env, SignerError::RecoveryDisabled);
+    };
+    signatures::next_payload(env, &config.recovery_key, &claim_digest(env, signers, threshold))
+}
+
+fn claim_digest(env: &Env, signers: &Vec<BytesN<32>>, threshold: u32) -> BytesN<32> {
+    let digest = (symbol_short!("recover"), env.current_contract_address(), signers.clone(), threshold);
+    env.crypto().sha256(&digest.to_xdr(env)).into()
+}
+
+/// Replaces the signer set with `signers` and `threshold` on the recovery
+/// key's authority. `signature` is the recovery key's over
+/// `get_recovery_payload` at its current nonce. Fails with
+/// `RecoveryDisabled` without a recovery configuration, and with
+/// `RecoveryNotDue` until more than `inactivity_seconds` have passed since
+/// the last approval or execution.
+pub fn claim(env: &Env, signers: Vec<BytesN<32>>, threshold: u32, signature: BytesN<64>) {
+    validation::require_active(env);
+
+    let Some((config, claimable_after)) = status(env) else {
+        panic_with_error!(env, SignerError::RecoveryDisabled);
+    };
+    if clock::now(env) <= claimable_after {
+        panic_with_error!(env, SignerError::RecoveryNotDue);
+    }
+    signers::validate_set(env, &signers, threshold);
+    let nonce = storage::read_signer_nonce(env, &config.recovery_key);
+    signatures::consume(env, &config.recovery_key, nonce, &claim_digest(env, &signers, threshold), &signature);
+
+    signers::replace_set(env, &signers, threshold);
+    touch(env);
+    RecoveryClaimed {
+        recovery_key: config.recovery_key,
+        signers,
+        threshold,
+    }
+    .publish(env);
+}
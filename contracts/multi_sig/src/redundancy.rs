@@ -0,0 +1,45 @@
+use soroban_sdk::Env;
+
+use crate::errors::SignerError;
+use crate::events::{RedundancyLost, RedundancyRestored};
+use crate::storage;
+use crate::types::{MultisigConfig, RedundancyPolicy};
+
+/// Whether a removal leaving `remaining` signers is allowed under the current
+/// policy. Only `Block` refuses, and only when the removal did not opt in.
+pub fn check_removal(env: &Env, remaining: u32, accept_no_redundancy: bool) -> Result<(), SignerError> {
+    if remaining == storage::read_threshold(env)
+        && !accept_no_redundancy
+        && storage::read_redundancy_policy(env) == RedundancyPolicy::Block
+    {
+        return Err(SignerError::RedundancyRequired);
+    }
+    Ok(())
+}
+
+/// Brings the `no_redundancy` flag in line with the signer set after it
+/// changes, announcing the transition when there is one.
+pub fn refresh(env: &Env) {
+    let signer_count = storage::read_signer_count(env);
+    let threshold = storage::read_threshold(env);
+    let lost = signer_count == threshold;
+    if lost == storage::is_no_redundancy(env) {
+        return;
+    }
+
+    storage::set_no_redundancy(env, lost);
+    if lost {
+        RedundancyLost { signer_count, threshold }.publish(env);
+    } else {
+        RedundancyRestored { signer_count, threshold }.publish(env);
+    }
+}
+
+pub fn config(env: &Env) -> MultisigConfig {
+    MultisigConfig {
+        signer_count: storage::read_signer_count(env),
+        threshold: storage::read_threshold(env),
+        redundancy_policy: storage::read_redundancy_policy(env),
+        no_redundancy: storage::is_no_redundancy(env),
+    }
+}
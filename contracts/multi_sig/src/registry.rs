@@ -0,0 +1,38 @@
+use soroban_sdk::{contractclient, panic_with_error, Address, BytesN, Env};
+
+use crate::errors::SignerError;
+
+/// Interface a contract must implement to hold the signer set for one or more
+/// multisig instances. This contract implements it itself, so one instance can
+/// serve as the registry for others.
+#[contractclient(name = "SignerRegistryClient")]
+pub trait SignerRegistry {
+    fn is_signer(env: Env, signer: BytesN<32>) -> bool;
+    fn signer_count(env: Env) -> u32;
+    fn threshold(env: Env) -> u32;
+}
+
+// Each read is a cross-contract call; a registry that traps, is missing or
+// answers with the wrong type surfaces as `RegistryUnavailable` rather than as
+// an opaque host error.
+
+pub fn is_signer(env: &Env, registry: &Address, key: &BytesN<32>) -> bool {
+    match SignerRegistryClient::new(env, registry).try_is_signer(key) {
+        Ok(Ok(is_signer)) => is_signer,
+        _ => panic_with_error!(env, SignerError::RegistryUnavailable),
+    }
+}
+
+pub fn signer_count(env: &Env, registry: &Address) -> u32 {
+    match SignerRegistryClient::new(env, registry).try_signer_count() {
+        Ok(Ok(count)) => count,
+        _ => panic_with_error!(env, SignerError::RegistryUnavailable),
+    }
+}
+
+pub fn threshold(env: &Env, registry: &Address) -> u32 {
+    match SignerRegistryClient::new(env, registry).try_threshold() {
+        Ok(Ok(threshold)) => threshold,
+        _ => panic_with_error!(env, SignerError::RegistryUnavailable),
+    }
+}
@@ -0,0 +1,62 @@
+use soroban_sdk::{panic_with_error, symbol_short, token, Env, IntoVal, Vec};
+
+use crate::errors::MultisigError;
+use crate::events::Retired;
+use crate::governance;
+use crate::payments;
+use crate::storage;
+use crate::types::Retirement;
+
+// Retiring hands the treasury over to a successor for good. The listed tokens
+// are swept first, so that nothing the signers meant to move stays behind, and
+// the successor is then recorded under `PolicyKey::Retired`. Nothing ever
+// removes that entry: from then on every call that could change state fails
+// with `ContractRetired`, including `__check_auth`, so old signers cannot move
+// funds by signing for the contract's address either. Views keep working.
+
+/// Checks `retirement` for `Retire`: the successor must be another address,
+/// and the tokens distinct and not frozen.
+pub fn validate(env: &Env, retirement: &Retirement) {
+    if retirement.successor == env.current_contract_address() {
+        panic_with_error!(env, MultisigError::InvalidProposal);
+    }
+    for (position, token) in retirement.tokens.iter().enumerate() {
+        if retirement.tokens.first_index_of(&token) != Some(position as u32) {
+            panic_with_error!(env, MultisigError::InvalidProposal);
+        }
+        governance::require_token_not_frozen(env, &token);
+    }
+}
+
+/// Sweeps the whole balance of every listed token to the successor, then
+/// retires the contract. A token that refuses the transfer fails the
+/// retirement with `TransferFailed`, leaving the contract as it was.
+pub fn retire(env: &Env, retirement: &Retirement) {
+    validate(env, retirement);
+
+    let this = env.current_contract_address();
+    let mut swept = Vec::new(env);
+    for token in retirement.tokens.iter() {
+        let client = token::Client::new(env, &token);
+        let balance = client.balance(&this);
+        if balance > 0 {
+            payments::authorize_forwarded_call(
+                env,
+                &token,
+                symbol_short!("transfer"),
+                (this.clone(), retirement.successor.clone(), balance).into_val(env),
+            );
+            if client.try_transfer(&this, &retirement.successor, &balance).is_err() {
+                panic_with_error!(env, MultisigError::TransferFailed);
+            }
+        }
+        swept.push_back((token, balance));
+    }
+
+    storage::write_successor(env, &retirement.successor);
+    Retired {
+        successor: retirement.successor.clone(),
+        swept,
+    }
+    .publish(env);
+}
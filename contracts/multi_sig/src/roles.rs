@@ -0,0 +1,94 @@
+use soroban_sdk::{panic_with_error, BytesN, Env, Vec};
+
+use crate::errors::MultisigError;
+use crate::features;
+use crate::storage;
+use crate::types::{ProposalApproval, RoleProgress, RoleRequirement, SignerRoleAssignment};
+
+pub fn assign_role(env: &Env, assignment: &SignerRoleAssignment) {
+    if !storage::is_signer(env, &assignment.signer) {
+        panic_with_error!(env, MultisigError::SignerNotFound);
+    }
+    storage::write_signer_role(env, &assignment.signer, Some(&assignment.role));
+}
+
+pub fn clear_role(env: &Env, signer: &BytesN<32>) {
+    storage::write_signer_role(env, signer, None);
+}
+
+/// Rejects requirement lists that name a role twice or ask for zero approvals.
+pub fn validate_requirements(env: &Env, requirements: &Vec<RoleRequirement>) {
+    for i in 0..requirements.len() {
+        let requirement = requirements.get_unchecked(i);
+        if requirement.count == 0 {
+            panic_with_error!(env, MultisigError::InvalidProposal);
+        }
+        for j in (i + 1)..requirements.len() {
+            if requirements.get_unchecked(j).role == requirement.role {
+                panic_with_error!(env, MultisigError::InvalidProposal);
+            }
+        }
+    }
+}
+
+// Roles are recorded as approvals arrive so that a later role change can be
+// detected at execution time.
+pub fn record_approval_role(env: &Env, proposal_id: u64, approver: &BytesN<32>) {
+    let Some(role) = storage::read_signer_role(env, approver) else {
+        return;
+    };
+    let mut roles = storage::read_approval_roles(env, proposal_id);
+    roles.set(approver.clone(), role);
+    storage::write_approval_roles(env, proposal_id, &roles);
+}
+
+pub fn forget_approval_role(env: &Env, proposal_id: u64, approver: &BytesN<32>) {
+    let mut roles = storage::read_approval_roles(env, proposal_id);
+    if roles.contains_key(approver.clone()) {
+        roles.remove(approver.clone());
+        storage::write_approval_roles(env, proposal_id, &roles);
+    }
+}
+
+/// Fulfillment of each role bucket for a proposal, or nothing when staged
+/// approval is off. A direct approval counts toward the role the signer held
+/// when approving, and only while they still hold it; a delegated vote counts
+/// toward the delegator's current role.
+pub fn role_progress(
+    env: &Env,
+    proposal_id: u64,
+    approvals: &Vec<ProposalApproval>,
+    approvers: &Vec<BytesN<32>>,
+) -> Vec<RoleProgress> {
+    let mut progress = Vec::new(env);
+    if !features::is_enabled(env, &features::STAGED_APPROVAL) {
+        return progress;
+    }
+
+    let recorded = storage::read_approval_roles(env, proposal_id);
+
+    for requirement in storage::read_role_requirements(env).iter() {
+        let mut count = 0u32;
+        for approver in approvers.iter() {
+            let Some(current) = storage::read_signer_role(env, &approver) else {
+                continue;
+            };
+            if current != requirement.role {
+                continue;
+            }
+            let approved_directly = approvals.iter().any(|a| a.signer == approver);
+            if approved_directly && recorded.get(approver.clone()) != Some(current) {
+                continue;
+            }
+            count += 1;
+        }
+
+        progress.push_back(RoleProgress {
+            role: requirement.role,
+            approvals: count,
+            required: requirement.count,
+        });
+    }
+
+    progress
+}
@@ -0,0 +1,86 @@
+use soroban_sdk::{panic_with_error, Env, Symbol, Vec};
+
+use crate::errors::{GovernanceError, MultisigError};
+use crate::storage;
+use crate::types::StorageSchema;
+
+/// Storage layout this code reads and writes. Bump it, and extend `FAMILIES`,
+/// whenever a release changes what is stored under any `DataKey`, including
+/// the key enums nested in it.
+///
+/// 7 covers every family added after 6, the move of later keys into nested
+/// enums, and the execution details on `Proposal`. 8 adds signer-set
+/// checkpoints. 9 keeps the full signer of an addition until it executes.
+pub const SCHEMA_VERSION: u32 = 9;
+
+/// Names of the storage key variants `SCHEMA_VERSION` covers, nested ones
+/// included.
+const FAMILIES: [&str; 110] = [
+    "Initialized", "SignerCount", "Signer", "Threshold", "Nonce", "ProposalCount", "Proposal",
+    "ProposalApprovals", "ProposalContentHash", "ProposalFlags", "SignerChangeProposal",
+    "SignerChangeApprovals", "VoteDelegations", "GovernanceProposal", "GovernanceApprovals", "FrozenToken", "FeatureFlags", "AllowLowSecurity",
+    "TokenWatchers", "SignerRole", "RoleRequirements", "ApprovalRoles", "ProtectedSigner",
+    "ProtectedQuorum", "Outcome", "ProposerCap", "ProposerUsage", "SignerHistoryCount", "SignerHistory",
+    "SignerRegistry", "FinalApprover", "AllocationTargets", "InitialDeposit", "TemplateCount", "Template",
+    "ProposalTemplate", "AddressBookNextId", "AddressBookIds", "AddressBookEntry", "AddressBookId",
+    "RecipientLabel", "TokenImplementation", "ReserveFloor", "IdempotencyKey", "ExecutionGrace",
+    "FullyApprovedAt", "FullyApprovedLedger", "ProposalNotes", "Observers", "IndexChunkCount",
+    "IndexChunk", "ExecutionReceipt", "SchemaVersion", "SchemaFamilies", "SessionCount", "SigningSession", "MemoRequired",
+    "SignerNonce", "SignerAddress", "RedundancyPolicy", "NoRedundancy", "DocumentThreshold",
+    "ExecutionSchedule", "AuditCount", "AuditEntry", "Passkey", "ProposalRejections", "Secp256k1Key",
+    "ConfigFingerprint", "BlsKey", "BlsSigners", "CancelGrace", "PendingCancellation",
+    "Delegation", "DelegatorOf", "ExchangeRate", "MaxRateAge", "ValueCap", "ProposerValueUsage",
+    "DocumentValueThreshold", "ImportDeadline", "ImportRef", "ImportedProposal",
+    "KeyRing", "LastRingActivation", "SignerJoinedAt", "ProposalSignerSetVersion",
+    "RequiredApprovals", "ExecutionRecord", "Paused", "Retired", "Guardian", "ProposalVetoed", "Recovery", "LastActivity", "SuspendedSigners",
+    "SignerReplacement", "SignerBatch", "PaymentProposalCount", "SignerChangeCount", "CountBackfill", "SignerList", "SignerLabel", "SignerHistoryProposal", "SignerPolicy", "SignerPermissions", "SignerLastActive", "InactivityRemoval",
+    "SignerSetCheckpoint", "SignerAddition",
+];
+
+// Code must never act on storage laid out by a version it does not know: after
+// a rollback, an older wasm would otherwise misread whatever the newer one
+// wrote. Deployments that predate the schema record carry none and are let
+// through until a migration stamps them.
+
+/// Fails with `SchemaMismatch` unless storage is laid out as this code
+/// expects. Runs as part of `require_initialized`, so every entry point
+/// other than initialization passes through it.
+pub fn require_schema(env: &Env) {
+    match storage::read_schema_version(env) {
+        None => {}
+        Some(version) if version == SCHEMA_VERSION => {}
+        Some(_) => panic_with_error!(env, GovernanceError::SchemaMismatch),
+    }
+}
+
+/// Migrations may start from any older layout, never from a newer one.
+pub fn require_migratable(env: &Env) {
+    if !storage::is_initialized(env) {
+        panic_with_error!(env, MultisigError::NotInitialized);
+    }
+    if storage::read_schema_version(env).is_some_and(|version| version > SCHEMA_VERSION) {
+        panic_with_error!(env, GovernanceError::SchemaMismatch);
+    }
+}
+
+/// Records that storage now follows this code's layout. Called on
+/// initialization and by the migration path.
+pub fn stamp(env: &Env) {
+    storage::write_schema_version(env, SCHEMA_VERSION);
+    storage::write_schema_families(env, &families(env));
+}
+
+pub fn current(env: &Env) -> StorageSchema {
+    StorageSchema {
+        version: storage::read_schema_version(env).unwrap_or(0),
+        families: storage::read_schema_families(env),
+    }
+}
+
+fn families(env: &Env) -> Vec<Symbol> {
+    let mut families = Vec::new(env);
+    for name in FAMILIES {
+        families.push_back(Symbol::new(env, name));
+    }
+    families
+}
@@ -0,0 +1,133 @@
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{panic_with_error, symbol_short, BytesN, Env, Vec};
+
+use crate::clock;
+use crate::errors::{MultisigError, SignerError};
+use crate::outcomes;
+use crate::payments;
+use crate::signatures;
+use crate::storage;
+use crate::types::{SessionApprovalReport, SigningSession};
+use crate::validation;
+
+/// Most proposals one signing session may cover.
+pub const MAX_SESSION_PROPOSALS: u32 = 50;
+
+/// What happened to one proposal when a session was approved.
+pub enum SessionItem {
+    Approved,
+    AlreadyApproved,
+    Amended,
+    Unavailable,
+}
+
+pub fn open(env: &Env, opener: BytesN<32>, proposal_ids: Vec<u64>, expires_in_seconds: u64) -> u64 {
+    validation::require_active(env);
+    validation::require_signer(env, &opener);
+    validation::validate_expiry(env, expires_in_seconds);
+
+    if proposal_ids.is_empty() || proposal_ids.len() > MAX_SESSION_PROPOSALS {
+        panic_with_error!(env, MultisigError::InvalidProposal);
+    }
+
+    let mut content_hashes = Vec::new(env);
+    for (i, proposal_id) in proposal_ids.iter().enumerate() {
+        if proposal_ids.first_index_of(proposal_id) != Some(i as u32) {
+            panic_with_error!(env, MultisigError::InvalidProposal);
+        }
+        let Some(proposal) = storage::read_proposal(env, proposal_id) else {
+            panic_with_error!(env, MultisigError::ProposalNotFound);
+        };
+        if outcomes::is_closed(env, proposal_id) || storage::has_proposal_flag(env, proposal_id, storage::FLAG_EXECUTED) {
+            panic_with_error!(env, MultisigError::ProposalAlreadyExecuted);
+        }
+        if clock::now(env) > proposal.expires_at {
+            panic_with_error!(env, MultisigError::ProposalExpired);
+        }
+        content_hashes.push_back(storage::read_proposal_content_hash(env, &proposal));
+    }
+
+    let created_at = clock::now(env);
+    let expires_at = created_at + expires_in_seconds;
+    let id = storage::next_session_id(env);
+    let session_hash = session_hash(env, id, &proposal_ids, &content_hashes, expires_at);
+
+    storage::write_signing_session(
+        env,
+        &SigningSession {
+            id,
+            opened_by: opener,
+            proposal_ids,
+            content_hashes,
+            session_hash,
+            created_at,
+            expires_at,
+        },
+    );
+    id
+}
+
+/// Records `signer`'s approval on every proposal in the session that can still
+/// take it. `signature` is the signer's ed25519 signature over the session
+/// hash at their nonce `nonce`, so anyone may relay it, but only once.
+pub fn approve(env: &Env, session_id: u64, signer: BytesN<32>, nonce: u64, signature: BytesN<64>) -> SessionApprovalReport {
+    validation::require_active(env);
+    validation::require_signer(env, &signer);
+
+    let session = load(env, session_id);
+    if clock::now(env) > session.expires_at {
+        panic_with_error!(env, SignerError::SessionExpired);
+    }
+
+    signatures::consume(env, &signer, nonce, &session.session_hash, &signature);
+
+    let mut report = SessionApprovalReport {
+        approved: Vec::new(env),
+        already_approved: Vec::new(env),
+        amended: Vec::new(env),
+        unavailable: Vec::new(env),
+    };
+    for (proposal_id, snapshot) in session.proposal_ids.iter().zip(session.content_hashes.iter()) {
+        let bucket = match payments::approve_snapshot(env, proposal_id, &signer, &snapshot) {
+            SessionItem::Approved => &mut report.approved,
+            SessionItem::AlreadyApproved => &mut report.already_approved,
+            SessionItem::Amended => &mut report.amended,
+            SessionItem::Unavailable => &mut report.unavailable,
+        };
+        bucket.push_back(proposal_id);
+    }
+    report
+}
+
+/// What `signer` must sign to approve the session next.
+pub fn next_payload(env: &Env, session_id: u64, signer: &BytesN<32>) -> BytesN<32> {
+    validation::require_initialized(env);
+    signatures::next_payload(env, signer, &load(env, session_id).session_hash)
+}
+
+pub fn load(env: &Env, session_id: u64) -> SigningSession {
+    let Some(session) = storage::read_signing_session(env, session_id) else {
+        panic_with_error!(env, SignerError::SessionNotFound);
+    };
+    session
+}
+
+// Binds the signature to this contract, this session and the exact contents of
+// every proposal in it, so it cannot be replayed anywhere else.
+fn session_hash(
+    env: &Env,
+    session_id: u64,
+    proposal_ids: &Vec<u64>,
+    content_hashes: &Vec<BytesN<32>>,
+    expires_at: u64,
+) -> BytesN<32> {
+    let contents = (
+        symbol_short!("session"),
+        env.current_contract_address(),
+        session_id,
+        proposal_ids.clone(),
+        content_hashes.clone(),
+        expires_at,
+    );
+    env.crypto().sha256(&contents.to_xdr(env)).into()
+}
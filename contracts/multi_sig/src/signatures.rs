@@ -0,0 +1,45 @@
+use soroban_sdk::crypto::Hash;
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{panic_with_error, BytesN, Env};
+
+use crate::errors::MultisigError;
+use crate::storage;
+
+// Every signature the contract accepts is over a digest of what is being
+// authorized combined with the signer's next nonce. Accepting one uses the
+// nonce up, so a signature works exactly once even if the same digest comes
+// round again, for example when a proposal is recreated.
+
+/// What `signer` signs to authorize `digest` with their nonce `nonce`.
+pub fn payload(env: &Env, digest: &BytesN<32>, nonce: u64) -> BytesN<32> {
+    env.crypto().sha256(&(digest.clone(), nonce).to_xdr(env)).into()
+}
+
+/// `payload` hashed with keccak256 instead, for secp256k1 signers. `digest`
+/// is then the keccak256 proposal hash as well.
+pub fn payload_keccak(env: &Env, digest: &BytesN<32>, nonce: u64) -> Hash<32> {
+    env.crypto().keccak256(&(digest.clone(), nonce).to_xdr(env))
+}
+
+/// `payload` at `signer`'s next nonce.
+pub fn next_payload(env: &Env, signer: &BytesN<32>, digest: &BytesN<32>) -> BytesN<32> {
+    payload(env, digest, storage::read_signer_nonce(env, signer))
+}
+
+/// Verifies `signature` as `signer`'s over `digest` at `nonce`, then advances
+/// their nonce. A nonce other than the signer's next one, as carried by a
+/// replayed signature, fails with `InvalidNonce`.
+pub fn consume(env: &Env, signer: &BytesN<32>, nonce: u64, digest: &BytesN<32>, signature: &BytesN<64>) {
+    advance(env, signer, nonce);
+    env.crypto().ed25519_verify(signer, &payload(env, digest, nonce).into(), signature);
+}
+
+/// Uses up `signer`'s nonce `nonce` for a signature checked by the caller,
+/// failing with `InvalidNonce` unless it is their next one. A signature that
+/// then fails to verify aborts the call, taking the advance with it.
+pub fn advance(env: &Env, signer: &BytesN<32>, nonce: u64) {
+    if nonce != storage::read_signer_nonce(env, signer) {
+        panic_with_error!(env, MultisigError::InvalidNonce);
+    }
+    storage::write_signer_nonce(env, signer, nonce + 1);
+}
@@ -0,0 +1,1081 @@
+use soroban_sdk::xdr::{FromXdr, ToXdr};
+use soroban_sdk::{panic_with_error, symbol_short, Address, Bytes, BytesN, Env, Vec};
+
+use crate::activity;
+use crate::bls;
+use crate::clock;
+use crate::counts;
+use crate::delegation;
+use crate::features;
+use crate::errors::{MultisigError, SignerError};
+use crate::events::{SignerChangeCanceled, SignerKeyRotated, ThresholdChanged};
+use crate::fingerprint;
+use crate::history;
+use crate::import;
+use crate::index;
+use crate::keyring;
+use crate::labels;
+use crate::registry;
+use crate::outcomes;
+use crate::pagination;
+use crate::payments;
+use crate::recovery;
+use crate::redundancy;
+use crate::roles;
+use crate::schema;
+use crate::signatures;
+use crate::storage;
+use crate::types::{IndexKey, ProposalOutcome, RedundancyPolicy, Signer, SignerBatch, SignerChangeApproval, SignerChangeKind, SignerChangeProposal, SignerPermissions, SignerPolicy, SignerSetChange};
+use crate::validation;
+
+pub fn initialize(env: &Env, signers: Vec<BytesN<32>>, threshold: u32, allow_low_security: bool) {
+    if storage::is_initialized(env) {
+        panic_with_error!(env, MultisigError::AlreadyInitialized);
+    }
+
+    if signers.is_empty() {
+        panic_with_error!(env, MultisigError::EmptySignersList);
+    }
+
+    if threshold == 0 {
+        panic_with_error!(env, MultisigError::InvalidThreshold);
+    }
+
+    if threshold > signers.len() {
+        panic_with_error!(env, MultisigError::ThresholdExceedsSigners);
+    }
+
+    validation::validate_security_floor(env, signers.len(), threshold, allow_low_security);
+
+    // Check for duplicate signers
+    for i in 0..signers.len() {
+        for j in (i + 1)..signers.len() {
+            if signers.get_unchecked(i) == signers.get_unchecked(j) {
+                panic_with_error!(env, MultisigError::DuplicateSigner);
+            }
+        }
+    }
+
+    storage::set_initialized(env);
+    schema::stamp(env);
+    import::open(env);
+    counts::open(env);
+    storage::write_signer_count(env, signers.len());
+    storage::write_threshold(env, threshold);
+    storage::write_nonce(env, 0);
+    storage::write_allow_low_security(env, allow_low_security);
+
+    for signer in signers.iter() {
+        storage::add_signer(env, &signer);
+    }
+
+    history::record(env, SignerSetChange::Initialized(signers, threshold));
+    redundancy::refresh(env);
+}
+
+/// `initialize` with a signer set that may include contracts. Each is stored
+/// under its `Signer::id`. Contract signers are bound to their address so
+/// everything they do needs that contract's authorization, and secp256r1 and
+/// secp256k1 signers to their public key. With a `policy` the signer count is
+/// held within it from then on; the initial set must already be. Signers
+/// listed in `permissions` are restricted to what they are granted there, and
+/// `only_signers_execute` starts the instance with `SIGNERS_EXECUTE` on.
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_with_signers(
+    env: &Env,
+    signers: Vec<Signer>,
+    threshold: u32,
+    allow_low_security: bool,
+    policy: Option<SignerPolicy>,
+    permissions: Vec<(BytesN<32>, SignerPermissions)>,
+    only_signers_execute: bool,
+) {
+    let mut ids = Vec::new(env);
+    for signer in signers.iter() {
+        ids.push_back(signer.id(env));
+    }
+    initialize(env, ids, threshold, allow_low_security);
+
+    for signer in signers.iter() {
+        bind(env, &signer);
+    }
+    if let Some(policy) = policy {
+        validate_signer_policy(env, &policy);
+        storage::write_signer_policy(env, Some(&policy));
+    }
+    for (signer, granted) in permissions.iter() {
+        validate_permissions(env, &signer, &granted);
+        set_permissions(env, &signer, &granted);
+    }
+    if only_signers_execute {
+        features::set_enabled(env, &features::SIGNERS_EXECUTE, true);
+    }
+}
+
+// Contract signers carry no key to sign with, so their id is always bound to
+// the address that authorizes for them. Secp256r1 and secp256k1 ids are bound
+// to the public key their signatures are checked against. Every such id is
+// derived from what it is bound to, so rebinding one never changes anything.
+fn bind(env: &Env, signer: &Signer) -> BytesN<32> {
+    let id = signer.id(env);
+    match signer {
+        Signer::Contract(address) => storage::write_signer_address(env, &id, Some(address)),
+        Signer::Secp256r1(passkey) => storage::write_passkey(env, &id, Some(passkey)),
+        Signer::Secp256k1(public_key) => storage::write_secp256k1_key(env, &id, Some(public_key)),
+        Signer::Ed25519(_) => {}
+    }
+    id
+}
+
+/// Binds signer keys to the addresses that must authorize their calls. Keys
+/// left out stay unbound and act on the key alone, as before.
+pub fn bind_addresses(env: &Env, bindings: Vec<(BytesN<32>, Address)>) {
+    for (key, address) in bindings.iter() {
+        validation::require_signer(env, &key);
+        if storage::read_signer_address(env, &key).is_some() {
+            panic_with_error!(env, MultisigError::DuplicateSigner);
+        }
+        storage::write_signer_address(env, &key, Some(&address));
+    }
+}
+
+/// Requires authorization from the address bound to `key`, if it has one.
+pub fn require_auth(env: &Env, key: &BytesN<32>) {
+    if let Some(address) = storage::read_signer_address(env, key) {
+        address.require_auth();
+    }
+}
+
+/// Requires authorization for `key` even when no address is bound to it. An
+/// unbound ed25519 key stands for the Stellar account it is the master key
+/// of. Unbound secp256r1 and secp256k1 keys have no account, so they fail
+/// with `SignerUnbound`.
+pub fn require_account_auth(env: &Env, key: &BytesN<32>) {
+    if let Some(address) = storage::read_signer_address(env, key) {
+        address.require_auth();
+        return;
+    }
+    if storage::read_passkey(env, key).is_some() || storage::read_secp256k1_key(env, key).is_some() {
+        panic_with_error!(env, SignerError::SignerUnbound);
+    }
+
+    // `ScVal::Address(ScAddress::Account(PublicKey::Ed25519(key)))` as XDR
+    let mut account = Bytes::from_array(env, &[0, 0, 0, 18, 0, 0, 0, 0, 0, 0, 0, 0]);
+    account.append(&Bytes::from(key.clone()));
+    Address::from_xdr(env, &account)
+        .unwrap_or_else(|_| panic_with_error!(env, SignerError::SignerUnbound))
+        .require_auth();
+}
+
+/// Initializes an instance whose signers and threshold are held by `registry`
+/// instead of locally. Membership changes are made on the registry and apply
+/// to every instance sharing it.
+pub fn initialize_with_registry(env: &Env, registry: Address) {
+    if storage::is_initialized(env) {
+        panic_with_error!(env, MultisigError::AlreadyInitialized);
+    }
+
+    if registry == env.current_contract_address() {
+        panic_with_error!(env, MultisigError::InvalidProposal);
+    }
+
+    // Fail now rather than on first use if the registry cannot answer
+    registry::threshold(env, &registry);
+
+    storage::set_initialized(env);
+    schema::stamp(env);
+    import::open(env);
+    counts::open(env);
+    storage::write_nonce(env, 0);
+    storage::write_signer_registry(env, &registry);
+}
+
+pub fn propose_signer_change(
+    env: &Env,
+    proposer: BytesN<32>,
+    kind: SignerChangeKind,
+    signer: BytesN<32>,
+    expires_in_seconds: u64,
+) -> u64 {
+    propose(env, proposer, kind, signer, Schedule::unscheduled(expires_in_seconds), false, Detail::Single)
+}
+
+/// `propose_signer_change` for a `Signer`, so contracts and secp256r1 or
+/// secp256k1 keys can be added to or removed from the signer set.
+pub fn propose_signer_change_v2(
+    env: &Env,
+    proposer: BytesN<32>,
+    kind: SignerChangeKind,
+    signer: Signer,
+    expires_in_seconds: u64,
+) -> u64 {
+    // Whatever the key is tied to only takes effect once the addition does
+    let id = signer.id(env);
+    let detail = if kind == SignerChangeKind::Add { Detail::Addition(signer) } else { Detail::Single };
+    propose(env, proposer, kind, id, Schedule::unscheduled(expires_in_seconds), false, detail)
+}
+
+/// Like `propose_signer_change`, but approvals close `approval_window_seconds`
+/// after creation and execution waits until `execute_not_before`, if given.
+pub fn propose_scheduled_signer_change(
+    env: &Env,
+    proposer: BytesN<32>,
+    kind: SignerChangeKind,
+    signer: BytesN<32>,
+    approval_window_seconds: u64,
+    execute_not_before: Option<u64>,
+    expires_in_seconds: u64,
+) -> u64 {
+    let schedule = Schedule {
+        approval_window_seconds,
+        execute_not_before,
+        expires_in_seconds,
+    };
+    propose(env, proposer, kind, signer, schedule, false, Detail::Single)
+}
+
+/// Proposes removing `signer`. With `accept_no_redundancy` the removal may
+/// leave exactly as many signers as the threshold even under
+/// `RedundancyPolicy::Block`.
+pub fn propose_signer_removal(
+    env: &Env,
+    proposer: BytesN<32>,
+    signer: BytesN<32>,
+    expires_in_seconds: u64,
+    accept_no_redundancy: bool,
+) -> u64 {
+    propose(env, proposer, SignerChangeKind::Remove, signer, Schedule::unscheduled(expires_in_seconds), accept_no_redundancy, Detail::Single)
+}
+
+/// Proposes a "replace" change that swaps `old_signer` for `new_signer` in
+/// one vote, leaving the signer count as it is.
+pub fn propose_signer_replacement(
+    env: &Env,
+    proposer: BytesN<32>,
+    old_signer: BytesN<32>,
+    new_signer: BytesN<32>,
+    expires_in_seconds: u64,
+) -> u64 {
+    let schedule = Schedule::unscheduled(expires_in_seconds);
+    propose(env, proposer, SignerChangeKind::Replace, old_signer, schedule, false, Detail::Replacement(new_signer))
+}
+
+/// Most additions and removals one batch may carry together.
+pub const MAX_BATCH_CHANGES: u32 = 10;
+
+/// Proposes a "batch" change that adds `additions`, removes `removals` and
+/// sets `new_threshold`, if given, as one unit under one vote.
+pub fn propose_signer_batch(
+    env: &Env,
+    proposer: BytesN<32>,
+    additions: Vec<BytesN<32>>,
+    removals: Vec<BytesN<32>>,
+    new_threshold: Option<u32>,
+    expires_in_seconds: u64,
+) -> u64 {
+    let batch = SignerBatch {
+        additions,
+        removals,
+        threshold: new_threshold,
+    };
+    let schedule = Schedule::unscheduled(expires_in_seconds);
+    propose(env, proposer.clone(), SignerChangeKind::Batch, proposer, schedule, false, Detail::Batch(batch))
+}
+
+// What a signer change carries beyond its `signer`.
+enum Detail {
+    Single,
+    Addition(Signer),
+    Replacement(BytesN<32>),
+    Batch(SignerBatch),
+}
+
+// When a signer change may be approved and executed.
+struct Schedule {
+    approval_window_seconds: u64,
+    execute_not_before: Option<u64>,
+    expires_in_seconds: u64,
+}
+
+impl Schedule {
+    fn unscheduled(expires_in_seconds: u64) -> Self {
+        Schedule {
+            approval_window_seconds: expires_in_seconds,
+            execute_not_before: None,
+            expires_in_seconds,
+        }
+    }
+}
+
+fn propose(
+    env: &Env,
+    proposer: BytesN<32>,
+    kind: SignerChangeKind,
+    signer: BytesN<32>,
+    schedule: Schedule,
+    accept_no_redundancy: bool,
+    detail: Detail,
+) -> u64 {
+    validation::require_active(env);
+
+    let Schedule {
+        approval_window_seconds,
+        execute_not_before,
+        expires_in_seconds,
+    } = schedule;
+
+    if storage::read_signer_registry(env).is_some() {
+        panic_with_error!(env, SignerError::RegistryManaged);
+    }
+
+    validation::validate_expiry(env, expires_in_seconds);
+    validate_schedule(env, approval_window_seconds, execute_not_before, expires_in_seconds);
+    validation::require_signer(env, &proposer);
+    require_not_suspended(env, &proposer);
+    require_can_propose(env, &proposer);
+    require_account_auth(env, &proposer);
+
+    // Replacements and batches carry more than one key, so only their own
+    // entry points propose them
+    match (&kind, &detail) {
+        (SignerChangeKind::Add, Detail::Single | Detail::Addition(_)) => {
+            if storage::is_signer(env, &signer) {
+                panic_with_error!(env, MultisigError::DuplicateSigner);
+            }
+        }
+        (SignerChangeKind::Remove, Detail::Single) => {
+            if !storage::is_signer(env, &signer) {
+                panic_with_error!(env, MultisigError::SignerNotFound);
+            }
+
+            let current_count = storage::read_signer_count(env);
+            let threshold = storage::read_threshold(env);
+            if active_count_without(env, &signer) < threshold {
+                panic_with_error!(env, MultisigError::ThresholdExceedsSigners);
+            }
+            validation::require_security_floor(env, current_count - 1, threshold);
+            validation::raise(env, redundancy::check_removal(env, current_count - 1, accept_no_redundancy));
+        }
+        // The signer must be one, and in the opposite state
+        (SignerChangeKind::Suspend | SignerChangeKind::Reinstate, Detail::Single) => {
+            validation::require_signer(env, &signer);
+            let suspend = kind == SignerChangeKind::Suspend;
+            if is_suspended(env, &signer) == suspend {
+                panic_with_error!(env, MultisigError::InvalidProposal);
+            }
+            if suspend && active_count_without(env, &signer) < storage::read_threshold(env) {
+                panic_with_error!(env, MultisigError::ThresholdExceedsSigners);
+            }
+        }
+        // The old key must be a signer and the new one must not
+        (SignerChangeKind::Replace, Detail::Replacement(new_signer)) => check_replacement(env, &signer, new_signer),
+        // The set it leaves behind must be valid
+        (SignerChangeKind::Batch, Detail::Batch(batch)) => {
+            check_batch(env, batch);
+        }
+        _ => panic_with_error!(env, MultisigError::InvalidProposal),
+    }
+
+    let current_time = clock::now(env);
+    let proposal_id = storage::next_proposal_id(env);
+
+    let proposal = SignerChangeProposal {
+        id: proposal_id,
+        proposer,
+        change_type: kind,
+        signer,
+        created_at: current_time,
+        expires_at: current_time + expires_in_seconds,
+        approval_deadline: current_time + approval_window_seconds,
+        execute_not_before,
+        executed: false,
+        accept_no_redundancy,
+    };
+
+    storage::write_signer_change(env, &proposal);
+    storage::write_signer_change_approvals(env, proposal_id, &Vec::new(env));
+    storage::write_required_approvals(env, proposal_id, storage::read_threshold(env));
+    counts::record_signer_change(env);
+    activity::record(env, &proposal.proposer);
+    match &detail {
+        Detail::Single => {}
+        Detail::Addition(signer) => storage::write_signer_addition(env, proposal_id, signer),
+        Detail::Replacement(new_signer) => storage::write_signer_replacement(env, proposal_id, new_signer),
+        Detail::Batch(batch) => storage::write_signer_batch(env, proposal_id, batch),
+    }
+    index::append(env, &IndexKey::OpenSignerChanges, proposal_id);
+
+    proposal_id
+}
+
+pub fn approve_signer_change(env: &Env, proposal_id: u64, approver: BytesN<32>) {
+    validation::require_active(env);
+
+    validation::require_signer(env, &approver);
+    require_not_suspended(env, &approver);
+    require_can_approve(env, &approver);
+    require_account_auth(env, &approver);
+
+    let proposal = load_open_signer_change(env, proposal_id);
+
+    if clock::now(env) > proposal.expires_at {
+        panic_with_error!(env, SignerError::SignerChangeExpired);
+    }
+
+    if clock::now(env) > proposal.approval_deadline {
+        panic_with_error!(env, SignerError::SignerChangeApprovalClosed);
+    }
+
+    let mut approvals = storage::read_signer_change_approvals(env, proposal_id);
+
+    // Check if already approved
+    for i in 0..approvals.len() {
+        let approval = approvals.get_unchecked(i);
+        if approval.signer == approver {
+            panic_with_error!(env, SignerError::SignerChangeAlreadyApproved);
+        }
+    }
+
+    let approval = SignerChangeApproval {
+        signer: approver,
+        approved_at: clock::now(env),
+    };
+
+    activity::record(env, &approval.signer);
+    approvals.push_back(approval);
+    storage::write_signer_change_approvals(env, proposal_id, &approvals);
+    recovery::touch(env);
+}
+
+pub fn revoke_signer_change_approval(env: &Env, proposal_id: u64, revoker: BytesN<32>) {
+    validation::require_active(env);
+
+    validation::require_signer(env, &revoker);
+    require_account_auth(env, &revoker);
+
+    load_open_signer_change(env, proposal_id);
+
+    let mut approvals = storage::read_signer_change_approvals(env, proposal_id);
+    let Some(position) = approvals.iter().position(|approval| approval.signer == revoker) else {
+        panic_with_error!(env, MultisigError::SignerNotFound);
+    };
+    approvals.remove(position as u32);
+
+    storage::write_signer_change_approvals(env, proposal_id, &approvals);
+}
+
+/// Withdraws `proposal_id` as its proposer. Unlike a payment cancellation
+/// there is no grace to restore it in: a signer change is cheap to propose
+/// again, so it closes as `Canceled` at once.
+pub fn cancel_signer_change(env: &Env, proposal_id: u64, proposer: BytesN<32>) {
+    validation::require_active(env);
+
+    validation::require_signer(env, &proposer);
+    require_account_auth(env, &proposer);
+
+    let proposal = load_open_signer_change(env, proposal_id);
+    if proposal.proposer != proposer {
+        panic_with_error!(env, MultisigError::NotProposer);
+    }
+
+    outcomes::record(env, proposal_id, ProposalOutcome::Canceled, Some(proposer.clone()));
+    index::remove(env, &IndexKey::OpenSignerChanges, proposal_id);
+    SignerChangeCanceled { proposal_id, proposer }.publish(env);
+}
+
+pub fn execute_signer_change(env: &Env, proposal_id: u64) {
+    validation::require_active(env);
+
+    let proposal = load_open_signer_change(env, proposal_id);
+
+    if clock::now(env) > proposal.expires_at {
+        panic_with_error!(env, SignerError::SignerChangeExpired);
+    }
+
+    if proposal.execute_not_before.is_some_and(|effective| clock::now(env) < effective) {
+        panic_with_error!(env, SignerError::SignerChangeNotYetEffective);
+    }
+
+    // Approvals by signers removed since no longer count
+    let approvals = storage::read_signer_change_approvals(env, proposal_id);
+    let current = approvals.iter().filter(|approval| storage::is_signer(env, &approval.signer)).count() as u32;
+    let threshold = storage::read_threshold(env);
+
+    if current < required_signer_change_approvals(env, &proposal) {
+        panic_with_error!(env, SignerError::InsufficientSignerChangeApprovals);
+    }
+
+    // Execute the signer change
+    let version = history::version(env);
+    match proposal.change_type {
+        SignerChangeKind::Add => {
+            // Another change may have added the key in the meantime
+            if storage::is_signer(env, &proposal.signer) {
+                panic_with_error!(env, MultisigError::DuplicateSigner);
+            }
+            validation::raise(env, check_signer_policy(env, storage::read_signer_count(env) + 1));
+            storage::add_signer(env, &proposal.signer);
+            if let Some(signer) = storage::read_signer_addition(env, proposal_id) {
+                bind(env, &signer);
+            }
+            storage::write_signer_count(env, storage::read_signer_count(env) + 1);
+            history::record(env, SignerSetChange::Added(proposal.signer.clone()));
+            history::record_joined(env, &proposal.signer);
+        }
+        SignerChangeKind::Remove => {
+            // Other removals may have executed since this one was proposed
+            if !storage::is_signer(env, &proposal.signer) {
+                panic_with_error!(env, MultisigError::SignerNotFound);
+            }
+            let remaining = storage::read_signer_count(env) - 1;
+            if active_count_without(env, &proposal.signer) < threshold {
+                panic_with_error!(env, MultisigError::ThresholdExceedsSigners);
+            }
+            validation::require_security_floor(env, remaining, threshold);
+            validation::raise(env, redundancy::check_removal(env, remaining, proposal.accept_no_redundancy));
+            validation::raise(env, check_signer_policy(env, remaining));
+
+            evict(env, &proposal.signer);
+            storage::write_signer_count(env, storage::read_signer_count(env) - 1);
+            history::record(env, SignerSetChange::Removed(proposal.signer.clone()));
+        }
+        SignerChangeKind::Suspend => {
+            // Other suspensions and removals may have executed in the meantime
+            validation::require_signer(env, &proposal.signer);
+            if active_count_without(env, &proposal.signer) < threshold {
+                panic_with_error!(env, MultisigError::ThresholdExceedsSigners);
+            }
+            set_suspended(env, &proposal.signer, true);
+            payments::drop_approvals_by(env, &proposal.signer);
+        }
+        SignerChangeKind::Reinstate => {
+            validation::require_signer(env, &proposal.signer);
+            set_suspended(env, &proposal.signer, false);
+        }
+        SignerChangeKind::Replace => {
+            // Either key may have left or joined the set in the meantime
+            let new_signer = storage::read_signer_replacement(env, proposal_id).unwrap();
+            check_replacement(env, &proposal.signer, &new_signer);
+
+            // The count stays as it is; nothing tied to the old key carries over
+            evict(env, &proposal.signer);
+            storage::add_signer(env, &new_signer);
+            history::record(env, SignerSetChange::Removed(proposal.signer.clone()));
+            history::record(env, SignerSetChange::Added(new_signer.clone()));
+            history::record_joined(env, &new_signer);
+        }
+        SignerChangeKind::Batch => {
+            // Validated as a whole against the set as it is now, before any of it
+            // applies
+            let batch = storage::read_signer_batch(env, proposal_id).unwrap();
+            let signers = check_batch(env, &batch);
+
+            for signer in batch.removals.iter() {
+                evict(env, &signer);
+                drop_signer_change_approvals_by(env, &signer);
+                history::record(env, SignerSetChange::Removed(signer));
+            }
+            for signer in batch.additions.iter() {
+                storage::add_signer(env, &signer);
+                history::record(env, SignerSetChange::Added(signer.clone()));
+                history::record_joined(env, &signer);
+            }
+            storage::write_signer_count(env, signers.len());
+            if let Some(threshold) = batch.threshold.filter(|changed| *changed != threshold) {
+                set_threshold(env, threshold);
+            }
+        }
+    }
+    history::attribute(env, version, proposal_id);
+    debug_assert!(signer_set_consistent(env));
+    redundancy::refresh(env);
+    fingerprint::refresh(env);
+    recovery::touch(env);
+
+    // Mark as executed
+    storage::set_proposal_flag(env, proposal_id, storage::FLAG_EXECUTED);
+    outcomes::record(env, proposal_id, ProposalOutcome::Executed, None);
+    index::remove(env, &IndexKey::OpenSignerChanges, proposal_id);
+    if matches!(proposal.change_type, SignerChangeKind::Remove | SignerChangeKind::Suspend | SignerChangeKind::Replace) {
+        drop_signer_change_approvals_by(env, &proposal.signer);
+    }
+
+    let mut updated_proposal = proposal;
+    updated_proposal.executed = true;
+    storage::write_signer_change(env, &updated_proposal);
+}
+
+// The stored count, the signer list and the per-key flags all describe the
+// same set.
+fn signer_set_consistent(env: &Env) -> bool {
+    let list = storage::read_signer_list(env);
+    list.len() == storage::read_signer_count(env) && list.iter().all(|key| storage::is_signer(env, &key))
+}
+
+// A replacement swaps a signer for a key that is not one yet.
+fn check_replacement(env: &Env, old_signer: &BytesN<32>, new_signer: &BytesN<32>) {
+    if !storage::is_signer(env, old_signer) {
+        panic_with_error!(env, MultisigError::SignerNotFound);
+    }
+    if storage::is_signer(env, new_signer) {
+        panic_with_error!(env, MultisigError::DuplicateSigner);
+    }
+}
+
+// Checks the set `batch` would leave behind, as `initialize` would check it,
+// and returns it. Its threshold must be within the active signers, and leave
+// a spare signer under `RedundancyPolicy::Block`.
+fn check_batch(env: &Env, batch: &SignerBatch) -> Vec<BytesN<32>> {
+    let changes = batch.additions.len() + batch.removals.len();
+    if changes > MAX_BATCH_CHANGES || (changes == 0 && batch.threshold.is_none()) {
+        panic_with_error!(env, MultisigError::InvalidProposal);
+    }
+
+    let mut signers = storage::read_signer_list(env);
+    for signer in batch.removals.iter() {
+        let Some(position) = signers.first_index_of(&signer) else {
+            panic_with_error!(env, MultisigError::SignerNotFound);
+        };
+        signers.remove(position);
+    }
+    for signer in batch.additions.iter() {
+        if batch.removals.contains(&signer) {
+            panic_with_error!(env, MultisigError::InvalidProposal);
+        }
+        signers.push_back(signer);
+    }
+
+    let threshold = batch.threshold.unwrap_or_else(|| storage::read_threshold(env));
+    validate_set(env, &signers, threshold);
+    let active = signers.iter().filter(|signer| counts_towards_threshold(env, signer)).count() as u32;
+    if threshold > active {
+        panic_with_error!(env, MultisigError::ThresholdExceedsSigners);
+    }
+    if threshold == signers.len() && storage::read_redundancy_policy(env) == RedundancyPolicy::Block {
+        panic_with_error!(env, SignerError::RedundancyRequired);
+    }
+    signers
+}
+
+// Drops `signer` and everything tied to their key, short of the count and the
+// history, which the caller keeps.
+fn evict(env: &Env, signer: &BytesN<32>) {
+    storage::remove_signer(env, signer);
+    payments::drop_approvals_by(env, signer);
+    storage::write_signer_address(env, signer, None);
+    storage::write_passkey(env, signer, None);
+    storage::write_secp256k1_key(env, signer, None);
+    bls::unregister(env, signer);
+    delegation::clear(env, signer);
+    keyring::clear(env, signer);
+    labels::clear(env, signer);
+    activity::clear(env, signer);
+    history::forget_joined(env, signer);
+    roles::clear_role(env, signer);
+    storage::write_signer_permissions(env, signer, None);
+    storage::set_protected_signer(env, signer, false);
+    set_suspended(env, signer, false);
+}
+
+// A suspended signer keeps their seat, so their key, role and protection stay
+// as they were, but they can neither propose nor approve anything until a
+// reinstatement, and do not count as a signer the threshold can rely on.
+// Suspension drops the approvals they had given; a reinstated signer approves
+// again like anyone else.
+
+pub fn is_suspended(env: &Env, signer: &BytesN<32>) -> bool {
+    storage::read_suspended_signers(env).contains(signer)
+}
+
+pub fn check_not_suspended(env: &Env, signer: &BytesN<32>) -> Result<(), MultisigError> {
+    if is_suspended(env, signer) {
+        return Err(MultisigError::SignerSuspended);
+    }
+    Ok(())
+}
+
+/// Fails with `SignerSuspended` while `signer` is suspended.
+pub fn require_not_suspended(env: &Env, signer: &BytesN<32>) {
+    validation::raise(env, check_not_suspended(env, signer));
+}
+
+/// Signers who may approve and are not suspended. The threshold may never
+/// exceed it.
+pub fn active_count(env: &Env) -> u32 {
+    storage::read_signer_list(env).iter().filter(|signer| counts_towards_threshold(env, signer)).count() as u32
+}
+
+// Active signers left once `signer` is removed or suspended.
+fn active_count_without(env: &Env, signer: &BytesN<32>) -> u32 {
+    if counts_towards_threshold(env, signer) {
+        active_count(env) - 1
+    } else {
+        active_count(env)
+    }
+}
+
+fn counts_towards_threshold(env: &Env, signer: &BytesN<32>) -> bool {
+    !is_suspended(env, signer) && permissions(env, signer).can_approve
+}
+
+// Permissions split a seat's rights: a signer who may only propose drafts
+// proposals for the others to vote on. Like a suspended signer they do not
+// count towards the threshold, and losing the right to approve drops the
+// approvals they had given.
+
+pub fn permissions(env: &Env, signer: &BytesN<32>) -> SignerPermissions {
+    storage::read_signer_permissions(env, signer).unwrap_or(SignerPermissions {
+        can_propose: true,
+        can_approve: true,
+    })
+}
+
+pub fn can_approve(env: &Env, signer: &BytesN<32>) -> bool {
+    permissions(env, signer).can_approve
+}
+
+pub fn check_can_propose(env: &Env, signer: &BytesN<32>) -> Result<(), MultisigError> {
+    if !permissions(env, signer).can_propose {
+        return Err(MultisigError::RoleNotPermitted);
+    }
+    Ok(())
+}
+
+pub fn check_can_approve(env: &Env, signer: &BytesN<32>) -> Result<(), MultisigError> {
+    if !can_approve(env, signer) {
+        return Err(MultisigError::RoleNotPermitted);
+    }
+    Ok(())
+}
+
+/// Fails with `RoleNotPermitted` unless `signer` may propose.
+pub fn require_can_propose(env: &Env, signer: &BytesN<32>) {
+    validation::raise(env, check_can_propose(env, signer));
+}
+
+/// Fails with `RoleNotPermitted` unless `signer` may approve.
+pub fn require_can_approve(env: &Env, signer: &BytesN<32>) {
+    validation::raise(env, check_can_approve(env, signer));
+}
+
+/// Checks `granted` for `signer` in `SetSignerPermissions`: the signer must
+/// be one and keep at least one right, and taking away their approval must
+/// leave enough active signers for the threshold.
+pub fn validate_permissions(env: &Env, signer: &BytesN<32>, granted: &SignerPermissions) {
+    if storage::read_signer_registry(env).is_some() {
+        panic_with_error!(env, SignerError::RegistryManaged);
+    }
+    validation::require_signer(env, signer);
+    if !granted.can_propose && !granted.can_approve {
+        panic_with_error!(env, MultisigError::InvalidProposal);
+    }
+    if !granted.can_approve && active_count_without(env, signer) < storage::read_threshold(env) {
+        panic_with_error!(env, MultisigError::ThresholdExceedsSigners);
+    }
+}
+
+/// Grants `signer` exactly `granted`.
+pub fn set_permissions(env: &Env, signer: &BytesN<32>, granted: &SignerPermissions) {
+    let full = granted.can_propose && granted.can_approve;
+    storage::write_signer_permissions(env, signer, (!full).then_some(granted));
+    if !granted.can_approve {
+        payments::drop_approvals_by(env, signer);
+        drop_signer_change_approvals_by(env, signer);
+    }
+}
+
+fn set_suspended(env: &Env, signer: &BytesN<32>, suspended: bool) {
+    let mut signers = storage::read_suspended_signers(env);
+    match signers.first_index_of(signer) {
+        Some(position) if !suspended => {
+            signers.remove(position);
+        }
+        None if suspended => signers.push_back(signer.clone()),
+        _ => return,
+    }
+    storage::write_suspended_signers(env, &signers);
+}
+
+/// Fails unless `signers` and `threshold` would make a valid signer set,
+/// under the same rules as `initialize` and the deployment's security floor.
+pub fn validate_set(env: &Env, signers: &Vec<BytesN<32>>, threshold: u32) {
+    if signers.is_empty() {
+        panic_with_error!(env, MultisigError::EmptySignersList);
+    }
+    if threshold == 0 {
+        panic_with_error!(env, MultisigError::InvalidThreshold);
+    }
+    if threshold > signers.len() {
+        panic_with_error!(env, MultisigError::ThresholdExceedsSigners);
+    }
+    validation::require_security_floor(env, signers.len(), threshold);
+    validation::raise(env, check_signer_policy(env, signers.len()));
+    for (position, signer) in signers.iter().enumerate() {
+        if signers.first_index_of(&signer) != Some(position as u32) {
+            panic_with_error!(env, MultisigError::DuplicateSigner);
+        }
+    }
+}
+
+/// Replaces the whole signer set and threshold at once. Every current signer
+/// is removed as a signer change would remove them, including their
+/// approvals, and the replacement is recorded in the history like an
+/// initialization. Callers check the new set first with `validate_set`.
+pub fn replace_set(env: &Env, signers: &Vec<BytesN<32>>, threshold: u32) {
+    if storage::read_signer_registry(env).is_some() {
+        panic_with_error!(env, SignerError::RegistryManaged);
+    }
+
+    for signer in storage::read_signer_list(env).iter() {
+        evict(env, &signer);
+        drop_signer_change_approvals_by(env, &signer);
+    }
+    storage::write_final_approver(env, None);
+    history::record(env, SignerSetChange::Initialized(signers.clone(), threshold));
+    for signer in signers.iter() {
+        storage::add_signer(env, &signer);
+        history::record_joined(env, &signer);
+    }
+    storage::write_signer_count(env, signers.len());
+    storage::write_threshold(env, threshold);
+    redundancy::refresh(env);
+    fingerprint::refresh(env);
+}
+
+/// What `old_key` must sign to hand its seat to `new_key` next.
+pub fn get_rotation_payload(env: &Env, old_key: &BytesN<32>, new_key: &BytesN<32>) -> BytesN<32> {
+    validation::require_initialized(env);
+    signatures::next_payload(env, old_key, &rotation_digest(env, old_key, new_key))
+}
+
+fn rotation_digest(env: &Env, old_key: &BytesN<32>, new_key: &BytesN<32>) -> BytesN<32> {
+    let digest = (symbol_short!("rotate"), env.current_contract_address(), old_key.clone(), new_key.clone());
+    env.crypto().sha256(&digest.to_xdr(env)).into()
+}
+
+// A rotation swaps one key for another in place, without a signer change
+// proposal, so the signer count, threshold and the seat's role, protection,
+// bound address, key ring and final approver standing all stay as they were.
+// What was tied to the old key itself does not carry over: its approvals on
+// open proposals are dropped, and its BLS key and standing delegation have to
+// be set up again under the new key.
+
+/// Replaces signer `old_key` with `new_key`. `signature` is `old_key`'s over
+/// `get_rotation_payload` at its current nonce.
+pub fn rotate_own_key(env: &Env, old_key: BytesN<32>, new_key: BytesN<32>, signature: BytesN<64>) {
+    validation::require_active(env);
+
+    check_swap(env, &old_key, &new_key);
+    require_auth(env, &old_key);
+    let nonce = storage::read_signer_nonce(env, &old_key);
+    signatures::consume(env, &old_key, nonce, &rotation_digest(env, &old_key, &new_key), &signature);
+
+    swap_key(env, &old_key, &new_key);
+    SignerKeyRotated { old_key, new_key }.publish(env);
+    fingerprint::refresh(env);
+}
+
+/// Fails unless signer `old_key` may hand its seat to `new_key`.
+pub fn check_swap(env: &Env, old_key: &BytesN<32>, new_key: &BytesN<32>) {
+    if storage::read_signer_registry(env).is_some() {
+        panic_with_error!(env, SignerError::RegistryManaged);
+    }
+    validation::require_signer(env, old_key);
+    require_not_suspended(env, old_key);
+    if storage::is_signer(env, new_key) {
+        panic_with_error!(env, MultisigError::DuplicateSigner);
+    }
+}
+
+/// Moves `old_key`'s seat to `new_key`. Callers check it first with
+/// `check_swap`.
+pub fn swap_key(env: &Env, old_key: &BytesN<32>, new_key: &BytesN<32>) {
+    payments::drop_approvals_by(env, old_key);
+    drop_signer_change_approvals_by(env, old_key);
+    bls::unregister(env, old_key);
+    delegation::clear(env, old_key);
+    keyring::transfer(env, old_key, new_key);
+    labels::transfer(env, old_key, new_key);
+    activity::transfer(env, old_key, new_key);
+    history::transfer_joined(env, old_key, new_key);
+
+    storage::remove_signer(env, old_key);
+    storage::add_signer(env, new_key);
+    let address = storage::read_signer_address(env, old_key);
+    storage::write_signer_address(env, old_key, None);
+    storage::write_signer_address(env, new_key, address.as_ref());
+    let role = storage::read_signer_role(env, old_key);
+    roles::clear_role(env, old_key);
+    storage::write_signer_role(env, new_key, role.as_ref());
+    let permissions = storage::read_signer_permissions(env, old_key);
+    storage::write_signer_permissions(env, old_key, None);
+    storage::write_signer_permissions(env, new_key, permissions.as_ref());
+    if storage::is_protected_signer(env, old_key) {
+        storage::set_protected_signer(env, old_key, false);
+        storage::set_protected_signer(env, new_key, true);
+    }
+    if storage::read_final_approver(env).as_ref() == Some(old_key) {
+        storage::write_final_approver(env, Some(new_key));
+    }
+
+    history::record(env, SignerSetChange::Removed(old_key.clone()));
+    history::record(env, SignerSetChange::Added(new_key.clone()));
+}
+
+pub fn approvals_bulk(env: &Env, proposal_ids: &Vec<u64>) -> Vec<(u64, Vec<SignerChangeApproval>)> {
+    validation::require_initialized(env);
+    pagination::check_limit(env, proposal_ids.len());
+
+    let mut found = Vec::new(env);
+    for proposal_id in proposal_ids.iter() {
+        if storage::has_signer_change(env, proposal_id) {
+            found.push_back((proposal_id, storage::read_signer_change_approvals(env, proposal_id)));
+        }
+    }
+    found
+}
+
+/// Approvals needed to remove a protected signer or change who is protected.
+/// Defaults to every current signer until governance configures a lower count.
+pub fn protected_quorum(env: &Env) -> u32 {
+    let signer_count = storage::read_signer_count(env);
+    let quorum = storage::read_protected_quorum(env).unwrap_or(signer_count);
+    quorum.min(signer_count).max(storage::read_threshold(env))
+}
+
+/// Approvals `proposal` needs: the elevated protected quorum when it removes,
+/// suspends or replaces a protected signer, otherwise the threshold in force
+/// when it was proposed, or the inactivity threshold for removing a signer
+/// inactive for long enough.
+pub fn required_signer_change_approvals(env: &Env, proposal: &SignerChangeProposal) -> u32 {
+    let guarded = matches!(proposal.change_type, SignerChangeKind::Remove | SignerChangeKind::Suspend | SignerChangeKind::Replace);
+    let removes_protected = storage::read_signer_batch(env, proposal.id)
+        .is_some_and(|batch| batch.removals.iter().any(|signer| storage::is_protected_signer(env, &signer)));
+    if (guarded && storage::is_protected_signer(env, &proposal.signer)) || removes_protected {
+        protected_quorum(env)
+    } else {
+        let required = storage::read_required_approvals(env, proposal.id).unwrap_or_else(|| storage::read_threshold(env));
+        match proposal.change_type {
+            SignerChangeKind::Remove => activity::removal_threshold(env, &proposal.signer).map_or(required, |reduced| reduced.min(required)),
+            _ => required,
+        }
+    }
+}
+
+pub fn set_protected(env: &Env, signer: &BytesN<32>, protected: bool) {
+    if !storage::is_signer(env, signer) {
+        panic_with_error!(env, MultisigError::SignerNotFound);
+    }
+    storage::set_protected_signer(env, signer, protected);
+}
+
+pub fn validate_protected_quorum(env: &Env, quorum: u32) {
+    if quorum < storage::read_threshold(env) || quorum > storage::read_signer_count(env) {
+        panic_with_error!(env, MultisigError::InvalidThreshold);
+    }
+}
+
+/// Checks `policy` for `SetSignerPolicy` and `initialize_with_signers`: it must
+/// allow at least one signer and no fewer than it requires, and the current
+/// set must be within it.
+pub fn validate_signer_policy(env: &Env, policy: &SignerPolicy) {
+    if storage::read_signer_registry(env).is_some() {
+        panic_with_error!(env, SignerError::RegistryManaged);
+    }
+    if policy.min_signers == 0 || policy.min_signers > policy.max_signers {
+        panic_with_error!(env, MultisigError::InvalidProposal);
+    }
+    if !within(policy, storage::read_signer_count(env)) {
+        panic_with_error!(env, SignerError::SignerPolicyViolation);
+    }
+}
+
+/// Fails with `SignerPolicyViolation` unless a set of `count` signers is
+/// within the signer policy. Without a policy any count is.
+pub fn check_signer_policy(env: &Env, count: u32) -> Result<(), SignerError> {
+    match storage::read_signer_policy(env) {
+        Some(policy) if !within(&policy, count) => Err(SignerError::SignerPolicyViolation),
+        _ => Ok(()),
+    }
+}
+
+fn within(policy: &SignerPolicy, count: u32) -> bool {
+    (policy.min_signers..=policy.max_signers).contains(&count)
+}
+
+/// Checks `threshold` for `SetThreshold`: it must be at least one, within the
+/// active signers and the protected quorum, and meet the security floor. Under
+/// `RedundancyPolicy::Block` it must also stay below the signer count.
+pub fn validate_threshold(env: &Env, threshold: u32) {
+    if storage::read_signer_registry(env).is_some() {
+        panic_with_error!(env, SignerError::RegistryManaged);
+    }
+    if threshold == 0 {
+        panic_with_error!(env, MultisigError::InvalidThreshold);
+    }
+    if threshold > active_count(env) {
+        panic_with_error!(env, MultisigError::ThresholdExceedsSigners);
+    }
+    if threshold > protected_quorum(env) {
+        panic_with_error!(env, MultisigError::InvalidThreshold);
+    }
+    let signer_count = storage::read_signer_count(env);
+    validation::require_security_floor(env, signer_count, threshold);
+    if threshold == signer_count && storage::read_redundancy_policy(env) == RedundancyPolicy::Block {
+        panic_with_error!(env, SignerError::RedundancyRequired);
+    }
+}
+
+/// Replaces the threshold, re-checking it against the signer set as it is
+/// now. Open proposals keep the requirement they were created with.
+pub fn set_threshold(env: &Env, threshold: u32) {
+    validate_threshold(env, threshold);
+    let old_threshold = storage::read_threshold(env);
+    storage::write_threshold(env, threshold);
+    history::record(env, SignerSetChange::ThresholdChanged(threshold));
+    ThresholdChanged { old_threshold, threshold }.publish(env);
+    redundancy::refresh(env);
+}
+
+// The approval window must be a valid lifetime of its own and end no later
+// than the proposal, and an effective date must leave time to execute.
+fn validate_schedule(env: &Env, approval_window_seconds: u64, execute_not_before: Option<u64>, expires_in_seconds: u64) {
+    validation::validate_expiry(env, approval_window_seconds);
+    if approval_window_seconds > expires_in_seconds {
+        panic_with_error!(env, MultisigError::InvalidExpiryTime);
+    }
+
+    let expires_at = clock::now(env) + expires_in_seconds;
+    if execute_not_before.is_some_and(|effective| effective > expires_at) {
+        panic_with_error!(env, MultisigError::InvalidExpiryTime);
+    }
+}
+
+// Strips `signer`'s approvals from every open signer change, once they are
+// removed, replaced or suspended and their approvals stop counting.
+fn drop_signer_change_approvals_by(env: &Env, signer: &BytesN<32>) {
+    for proposal_id in index::iter(env, &IndexKey::OpenSignerChanges) {
+        let mut approvals = storage::read_signer_change_approvals(env, proposal_id);
+        if let Some(position) = approvals.iter().position(|a| a.signer == *signer) {
+            approvals.remove(position as u32);
+            storage::write_signer_change_approvals(env, proposal_id, &approvals);
+        }
+    }
+}
+
+// Loads a signer-change proposal that exists and is neither executed nor
+// canceled.
+fn load_open_signer_change(env: &Env, proposal_id: u64) -> SignerChangeProposal {
+    let Some(proposal) = storage::read_signer_change(env, proposal_id) else {
+        panic_with_error!(env, SignerError::SignerChangeNotFound);
+    };
+
+    if storage::has_proposal_flag(env, proposal_id, storage::FLAG_EXECUTED) {
+        panic_with_error!(env, SignerError::SignerChangeAlreadyExecuted);
+    }
+    if outcomes::is_closed(env, proposal_id) {
+        panic_with_error!(env, MultisigError::ProposalCanceled);
+    }
+
+    proposal
+}
@@ -0,0 +1,60 @@
+use soroban_sdk::{token, Address, Env, Symbol, Vec};
+
+use crate::address_book;
+use crate::clock;
+use crate::governance;
+use crate::payments;
+use crate::storage;
+use crate::treasury;
+use crate::types::{ExecutionVerdict, PolicyCheck, ProposalAction, TransferAction};
+use crate::validation;
+
+// A simulation calls the same `check_*` functions the creation and execution
+// paths raise, so a rule cannot change in one place without the other. It
+// adds the token's own balance check, which execution leaves to the token.
+
+/// Every rule a transfer of `amount` of `token` to `recipient`, executed at
+/// `execute_at`, would meet or break, as far as they can be judged without a
+/// proposal. Rules tied to a proposer, such as their caps, are left to
+/// `validate_draft`. Balances are today's.
+pub fn simulate(env: &Env, token: Address, recipient: Address, amount: i128, execute_at: u64) -> ExecutionVerdict {
+    validation::require_initialized(env);
+
+    let now = clock::now(env);
+    let action = ProposalAction::Transfer(TransferAction {
+        token_address: token.clone(),
+        recipient: recipient.clone(),
+        amount,
+    });
+
+    let mut checks = Vec::new(env);
+    push(env, &mut checks, "amount", validation::check_amount(amount).is_ok(), Some(amount), Some(validation::MAX_AMOUNT));
+    push(env, &mut checks, "execute_at", now <= execute_at, Some(now as i128), Some(execute_at as i128));
+    push(env, &mut checks, "token_frozen", governance::check_token_not_frozen(env, &token).is_ok(), None, None);
+    push(env, &mut checks, "unlisted_recipient", address_book::check_listed(env, &recipient).is_ok(), None, None);
+    push(env, &mut checks, "memo_required", payments::check_memo(env, &action, &None).is_ok(), None, None);
+    // Rates are judged as of `execute_at`, when the payment would be proposed
+    let document_threshold = storage::read_document_threshold(env, &token);
+    let document = payments::check_document(env, &action, &None, execute_at).is_ok();
+    push(env, &mut checks, "document_required", document, Some(amount), document_threshold);
+
+    let balance = token::Client::new(env, &token).balance(&env.current_contract_address());
+    push(env, &mut checks, "balance", amount <= balance, Some(amount), Some(balance));
+    let reserve_limit = (storage::read_reserve_floor(env, &token) > 0).then(|| treasury::free_balance(env, &token));
+    let reserve = treasury::check_within_reserve(env, &token, amount).is_ok();
+    push(env, &mut checks, "reserve_floor", reserve, Some(amount), reserve_limit);
+
+    ExecutionVerdict {
+        passed: checks.iter().all(|check| check.passed),
+        checks,
+    }
+}
+
+fn push(env: &Env, checks: &mut Vec<PolicyCheck>, rule: &str, passed: bool, actual: Option<i128>, limit: Option<i128>) {
+    checks.push_back(PolicyCheck {
+        rule: Symbol::new(env, rule),
+        passed,
+        actual,
+        limit,
+    });
+}
@@ -0,0 +1,102 @@
+use soroban_sdk::{panic_with_error, Address, BytesN, Env};
+
+use crate::clock;
+use crate::errors::MultisigError;
+use crate::rates;
+use crate::storage;
+use crate::types::{ProposerCap, ProposerUsage, ValueCap};
+
+pub fn validate_cap(env: &Env, cap: &ProposerCap) {
+    if cap.amount <= 0 || cap.window_seconds == 0 {
+        panic_with_error!(env, MultisigError::InvalidProposal);
+    }
+}
+
+pub fn validate_value_cap(env: &Env, cap: &ValueCap) {
+    if cap.amount <= 0 || cap.window_seconds == 0 {
+        panic_with_error!(env, MultisigError::InvalidProposal);
+    }
+}
+
+// Usage as it stands now: a window that has run its course starts over.
+fn in_window(env: &Env, stored: Option<ProposerUsage>, window_seconds: u64) -> ProposerUsage {
+    let now = clock::now(env);
+    match stored {
+        Some(usage) if now < usage.window_start.saturating_add(window_seconds) => usage,
+        _ => ProposerUsage {
+            window_start: now,
+            proposed: 0,
+        },
+    }
+}
+
+fn current_usage(env: &Env, cap: &ProposerCap, proposer: &BytesN<32>) -> ProposerUsage {
+    in_window(env, storage::read_proposer_usage(env, proposer, &cap.token), cap.window_seconds)
+}
+
+fn current_value_usage(env: &Env, cap: &ValueCap, proposer: &BytesN<32>) -> ProposerUsage {
+    in_window(env, storage::read_proposer_value_usage(env, proposer), cap.window_seconds)
+}
+
+fn add_proposed(env: &Env, usage: &mut ProposerUsage, amount: i128) {
+    usage.proposed = match usage.proposed.checked_add(amount) {
+        Some(total) => total,
+        None => panic_with_error!(env, MultisigError::ArithmeticOverflow),
+    };
+}
+
+/// How much more of `token` `proposer` may put into proposals in the current
+/// window, or `None` when the token is uncapped.
+pub fn remaining(env: &Env, proposer: &BytesN<32>, token: &Address) -> Option<i128> {
+    let cap = storage::read_proposer_cap(env, token)?;
+    let usage = current_usage(env, &cap, proposer);
+    Some((cap.amount - usage.proposed).max(0))
+}
+
+pub fn check_within_cap(env: &Env, proposer: &BytesN<32>, token: &Address, amount: i128) -> Result<(), MultisigError> {
+    match remaining(env, proposer, token) {
+        Some(left) if amount > left => Err(MultisigError::ProposerCapExceeded),
+        _ => Ok(()),
+    }
+}
+
+/// How much more value, in the reference unit, `proposer` may put into
+/// proposals across all tokens in the current window, or `None` without a
+/// value cap.
+pub fn remaining_value(env: &Env, proposer: &BytesN<32>) -> Option<i128> {
+    let cap = storage::read_value_cap(env)?;
+    let usage = current_value_usage(env, &cap, proposer);
+    Some((cap.amount - usage.proposed).max(0))
+}
+
+pub fn check_within_value_cap(env: &Env, proposer: &BytesN<32>, token: &Address, amount: i128) -> Result<(), MultisigError> {
+    let Some(left) = remaining_value(env, proposer) else {
+        return Ok(());
+    };
+    if rates::to_reference(env, token, amount)? > left {
+        return Err(MultisigError::ProposerCapExceeded);
+    }
+    Ok(())
+}
+
+/// Adds `amount` to the proposer's running totals for `token` and, valued in
+/// the reference unit, across tokens. Callers check both caps first with
+/// `check_within_cap` and `check_within_value_cap`.
+pub fn record_proposed(env: &Env, proposer: &BytesN<32>, token: &Address, amount: i128) {
+    if let Some(cap) = storage::read_value_cap(env) {
+        let mut usage = current_value_usage(env, &cap, proposer);
+        add_proposed(env, &mut usage, rates::quote(env, token, amount));
+        storage::write_proposer_value_usage(env, proposer, &usage);
+        #[cfg(feature = "testing-hooks")]
+        crate::hooks::track_value_usage(env, proposer);
+    }
+
+    let Some(cap) = storage::read_proposer_cap(env, token) else {
+        return;
+    };
+    let mut usage = current_usage(env, &cap, proposer);
+    add_proposed(env, &mut usage, amount);
+    storage::write_proposer_usage(env, proposer, token, &usage);
+    #[cfg(feature = "testing-hooks")]
+    crate::hooks::track_usage(env, proposer, token);
+}
@@ -0,0 +1,1289 @@
+use soroban_sdk::{panic_with_error, Address, BytesN, Env, FromVal, IntoVal, Map, String, Symbol, TryFromVal, Val, Vec};
+
+use crate::clock;
+use crate::errors::MultisigError;
+use crate::history;
+use crate::registry;
+use crate::types::{
+    AddressBookEntry, AllocationTarget, AuditEntry, DataKey, Delegation, PolicyKey, ProposalKey, SignerKey, Deposit, DocumentThreshold, ExchangeRate, ExecutionSchedule, ExecutionReceipt, ExecutionRecord, InactivityRemoval, IndexKey, LegacySignerChangeProposal, PendingCancellation, ProposalTemplate, ReserveFloor, TokenImplementation, ProposerCap, ProposerUsage, RecoveryConfig, ValueCap, RoleRequirement, Signer, SignerBatch, SignerChangeKind, SignerHistoryEntry, SignerPermissions, SignerPolicy, TerminalRecord, GovernanceApproval, GovernanceProposal, Proposal, ProposalApproval, ProposalNote, RedundancyPolicy, Secp256r1Key, SignerSetSnapshot, SigningSession,
+    SignerChangeApproval, SignerChangeProposal, VoteDelegation,
+};
+
+// Configuration
+
+pub fn is_initialized(env: &Env) -> bool {
+    env.storage().instance().has(&DataKey::Initialized)
+}
+
+pub fn read_schema_version(env: &Env) -> Option<u32> {
+    env.storage().instance().get(&DataKey::Policy(PolicyKey::SchemaVersion))
+}
+
+pub fn write_schema_version(env: &Env, version: u32) {
+    env.storage().instance().set(&DataKey::Policy(PolicyKey::SchemaVersion), &version);
+}
+
+pub fn read_schema_families(env: &Env) -> Vec<Symbol> {
+    env.storage().instance().get(&DataKey::Policy(PolicyKey::SchemaFamilies)).unwrap_or(Vec::new(env))
+}
+
+pub fn write_schema_families(env: &Env, families: &Vec<Symbol>) {
+    env.storage().instance().set(&DataKey::Policy(PolicyKey::SchemaFamilies), families);
+}
+
+pub fn set_initialized(env: &Env) {
+    env.storage().instance().set(&DataKey::Initialized, &true);
+}
+
+// With a signer registry configured, membership, signer count and threshold are
+// read from the registry instead of local storage.
+
+pub fn read_signer_registry(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::SignerRegistry)
+}
+
+pub fn write_signer_registry(env: &Env, registry: &Address) {
+    env.storage().instance().set(&DataKey::SignerRegistry, registry);
+}
+
+pub fn read_initial_deposit(env: &Env) -> Option<Deposit> {
+    env.storage().instance().get(&DataKey::InitialDeposit)
+}
+
+pub fn write_initial_deposit(env: &Env, deposit: &Deposit) {
+    env.storage().instance().set(&DataKey::InitialDeposit, deposit);
+}
+
+pub fn read_threshold(env: &Env) -> u32 {
+    if let Some(registry) = read_signer_registry(env) {
+        return registry::threshold(env, &registry);
+    }
+    env.storage().instance().get(&DataKey::Threshold).unwrap()
+}
+
+pub fn write_threshold(env: &Env, threshold: u32) {
+    env.storage().instance().set(&DataKey::Threshold, &threshold);
+}
+
+pub fn is_paused(env: &Env) -> bool {
+    env.storage().instance().get(&DataKey::Policy(PolicyKey::Paused)).unwrap_or(false)
+}
+
+/// The successor a retired contract handed its treasury to. There is no way to
+/// clear it.
+pub fn read_successor(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Policy(PolicyKey::Retired))
+}
+
+pub fn write_successor(env: &Env, successor: &Address) {
+    env.storage().instance().set(&DataKey::Policy(PolicyKey::Retired), successor);
+}
+
+pub fn read_guardian(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Policy(PolicyKey::Guardian))
+}
+
+pub fn write_guardian(env: &Env, guardian: Option<&Address>) {
+    match guardian {
+        Some(guardian) => env.storage().instance().set(&DataKey::Policy(PolicyKey::Guardian), guardian),
+        None => env.storage().instance().remove(&DataKey::Policy(PolicyKey::Guardian)),
+    }
+}
+
+pub fn read_recovery(env: &Env) -> Option<RecoveryConfig> {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::Recovery))
+}
+
+pub fn write_recovery(env: &Env, config: Option<&RecoveryConfig>) {
+    match config {
+        Some(config) => env.storage().instance().set(&DataKey::Signers(SignerKey::Recovery), config),
+        None => env.storage().instance().remove(&DataKey::Signers(SignerKey::Recovery)),
+    }
+}
+
+pub fn read_last_activity(env: &Env) -> Option<u64> {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::LastActivity))
+}
+
+pub fn write_last_activity(env: &Env, timestamp: u64) {
+    env.storage().instance().set(&DataKey::Signers(SignerKey::LastActivity), &timestamp);
+}
+
+pub fn set_paused(env: &Env, paused: bool) {
+    if paused {
+        env.storage().instance().set(&DataKey::Policy(PolicyKey::Paused), &true);
+    } else {
+        env.storage().instance().remove(&DataKey::Policy(PolicyKey::Paused));
+    }
+}
+
+pub fn allows_low_security(env: &Env) -> bool {
+    env.storage().instance().get(&DataKey::AllowLowSecurity).unwrap_or(false)
+}
+
+pub fn write_allow_low_security(env: &Env, allow: bool) {
+    env.storage().instance().set(&DataKey::AllowLowSecurity, &allow);
+}
+
+pub fn read_nonce(env: &Env) -> u64 {
+    env.storage().instance().get(&DataKey::Nonce).unwrap()
+}
+
+pub fn write_nonce(env: &Env, nonce: u64) {
+    env.storage().instance().set(&DataKey::Nonce, &nonce);
+}
+
+// Signers
+
+pub fn read_signer_count(env: &Env) -> u32 {
+    if let Some(registry) = read_signer_registry(env) {
+        return registry::signer_count(env, &registry);
+    }
+    env.storage().instance().get(&DataKey::SignerCount).unwrap()
+}
+
+pub fn write_signer_count(env: &Env, count: u32) {
+    env.storage().instance().set(&DataKey::SignerCount, &count);
+}
+
+pub fn is_signer(env: &Env, key: &BytesN<32>) -> bool {
+    if let Some(registry) = read_signer_registry(env) {
+        return registry::is_signer(env, &registry, key);
+    }
+    env.storage().instance().has(&DataKey::Signer(key.clone()))
+}
+
+// Every signer key is stored on its own, for `is_signer`, and in the
+// `SignerList`, for enumeration. Both change together here and nowhere else.
+// Deployments from before the list replay it from the signer history until
+// their next signer change writes it.
+
+/// Current signers in the order they joined.
+pub fn read_signer_list(env: &Env) -> Vec<BytesN<32>> {
+    env.storage().instance()
+        .get(&DataKey::Signers(SignerKey::SignerList))
+        .unwrap_or_else(|| history::signer_set_at(env, clock::now(env)).signers)
+}
+
+pub fn add_signer(env: &Env, key: &BytesN<32>) {
+    env.storage().instance().set(&DataKey::Signer(key.clone()), &true);
+    let mut list = read_signer_list(env);
+    if !list.contains(key) {
+        list.push_back(key.clone());
+    }
+    env.storage().instance().set(&DataKey::Signers(SignerKey::SignerList), &list);
+}
+
+pub fn remove_signer(env: &Env, key: &BytesN<32>) {
+    env.storage().instance().remove(&DataKey::Signer(key.clone()));
+    let mut list = read_signer_list(env);
+    if let Some(position) = list.first_index_of(key) {
+        list.remove(position);
+    }
+    env.storage().instance().set(&DataKey::Signers(SignerKey::SignerList), &list);
+}
+
+pub fn read_signer_address(env: &Env, key: &BytesN<32>) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::SignerAddress(key.clone())))
+}
+
+pub fn write_signer_address(env: &Env, key: &BytesN<32>, address: Option<&Address>) {
+    let storage_key = DataKey::Signers(SignerKey::SignerAddress(key.clone()));
+    match address {
+        Some(address) => env.storage().instance().set(&storage_key, address),
+        None => env.storage().instance().remove(&storage_key),
+    }
+}
+
+pub fn read_passkey(env: &Env, key: &BytesN<32>) -> Option<Secp256r1Key> {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::Passkey(key.clone())))
+}
+
+pub fn write_passkey(env: &Env, key: &BytesN<32>, passkey: Option<&Secp256r1Key>) {
+    let storage_key = DataKey::Signers(SignerKey::Passkey(key.clone()));
+    match passkey {
+        Some(passkey) => env.storage().instance().set(&storage_key, passkey),
+        None => env.storage().instance().remove(&storage_key),
+    }
+}
+
+pub fn read_secp256k1_key(env: &Env, key: &BytesN<32>) -> Option<BytesN<65>> {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::Secp256k1Key(key.clone())))
+}
+
+pub fn write_secp256k1_key(env: &Env, key: &BytesN<32>, public_key: Option<&BytesN<65>>) {
+    let storage_key = DataKey::Signers(SignerKey::Secp256k1Key(key.clone()));
+    match public_key {
+        Some(public_key) => env.storage().instance().set(&storage_key, public_key),
+        None => env.storage().instance().remove(&storage_key),
+    }
+}
+
+pub fn read_config_fingerprint(env: &Env) -> Option<BytesN<32>> {
+    env.storage().instance().get(&DataKey::Policy(PolicyKey::ConfigFingerprint))
+}
+
+pub fn write_config_fingerprint(env: &Env, fingerprint: &BytesN<32>) {
+    env.storage().instance().set(&DataKey::Policy(PolicyKey::ConfigFingerprint), fingerprint);
+}
+
+pub fn read_bls_key(env: &Env, signer: &BytesN<32>) -> Option<BytesN<96>> {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::BlsKey(signer.clone())))
+}
+
+pub fn write_bls_key(env: &Env, signer: &BytesN<32>, key: Option<&BytesN<96>>) {
+    let storage_key = DataKey::Signers(SignerKey::BlsKey(signer.clone()));
+    match key {
+        Some(key) => env.storage().instance().set(&storage_key, key),
+        None => env.storage().instance().remove(&storage_key),
+    }
+}
+
+pub fn read_bls_signers(env: &Env) -> Vec<BytesN<32>> {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::BlsSigners)).unwrap_or(Vec::new(env))
+}
+
+pub fn write_bls_signers(env: &Env, signers: &Vec<BytesN<32>>) {
+    env.storage().instance().set(&DataKey::Signers(SignerKey::BlsSigners), signers);
+}
+
+pub fn read_signer_role(env: &Env, key: &BytesN<32>) -> Option<Symbol> {
+    env.storage().instance().get(&DataKey::SignerRole(key.clone()))
+}
+
+pub fn write_signer_role(env: &Env, key: &BytesN<32>, role: Option<&Symbol>) {
+    match role {
+        Some(role) => env.storage().instance().set(&DataKey::SignerRole(key.clone()), role),
+        None => env.storage().instance().remove(&DataKey::SignerRole(key.clone())),
+    }
+}
+
+pub fn read_role_requirements(env: &Env) -> Vec<RoleRequirement> {
+    env.storage().instance()
+        .get(&DataKey::RoleRequirements)
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn write_role_requirements(env: &Env, requirements: &Vec<RoleRequirement>) {
+    env.storage().instance().set(&DataKey::RoleRequirements, requirements);
+}
+
+pub fn is_protected_signer(env: &Env, key: &BytesN<32>) -> bool {
+    env.storage().instance().has(&DataKey::ProtectedSigner(key.clone()))
+}
+
+pub fn set_protected_signer(env: &Env, key: &BytesN<32>, protected: bool) {
+    if protected {
+        env.storage().instance().set(&DataKey::ProtectedSigner(key.clone()), &true);
+    } else {
+        env.storage().instance().remove(&DataKey::ProtectedSigner(key.clone()));
+    }
+}
+
+pub fn read_protected_quorum(env: &Env) -> Option<u32> {
+    env.storage().instance().get(&DataKey::ProtectedQuorum)
+}
+
+pub fn write_protected_quorum(env: &Env, quorum: u32) {
+    env.storage().instance().set(&DataKey::ProtectedQuorum, &quorum);
+}
+
+// Signer history entries, audit entries and proposal notes never change once written and are
+// kept for audits, so unlike everything else they live in persistent storage
+// with a long TTL.
+
+/// Remaining lifetime, in ledgers, below which a history entry's TTL is topped up.
+const HISTORY_TTL_THRESHOLD: u32 = 518_400;
+
+pub fn read_signer_history_count(env: &Env) -> u64 {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::SignerHistoryCount)).unwrap_or(0u64)
+}
+
+pub fn write_signer_history_count(env: &Env, count: u64) {
+    env.storage().instance().set(&DataKey::Signers(SignerKey::SignerHistoryCount), &count);
+}
+
+pub fn read_signer_history_entry(env: &Env, seq: u64) -> Option<SignerHistoryEntry> {
+    env.storage().persistent().get(&DataKey::Signers(SignerKey::SignerHistory(seq)))
+}
+
+pub fn write_signer_history_entry(env: &Env, entry: &SignerHistoryEntry) {
+    let key = DataKey::Signers(SignerKey::SignerHistory(entry.seq));
+    env.storage().persistent().set(&key, entry);
+    env.storage().persistent().extend_ttl(&key, HISTORY_TTL_THRESHOLD, env.storage().max_ttl());
+}
+
+pub fn read_signer_set_checkpoint(env: &Env, seq: u64) -> Option<SignerSetSnapshot> {
+    env.storage().persistent().get(&DataKey::Signers(SignerKey::SignerSetCheckpoint(seq)))
+}
+
+pub fn write_signer_set_checkpoint(env: &Env, seq: u64, snapshot: &SignerSetSnapshot) {
+    let key = DataKey::Signers(SignerKey::SignerSetCheckpoint(seq));
+    env.storage().persistent().set(&key, snapshot);
+    env.storage().persistent().extend_ttl(&key, HISTORY_TTL_THRESHOLD, env.storage().max_ttl());
+}
+
+pub fn read_signer_history_proposal(env: &Env, seq: u64) -> Option<u64> {
+    env.storage().persistent().get(&DataKey::Signers(SignerKey::SignerHistoryProposal(seq)))
+}
+
+pub fn write_signer_history_proposal(env: &Env, seq: u64, proposal_id: u64) {
+    let key = DataKey::Signers(SignerKey::SignerHistoryProposal(seq));
+    env.storage().persistent().set(&key, &proposal_id);
+    env.storage().persistent().extend_ttl(&key, HISTORY_TTL_THRESHOLD, env.storage().max_ttl());
+}
+
+pub fn read_audit_count(env: &Env) -> u64 {
+    env.storage().instance().get(&DataKey::Policy(PolicyKey::AuditCount)).unwrap_or(0u64)
+}
+
+pub fn write_audit_count(env: &Env, count: u64) {
+    env.storage().instance().set(&DataKey::Policy(PolicyKey::AuditCount), &count);
+}
+
+pub fn read_audit_entry(env: &Env, seq: u64) -> Option<AuditEntry> {
+    env.storage().persistent().get(&DataKey::Policy(PolicyKey::AuditEntry(seq)))
+}
+
+pub fn write_audit_entry(env: &Env, entry: &AuditEntry) {
+    let key = DataKey::Policy(PolicyKey::AuditEntry(entry.seq));
+    env.storage().persistent().set(&key, entry);
+    env.storage().persistent().extend_ttl(&key, HISTORY_TTL_THRESHOLD, env.storage().max_ttl());
+}
+
+pub fn next_session_id(env: &Env) -> u64 {
+    let session_id = env.storage().instance().get(&DataKey::Proposals(ProposalKey::SessionCount)).unwrap_or(0u64) + 1;
+    env.storage().instance().set(&DataKey::Proposals(ProposalKey::SessionCount), &session_id);
+    session_id
+}
+
+pub fn read_signing_session(env: &Env, session_id: u64) -> Option<SigningSession> {
+    env.storage().persistent().get(&DataKey::Proposals(ProposalKey::SigningSession(session_id)))
+}
+
+pub fn write_signing_session(env: &Env, session: &SigningSession) {
+    let key = DataKey::Proposals(ProposalKey::SigningSession(session.id));
+    env.storage().persistent().set(&key, session);
+    env.storage().persistent().extend_ttl(&key, HISTORY_TTL_THRESHOLD, env.storage().max_ttl());
+}
+
+pub fn read_proposal_notes(env: &Env, proposal_id: u64) -> Vec<ProposalNote> {
+    env.storage().persistent()
+        .get(&DataKey::Proposals(ProposalKey::ProposalNotes(proposal_id)))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn write_proposal_notes(env: &Env, proposal_id: u64, notes: &Vec<ProposalNote>) {
+    let key = DataKey::Proposals(ProposalKey::ProposalNotes(proposal_id));
+    env.storage().persistent().set(&key, notes);
+    env.storage().persistent().extend_ttl(&key, HISTORY_TTL_THRESHOLD, env.storage().max_ttl());
+}
+
+// One per proposal, so kept out of the size-limited instance entry like the
+// notes
+pub fn read_proposal_signer_set_version(env: &Env, proposal_id: u64) -> Option<u64> {
+    env.storage().persistent().get(&DataKey::Proposals(ProposalKey::ProposalSignerSetVersion(proposal_id)))
+}
+
+pub fn write_proposal_signer_set_version(env: &Env, proposal_id: u64, version: u64) {
+    let key = DataKey::Proposals(ProposalKey::ProposalSignerSetVersion(proposal_id));
+    env.storage().persistent().set(&key, &version);
+    env.storage().persistent().extend_ttl(&key, HISTORY_TTL_THRESHOLD, env.storage().max_ttl());
+}
+
+/// Threshold in force when proposal `proposal_id` of either family was
+/// created.
+pub fn read_required_approvals(env: &Env, proposal_id: u64) -> Option<u32> {
+    env.storage().persistent().get(&DataKey::Proposals(ProposalKey::RequiredApprovals(proposal_id)))
+}
+
+pub fn write_required_approvals(env: &Env, proposal_id: u64, required: u32) {
+    let key = DataKey::Proposals(ProposalKey::RequiredApprovals(proposal_id));
+    env.storage().persistent().set(&key, &required);
+    env.storage().persistent().extend_ttl(&key, HISTORY_TTL_THRESHOLD, env.storage().max_ttl());
+}
+
+pub fn read_execution_record(env: &Env, proposal_id: u64) -> Option<ExecutionRecord> {
+    env.storage().persistent().get(&DataKey::Proposals(ProposalKey::ExecutionRecord(proposal_id)))
+}
+
+pub fn write_execution_record(env: &Env, proposal_id: u64, record: &ExecutionRecord) {
+    let key = DataKey::Proposals(ProposalKey::ExecutionRecord(proposal_id));
+    env.storage().persistent().set(&key, record);
+    env.storage().persistent().extend_ttl(&key, HISTORY_TTL_THRESHOLD, env.storage().max_ttl());
+}
+
+/// The guardian who vetoed `proposal_id`, if one did.
+pub fn read_veto(env: &Env, proposal_id: u64) -> Option<Address> {
+    env.storage().persistent().get(&DataKey::Proposals(ProposalKey::ProposalVetoed(proposal_id)))
+}
+
+pub fn write_veto(env: &Env, proposal_id: u64, guardian: &Address) {
+    let key = DataKey::Proposals(ProposalKey::ProposalVetoed(proposal_id));
+    env.storage().persistent().set(&key, guardian);
+    env.storage().persistent().extend_ttl(&key, HISTORY_TTL_THRESHOLD, env.storage().max_ttl());
+}
+
+/// Key a "replace" signer change puts in place of its `signer`.
+pub fn read_signer_replacement(env: &Env, proposal_id: u64) -> Option<BytesN<32>> {
+    env.storage().persistent().get(&DataKey::Signers(SignerKey::SignerReplacement(proposal_id)))
+}
+
+pub fn write_signer_replacement(env: &Env, proposal_id: u64, new_signer: &BytesN<32>) {
+    let key = DataKey::Signers(SignerKey::SignerReplacement(proposal_id));
+    env.storage().persistent().set(&key, new_signer);
+    env.storage().persistent().extend_ttl(&key, HISTORY_TTL_THRESHOLD, env.storage().max_ttl());
+}
+
+/// Signer an "add" signer change brings in, when proposed as a `Signer`.
+pub fn read_signer_addition(env: &Env, proposal_id: u64) -> Option<Signer> {
+    env.storage().persistent().get(&DataKey::Signers(SignerKey::SignerAddition(proposal_id)))
+}
+
+pub fn write_signer_addition(env: &Env, proposal_id: u64, signer: &Signer) {
+    let key = DataKey::Signers(SignerKey::SignerAddition(proposal_id));
+    env.storage().persistent().set(&key, signer);
+    env.storage().persistent().extend_ttl(&key, HISTORY_TTL_THRESHOLD, env.storage().max_ttl());
+}
+
+pub fn read_signer_batch(env: &Env, proposal_id: u64) -> Option<SignerBatch> {
+    env.storage().persistent().get(&DataKey::Signers(SignerKey::SignerBatch(proposal_id)))
+}
+
+pub fn write_signer_batch(env: &Env, proposal_id: u64, batch: &SignerBatch) {
+    let key = DataKey::Signers(SignerKey::SignerBatch(proposal_id));
+    env.storage().persistent().set(&key, batch);
+    env.storage().persistent().extend_ttl(&key, HISTORY_TTL_THRESHOLD, env.storage().max_ttl());
+}
+
+// Index chunks grow with the number of proposals, so they live in persistent
+// storage next to the history rather than in the size-limited instance entry.
+
+pub fn read_index_chunk_count(env: &Env, key: &IndexKey) -> u32 {
+    env.storage().persistent().get(&DataKey::IndexChunkCount(key.clone())).unwrap_or(0)
+}
+
+pub fn write_index_chunk_count(env: &Env, key: &IndexKey, count: u32) {
+    let key = DataKey::IndexChunkCount(key.clone());
+    env.storage().persistent().set(&key, &count);
+    env.storage().persistent().extend_ttl(&key, HISTORY_TTL_THRESHOLD, env.storage().max_ttl());
+}
+
+pub fn read_index_chunk(env: &Env, key: &IndexKey, page: u32) -> Vec<u64> {
+    env.storage().persistent()
+        .get(&DataKey::IndexChunk(key.clone(), page))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn write_index_chunk(env: &Env, key: &IndexKey, page: u32, ids: &Vec<u64>) {
+    let key = DataKey::IndexChunk(key.clone(), page);
+    env.storage().persistent().set(&key, ids);
+    env.storage().persistent().extend_ttl(&key, HISTORY_TTL_THRESHOLD, env.storage().max_ttl());
+}
+
+pub fn remove_index_chunk(env: &Env, key: &IndexKey, page: u32) {
+    env.storage().persistent().remove(&DataKey::IndexChunk(key.clone(), page));
+}
+
+pub fn read_final_approver(env: &Env) -> Option<BytesN<32>> {
+    env.storage().instance().get(&DataKey::FinalApprover)
+}
+
+pub fn write_final_approver(env: &Env, signer: Option<&BytesN<32>>) {
+    match signer {
+        Some(signer) => env.storage().instance().set(&DataKey::FinalApprover, signer),
+        None => env.storage().instance().remove(&DataKey::FinalApprover),
+    }
+}
+
+pub fn is_memo_required(env: &Env, recipient: &Address) -> bool {
+    env.storage().instance().has(&DataKey::Policy(PolicyKey::MemoRequired(recipient.clone())))
+}
+
+pub fn set_memo_required(env: &Env, recipient: &Address, required: bool) {
+    let key = DataKey::Policy(PolicyKey::MemoRequired(recipient.clone()));
+    if required {
+        env.storage().instance().set(&key, &true);
+    } else {
+        env.storage().instance().remove(&key);
+    }
+}
+
+pub fn read_document_threshold(env: &Env, token: &Address) -> Option<i128> {
+    env.storage().instance().get(&DataKey::Policy(PolicyKey::DocumentThreshold(token.clone())))
+}
+
+pub fn write_document_threshold(env: &Env, threshold: &DocumentThreshold) {
+    env.storage().instance().set(&DataKey::Policy(PolicyKey::DocumentThreshold(threshold.token.clone())), &threshold.amount);
+}
+
+pub fn remove_document_threshold(env: &Env, token: &Address) {
+    env.storage().instance().remove(&DataKey::Policy(PolicyKey::DocumentThreshold(token.clone())));
+}
+
+pub fn read_document_value_threshold(env: &Env) -> Option<i128> {
+    env.storage().instance().get(&DataKey::Policy(PolicyKey::DocumentValueThreshold))
+}
+
+pub fn write_document_value_threshold(env: &Env, threshold: Option<i128>) {
+    match threshold {
+        Some(value) => env.storage().instance().set(&DataKey::Policy(PolicyKey::DocumentValueThreshold), &value),
+        None => env.storage().instance().remove(&DataKey::Policy(PolicyKey::DocumentValueThreshold)),
+    }
+}
+
+pub fn read_execution_schedule(env: &Env, proposal_id: u64) -> Option<ExecutionSchedule> {
+    env.storage().instance().get(&DataKey::Proposals(ProposalKey::ExecutionSchedule(proposal_id)))
+}
+
+pub fn write_execution_schedule(env: &Env, proposal_id: u64, schedule: &ExecutionSchedule) {
+    env.storage().instance().set(&DataKey::Proposals(ProposalKey::ExecutionSchedule(proposal_id)), schedule);
+}
+
+pub fn read_signer_nonce(env: &Env, signer: &BytesN<32>) -> u64 {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::SignerNonce(signer.clone()))).unwrap_or(0)
+}
+
+pub fn write_signer_nonce(env: &Env, signer: &BytesN<32>, nonce: u64) {
+    env.storage().instance().set(&DataKey::Signers(SignerKey::SignerNonce(signer.clone())), &nonce);
+}
+
+pub fn read_redundancy_policy(env: &Env) -> RedundancyPolicy {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::RedundancyPolicy)).unwrap_or(RedundancyPolicy::Warn)
+}
+
+pub fn write_redundancy_policy(env: &Env, policy: RedundancyPolicy) {
+    env.storage().instance().set(&DataKey::Signers(SignerKey::RedundancyPolicy), &policy);
+}
+
+pub fn is_no_redundancy(env: &Env) -> bool {
+    env.storage().instance().has(&DataKey::Signers(SignerKey::NoRedundancy))
+}
+
+pub fn set_no_redundancy(env: &Env, lost: bool) {
+    if lost {
+        env.storage().instance().set(&DataKey::Signers(SignerKey::NoRedundancy), &true);
+    } else {
+        env.storage().instance().remove(&DataKey::Signers(SignerKey::NoRedundancy));
+    }
+}
+
+pub fn read_execution_grace(env: &Env) -> u64 {
+    env.storage().instance().get(&DataKey::Policy(PolicyKey::ExecutionGrace)).unwrap_or(0)
+}
+
+pub fn write_execution_grace(env: &Env, grace_seconds: u64) {
+    env.storage().instance().set(&DataKey::Policy(PolicyKey::ExecutionGrace), &grace_seconds);
+}
+
+pub fn read_delegation(env: &Env, signer: &BytesN<32>) -> Option<Delegation> {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::Delegation(signer.clone())))
+}
+
+pub fn write_delegation(env: &Env, signer: &BytesN<32>, delegation: Option<&Delegation>) {
+    let key = DataKey::Signers(SignerKey::Delegation(signer.clone()));
+    match delegation {
+        Some(delegation) => env.storage().instance().set(&key, delegation),
+        None => env.storage().instance().remove(&key),
+    }
+}
+
+pub fn read_delegator_of(env: &Env, delegate: &BytesN<32>) -> Option<BytesN<32>> {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::DelegatorOf(delegate.clone())))
+}
+
+pub fn write_delegator_of(env: &Env, delegate: &BytesN<32>, delegator: Option<&BytesN<32>>) {
+    let key = DataKey::Signers(SignerKey::DelegatorOf(delegate.clone()));
+    match delegator {
+        Some(delegator) => env.storage().instance().set(&key, delegator),
+        None => env.storage().instance().remove(&key),
+    }
+}
+
+pub fn read_cancel_grace(env: &Env) -> Option<u64> {
+    env.storage().instance().get(&DataKey::Policy(PolicyKey::CancelGrace))
+}
+
+pub fn write_cancel_grace(env: &Env, grace_seconds: u64) {
+    env.storage().instance().set(&DataKey::Policy(PolicyKey::CancelGrace), &grace_seconds);
+}
+
+pub fn read_pending_cancellation(env: &Env, proposal_id: u64) -> Option<PendingCancellation> {
+    env.storage().instance().get(&DataKey::Proposals(ProposalKey::PendingCancellation(proposal_id)))
+}
+
+pub fn write_pending_cancellation(env: &Env, proposal_id: u64, pending: Option<&PendingCancellation>) {
+    let key = DataKey::Proposals(ProposalKey::PendingCancellation(proposal_id));
+    match pending {
+        Some(pending) => env.storage().instance().set(&key, pending),
+        None => env.storage().instance().remove(&key),
+    }
+}
+
+pub fn read_fully_approved_at(env: &Env, proposal_id: u64) -> Option<u64> {
+    env.storage().instance().get(&DataKey::Proposals(ProposalKey::FullyApprovedAt(proposal_id)))
+}
+
+pub fn write_fully_approved_at(env: &Env, proposal_id: u64, timestamp: Option<u64>) {
+    let key = DataKey::Proposals(ProposalKey::FullyApprovedAt(proposal_id));
+    match timestamp {
+        Some(timestamp) => env.storage().instance().set(&key, &timestamp),
+        None => env.storage().instance().remove(&key),
+    }
+}
+
+pub fn read_suspended_signers(env: &Env) -> Vec<BytesN<32>> {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::SuspendedSigners)).unwrap_or(Vec::new(env))
+}
+
+pub fn write_suspended_signers(env: &Env, signers: &Vec<BytesN<32>>) {
+    if signers.is_empty() {
+        env.storage().instance().remove(&DataKey::Signers(SignerKey::SuspendedSigners));
+    } else {
+        env.storage().instance().set(&DataKey::Signers(SignerKey::SuspendedSigners), signers);
+    }
+}
+
+pub fn read_observers(env: &Env) -> Vec<BytesN<32>> {
+    env.storage().instance().get(&DataKey::Policy(PolicyKey::Observers)).unwrap_or(Vec::new(env))
+}
+
+pub fn write_observers(env: &Env, observers: &Vec<BytesN<32>>) {
+    env.storage().instance().set(&DataKey::Policy(PolicyKey::Observers), observers);
+}
+
+pub fn read_fully_approved_ledger(env: &Env, proposal_id: u64) -> Option<u32> {
+    env.storage().instance().get(&DataKey::Proposals(ProposalKey::FullyApprovedLedger(proposal_id)))
+}
+
+pub fn write_fully_approved_ledger(env: &Env, proposal_id: u64, sequence: Option<u32>) {
+    let key = DataKey::Proposals(ProposalKey::FullyApprovedLedger(proposal_id));
+    match sequence {
+        Some(sequence) => env.storage().instance().set(&key, &sequence),
+        None => env.storage().instance().remove(&key),
+    }
+}
+
+// Proposal ids are shared by every proposal family
+
+pub fn read_proposal_count(env: &Env) -> u64 {
+    env.storage().instance().get(&DataKey::ProposalCount).unwrap_or(0u64)
+}
+
+pub fn next_proposal_id(env: &Env) -> u64 {
+    let proposal_id = read_proposal_count(env) + 1;
+    env.storage().instance().set(&DataKey::ProposalCount, &proposal_id);
+    // Every id starts with a flags entry, so status checks on it never need
+    // the legacy fallback
+    write_proposal_flags(env, proposal_id, 0);
+    proposal_id
+}
+
+// Proposals of each family created so far; see `counts`
+
+pub fn read_payment_proposal_count(env: &Env) -> Option<u64> {
+    env.storage().instance().get(&DataKey::Proposals(ProposalKey::PaymentProposalCount))
+}
+
+pub fn write_payment_proposal_count(env: &Env, count: u64) {
+    env.storage().instance().set(&DataKey::Proposals(ProposalKey::PaymentProposalCount), &count);
+}
+
+pub fn read_signer_change_count(env: &Env) -> Option<u64> {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::SignerChangeCount))
+}
+
+pub fn write_signer_change_count(env: &Env, count: u64) {
+    env.storage().instance().set(&DataKey::Signers(SignerKey::SignerChangeCount), &count);
+}
+
+/// Progress of a count backfill: the next id to visit and the payment
+/// proposals and signer changes counted before it.
+pub fn read_count_backfill(env: &Env) -> Option<(u64, u64, u64)> {
+    env.storage().instance().get(&DataKey::Proposals(ProposalKey::CountBackfill))
+}
+
+pub fn write_count_backfill(env: &Env, progress: Option<(u64, u64, u64)>) {
+    match progress {
+        Some(progress) => env.storage().instance().set(&DataKey::Proposals(ProposalKey::CountBackfill), &progress),
+        None => env.storage().instance().remove(&DataKey::Proposals(ProposalKey::CountBackfill)),
+    }
+}
+
+// Status bits of a proposal of any family, packed into one `ProposalFlags`
+// entry per id. The flags are the single source of truth for whether a
+// proposal has been executed; the `executed` field on the stored structs is
+// kept in sync for display only.
+
+pub const FLAG_EXECUTED: u32 = 1 << 0;
+pub const FLAG_EXPIRED: u32 = 1 << 1;
+
+pub fn read_proposal_flags(env: &Env, proposal_id: u64) -> u32 {
+    env.storage().instance()
+        .get(&DataKey::Proposals(ProposalKey::ProposalFlags(proposal_id)))
+        .unwrap_or_else(|| read_legacy_flags(env, proposal_id))
+}
+
+pub fn write_proposal_flags(env: &Env, proposal_id: u64, flags: u32) {
+    env.storage().instance().set(&DataKey::Proposals(ProposalKey::ProposalFlags(proposal_id)), &flags);
+}
+
+pub fn has_proposal_flag(env: &Env, proposal_id: u64, flag: u32) -> bool {
+    read_proposal_flags(env, proposal_id) & flag != 0
+}
+
+pub fn set_proposal_flag(env: &Env, proposal_id: u64, flag: u32) {
+    write_proposal_flags(env, proposal_id, read_proposal_flags(env, proposal_id) | flag);
+}
+
+/// Folds the per-family `*Executed` keys of a proposal created before flags
+/// were packed into its `ProposalFlags` entry. Returns whether anything was
+/// converted.
+pub fn migrate_legacy_flags(env: &Env, proposal_id: u64) -> bool {
+    if env.storage().instance().has(&DataKey::Proposals(ProposalKey::ProposalFlags(proposal_id))) {
+        return false;
+    }
+    write_proposal_flags(env, proposal_id, read_legacy_flags(env, proposal_id));
+    for key in legacy_flag_keys(proposal_id) {
+        env.storage().instance().remove(&key);
+    }
+    true
+}
+
+fn read_legacy_flags(env: &Env, proposal_id: u64) -> u32 {
+    let executed = legacy_flag_keys(proposal_id)
+        .iter()
+        .any(|key| env.storage().instance().has(key));
+    if executed { FLAG_EXECUTED } else { 0 }
+}
+
+fn legacy_flag_keys(proposal_id: u64) -> [DataKey; 3] {
+    [
+        DataKey::ProposalExecuted(proposal_id),
+        DataKey::SignerChangeExecuted(proposal_id),
+        DataKey::GovernanceExecuted(proposal_id),
+    ]
+}
+
+pub fn read_outcome(env: &Env, proposal_id: u64) -> Option<TerminalRecord> {
+    env.storage().instance().get(&DataKey::Outcome(proposal_id))
+}
+
+pub fn write_outcome(env: &Env, proposal_id: u64, record: &TerminalRecord) {
+    env.storage().instance().set(&DataKey::Outcome(proposal_id), record);
+}
+
+// Payment proposals
+
+pub fn read_proposal(env: &Env, proposal_id: u64) -> Option<Proposal> {
+    let mut stored: Map<Symbol, Val> = env.storage().instance().get(&DataKey::Proposal(proposal_id))?;
+
+    // Stored before proposals carried a memo, document hash or execution details
+    for field in ["destination_memo", "document_hash", "executed_by", "executed_at"] {
+        let field = Symbol::new(env, field);
+        if !stored.contains_key(field.clone()) {
+            stored.set(field, Val::VOID.to_val());
+        }
+    }
+    Some(Proposal::from_val(env, &stored.to_val()))
+}
+
+pub fn has_proposal(env: &Env, proposal_id: u64) -> bool {
+    env.storage().instance().has(&DataKey::Proposal(proposal_id))
+}
+
+/// Reads a stored proposal as its raw field map, whatever layout it was written in.
+pub fn read_proposal_fields(env: &Env, proposal_id: u64) -> Option<Map<Symbol, Val>> {
+    env.storage().instance().get(&DataKey::Proposal(proposal_id))
+}
+
+pub fn write_proposal(env: &Env, proposal: &Proposal) {
+    env.storage().instance().set(&DataKey::Proposal(proposal.id), proposal);
+    env.storage().instance().set(&DataKey::ProposalContentHash(proposal.id), &proposal.content_hash(env));
+}
+
+/// Hash of `proposal` as last written. Proposals stored before hashes were
+/// kept fall back to hashing their current contents.
+pub fn read_proposal_content_hash(env: &Env, proposal: &Proposal) -> BytesN<32> {
+    env.storage().instance()
+        .get(&DataKey::ProposalContentHash(proposal.id))
+        .unwrap_or_else(|| proposal.content_hash(env))
+}
+
+pub fn read_proposal_approvals(env: &Env, proposal_id: u64) -> Vec<ProposalApproval> {
+    let stored: Vec<Map<Symbol, Val>> = env.storage().instance()
+        .get(&DataKey::ProposalApprovals(proposal_id))
+        .unwrap_or(Vec::new(env));
+
+    // Stored before approvals could be relayed
+    let relayer = Symbol::new(env, "relayer");
+    let mut approvals = Vec::new(env);
+    for mut fields in stored.iter() {
+        if !fields.contains_key(relayer.clone()) {
+            fields.set(relayer.clone(), Val::VOID.to_val());
+        }
+        approvals.push_back(ProposalApproval::from_val(env, &fields.to_val()));
+    }
+    approvals
+}
+
+pub fn write_proposal_approvals(env: &Env, proposal_id: u64, approvals: &Vec<ProposalApproval>) {
+    env.storage().instance().set(&DataKey::ProposalApprovals(proposal_id), approvals);
+}
+
+pub fn read_proposal_rejections(env: &Env, proposal_id: u64) -> Vec<BytesN<32>> {
+    env.storage().instance()
+        .get(&DataKey::Proposals(ProposalKey::ProposalRejections(proposal_id)))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn write_proposal_rejections(env: &Env, proposal_id: u64, rejections: &Vec<BytesN<32>>) {
+    env.storage().instance().set(&DataKey::Proposals(ProposalKey::ProposalRejections(proposal_id)), rejections);
+}
+
+
+pub fn read_vote_delegations(env: &Env, proposal_id: u64) -> Vec<VoteDelegation> {
+    env.storage().instance()
+        .get(&DataKey::VoteDelegations(proposal_id))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn write_vote_delegations(env: &Env, proposal_id: u64, delegations: &Vec<VoteDelegation>) {
+    env.storage().instance().set(&DataKey::VoteDelegations(proposal_id), delegations);
+}
+
+pub fn read_approval_roles(env: &Env, proposal_id: u64) -> Map<BytesN<32>, Symbol> {
+    env.storage().instance()
+        .get(&DataKey::ApprovalRoles(proposal_id))
+        .unwrap_or(Map::new(env))
+}
+
+pub fn write_approval_roles(env: &Env, proposal_id: u64, roles: &Map<BytesN<32>, Symbol>) {
+    env.storage().instance().set(&DataKey::ApprovalRoles(proposal_id), roles);
+}
+
+pub fn read_execution_receipt(env: &Env, proposal_id: u64) -> Option<ExecutionReceipt> {
+    env.storage().instance().get(&DataKey::ExecutionReceipt(proposal_id))
+}
+
+pub fn write_execution_receipt(env: &Env, receipt: &ExecutionReceipt) {
+    env.storage().instance().set(&DataKey::ExecutionReceipt(receipt.proposal_id), receipt);
+}
+
+// Signer-change proposals
+
+pub fn read_signer_change(env: &Env, proposal_id: u64) -> Option<SignerChangeProposal> {
+    let mut stored: Map<Symbol, Val> = env.storage().instance().get(&DataKey::SignerChangeProposal(proposal_id))?;
+    if stored.contains_key(Symbol::new(env, "approval_deadline")) {
+        // Stored while change types were strings
+        let change_type = Symbol::new(env, "change_type");
+        if let Ok(name) = String::try_from_val(env, &stored.get_unchecked(change_type.clone())) {
+            stored.set(change_type, change_kind(env, &name).into_val(env));
+        }
+        // Stored before removals could accept losing redundancy
+        let accept = Symbol::new(env, "accept_no_redundancy");
+        if !stored.contains_key(accept.clone()) {
+            stored.set(accept, false.into_val(env));
+        }
+        return Some(SignerChangeProposal::from_val(env, &stored.to_val()));
+    }
+
+    // Stored before scheduling existed: approvals stay open until expiry and
+    // execution is not delayed
+    let legacy = LegacySignerChangeProposal::from_val(env, &stored.to_val());
+    Some(SignerChangeProposal {
+        id: legacy.id,
+        proposer: legacy.proposer,
+        change_type: change_kind(env, &legacy.change_type),
+        signer: legacy.signer,
+        created_at: legacy.created_at,
+        expires_at: legacy.expires_at,
+        approval_deadline: legacy.expires_at,
+        execute_not_before: None,
+        executed: legacy.executed,
+        accept_no_redundancy: false,
+    })
+}
+
+// Change types were stored by their lowercase names before `SignerChangeKind`.
+// A name that was never one of them means the entry is corrupt.
+fn change_kind(env: &Env, name: &String) -> SignerChangeKind {
+    let kinds = [
+        ("add", SignerChangeKind::Add),
+        ("remove", SignerChangeKind::Remove),
+        ("suspend", SignerChangeKind::Suspend),
+        ("reinstate", SignerChangeKind::Reinstate),
+        ("replace", SignerChangeKind::Replace),
+        ("batch", SignerChangeKind::Batch),
+    ];
+    match kinds.into_iter().find(|(known, _)| *name == String::from_str(env, known)) {
+        Some((_, kind)) => kind,
+        None => panic_with_error!(env, MultisigError::InvalidProposal),
+    }
+}
+
+pub fn has_signer_change(env: &Env, proposal_id: u64) -> bool {
+    env.storage().instance().has(&DataKey::SignerChangeProposal(proposal_id))
+}
+
+pub fn write_signer_change(env: &Env, proposal: &SignerChangeProposal) {
+    env.storage().instance().set(&DataKey::SignerChangeProposal(proposal.id), proposal);
+}
+
+pub fn read_signer_change_approvals(env: &Env, proposal_id: u64) -> Vec<SignerChangeApproval> {
+    env.storage().instance()
+        .get(&DataKey::SignerChangeApprovals(proposal_id))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn write_signer_change_approvals(env: &Env, proposal_id: u64, approvals: &Vec<SignerChangeApproval>) {
+    env.storage().instance().set(&DataKey::SignerChangeApprovals(proposal_id), approvals);
+}
+
+// Governance proposals
+
+pub fn read_governance(env: &Env, proposal_id: u64) -> Option<GovernanceProposal> {
+    env.storage().instance().get(&DataKey::GovernanceProposal(proposal_id))
+}
+
+pub fn write_governance(env: &Env, proposal: &GovernanceProposal) {
+    env.storage().instance().set(&DataKey::GovernanceProposal(proposal.id), proposal);
+}
+
+pub fn read_governance_approvals(env: &Env, proposal_id: u64) -> Vec<GovernanceApproval> {
+    env.storage().instance()
+        .get(&DataKey::GovernanceApprovals(proposal_id))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn write_governance_approvals(env: &Env, proposal_id: u64, approvals: &Vec<GovernanceApproval>) {
+    env.storage().instance().set(&DataKey::GovernanceApprovals(proposal_id), approvals);
+}
+
+pub fn is_token_frozen(env: &Env, token: &Address) -> bool {
+    env.storage().instance().has(&DataKey::FrozenToken(token.clone()))
+}
+
+pub fn set_token_frozen(env: &Env, token: &Address, frozen: bool) {
+    if frozen {
+        env.storage().instance().set(&DataKey::FrozenToken(token.clone()), &true);
+    } else {
+        env.storage().instance().remove(&DataKey::FrozenToken(token.clone()));
+    }
+}
+
+pub fn read_feature_flags(env: &Env) -> Option<Map<Symbol, bool>> {
+    env.storage().instance().get(&DataKey::FeatureFlags)
+}
+
+pub fn write_feature_flags(env: &Env, flags: &Map<Symbol, bool>) {
+    env.storage().instance().set(&DataKey::FeatureFlags, flags);
+}
+
+pub fn read_token_watchers(env: &Env, token: &Address) -> Vec<Address> {
+    env.storage().instance()
+        .get(&DataKey::TokenWatchers(token.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn write_token_watchers(env: &Env, token: &Address, watchers: &Vec<Address>) {
+    if watchers.is_empty() {
+        env.storage().instance().remove(&DataKey::TokenWatchers(token.clone()));
+    } else {
+        env.storage().instance().set(&DataKey::TokenWatchers(token.clone()), watchers);
+    }
+}
+
+pub fn read_proposer_cap(env: &Env, token: &Address) -> Option<ProposerCap> {
+    env.storage().instance().get(&DataKey::Policy(PolicyKey::ProposerCap(token.clone())))
+}
+
+pub fn write_proposer_cap(env: &Env, cap: &ProposerCap) {
+    env.storage().instance().set(&DataKey::Policy(PolicyKey::ProposerCap(cap.token.clone())), cap);
+}
+
+pub fn remove_proposer_cap(env: &Env, token: &Address) {
+    env.storage().instance().remove(&DataKey::Policy(PolicyKey::ProposerCap(token.clone())));
+}
+
+pub fn read_proposer_usage(env: &Env, proposer: &BytesN<32>, token: &Address) -> Option<ProposerUsage> {
+    env.storage().instance().get(&DataKey::Policy(PolicyKey::ProposerUsage(proposer.clone(), token.clone())))
+}
+
+pub fn write_proposer_usage(env: &Env, proposer: &BytesN<32>, token: &Address, usage: &ProposerUsage) {
+    env.storage().instance().set(&DataKey::Policy(PolicyKey::ProposerUsage(proposer.clone(), token.clone())), usage);
+}
+
+#[cfg(feature = "testing-hooks")]
+pub fn remove_proposer_usage(env: &Env, proposer: &BytesN<32>, token: &Address) {
+    env.storage().instance().remove(&DataKey::Policy(PolicyKey::ProposerUsage(proposer.clone(), token.clone())));
+}
+
+pub fn read_value_cap(env: &Env) -> Option<ValueCap> {
+    env.storage().instance().get(&DataKey::Policy(PolicyKey::ValueCap))
+}
+
+pub fn write_value_cap(env: &Env, cap: Option<&ValueCap>) {
+    match cap {
+        Some(cap) => env.storage().instance().set(&DataKey::Policy(PolicyKey::ValueCap), cap),
+        None => env.storage().instance().remove(&DataKey::Policy(PolicyKey::ValueCap)),
+    }
+}
+
+pub fn read_proposer_value_usage(env: &Env, proposer: &BytesN<32>) -> Option<ProposerUsage> {
+    env.storage().instance().get(&DataKey::Policy(PolicyKey::ProposerValueUsage(proposer.clone())))
+}
+
+pub fn write_proposer_value_usage(env: &Env, proposer: &BytesN<32>, usage: &ProposerUsage) {
+    env.storage().instance().set(&DataKey::Policy(PolicyKey::ProposerValueUsage(proposer.clone())), usage);
+}
+
+#[cfg(feature = "testing-hooks")]
+pub fn remove_proposer_value_usage(env: &Env, proposer: &BytesN<32>) {
+    env.storage().instance().remove(&DataKey::Policy(PolicyKey::ProposerValueUsage(proposer.clone())));
+}
+
+pub fn read_exchange_rate(env: &Env, token: &Address) -> Option<ExchangeRate> {
+    env.storage().instance().get(&DataKey::Policy(PolicyKey::ExchangeRate(token.clone())))
+}
+
+pub fn write_exchange_rate(env: &Env, rate: &ExchangeRate) {
+    env.storage().instance().set(&DataKey::Policy(PolicyKey::ExchangeRate(rate.token.clone())), rate);
+}
+
+pub fn remove_exchange_rate(env: &Env, token: &Address) {
+    env.storage().instance().remove(&DataKey::Policy(PolicyKey::ExchangeRate(token.clone())));
+}
+
+pub fn read_import_deadline(env: &Env) -> Option<u64> {
+    env.storage().instance().get(&DataKey::Proposals(ProposalKey::ImportDeadline))
+}
+
+pub fn write_import_deadline(env: &Env, deadline: Option<u64>) {
+    match deadline {
+        Some(deadline) => env.storage().instance().set(&DataKey::Proposals(ProposalKey::ImportDeadline), &deadline),
+        None => env.storage().instance().remove(&DataKey::Proposals(ProposalKey::ImportDeadline)),
+    }
+}
+
+pub fn read_import_ref(env: &Env, proposal_id: u64) -> Option<u64> {
+    env.storage().instance().get(&DataKey::Proposals(ProposalKey::ImportRef(proposal_id)))
+}
+
+pub fn read_imported_proposal(env: &Env, external_ref: u64) -> Option<u64> {
+    env.storage().instance().get(&DataKey::Proposals(ProposalKey::ImportedProposal(external_ref)))
+}
+
+pub fn write_import_ref(env: &Env, proposal_id: u64, external_ref: u64) {
+    env.storage().instance().set(&DataKey::Proposals(ProposalKey::ImportRef(proposal_id)), &external_ref);
+    env.storage().instance().set(&DataKey::Proposals(ProposalKey::ImportedProposal(external_ref)), &proposal_id);
+}
+
+pub fn read_key_ring(env: &Env, signer: &BytesN<32>) -> Option<Vec<BytesN<32>>> {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::KeyRing(signer.clone())))
+}
+
+pub fn write_key_ring(env: &Env, signer: &BytesN<32>, keys: Option<&Vec<BytesN<32>>>) {
+    match keys {
+        Some(keys) => env.storage().instance().set(&DataKey::Signers(SignerKey::KeyRing(signer.clone())), keys),
+        None => env.storage().instance().remove(&DataKey::Signers(SignerKey::KeyRing(signer.clone()))),
+    }
+}
+
+pub fn read_last_ring_activation(env: &Env, signer: &BytesN<32>) -> Option<u64> {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::LastRingActivation(signer.clone())))
+}
+
+pub fn write_last_ring_activation(env: &Env, signer: &BytesN<32>, activated_at: Option<u64>) {
+    match activated_at {
+        Some(activated_at) => env.storage().instance().set(&DataKey::Signers(SignerKey::LastRingActivation(signer.clone())), &activated_at),
+        None => env.storage().instance().remove(&DataKey::Signers(SignerKey::LastRingActivation(signer.clone()))),
+    }
+}
+
+pub fn read_signer_joined_at(env: &Env, signer: &BytesN<32>) -> Option<u64> {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::SignerJoinedAt(signer.clone())))
+}
+
+pub fn write_signer_joined_at(env: &Env, signer: &BytesN<32>, version: Option<u64>) {
+    match version {
+        Some(version) => env.storage().instance().set(&DataKey::Signers(SignerKey::SignerJoinedAt(signer.clone())), &version),
+        None => env.storage().instance().remove(&DataKey::Signers(SignerKey::SignerJoinedAt(signer.clone()))),
+    }
+}
+
+pub fn read_signer_policy(env: &Env) -> Option<SignerPolicy> {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::SignerPolicy))
+}
+
+pub fn write_signer_policy(env: &Env, policy: Option<&SignerPolicy>) {
+    match policy {
+        Some(policy) => env.storage().instance().set(&DataKey::Signers(SignerKey::SignerPolicy), policy),
+        None => env.storage().instance().remove(&DataKey::Signers(SignerKey::SignerPolicy)),
+    }
+}
+
+pub fn read_signer_permissions(env: &Env, signer: &BytesN<32>) -> Option<SignerPermissions> {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::SignerPermissions(signer.clone())))
+}
+
+pub fn write_signer_permissions(env: &Env, signer: &BytesN<32>, permissions: Option<&SignerPermissions>) {
+    let key = DataKey::Signers(SignerKey::SignerPermissions(signer.clone()));
+    match permissions {
+        Some(permissions) => env.storage().instance().set(&key, permissions),
+        None => env.storage().instance().remove(&key),
+    }
+}
+
+pub fn read_signer_last_active(env: &Env, signer: &BytesN<32>) -> Option<u64> {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::SignerLastActive(signer.clone())))
+}
+
+pub fn write_signer_last_active(env: &Env, signer: &BytesN<32>, timestamp: Option<u64>) {
+    let key = DataKey::Signers(SignerKey::SignerLastActive(signer.clone()));
+    match timestamp {
+        Some(timestamp) => env.storage().instance().set(&key, &timestamp),
+        None => env.storage().instance().remove(&key),
+    }
+}
+
+pub fn read_inactivity_removal(env: &Env) -> Option<InactivityRemoval> {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::InactivityRemoval))
+}
+
+pub fn write_inactivity_removal(env: &Env, config: Option<&InactivityRemoval>) {
+    match config {
+        Some(config) => env.storage().instance().set(&DataKey::Signers(SignerKey::InactivityRemoval), config),
+        None => env.storage().instance().remove(&DataKey::Signers(SignerKey::InactivityRemoval)),
+    }
+}
+
+pub fn read_signer_label(env: &Env, signer: &BytesN<32>) -> Option<String> {
+    env.storage().instance().get(&DataKey::Signers(SignerKey::SignerLabel(signer.clone())))
+}
+
+pub fn write_signer_label(env: &Env, signer: &BytesN<32>, label: Option<&String>) {
+    let key = DataKey::Signers(SignerKey::SignerLabel(signer.clone()));
+    match label {
+        Some(label) => env.storage().instance().set(&key, label),
+        None => env.storage().instance().remove(&key),
+    }
+}
+
+pub fn read_max_rate_age(env: &Env) -> Option<u64> {
+    env.storage().instance().get(&DataKey::Policy(PolicyKey::MaxRateAge))
+}
+
+pub fn write_max_rate_age(env: &Env, max_age_seconds: u64) {
+    env.storage().instance().set(&DataKey::Policy(PolicyKey::MaxRateAge), &max_age_seconds);
+}
+
+pub fn read_allocation_targets(env: &Env) -> Vec<AllocationTarget> {
+    env.storage().instance()
+        .get(&DataKey::AllocationTargets)
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn write_allocation_targets(env: &Env, targets: &Vec<AllocationTarget>) {
+    env.storage().instance().set(&DataKey::AllocationTargets, targets);
+}
+
+pub fn read_template_count(env: &Env) -> u32 {
+    env.storage().instance().get(&DataKey::TemplateCount).unwrap_or(0)
+}
+
+pub fn write_template_count(env: &Env, count: u32) {
+    env.storage().instance().set(&DataKey::TemplateCount, &count);
+}
+
+pub fn read_template(env: &Env, template_id: u32) -> Option<ProposalTemplate> {
+    env.storage().instance().get(&DataKey::Template(template_id))
+}
+
+pub fn write_template(env: &Env, template: &ProposalTemplate) {
+    env.storage().instance().set(&DataKey::Template(template.id), template);
+}
+
+pub fn read_proposal_template(env: &Env, proposal_id: u64) -> Option<u32> {
+    env.storage().instance().get(&DataKey::ProposalTemplate(proposal_id))
+}
+
+pub fn write_proposal_template(env: &Env, proposal_id: u64, template_id: u32) {
+    env.storage().instance().set(&DataKey::ProposalTemplate(proposal_id), &template_id);
+}
+
+pub fn read_address_book_next_id(env: &Env) -> u32 {
+    env.storage().instance().get(&DataKey::AddressBookNextId).unwrap_or(0)
+}
+
+pub fn write_address_book_next_id(env: &Env, id: u32) {
+    env.storage().instance().set(&DataKey::AddressBookNextId, &id);
+}
+
+pub fn read_address_book_ids(env: &Env) -> Vec<u32> {
+    env.storage().instance()
+        .get(&DataKey::AddressBookIds)
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn write_address_book_ids(env: &Env, ids: &Vec<u32>) {
+    env.storage().instance().set(&DataKey::AddressBookIds, ids);
+}
+
+pub fn read_address_book_entry(env: &Env, entry_id: u32) -> Option<AddressBookEntry> {
+    env.storage().instance().get(&DataKey::AddressBookEntry(entry_id))
+}
+
+pub fn read_address_book_id(env: &Env, address: &Address) -> Option<u32> {
+    env.storage().instance().get(&DataKey::AddressBookId(address.clone()))
+}
+
+pub fn write_address_book_entry(env: &Env, entry: &AddressBookEntry) {
+    env.storage().instance().set(&DataKey::AddressBookEntry(entry.id), entry);
+    env.storage().instance().set(&DataKey::AddressBookId(entry.address.clone()), &entry.id);
+}
+
+pub fn remove_address_book_entry(env: &Env, entry: &AddressBookEntry) {
+    env.storage().instance().remove(&DataKey::AddressBookEntry(entry.id));
+    env.storage().instance().remove(&DataKey::AddressBookId(entry.address.clone()));
+}
+
+pub fn read_recipient_label(env: &Env, proposal_id: u64) -> Option<Symbol> {
+    env.storage().instance().get(&DataKey::RecipientLabel(proposal_id))
+}
+
+pub fn write_recipient_label(env: &Env, proposal_id: u64, label: &Symbol) {
+    env.storage().instance().set(&DataKey::RecipientLabel(proposal_id), label);
+}
+
+pub fn read_token_implementation(env: &Env, token: &Address) -> Option<Address> {
+    env.storage().instance().get(&DataKey::TokenImplementation(token.clone()))
+}
+
+pub fn write_token_implementation(env: &Env, entry: &TokenImplementation) {
+    env.storage().instance().set(&DataKey::TokenImplementation(entry.token.clone()), &entry.implementation);
+}
+
+pub fn remove_token_implementation(env: &Env, token: &Address) {
+    env.storage().instance().remove(&DataKey::TokenImplementation(token.clone()));
+}
+
+pub fn read_reserve_floor(env: &Env, token: &Address) -> i128 {
+    env.storage().instance().get(&DataKey::ReserveFloor(token.clone())).unwrap_or(0)
+}
+
+pub fn write_reserve_floor(env: &Env, floor: &ReserveFloor) {
+    env.storage().instance().set(&DataKey::ReserveFloor(floor.token.clone()), &floor.amount);
+}
+
+pub fn remove_reserve_floor(env: &Env, token: &Address) {
+    env.storage().instance().remove(&DataKey::ReserveFloor(token.clone()));
+}
+
+pub fn read_idempotency_key(env: &Env, scoped_key: &BytesN<32>) -> Option<u64> {
+    env.storage().instance().get(&DataKey::IdempotencyKey(scoped_key.clone()))
+}
+
+pub fn write_idempotency_key(env: &Env, scoped_key: &BytesN<32>, proposal_id: u64) {
+    env.storage().instance().set(&DataKey::IdempotencyKey(scoped_key.clone()), &proposal_id);
+}
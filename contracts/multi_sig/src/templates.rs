@@ -0,0 +1,65 @@
+use soroban_sdk::{panic_with_error, Env, Vec};
+
+use crate::errors::{GovernanceError, MultisigError};
+use crate::storage;
+use crate::types::{ProposalTemplate, TemplateDraft};
+
+/// Most templates a multisig may hold, active or not, so `get_templates` stays
+/// a single bounded read.
+pub const MAX_TEMPLATES: u32 = 20;
+/// Longest template name, in bytes.
+pub const MAX_TEMPLATE_NAME_LEN: u32 = 32;
+
+pub fn validate_draft(env: &Env, draft: &TemplateDraft) {
+    if draft.name.is_empty() || draft.name.len() > MAX_TEMPLATE_NAME_LEN {
+        panic_with_error!(env, MultisigError::InvalidProposal);
+    }
+    if storage::read_template_count(env) >= MAX_TEMPLATES {
+        panic_with_error!(env, GovernanceError::TooManyTemplates);
+    }
+}
+
+pub fn create(env: &Env, draft: &TemplateDraft) {
+    // Checked again because other templates may have been created meanwhile
+    validate_draft(env, draft);
+
+    let id = storage::read_template_count(env) + 1;
+    let template = ProposalTemplate {
+        id,
+        name: draft.name.clone(),
+        token: draft.token.clone(),
+        recipient: draft.recipient.clone(),
+        active: true,
+    };
+    storage::write_template(env, &template);
+    storage::write_template_count(env, id);
+}
+
+pub fn deactivate(env: &Env, template_id: u32) {
+    let mut template = load(env, template_id);
+    template.active = false;
+    storage::write_template(env, &template);
+}
+
+pub fn all(env: &Env) -> Vec<ProposalTemplate> {
+    let mut templates = Vec::new(env);
+    for id in 1..=storage::read_template_count(env) {
+        templates.push_back(storage::read_template(env, id).unwrap());
+    }
+    templates
+}
+
+pub fn load(env: &Env, template_id: u32) -> ProposalTemplate {
+    let Some(template) = storage::read_template(env, template_id) else {
+        panic_with_error!(env, GovernanceError::TemplateNotFound);
+    };
+    template
+}
+
+pub fn load_active(env: &Env, template_id: u32) -> ProposalTemplate {
+    let template = load(env, template_id);
+    if !template.active {
+        panic_with_error!(env, GovernanceError::TemplateInactive);
+    }
+    template
+}
@@ -1,7 +1,32 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Vec};
+use crate::bls;
+use crate::errors::{GovernanceError, MultisigError, SignerError};
+use crate::history;
+use crate::import;
+use crate::index;
+use crate::keyring;
+use crate::pagination;
+use crate::payments;
+use crate::rates;
+use crate::schema;
+use crate::signers;
+use crate::storage;
+use crate::templates;
+use crate::validation;
+use crate::webauthn;
+use crate::types::{
+    AddressBookEntry, AddressLabel, AllocationEntry, AllocationTarget, AllowanceAction, ApprovalProgress, DataKey, Deposit, PolicyKey, ProposalKey, SignerKey, DocumentThreshold, ExchangeRate, ExecutionRecord, ExecutionSchedule, ExecutionTier, ExecutionVerdict, GovernanceAction, ImportedProposal, PolicyAction, SignerAction, TreasuryAction, IndexKey, InitialFunding, InvokeAction, KeyRing, LegacyProposal, LegacySignerChangeProposal, Obligation, ProposalAction, TransferAction,
+    ConfigSigner, ConfigSnapshot, MultisigConfig, PolicyCheck, ProposalNote, ProposalOutcome, RedundancyPolicy, SessionApprovalReport, ProposalPage, ProposalTemplate, ProposerCap, ReserveFloor, RoleProgress, RoleRequirement, SignerHistoryEntry, SignerRoleAssignment,
+    ProposalState, ProposalStatus, Secp256r1Key, Signer, SignerBatch, SignerChangeKind, SignerSetChange, SignerSetSnapshot, TemplateDraft, TokenImplementation, UnifiedProposal, UnifiedProposalPage, ValueCap, WatcherRegistration,
+};
+use ed25519_dalek::{Signer as _, SigningKey};
+use soroban_sdk::xdr::ToXdr;
+use payment_watcher::{ExecutedPayment, PaymentWatcher, PaymentWatcherClient};
+use proxy_token::{ProxyToken, ProxyTokenClient};
+use soroban_sdk::crypto::bls12_381::{Fr, G1Affine, G2Affine};
+use soroban_sdk::{bytesn, contract, contracterror, contractimpl, map, panic_with_error, symbol_short, testutils::{Address as _, AuthorizedFunction, Events as _, Ledger as _, MockAuth, MockAuthInvoke}, vec, Address, Bytes, BytesN, Env, IntoVal, Map, String, Symbol, U256, Val, Vec};
 
 // Helper to create test signers
 fn create_test_signers(env: &Env, count: u32) -> Vec<BytesN<32>> {
@@ -15,6 +40,33 @@ fn create_test_signers(env: &Env, count: u32) -> Vec<BytesN<32>> {
     signers
 }
 
+// The Stellar account an unbound ed25519 signer key authorizes through
+fn signer_account(env: &Env, key: &BytesN<32>) -> Address {
+    let mut account = Bytes::from_array(env, &[0, 0, 0, 18, 0, 0, 0, 0, 0, 0, 0, 0]);
+    account.append(&Bytes::from(key.clone()));
+    <Address as soroban_sdk::xdr::FromXdr>::from_xdr(env, &account).unwrap()
+}
+
+fn as_signers(env: &Env, keys: &Vec<BytesN<32>>) -> Vec<Signer> {
+    let mut signers = Vec::new(env);
+    for key in keys.iter() {
+        signers.push_back(Signer::Ed25519(key));
+    }
+    signers
+}
+
+// Events the multisig itself published during the last call, leaving out
+// those of the token contracts it called into
+fn contract_events(env: &Env, contract_id: &Address) -> Vec<(Address, Vec<Val>, Val)> {
+    let mut events = Vec::new(env);
+    for event in env.events().all().iter() {
+        if event.0 == *contract_id {
+            events.push_back(event);
+        }
+    }
+    events
+}
+
 #[test]
 fn test_initialize_success() {
     let env = Env::default();
@@ -22,7 +74,7 @@ fn test_initialize_success() {
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     assert_eq!(client.threshold(), 2);
     assert_eq!(client.signer_count(), 3);
@@ -37,7 +89,7 @@ fn test_initialize_zero_threshold() {
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &0); // Should fail
+    client.initialize(&signers, &0, &false); // Should fail
 }
 
 #[test]
@@ -48,7 +100,7 @@ fn test_initialize_threshold_exceeds_signers() {
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &5); // Threshold > signers
+    client.initialize(&signers, &5, &false); // Threshold > signers
 }
 
 #[test]
@@ -59,7 +111,49 @@ fn test_initialize_empty_signers() {
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let empty_signers = Vec::new(&env);
-    client.initialize(&empty_signers, &1);
+    client.initialize(&empty_signers, &1, &false);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #36)")]
+fn test_initialize_single_signer_rejected() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 1);
+    client.initialize(&signers, &1, &false);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #36)")]
+fn test_initialize_threshold_one_rejected() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &1, &false);
+}
+
+#[test]
+fn test_initialize_low_security_opt_out() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 2);
+    client.initialize(&signers, &1, &true);
+
+    // The opt-out carries over to governance: the set may shrink to one signer
+    let remove_type = SignerChangeKind::Remove;
+    let proposal_id = client.propose_signer_change(&signers.get_unchecked(0), &remove_type, &signers.get_unchecked(1), &3600);
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(0));
+    client.execute_signer_change(&proposal_id);
+
+    assert_eq!(client.signer_count(), 1);
+    assert_eq!(client.threshold(), 1);
 }
 
 #[test]
@@ -70,10 +164,10 @@ fn test_add_signer() {
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 2);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let new_signer = BytesN::from_array(&env, &[99u8; 32]);
-    let add_type = String::from_str(&env, "add");
+    let add_type = SignerChangeKind::Add;
     let proposal_id = client.propose_signer_change(&signers.get_unchecked(0), &add_type, &new_signer, &3600);
 
     assert_eq!(proposal_id, 1);
@@ -91,10 +185,10 @@ fn test_remove_signer() {
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let signer_to_remove = signers.get_unchecked(2);
-    let remove_type = String::from_str(&env, "remove");
+    let remove_type = SignerChangeKind::Remove;
     let proposal_id = client.propose_signer_change(&signers.get_unchecked(0), &remove_type, &signer_to_remove, &3600);
 
     assert_eq!(proposal_id, 1);
@@ -107,14 +201,15 @@ fn test_remove_signer() {
 #[test]
 fn test_approve_signer_change_success() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register(MultiSigContract, ());
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let new_signer = BytesN::from_array(&env, &[99u8; 32]);
-    let add_type = String::from_str(&env, "add");
+    let add_type = SignerChangeKind::Add;
     let proposal_id = client.propose_signer_change(&signers.get_unchecked(0), &add_type, &new_signer, &3600);
 
     // First approval
@@ -129,14 +224,15 @@ fn test_approve_signer_change_success() {
 #[should_panic(expected = "Error(Contract, #22)")]
 fn test_approve_signer_change_twice() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register(MultiSigContract, ());
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let new_signer = BytesN::from_array(&env, &[99u8; 32]);
-    let add_type = String::from_str(&env, "add");
+    let add_type = SignerChangeKind::Add;
     let proposal_id = client.propose_signer_change(&signers.get_unchecked(0), &add_type, &new_signer, &3600);
 
     // First approval
@@ -149,14 +245,15 @@ fn test_approve_signer_change_twice() {
 #[test]
 fn test_execute_signer_change_success() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register(MultiSigContract, ());
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let new_signer = BytesN::from_array(&env, &[99u8; 32]);
-    let add_type = String::from_str(&env, "add");
+    let add_type = SignerChangeKind::Add;
     let proposal_id = client.propose_signer_change(&signers.get_unchecked(0), &add_type, &new_signer, &3600);
 
     // Get threshold approvals
@@ -176,14 +273,15 @@ fn test_execute_signer_change_success() {
 #[should_panic(expected = "Error(Contract, #23)")]
 fn test_execute_signer_change_insufficient_approvals() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register(MultiSigContract, ());
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let new_signer = BytesN::from_array(&env, &[99u8; 32]);
-    let add_type = String::from_str(&env, "add");
+    let add_type = SignerChangeKind::Add;
     let proposal_id = client.propose_signer_change(&signers.get_unchecked(0), &add_type, &new_signer, &3600);
 
     // Only one approval (need 2 for threshold)
@@ -196,14 +294,15 @@ fn test_execute_signer_change_insufficient_approvals() {
 #[test]
 fn test_execute_signer_change_remove_signer() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register(MultiSigContract, ());
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let signer_to_remove = signers.get_unchecked(2);
-    let remove_type = String::from_str(&env, "remove");
+    let remove_type = SignerChangeKind::Remove;
     let proposal_id = client.propose_signer_change(&signers.get_unchecked(0), &remove_type, &signer_to_remove, &3600);
 
     // Get threshold approvals (need 2 for threshold=2)
@@ -219,18 +318,54 @@ fn test_execute_signer_change_remove_signer() {
     assert!(client.is_signer_change_executed(&proposal_id));
 }
 
+#[test]
+fn test_duplicate_add_and_remove_keep_signer_count() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let newcomer = BytesN::from_array(&env, &[99u8; 32]);
+    let approve = |proposal_id: u64| {
+        client.approve_signer_change(&proposal_id, &signers.get_unchecked(0));
+        client.approve_signer_change(&proposal_id, &signers.get_unchecked(1));
+    };
+
+    // Two proposals for the same addition: only the first one lands
+    let first = client.propose_signer_change(&signers.get_unchecked(0), &SignerChangeKind::Add, &newcomer, &3600);
+    let second = client.propose_signer_change(&signers.get_unchecked(1), &SignerChangeKind::Add, &newcomer, &3600);
+    approve(first);
+    approve(second);
+    client.execute_signer_change(&first);
+    assert_eq!(client.try_execute_signer_change(&second), Err(Ok(MultisigError::DuplicateSigner.into())));
+    assert_eq!(client.signer_count(), 4);
+
+    // Likewise for removing it again
+    let first = client.propose_signer_change(&signers.get_unchecked(0), &SignerChangeKind::Remove, &newcomer, &3600);
+    let second = client.propose_signer_change(&signers.get_unchecked(1), &SignerChangeKind::Remove, &newcomer, &3600);
+    approve(first);
+    approve(second);
+    client.execute_signer_change(&first);
+    assert_eq!(client.try_execute_signer_change(&second), Err(Ok(MultisigError::SignerNotFound.into())));
+    assert_eq!(client.signer_count(), 3);
+    assert!(!client.is_signer(&newcomer));
+}
+
 #[test]
 #[should_panic(expected = "Error(Contract, #20)")]
 fn test_execute_signer_change_twice() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register(MultiSigContract, ());
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let new_signer = BytesN::from_array(&env, &[99u8; 32]);
-    let add_type = String::from_str(&env, "add");
+    let add_type = SignerChangeKind::Add;
     let proposal_id = client.propose_signer_change(&signers.get_unchecked(0), &add_type, &new_signer, &3600);
 
     // Get threshold approvals and execute (need 2 for threshold=2)
@@ -246,11 +381,12 @@ fn test_execute_signer_change_twice() {
 #[test]
 fn test_create_proposal() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register(MultiSigContract, ());
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let proposer = signers.get_unchecked(0);
     let token_address = Address::generate(&env);
@@ -274,9 +410,14 @@ fn test_create_proposal() {
     let proposal = client.get_proposal(&proposal_id);
     assert_eq!(proposal.id, proposal_id);
     assert_eq!(proposal.proposer, proposer);
-    assert_eq!(proposal.token_address, token_address);
-    assert_eq!(proposal.recipient, recipient);
-    assert_eq!(proposal.amount, amount);
+    assert_eq!(
+        proposal.action,
+        ProposalAction::Transfer(TransferAction {
+            token_address,
+            recipient,
+            amount,
+        })
+    );
     assert_eq!(proposal.reason, reason);
     assert!(!proposal.executed);
 }
@@ -289,7 +430,7 @@ fn test_create_proposal_unknown_proposer() {
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let unknown_proposer = BytesN::from_array(&env, &[99u8; 32]);
     let token_address = Address::generate(&env);
@@ -315,7 +456,7 @@ fn test_create_proposal_invalid_amount() {
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let proposer = signers.get_unchecked(0);
     let token_address = Address::generate(&env);
@@ -337,11 +478,12 @@ fn test_create_proposal_invalid_amount() {
 #[test]
 fn test_approve_proposal() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register(MultiSigContract, ());
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let proposer = signers.get_unchecked(0);
     let approver1 = signers.get_unchecked(1);
@@ -377,11 +519,12 @@ fn test_approve_proposal() {
 #[should_panic(expected = "Error(Contract, #16)")]
 fn test_approve_proposal_twice() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register(MultiSigContract, ());
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let proposer = signers.get_unchecked(0);
     let approver = signers.get_unchecked(1);
@@ -408,11 +551,12 @@ fn test_approve_proposal_twice() {
 #[should_panic(expected = "Error(Contract, #13)")]
 fn test_approve_nonexistent_proposal() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register(MultiSigContract, ());
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let approver = signers.get_unchecked(0);
     client.approve_proposal(&999u64, &approver); // Proposal does not exist
@@ -421,11 +565,12 @@ fn test_approve_nonexistent_proposal() {
 #[test]
 fn test_revoke_approval() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register(MultiSigContract, ());
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let proposer = signers.get_unchecked(0);
     let approver1 = signers.get_unchecked(1);
@@ -464,11 +609,12 @@ fn test_revoke_approval() {
 #[should_panic(expected = "Error(Contract, #6)")]
 fn test_revoke_nonexistent_approval() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register(MultiSigContract, ());
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let proposer = signers.get_unchecked(0);
     let approver = signers.get_unchecked(1);
@@ -495,11 +641,12 @@ fn test_revoke_nonexistent_approval() {
 #[should_panic(expected = "Error(Contract, #17)")]
 fn test_execute_proposal_insufficient_approvals() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register(MultiSigContract, ());
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let proposer = signers.get_unchecked(0);
     let approver = signers.get_unchecked(1);
@@ -532,7 +679,7 @@ fn test_create_proposal_expiry_too_short() {
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let token_address = Address::generate(&env);
     let recipient = Address::generate(&env);
@@ -550,7 +697,7 @@ fn test_create_proposal_expiry_too_long() {
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let token_address = Address::generate(&env);
     let recipient = Address::generate(&env);
@@ -563,11 +710,12 @@ fn test_create_proposal_expiry_too_long() {
 #[test]
 fn test_create_proposal_valid_expiry() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register(MultiSigContract, ());
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let token_address = Address::generate(&env);
     let recipient = Address::generate(&env);
@@ -586,10 +734,10 @@ fn test_propose_signer_change_expiry_too_short() {
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let new_signer = BytesN::from_array(&env, &[99u8; 32]);
-    let add_type = String::from_str(&env, "add");
+    let add_type = SignerChangeKind::Add;
     
     // Try with 30 minutes (1800 seconds) - should fail (minimum is 1 hour)
     client.propose_signer_change(&signers.get_unchecked(0), &add_type, &new_signer, &1800);
@@ -603,10 +751,10 @@ fn test_propose_signer_change_expiry_too_long() {
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let new_signer = BytesN::from_array(&env, &[99u8; 32]);
-    let add_type = String::from_str(&env, "add");
+    let add_type = SignerChangeKind::Add;
     
     // Try with 60 days (5,184,000 seconds) - should fail (maximum is 30 days)
     client.propose_signer_change(&signers.get_unchecked(0), &add_type, &new_signer, &5_184_000);
@@ -615,16 +763,7873 @@ fn test_propose_signer_change_expiry_too_long() {
 #[test]
 fn test_propose_signer_change_valid_expiry() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register(MultiSigContract, ());
     let client = MultiSigContractClient::new(&env, &contract_id);
 
     let signers = create_test_signers(&env, 3);
-    client.initialize(&signers, &2);
+    client.initialize(&signers, &2, &false);
 
     let new_signer = BytesN::from_array(&env, &[99u8; 32]);
-    let add_type = String::from_str(&env, "add");
+    let add_type = SignerChangeKind::Add;
     
     // Try with 2 hours (7200 seconds) - should succeed
     let proposal_id = client.propose_signer_change(&signers.get_unchecked(0), &add_type, &new_signer, &7200);
     assert_eq!(proposal_id, 1);
 }
+
+// Helper to deploy a token and fund the multisig with it
+fn create_funded_token(env: &Env, multisig: &Address, amount: i128) -> Address {
+    let admin = Address::generate(env);
+    let token = env.register_stellar_asset_contract_v2(admin);
+    let token_admin = soroban_sdk::token::StellarAssetClient::new(env, &token.address());
+    token_admin.mint(multisig, &amount);
+    token.address()
+}
+
+#[test]
+fn test_delegated_vote_counts_for_delegator() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &3, &false);
+
+    let token_address = create_funded_token(&env, &contract_id, 5000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+
+    // Signer 0 has a conflict of interest and hands their vote to signer 1
+    client.delegate_vote(&proposal_id, &signers.get_unchecked(0), &signers.get_unchecked(1));
+
+    let delegations = client.get_vote_delegations(&proposal_id);
+    assert_eq!(delegations.len(), 1);
+    assert_eq!(delegations.get_unchecked(0).delegate, signers.get_unchecked(1));
+
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(2));
+
+    // Two direct approvals plus the delegated vote meet the threshold of 3
+    client.execute_proposal(&proposal_id);
+
+    let token_client = soroban_sdk::token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&recipient), 1000);
+    assert!(client.is_proposal_executed(&proposal_id));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #25)")]
+fn test_delegator_cannot_also_vote() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+
+    client.delegate_vote(&proposal_id, &signers.get_unchecked(0), &signers.get_unchecked(1));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+
+    // The delegator's vote is already counted through the delegate
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #25)")]
+fn test_delegate_vote_no_chains() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+
+    client.delegate_vote(&proposal_id, &signers.get_unchecked(1), &signers.get_unchecked(2));
+
+    // Signer 1 no longer holds a vote on this proposal to pass along
+    client.delegate_vote(&proposal_id, &signers.get_unchecked(0), &signers.get_unchecked(1));
+}
+
+#[test]
+fn test_delegation_is_scoped_to_one_proposal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let first = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+    let second = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &2000, &reason, &3600);
+
+    client.delegate_vote(&first, &signers.get_unchecked(0), &signers.get_unchecked(1));
+
+    // The delegator can still vote on other proposals
+    client.approve_proposal(&second, &signers.get_unchecked(0));
+    assert_eq!(client.get_vote_delegations(&second).len(), 0);
+    assert_eq!(client.get_proposal_approvals(&second).len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_delegation_void_after_delegator_removed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 4);
+    client.initialize(&signers, &3, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(1), &token_address, &recipient, &1000, &reason, &3600);
+
+    client.delegate_vote(&proposal_id, &signers.get_unchecked(0), &signers.get_unchecked(1));
+
+    // Remove the delegator from the signer set
+    let remove_type = SignerChangeKind::Remove;
+    let change_id = client.propose_signer_change(&signers.get_unchecked(1), &remove_type, &signers.get_unchecked(0), &3600);
+    client.approve_signer_change(&change_id, &signers.get_unchecked(1));
+    client.approve_signer_change(&change_id, &signers.get_unchecked(2));
+    client.approve_signer_change(&change_id, &signers.get_unchecked(3));
+    client.execute_signer_change(&change_id);
+
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(2));
+
+    // Only two approvals remain; the removed signer's delegated vote is void
+    client.execute_proposal(&proposal_id);
+}
+
+#[test]
+fn test_revoke_vote_delegation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+
+    client.delegate_vote(&proposal_id, &signers.get_unchecked(0), &signers.get_unchecked(1));
+    client.revoke_vote_delegation(&proposal_id, &signers.get_unchecked(0));
+    assert_eq!(client.get_vote_delegations(&proposal_id).len(), 0);
+
+    // With the delegation withdrawn the delegator votes directly again
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    assert_eq!(client.get_proposal_approvals(&proposal_id).len(), 1);
+}
+
+#[test]
+fn test_vote_delegation_requires_delegator_auth() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    let alice = Address::generate(&env);
+    client.initialize_with_addresses(&signers, &2, &false, &vec![&env, (signers.get_unchecked(0), alice.clone())]);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    env.mock_all_auths();
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+    env.set_auths(&[]);
+
+    // Nobody else may hand alice's vote away, or take it back
+    let (delegator, delegate) = (signers.get_unchecked(0), signers.get_unchecked(1));
+    assert!(client.try_delegate_vote(&proposal_id, &delegator, &delegate).is_err());
+    assert_eq!(client.get_vote_delegations(&proposal_id).len(), 0);
+
+    env.mock_all_auths();
+    client.delegate_vote(&proposal_id, &delegator, &delegate);
+    assert_eq!(env.auths()[0].0, alice);
+    env.set_auths(&[]);
+    assert!(client.try_revoke_vote_delegation(&proposal_id, &delegator).is_err());
+    assert_eq!(client.get_vote_delegations(&proposal_id).len(), 1);
+
+    env.mock_all_auths();
+    client.revoke_vote_delegation(&proposal_id, &delegator);
+    assert_eq!(env.auths()[0].0, alice);
+    assert_eq!(client.get_vote_delegations(&proposal_id).len(), 0);
+}
+
+// Helper to pass a governance action with the given approvers
+fn pass_governance(client: &MultiSigContractClient, signers: &Vec<BytesN<32>>, action: &GovernanceAction, approvals: u32) -> u64 {
+    let proposal_id = client.propose_governance(&signers.get_unchecked(0), action, &3600);
+    for i in 0..approvals {
+        client.approve_governance(&proposal_id, &signers.get_unchecked(i));
+    }
+    client.execute_governance(&proposal_id);
+    proposal_id
+}
+
+#[test]
+fn test_freeze_and_unfreeze_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    assert!(!client.is_token_frozen(&token_address));
+
+    let freeze_id = pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::FreezeToken(token_address.clone())), 2);
+    assert!(client.is_token_frozen(&token_address));
+    assert!(client.is_governance_executed(&freeze_id));
+    assert!(client.get_governance_proposal(&freeze_id).executed);
+
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::UnfreezeToken(token_address.clone())), 2);
+    assert!(!client.is_token_frozen(&token_address));
+}
+
+#[test]
+fn test_governance_approval_requires_approver_auth() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    let bob = Address::generate(&env);
+    client.initialize_with_addresses(&signers, &2, &false, &vec![&env, (signers.get_unchecked(1), bob.clone())]);
+
+    let token_address = Address::generate(&env);
+    let action = GovernanceAction::Treasury(TreasuryAction::FreezeToken(token_address.clone()));
+    env.mock_all_auths();
+    let proposal_id = client.propose_governance(&signers.get_unchecked(0), &action, &3600);
+    client.approve_governance(&proposal_id, &signers.get_unchecked(0));
+
+    // Naming a bound signer is not enough to approve for them
+    env.set_auths(&[]);
+    assert!(client.try_approve_governance(&proposal_id, &signers.get_unchecked(1)).is_err());
+    assert_eq!(
+        client.try_execute_governance(&proposal_id),
+        Err(Ok(GovernanceError::InsufficientGovernanceApprovals.into()))
+    );
+
+    env.mock_all_auths();
+    client.approve_governance(&proposal_id, &signers.get_unchecked(1));
+    assert_eq!(env.auths()[0].0, bob);
+    client.execute_governance(&proposal_id);
+    assert!(client.is_token_frozen(&token_address));
+}
+
+#[test]
+fn test_governance_needs_signer_accounts_under_plain_initialize() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    // No key is bound to an address, so each stands for its own account
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    env.mock_all_auths();
+    let usdc = create_funded_token(&env, &contract_id, 1000);
+    env.set_auths(&[]);
+
+    // Knowing the public signer keys is not enough to retire to yourself
+    let attacker = Address::generate(&env);
+    let retire = GovernanceAction::Policy(PolicyAction::Retire(Retirement { successor: attacker.clone(), tokens: vec![&env, usdc.clone()] }));
+    assert!(client.try_propose_governance(&signers.get_unchecked(0), &retire, &3600).is_err());
+
+    env.mock_all_auths();
+    let proposal_id = client.propose_governance(&signers.get_unchecked(0), &retire, &3600);
+    env.set_auths(&[]);
+    assert!(client.try_approve_governance(&proposal_id, &signers.get_unchecked(0)).is_err());
+    assert!(client.try_approve_governance(&proposal_id, &signers.get_unchecked(1)).is_err());
+    assert_eq!(client.try_execute_governance(&proposal_id), Err(Ok(GovernanceError::InsufficientGovernanceApprovals.into())));
+    assert_eq!(soroban_sdk::token::Client::new(&env, &usdc).balance(&attacker), 0);
+
+    // The signers' own accounts approve
+    env.mock_all_auths();
+    client.approve_governance(&proposal_id, &signers.get_unchecked(1));
+    assert_eq!(env.auths()[0].0, signer_account(&env, &signers.get_unchecked(1)));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #31)")]
+fn test_freeze_token_insufficient_approvals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::FreezeToken(token_address)), 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #32)")]
+fn test_create_proposal_frozen_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::FreezeToken(token_address.clone())), 2);
+
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+}
+
+#[test]
+fn test_frozen_token_blocks_execution_until_unfrozen() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = create_funded_token(&env, &contract_id, 5000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(2));
+
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::FreezeToken(token_address.clone())), 2);
+
+    // The approved proposal stays open but cannot execute
+    let result = client.try_execute_proposal(&proposal_id);
+    assert_eq!(result, Err(Ok(MultisigError::TokenFrozen.into())));
+    assert!(!client.is_proposal_executed(&proposal_id));
+    assert_eq!(client.get_proposal_approvals(&proposal_id).len(), 2);
+
+    // Unfreezing resumes it without re-approval
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::UnfreezeToken(token_address.clone())), 2);
+    client.execute_proposal(&proposal_id);
+
+    let token_client = soroban_sdk::token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&recipient), 1000);
+}
+
+#[test]
+fn test_execution_receipt_records_counted_approvers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &3, &false);
+
+    let token_address = create_funded_token(&env, &contract_id, 5000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+
+    client.delegate_vote(&proposal_id, &signers.get_unchecked(0), &signers.get_unchecked(1));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(2));
+    assert_eq!(client.get_execution_receipt(&proposal_id), None);
+
+    client.execute_proposal(&proposal_id);
+
+    let receipt = client.get_execution_receipt(&proposal_id).unwrap();
+    assert_eq!(receipt.proposal_id, proposal_id);
+    assert_eq!(receipt.approvals_at_execution, 3);
+    assert_eq!(receipt.approvers.len(), 3);
+    assert!(receipt.approvers.contains(signers.get_unchecked(0)));
+    assert!(receipt.approvers.contains(signers.get_unchecked(1)));
+    assert!(receipt.approvers.contains(signers.get_unchecked(2)));
+
+    // Approvals can no longer be revoked, so the history cannot be rewritten
+    let result = client.try_revoke_approval(&proposal_id, &signers.get_unchecked(1));
+    assert_eq!(result, Err(Ok(MultisigError::ProposalAlreadyExecuted.into())));
+    assert_eq!(client.get_execution_receipt(&proposal_id).unwrap(), receipt);
+}
+
+#[test]
+fn test_revoke_signer_change_approval() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let new_signer = BytesN::from_array(&env, &[99u8; 32]);
+    let add_type = SignerChangeKind::Add;
+    let proposal_id = client.propose_signer_change(&signers.get_unchecked(0), &add_type, &new_signer, &3600);
+
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(1));
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(2));
+
+    // Revoke approval from first signer
+    client.revoke_signer_change_approval(&proposal_id, &signers.get_unchecked(1));
+
+    let approvals = client.get_signer_change_approvals(&proposal_id);
+    assert_eq!(approvals.len(), 1);
+    assert_eq!(approvals.get_unchecked(0).signer, signers.get_unchecked(2));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_revoke_nonexistent_signer_change_approval() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let new_signer = BytesN::from_array(&env, &[99u8; 32]);
+    let proposal_id = client.propose_signer_change(&signers.get_unchecked(0), &SignerChangeKind::Add, &new_signer, &3600);
+
+    // Try to revoke approval that doesn't exist
+    client.revoke_signer_change_approval(&proposal_id, &signers.get_unchecked(1)); // Should fail
+}
+
+#[test]
+fn test_signer_changes_need_signer_accounts_under_plain_initialize() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    // Naming a signer's public key does not let an outsider propose themselves
+    let attacker = BytesN::from_array(&env, &[66u8; 32]);
+    let add = SignerChangeKind::Add;
+    assert!(client.try_propose_signer_change(&signers.get_unchecked(0), &add, &attacker, &3600).is_err());
+    assert_eq!(client.get_signer_change_count(), 0);
+    assert!(!client.is_signer(&attacker));
+
+    env.mock_all_auths();
+    let proposal_id = client.propose_signer_change(&signers.get_unchecked(0), &add, &attacker, &3600);
+    assert_eq!(env.auths()[0].0, signer_account(&env, &signers.get_unchecked(0)));
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(0));
+
+    // Nor approve it, or withdraw an approval, on a signer's behalf
+    env.set_auths(&[]);
+    assert!(client.try_approve_signer_change(&proposal_id, &signers.get_unchecked(1)).is_err());
+    assert!(client.try_revoke_signer_change_approval(&proposal_id, &signers.get_unchecked(0)).is_err());
+    assert_eq!(client.get_signer_change_approvals(&proposal_id).len(), 1);
+    assert_eq!(
+        client.try_execute_signer_change(&proposal_id),
+        Err(Ok(SignerError::InsufficientSignerChangeApprovals.into()))
+    );
+    assert!(!client.is_signer(&attacker));
+}
+
+#[test]
+fn test_revoked_signer_change_approval_no_longer_counts() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    let bob = Address::generate(&env);
+    client.initialize_with_addresses(&signers, &2, &false, &vec![&env, (signers.get_unchecked(1), bob.clone())]);
+
+    let new_signer = BytesN::from_array(&env, &[99u8; 32]);
+    env.mock_all_auths();
+    let proposal_id = client.propose_signer_change(&signers.get_unchecked(0), &SignerChangeKind::Add, &new_signer, &3600);
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(0));
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(1));
+    env.set_auths(&[]);
+
+    // Only the approver's own address may withdraw their approval
+    assert!(client.try_revoke_signer_change_approval(&proposal_id, &signers.get_unchecked(1)).is_err());
+    env.mock_all_auths();
+    client.revoke_signer_change_approval(&proposal_id, &signers.get_unchecked(1));
+    assert_eq!(env.auths()[0].0, bob);
+
+    assert_eq!(
+        client.try_execute_signer_change(&proposal_id),
+        Err(Ok(SignerError::InsufficientSignerChangeApprovals.into()))
+    );
+    assert!(!client.is_signer(&new_signer));
+}
+
+#[test]
+fn test_cancel_signer_change() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let proposer = signers.get_unchecked(0);
+    let new_signer = BytesN::from_array(&env, &[99u8; 32]);
+    let proposal_id = client.propose_signer_change(&proposer, &SignerChangeKind::Add, &new_signer, &3600);
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(0));
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(1));
+
+    assert_eq!(
+        client.try_cancel_signer_change(&proposal_id, &signers.get_unchecked(1)),
+        Err(Ok(MultisigError::NotProposer.into()))
+    );
+    env.set_auths(&[]);
+    assert!(client.try_cancel_signer_change(&proposal_id, &proposer).is_err());
+    env.mock_all_auths();
+    client.cancel_signer_change(&proposal_id, &proposer);
+    let data: Map<Symbol, Val> = map![&env, (Symbol::new(&env, "proposer"), proposer.into_val(&env))];
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (contract_id.clone(), (Symbol::new(&env, "signer_change_canceled"), proposal_id).into_val(&env), data.into_val(&env))]
+    );
+    assert_eq!(client.get_proposal_outcome(&proposal_id).unwrap().outcome, ProposalOutcome::Canceled);
+
+    // Closed for good, approvals and all
+    assert_eq!(
+        client.try_approve_signer_change(&proposal_id, &signers.get_unchecked(2)),
+        Err(Ok(MultisigError::ProposalCanceled.into()))
+    );
+    assert_eq!(client.try_execute_signer_change(&proposal_id), Err(Ok(MultisigError::ProposalCanceled.into())));
+    assert_eq!(client.try_cancel_signer_change(&proposal_id, &proposer), Err(Ok(MultisigError::ProposalCanceled.into())));
+    assert!(!client.is_signer(&new_signer));
+}
+
+#[test]
+fn test_cancel_after_execution_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let proposer = signers.get_unchecked(0);
+    let new_signer = BytesN::from_array(&env, &[99u8; 32]);
+    let change_id = client.propose_signer_change(&proposer, &SignerChangeKind::Add, &new_signer, &3600);
+    client.approve_signer_change(&change_id, &signers.get_unchecked(0));
+    client.approve_signer_change(&change_id, &signers.get_unchecked(1));
+    client.execute_signer_change(&change_id);
+    assert_eq!(
+        client.try_cancel_signer_change(&change_id, &proposer),
+        Err(Ok(SignerError::SignerChangeAlreadyExecuted.into()))
+    );
+
+    let token_address = create_funded_token(&env, &contract_id, 5000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&proposer, &token_address, &recipient, &1000, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    client.execute_proposal(&proposal_id);
+    assert_eq!(
+        client.try_cancel_proposal(&proposal_id, &proposer),
+        Err(Ok(MultisigError::ProposalAlreadyExecuted.into()))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #14)")]
+fn test_revoke_approval_after_execution() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = create_funded_token(&env, &contract_id, 5000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(2));
+    client.execute_proposal(&proposal_id);
+
+    client.revoke_approval(&proposal_id, &signers.get_unchecked(1));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #20)")]
+fn test_revoke_signer_change_approval_after_execution() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let new_signer = BytesN::from_array(&env, &[99u8; 32]);
+    let add_type = SignerChangeKind::Add;
+    let proposal_id = client.propose_signer_change(&signers.get_unchecked(0), &add_type, &new_signer, &3600);
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(1));
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(2));
+    client.execute_signer_change(&proposal_id);
+
+    client.revoke_signer_change_approval(&proposal_id, &signers.get_unchecked(1));
+}
+
+#[test]
+fn test_get_proposals_pagination() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    for _ in 0..5 {
+        client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+    }
+
+    let first = client.get_proposals(&0, &2);
+    assert_eq!(first.proposals.len(), 2);
+    assert_eq!(first.proposals.get_unchecked(0).id, 1);
+    assert!(first.has_more);
+
+    let second = client.get_proposals(&first.next_cursor, &2);
+    assert_eq!(second.proposals.get_unchecked(0).id, 3);
+    assert!(second.has_more);
+
+    let third = client.get_proposals(&second.next_cursor, &2);
+    assert_eq!(third.proposals.len(), 1);
+    assert_eq!(third.proposals.get_unchecked(0).id, 5);
+    assert!(!third.has_more);
+
+    // Reading past the end returns an empty, final page
+    let past_end = client.get_proposals(&third.next_cursor, &2);
+    assert_eq!(past_end.proposals.len(), 0);
+    assert!(!past_end.has_more);
+}
+
+#[test]
+fn test_get_proposals_max_page_size() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let page = client.get_proposals(&0, &50);
+    assert_eq!(page.proposals.len(), 0);
+
+    let result = client.try_get_proposals(&0, &51);
+    assert_eq!(result, Err(Ok(MultisigError::PageTooLarge.into())));
+}
+
+#[test]
+fn test_allowance_proposal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = create_funded_token(&env, &contract_id, 5000);
+    let spender = Address::generate(&env);
+    let action = ProposalAction::Approve(AllowanceAction {
+        token_address: token_address.clone(),
+        spender: spender.clone(),
+        amount: 700,
+        expiration_ledger: 1000,
+    });
+    let reason = String::from_str(&env, "Allowance for payroll processor");
+    let proposal_id = client.create_proposal_v2(&signers.get_unchecked(0), &action, &reason, &3600);
+    assert_eq!(client.get_proposal(&proposal_id).action, action);
+
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(2));
+    client.execute_proposal(&proposal_id);
+
+    let token_client = soroban_sdk::token::Client::new(&env, &token_address);
+    assert_eq!(token_client.allowance(&contract_id, &spender), 700);
+}
+
+#[test]
+fn test_invoke_proposal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = create_funded_token(&env, &contract_id, 5000);
+    let recipient = Address::generate(&env);
+    let action = ProposalAction::Invoke(InvokeAction {
+        contract: token_address.clone(),
+        function: Symbol::new(&env, "transfer"),
+        args: vec![&env, contract_id.into_val(&env), recipient.into_val(&env), 250i128.into_val(&env)],
+    });
+    let reason = String::from_str(&env, "Generic call");
+    let proposal_id = client.create_proposal_v2(&signers.get_unchecked(0), &action, &reason, &3600);
+
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(2));
+    client.execute_proposal(&proposal_id);
+
+    let token_client = soroban_sdk::token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&recipient), 250);
+}
+
+#[test]
+fn test_migrate_legacy_proposals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+
+    // Proposal 1 uses the current layout, proposal 2 the pre-action one
+    client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+    let legacy = LegacyProposal {
+        id: 2,
+        proposer: signers.get_unchecked(0),
+        token_address: token_address.clone(),
+        recipient: recipient.clone(),
+        amount: 500,
+        reason: reason.clone(),
+        created_at: 0,
+        expires_at: 3600,
+        executed: false,
+    };
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&DataKey::Proposal(2), &legacy);
+        env.storage().instance().set(&DataKey::ProposalCount, &2u64);
+    });
+
+    let next_cursor = client.migrate_proposals(&0, &10);
+    assert!(next_cursor > client.get_last_proposal_id());
+
+    let migrated = client.get_proposal(&2);
+    assert_eq!(
+        migrated.action,
+        ProposalAction::Transfer(TransferAction {
+            token_address: token_address.clone(),
+            recipient: recipient.clone(),
+            amount: 500,
+        })
+    );
+    assert_eq!(migrated.reason, reason);
+    assert_eq!(client.get_proposal(&1).id, 1);
+}
+
+#[test]
+fn test_validate_expiry_bounds() {
+    let env = Env::default();
+
+    validation::validate_expiry(&env, validation::MIN_EXPIRY_SECONDS);
+    validation::validate_expiry(&env, validation::MAX_EXPIRY_SECONDS);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #24)")]
+fn test_validate_expiry_below_minimum() {
+    let env = Env::default();
+    validation::validate_expiry(&env, validation::MIN_EXPIRY_SECONDS - 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #24)")]
+fn test_validate_expiry_above_maximum() {
+    let env = Env::default();
+    validation::validate_expiry(&env, validation::MAX_EXPIRY_SECONDS + 1);
+}
+
+#[test]
+fn test_check_amount_boundary() {
+    assert_eq!(validation::check_amount(1), Ok(()));
+    assert_eq!(validation::check_amount(validation::MAX_AMOUNT), Ok(()));
+    assert_eq!(validation::check_amount(i128::MAX), Err(MultisigError::AmountTooLarge));
+    assert_eq!(validation::check_amount(0), Err(MultisigError::InvalidProposal));
+    assert_eq!(validation::check_amount(-1), Err(MultisigError::InvalidProposal));
+}
+
+#[test]
+fn test_validate_security_floor() {
+    let env = Env::default();
+    validation::validate_security_floor(&env, 2, 2, false);
+    validation::validate_security_floor(&env, 1, 1, true);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #36)")]
+fn test_validate_security_floor_single_signer() {
+    let env = Env::default();
+    validation::validate_security_floor(&env, 1, 2, false);
+}
+
+#[test]
+fn test_require_signer() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 2);
+    client.initialize(&signers, &2, &false);
+
+    env.as_contract(&contract_id, || {
+        validation::require_signer(&env, &signers.get_unchecked(0));
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #9)")]
+fn test_require_signer_unknown_key() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 2);
+    client.initialize(&signers, &2, &false);
+
+    let unknown = BytesN::from_array(&env, &[99u8; 32]);
+    env.as_contract(&contract_id, || {
+        validation::require_signer(&env, &unknown);
+    });
+}
+
+fn fingerprint_event(env: &Env, contract_id: &Address, fingerprint: &BytesN<32>) -> (Address, Vec<Val>, Val) {
+    let data: Map<Symbol, Val> = map![env, (Symbol::new(env, "fingerprint"), fingerprint.into_val(env))];
+    (contract_id.clone(), (Symbol::new(env, "config_fingerprint_changed"),).into_val(env), data.into_val(env))
+}
+
+fn readiness_event(env: &Env, contract_id: &Address, name: &str, proposal_id: u64, required: u32, approvals: u32) -> (Address, Vec<Val>, Val) {
+    let data: Map<Symbol, Val> = map![
+        env,
+        (Symbol::new(env, "approvals"), approvals.into_val(env)),
+        (Symbol::new(env, "required"), required.into_val(env)),
+    ];
+    (contract_id.clone(), (Symbol::new(env, name), proposal_id).into_val(env), data.into_val(env))
+}
+
+#[test]
+fn test_approval_emits_ready_when_threshold_crossed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    assert_eq!(env.events().all(), vec![&env]);
+    assert!(!client.get_approval_progress(&proposal_id).ready);
+
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    assert_eq!(
+        env.events().all(),
+        vec![&env, readiness_event(&env, &contract_id, "proposal_ready", proposal_id, 2, 2)]
+    );
+
+    // Approvals past the requirement do not re-emit
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(2));
+    assert_eq!(env.events().all(), vec![&env]);
+
+    let progress = client.get_approval_progress(&proposal_id);
+    assert_eq!(progress, ApprovalProgress { approvals: 3, required: 2, roles: Vec::new(&env), ready: true, stale_approvals: 0 });
+}
+
+#[test]
+fn test_revocation_emits_unready_when_dropping_below() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+
+    client.revoke_approval(&proposal_id, &signers.get_unchecked(1));
+    assert_eq!(
+        env.events().all(),
+        vec![&env, readiness_event(&env, &contract_id, "proposal_unready", proposal_id, 2, 1)]
+    );
+    assert_eq!(
+        client.get_approval_progress(&proposal_id),
+        ApprovalProgress { approvals: 1, required: 2, roles: Vec::new(&env), ready: false, stale_approvals: 0 }
+    );
+}
+
+#[test]
+fn test_delegation_can_make_proposal_ready() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.delegate_vote(&proposal_id, &signers.get_unchecked(1), &signers.get_unchecked(0));
+    assert_eq!(
+        env.events().all(),
+        vec![&env, readiness_event(&env, &contract_id, "proposal_ready", proposal_id, 2, 2)]
+    );
+
+    client.revoke_vote_delegation(&proposal_id, &signers.get_unchecked(1));
+    assert_eq!(
+        env.events().all(),
+        vec![&env, readiness_event(&env, &contract_id, "proposal_unready", proposal_id, 2, 1)]
+    );
+}
+
+#[test]
+fn test_feature_flags_toggled_by_governance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let delegation = Symbol::new(&env, "delegate");
+    assert!(client.is_feature_enabled(&delegation));
+    assert_eq!(client.get_enabled_features(), vec![&env, delegation.clone()]);
+
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::DisableFeature(delegation.clone())), 2);
+    assert!(!client.is_feature_enabled(&delegation));
+    assert_eq!(client.get_enabled_features(), Vec::new(&env));
+
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::EnableFeature(delegation.clone())), 2);
+    assert!(client.is_feature_enabled(&delegation));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #34)")]
+fn test_propose_unknown_feature() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let action = GovernanceAction::Policy(PolicyAction::EnableFeature(Symbol::new(&env, "teleport")));
+    client.propose_governance(&signers.get_unchecked(0), &action, &3600);
+}
+
+#[test]
+fn test_disabled_delegation_is_not_counted() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+
+    client.delegate_vote(&proposal_id, &signers.get_unchecked(1), &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    assert!(client.get_approval_progress(&proposal_id).ready);
+
+    let delegation = Symbol::new(&env, "delegate");
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::DisableFeature(delegation)), 2);
+    assert_eq!(client.get_approval_progress(&proposal_id).approvals, 1);
+
+    // The former delegator may vote directly again, but may not delegate anew
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    let result = client.try_delegate_vote(&proposal_id, &signers.get_unchecked(2), &signers.get_unchecked(0));
+    assert_eq!(result, Err(Ok(GovernanceError::FeatureDisabled.into())));
+}
+
+#[contract]
+pub struct FailingWatcher;
+
+#[contractimpl]
+impl FailingWatcher {
+    pub fn on_proposal_executed(_env: Env, _proposal_id: u64, _token: Address, _recipient: Address, _amount: i128) {
+        panic!("watcher failure");
+    }
+}
+
+#[test]
+fn test_watchers_notified_and_isolated_on_execution() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = create_funded_token(&env, &contract_id, 5000);
+    let failing = env.register(FailingWatcher, ());
+    let watcher = env.register(PaymentWatcher, (&contract_id,));
+
+    for registered in [failing.clone(), watcher.clone()] {
+        let registration = WatcherRegistration { token: token_address.clone(), watcher: registered };
+        pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::AddWatcher(registration)), 2);
+    }
+    assert_eq!(client.get_token_watchers(&token_address), vec![&env, failing.clone(), watcher.clone()]);
+
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payroll");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    client.execute_proposal(&proposal_id);
+
+    // The failing watcher neither reverted the payment nor starved the next one
+    assert!(client.is_proposal_executed(&proposal_id));
+    assert_eq!(soroban_sdk::token::Client::new(&env, &token_address).balance(&recipient), 1000);
+
+    let payments = PaymentWatcherClient::new(&env, &watcher).payments();
+    assert_eq!(
+        payments,
+        vec![&env, ExecutedPayment { proposal_id, token: token_address, recipient, amount: 1000 }]
+    );
+}
+
+#[test]
+fn test_watcher_registration_management() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let registration = WatcherRegistration { token: Address::generate(&env), watcher: Address::generate(&env) };
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::AddWatcher(registration.clone())), 2);
+
+    let duplicate = client.propose_governance(&signers.get_unchecked(0), &GovernanceAction::Treasury(TreasuryAction::AddWatcher(registration.clone())), &3600);
+    client.approve_governance(&duplicate, &signers.get_unchecked(0));
+    client.approve_governance(&duplicate, &signers.get_unchecked(1));
+    assert_eq!(client.try_execute_governance(&duplicate), Err(Ok(GovernanceError::WatcherAlreadyRegistered.into())));
+
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::RemoveWatcher(registration.clone())), 2);
+    assert_eq!(client.get_token_watchers(&registration.token), Vec::new(&env));
+
+    let missing = client.propose_governance(&signers.get_unchecked(0), &GovernanceAction::Treasury(TreasuryAction::RemoveWatcher(registration)), &3600);
+    client.approve_governance(&missing, &signers.get_unchecked(0));
+    client.approve_governance(&missing, &signers.get_unchecked(1));
+    assert_eq!(client.try_execute_governance(&missing), Err(Ok(GovernanceError::WatcherNotFound.into())));
+}
+
+// Five signers: 0 and 1 in finance, 2 executive, 3 and 4 in operations;
+// payments need 2 finance and 1 executive approval on top of the threshold.
+fn setup_staged_committee(env: &Env, client: &MultiSigContractClient) -> Vec<BytesN<32>> {
+    let signers = create_test_signers(env, 5);
+    client.initialize(&signers, &2, &false);
+
+    let roles = ["finance", "finance", "exec", "ops", "ops"];
+    for (i, role) in roles.iter().enumerate() {
+        let assignment = SignerRoleAssignment { signer: signers.get_unchecked(i as u32), role: Symbol::new(env, role) };
+        pass_governance(client, &signers, &GovernanceAction::Signers(SignerAction::SetSignerRole(assignment)), 2);
+    }
+
+    let requirements = vec![
+        env,
+        RoleRequirement { role: Symbol::new(env, "finance"), count: 2 },
+        RoleRequirement { role: Symbol::new(env, "exec"), count: 1 },
+    ];
+    pass_governance(client, &signers, &GovernanceAction::Signers(SignerAction::SetRoleRequirements(requirements)), 2);
+    pass_governance(client, &signers, &GovernanceAction::Policy(PolicyAction::EnableFeature(Symbol::new(env, "staged"))), 2);
+
+    signers
+}
+
+#[test]
+fn test_staged_approval_requires_every_role_bucket() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+    let signers = setup_staged_committee(&env, &client);
+
+    let token_address = create_funded_token(&env, &contract_id, 5000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Vendor invoice");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+
+    // Threshold met by finance and ops, but the executive has not signed off
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(3));
+
+    let progress = client.get_approval_progress(&proposal_id);
+    assert_eq!(progress.approvals, 3);
+    assert!(!progress.ready);
+    assert_eq!(
+        progress.roles,
+        vec![
+            &env,
+            RoleProgress { role: Symbol::new(&env, "finance"), approvals: 2, required: 2 },
+            RoleProgress { role: Symbol::new(&env, "exec"), approvals: 0, required: 1 },
+        ]
+    );
+    assert_eq!(client.try_execute_proposal(&proposal_id), Err(Ok(MultisigError::RoleRequirementNotMet.into())));
+
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(2));
+    assert!(client.get_approval_progress(&proposal_id).ready);
+    assert_eq!(client.get_approval_roles(&proposal_id).get(signers.get_unchecked(2)), Some(Symbol::new(&env, "exec")));
+
+    client.execute_proposal(&proposal_id);
+    assert!(client.is_proposal_executed(&proposal_id));
+}
+
+#[test]
+fn test_staged_approval_revalidates_roles_at_execution() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+    let signers = setup_staged_committee(&env, &client);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Vendor invoice");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(2));
+    assert!(client.get_approval_progress(&proposal_id).ready);
+
+    // Moving a finance approver to operations voids their finance approval
+    let assignment = SignerRoleAssignment { signer: signers.get_unchecked(1), role: Symbol::new(&env, "ops") };
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::SetSignerRole(assignment)), 2);
+
+    let progress = client.get_approval_progress(&proposal_id);
+    assert!(!progress.ready);
+    assert_eq!(progress.roles.get_unchecked(0).approvals, 1);
+    assert_eq!(client.try_execute_proposal(&proposal_id), Err(Ok(MultisigError::RoleRequirementNotMet.into())));
+}
+
+#[test]
+fn test_role_requirements_ignored_when_staged_disabled() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+    let signers = setup_staged_committee(&env, &client);
+
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::DisableFeature(Symbol::new(&env, "staged"))), 2);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Vendor invoice");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(3));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(4));
+
+    let progress = client.get_approval_progress(&proposal_id);
+    assert!(progress.ready);
+    assert_eq!(progress.roles, Vec::new(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn test_role_requirements_reject_duplicate_roles() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let finance = Symbol::new(&env, "finance");
+    let requirements = vec![
+        &env,
+        RoleRequirement { role: finance.clone(), count: 1 },
+        RoleRequirement { role: finance, count: 2 },
+    ];
+    client.propose_governance(&signers.get_unchecked(0), &GovernanceAction::Signers(SignerAction::SetRoleRequirements(requirements)), &3600);
+}
+
+fn propose_removal(client: &MultiSigContractClient, signers: &Vec<BytesN<32>>, target: &BytesN<32>, approvals: u32) -> u64 {
+    let proposal_id = client.propose_signer_change(&signers.get_unchecked(1), &SignerChangeKind::Remove, target, &3600);
+    for i in 0..approvals {
+        client.approve_signer_change(&proposal_id, &signers.get_unchecked(i));
+    }
+    proposal_id
+}
+
+#[test]
+fn test_protected_signer_removal_needs_elevated_quorum() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 5);
+    client.initialize(&signers, &2, &false);
+
+    let founder = signers.get_unchecked(0);
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::ProtectSigner(founder.clone())), 5);
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::SetProtectedQuorum(4)), 5);
+    assert!(client.is_protected_signer(&founder));
+    assert_eq!(client.get_protected_quorum(), 4);
+
+    // Two approvals remove an ordinary member...
+    let ordinary = propose_removal(&client, &signers, &signers.get_unchecked(4), 2);
+    assert_eq!(client.get_signer_change_quorum(&ordinary), 2);
+    client.execute_signer_change(&ordinary);
+    assert!(!client.is_signer(&signers.get_unchecked(4)));
+
+    // ...but not the founder
+    let protected = propose_removal(&client, &signers, &founder, 2);
+    assert_eq!(client.get_signer_change_quorum(&protected), 4);
+    assert_eq!(
+        client.try_execute_signer_change(&protected),
+        Err(Ok(SignerError::InsufficientSignerChangeApprovals.into()))
+    );
+
+    client.approve_signer_change(&protected, &signers.get_unchecked(2));
+    client.approve_signer_change(&protected, &signers.get_unchecked(3));
+    client.execute_signer_change(&protected);
+    assert!(!client.is_signer(&founder));
+    assert!(!client.is_protected_signer(&founder));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #31)")]
+fn test_protect_signer_needs_elevated_quorum() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::ProtectSigner(signers.get_unchecked(0))), 2);
+}
+
+#[test]
+fn test_outcome_recorded_on_execution() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = create_funded_token(&env, &contract_id, 5000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+    assert_eq!(client.get_proposal_outcome(&proposal_id), None);
+
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    client.execute_proposal(&proposal_id);
+
+    let record = client.get_proposal_outcome(&proposal_id).unwrap();
+    assert_eq!(record.outcome, ProposalOutcome::Executed);
+    assert_eq!(record.actor, None);
+
+    let freeze_id = pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::FreezeToken(token_address)), 2);
+    assert_eq!(client.get_proposal_outcome(&freeze_id).unwrap().outcome, ProposalOutcome::Executed);
+}
+
+#[test]
+fn test_expire_proposal_closes_it() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+
+    assert_eq!(client.try_expire_proposal(&proposal_id), Err(Ok(MultisigError::ProposalNotExpired.into())));
+
+    env.ledger().with_mut(|li| li.timestamp += 3601);
+    client.expire_proposal(&proposal_id);
+
+    let record = client.get_proposal_outcome(&proposal_id).unwrap();
+    assert_eq!(record.outcome, ProposalOutcome::Expired);
+    assert_eq!(record.timestamp, env.ledger().timestamp());
+
+    // A closed proposal takes no further changes
+    let result = client.try_revoke_approval(&proposal_id, &signers.get_unchecked(0));
+    assert_eq!(result, Err(Ok(MultisigError::ProposalClosed.into())));
+    assert_eq!(client.try_expire_proposal(&proposal_id), Err(Ok(MultisigError::ProposalClosed.into())));
+}
+
+#[test]
+fn test_get_proposals_created_between() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+
+    // Two proposals on each of days 10, 11 and 12, with a governance
+    // proposal in between that must not show up
+    let day = 86_400u64;
+    let mut ids = Vec::new(&env);
+    for d in 10..13u64 {
+        env.ledger().with_mut(|li| li.timestamp = d * day + 100);
+        ids.push_back(client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600));
+        client.propose_governance(&signers.get_unchecked(0), &GovernanceAction::Treasury(TreasuryAction::FreezeToken(token_address.clone())), &3600);
+        env.ledger().with_mut(|li| li.timestamp = d * day + 5000);
+        ids.push_back(client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600));
+    }
+
+    let ids_of = |page: &ProposalPage| {
+        let mut out = Vec::new(&env);
+        for p in page.proposals.iter() {
+            out.push_back(p.id);
+        }
+        out
+    };
+
+    // Whole of day 11
+    let page = client.get_proposals_created_between(&(11 * day), &(12 * day - 1), &0, &10);
+    assert_eq!(ids_of(&page), vec![&env, ids.get_unchecked(2), ids.get_unchecked(3)]);
+    assert!(!page.has_more);
+
+    // Range boundaries inside a bucket are honoured
+    let page = client.get_proposals_created_between(&(10 * day + 1000), &(12 * day + 100), &0, &10);
+    assert_eq!(
+        ids_of(&page),
+        vec![&env, ids.get_unchecked(1), ids.get_unchecked(2), ids.get_unchecked(3), ids.get_unchecked(4)]
+    );
+
+    // Paging across buckets
+    let first = client.get_proposals_created_between(&0, &(20 * day), &0, &4);
+    assert_eq!(first.proposals.len(), 4);
+    assert!(first.has_more);
+    let second = client.get_proposals_created_between(&0, &(20 * day), &first.next_cursor, &4);
+    assert_eq!(ids_of(&second), vec![&env, ids.get_unchecked(4), ids.get_unchecked(5)]);
+    assert!(!second.has_more);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #33)")]
+fn test_get_proposals_created_between_range_too_wide() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    client.get_proposals_created_between(&0, &(400 * 86_400), &0, &10);
+}
+
+#[test]
+fn test_creation_index_spans_chunks() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+
+    // Enough proposals in one day to need three chunks
+    let count = index::CHUNK_SIZE as u64 * 2 + 6;
+    for _ in 0..count {
+        client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+    }
+
+    let key = IndexKey::ProposalsByDay(0);
+    env.as_contract(&contract_id, || {
+        assert_eq!(env.storage().persistent().get::<_, u32>(&DataKey::IndexChunkCount(key.clone())), Some(3));
+        assert!(!env.storage().instance().has(&DataKey::IndexChunk(key.clone(), 0)));
+    });
+
+    let mut seen = Vec::new(&env);
+    let mut cursor = 0;
+    loop {
+        let page = client.get_proposals_created_between(&0, &86_399, &cursor, &50);
+        for proposal in page.proposals.iter() {
+            seen.push_back(proposal.id);
+        }
+        cursor = page.next_cursor;
+        if !page.has_more {
+            break;
+        }
+    }
+    assert_eq!(seen.len() as u64, count);
+    for (i, id) in seen.iter().enumerate() {
+        assert_eq!(id, i as u64 + 1);
+    }
+}
+
+#[test]
+fn test_open_proposals_skip_closed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+
+    // The first 40 expire quickly, emptying the whole first chunk and part of
+    // the second; the last 30 stay open
+    for i in 0..70u64 {
+        let expires_in = if i < 40 { 3600 } else { 86_400 };
+        client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &expires_in);
+    }
+    env.ledger().with_mut(|li| li.timestamp = 4000);
+    for id in 1..=40u64 {
+        client.expire_proposal(&id);
+    }
+
+    let first = client.get_open_proposals(&0, &25);
+    assert_eq!(first.proposals.len(), 25);
+    assert_eq!(first.proposals.get_unchecked(0).id, 41);
+    assert!(first.has_more);
+
+    let second = client.get_open_proposals(&first.next_cursor, &25);
+    assert_eq!(second.proposals.len(), 5);
+    assert_eq!(second.proposals.get_unchecked(0).id, 66);
+    assert!(!second.has_more);
+
+    // New proposals land at the end
+    let newest = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+    let last = client.get_open_proposals(&second.next_cursor, &25);
+    assert_eq!(last.proposals.len(), 1);
+    assert_eq!(last.proposals.get_unchecked(0).id, newest);
+}
+
+#[test]
+fn test_index_reuses_emptied_tail_chunk() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let key = IndexKey::OpenProposals;
+
+    env.as_contract(&contract_id, || {
+        for id in 1..=(index::CHUNK_SIZE as u64 + 1) {
+            index::append(&env, &key, id);
+        }
+        assert_eq!(storage::read_index_chunk_count(&env, &key), 2);
+
+        assert!(index::remove(&env, &key, index::CHUNK_SIZE as u64 + 1));
+        assert!(!index::remove(&env, &key, index::CHUNK_SIZE as u64 + 1));
+        assert_eq!(storage::read_index_chunk_count(&env, &key), 1);
+
+        // Removing from the middle keeps the order of what is left
+        assert!(index::remove(&env, &key, 5));
+        index::append(&env, &key, 100);
+        let mut ids = Vec::new(&env);
+        for id in index::iter(&env, &key) {
+            ids.push_back(id);
+        }
+        assert_eq!(ids.len(), index::CHUNK_SIZE);
+        assert_eq!(ids.slice(3..5), vec![&env, 4, 6]);
+        assert_eq!(ids.last(), Some(100));
+        assert_eq!(storage::read_index_chunk_count(&env, &key), 1);
+    });
+}
+
+#[test]
+fn test_index_inserts_older_ids_in_order() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let key = IndexKey::Obligations;
+
+    env.as_contract(&contract_id, || {
+        // Even ids fill the first chunk and start the second
+        for id in 1..=(index::CHUNK_SIZE as u64 + 1) {
+            index::append(&env, &key, id * 2);
+        }
+        assert_eq!(storage::read_index_chunk_count(&env, &key), 2);
+
+        // An older id squeezes into the full first chunk, pushing its largest
+        // id into the second; repeating it changes nothing
+        index::append(&env, &key, 3);
+        index::append(&env, &key, 3);
+        assert_eq!(storage::read_index_chunk(&env, &key, 0).len(), index::CHUNK_SIZE);
+        assert_eq!(storage::read_index_chunk(&env, &key, 1), vec![&env, index::CHUNK_SIZE as u64 * 2, index::CHUNK_SIZE as u64 * 2 + 2]);
+
+        let mut previous = 0;
+        let mut count = 0;
+        for id in index::iter(&env, &key) {
+            assert!(id > previous);
+            previous = id;
+            count += 1;
+        }
+        assert_eq!(count, index::CHUNK_SIZE + 2);
+        assert!(index::remove(&env, &key, 3));
+        assert!(index::remove(&env, &key, index::CHUNK_SIZE as u64 * 2));
+    });
+}
+
+#[test]
+fn test_open_proposal_pages_survive_closing_mid_iteration() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+
+    // Proposals 1, 3, 4 and 8 expire early
+    for id in 1..=10u64 {
+        let expires_in = if [1, 3, 4, 8].contains(&id) { 3600 } else { 86_400 };
+        client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &expires_in);
+    }
+    env.ledger().with_mut(|li| li.timestamp = 4000);
+
+    let mut seen: Vec<u64> = Vec::new(&env);
+    let first = client.get_open_proposals(&0, &3);
+    for proposal in first.proposals.iter() {
+        seen.push_back(proposal.id);
+    }
+
+    // The last id returned, the next one due and an earlier one all close
+    // before the next page is read
+    client.expire_proposal(&3);
+    client.expire_proposal(&4);
+    client.expire_proposal(&1);
+    let second = client.get_open_proposals(&first.next_cursor, &3);
+    for proposal in second.proposals.iter() {
+        seen.push_back(proposal.id);
+    }
+
+    client.expire_proposal(&8);
+    let newest = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &86_400);
+    let third = client.get_open_proposals(&second.next_cursor, &3);
+    for proposal in third.proposals.iter() {
+        seen.push_back(proposal.id);
+    }
+    assert!(!third.has_more);
+
+    assert_eq!(seen, vec![&env, 1, 2, 3, 5, 6, 7, 9, 10, newest]);
+    let last = client.get_open_proposals(&third.next_cursor, &3);
+    assert_eq!((last.proposals.len(), last.next_cursor, last.has_more), (0, third.next_cursor, false));
+}
+
+#[test]
+fn test_created_between_pages_imported_proposals_in_day_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 5 * 86_400);
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let reason = String::from_str(&env, "Payment");
+    let native = client.create_proposal(&signers.get_unchecked(0), &token, &Address::generate(&env), &100, &reason, &3600);
+    let later = client.create_proposal(&signers.get_unchecked(0), &token, &Address::generate(&env), &100, &reason, &3600);
+
+    // Imported last, but created two days before the others
+    let mut legacy = legacy_proposal(&env, 7, &signers.get_unchecked(1), &token, Vec::new(&env));
+    legacy.created_at = 3 * 86_400;
+    let imported = client.import_proposal(&legacy);
+
+    let mut seen: Vec<u64> = Vec::new(&env);
+    let mut cursor = 0;
+    loop {
+        let page = client.get_proposals_created_between(&0, &(6 * 86_400), &cursor, &1);
+        for proposal in page.proposals.iter() {
+            seen.push_back(proposal.id);
+        }
+        cursor = page.next_cursor;
+        if !page.has_more {
+            break;
+        }
+    }
+    assert_eq!(seen, vec![&env, imported, native, later]);
+}
+
+// Stand-in for a hostile invocation target. `drain` tries to spend the
+// multisig's tokens in its name; `reenter` tries to call back into it.
+#[contract]
+pub struct MaliciousTarget;
+
+#[contractimpl]
+impl MaliciousTarget {
+    pub fn drain(env: Env, token: Address, multisig: Address, to: Address, amount: i128) {
+        soroban_sdk::token::Client::new(&env, &token).transfer(&multisig, &to, &amount);
+    }
+
+    pub fn reenter(env: Env, multisig: Address, proposal_id: u64) {
+        MultiSigContractClient::new(&env, &multisig).execute_signer_change(&proposal_id);
+    }
+}
+
+#[test]
+fn test_invoke_proposal_cannot_target_self() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    // Push a signer change through the payment quorum instead of its own vote
+    let new_signer = BytesN::from_array(&env, &[99u8; 32]);
+    let add_type = SignerChangeKind::Add;
+    let change_id = client.propose_signer_change(&signers.get_unchecked(0), &add_type, &new_signer, &3600);
+
+    let action = ProposalAction::Invoke(InvokeAction {
+        contract: contract_id.clone(),
+        function: Symbol::new(&env, "execute_signer_change"),
+        args: vec![&env, change_id.into_val(&env)],
+    });
+    let reason = String::from_str(&env, "Bypass");
+    let result = client.try_create_proposal_v2(&signers.get_unchecked(0), &action, &reason, &3600);
+    assert_eq!(result, Err(Ok(MultisigError::SelfInvocationForbidden.into())));
+}
+
+#[test]
+fn test_invoke_target_gets_no_nested_authorization() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    env.mock_all_auths();
+    let token_address = create_funded_token(&env, &contract_id, 5000);
+
+    let target = env.register(MaliciousTarget, ());
+    let thief = Address::generate(&env);
+    let action = ProposalAction::Invoke(InvokeAction {
+        contract: target,
+        function: Symbol::new(&env, "drain"),
+        args: vec![
+            &env,
+            token_address.into_val(&env),
+            contract_id.into_val(&env),
+            thief.into_val(&env),
+            5000i128.into_val(&env),
+        ],
+    });
+    let reason = String::from_str(&env, "Looks harmless");
+    let proposal_id = client.create_proposal_v2(&signers.get_unchecked(0), &action, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+
+    // Back to enforced auth: only what the contracts themselves authorize
+    env.set_auths(&[]);
+    assert!(client.try_execute_proposal(&proposal_id).is_err());
+    assert!(!client.is_proposal_executed(&proposal_id));
+    assert_eq!(soroban_sdk::token::Client::new(&env, &token_address).balance(&thief), 0);
+}
+
+#[test]
+fn test_invoke_target_cannot_reenter() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let new_signer = BytesN::from_array(&env, &[99u8; 32]);
+    let add_type = SignerChangeKind::Add;
+    let change_id = client.propose_signer_change(&signers.get_unchecked(0), &add_type, &new_signer, &3600);
+    client.approve_signer_change(&change_id, &signers.get_unchecked(0));
+    client.approve_signer_change(&change_id, &signers.get_unchecked(1));
+
+    let target = env.register(MaliciousTarget, ());
+    let action = ProposalAction::Invoke(InvokeAction {
+        contract: target,
+        function: Symbol::new(&env, "reenter"),
+        args: vec![&env, contract_id.into_val(&env), change_id.into_val(&env)],
+    });
+    let reason = String::from_str(&env, "Looks harmless");
+    let proposal_id = client.create_proposal_v2(&signers.get_unchecked(0), &action, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+
+    assert!(client.try_execute_proposal(&proposal_id).is_err());
+    assert!(!client.is_proposal_executed(&proposal_id));
+    assert!(!client.is_signer_change_executed(&change_id));
+}
+
+// Token whose `transfer` calls back into the multisig that is paying out,
+// trying to execute the same proposal again or revoke an approval on it.
+#[contract]
+pub struct ReentrantToken;
+
+#[contractimpl]
+impl ReentrantToken {
+    pub fn arm(env: Env, multisig: Address, proposal_id: u64, revoker: Option<BytesN<32>>) {
+        env.storage().instance().set(&symbol_short!("target"), &(multisig, proposal_id, revoker));
+    }
+
+    pub fn transfer(env: Env, _from: Address, _to: Address, _amount: i128) {
+        let (multisig, proposal_id, revoker): (Address, u64, Option<BytesN<32>>) = env.storage().instance().get(&symbol_short!("target")).unwrap();
+        let client = MultiSigContractClient::new(&env, &multisig);
+        match revoker {
+            Some(revoker) => client.revoke_approval(&proposal_id, &revoker),
+            None => client.execute_proposal(&proposal_id),
+        }
+    }
+}
+
+#[test]
+fn test_token_cannot_reenter_execution() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let hostile = env.register(ReentrantToken, ());
+    let hostile_client = ReentrantTokenClient::new(&env, &hostile);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let approved = |token: &Address| {
+        let proposal_id = client.create_proposal(&signers.get_unchecked(0), token, &recipient, &100, &reason, &3600);
+        client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+        client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+        proposal_id
+    };
+
+    let proposal_id = approved(&hostile);
+    for revoker in [None, Some(signers.get_unchecked(0))] {
+        hostile_client.arm(&contract_id, &proposal_id, &revoker);
+        assert_eq!(client.try_execute_proposal(&proposal_id), Err(Ok(MultisigError::TransferFailed.into())));
+        assert!(!client.is_proposal_executed(&proposal_id));
+        assert_eq!(client.get_approval_progress(&proposal_id).approvals, 2);
+    }
+
+    // An ordinary token pays out as usual
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let honest = approved(&token);
+    client.execute_proposal(&honest);
+    assert_eq!(soroban_sdk::token::Client::new(&env, &token).balance(&recipient), 100);
+}
+
+#[test]
+fn test_validate_draft_matches_create_proposal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token = Address::generate(&env);
+    let frozen = Address::generate(&env);
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::FreezeToken(frozen.clone())), 2);
+
+    let proposer = signers.get_unchecked(0);
+    let stranger = BytesN::from_array(&env, &[99u8; 32]);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Draft");
+
+    let drafts = [
+        (proposer.clone(), token.clone(), 1000i128, 3600u64, vec![&env]),
+        (proposer.clone(), token.clone(), 1000, 60, vec![&env, Symbol::new(&env, "invalid_expiry")]),
+        (stranger.clone(), token.clone(), 1000, 3600, vec![&env, Symbol::new(&env, "unknown_proposer")]),
+        (proposer.clone(), token.clone(), 0, 3600, vec![&env, Symbol::new(&env, "invalid_amount")]),
+        (proposer.clone(), frozen.clone(), 1000, 3600, vec![&env, Symbol::new(&env, "token_frozen")]),
+        (
+            stranger,
+            frozen,
+            -5,
+            60,
+            vec![
+                &env,
+                Symbol::new(&env, "invalid_expiry"),
+                Symbol::new(&env, "unknown_proposer"),
+                Symbol::new(&env, "invalid_amount"),
+                Symbol::new(&env, "token_frozen"),
+            ],
+        ),
+    ];
+
+    for (who, token, amount, expires, expected) in drafts {
+        let violations = client.validate_draft(&who, &token, &recipient, &amount, &expires);
+        assert_eq!(violations, expected);
+
+        let created = client.try_create_proposal(&who, &token, &recipient, &amount, &reason, &expires);
+        assert_eq!(created.is_ok(), violations.is_empty());
+    }
+}
+
+#[test]
+fn test_proposer_cap_window_and_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let capped = Address::generate(&env);
+    let uncapped = Address::generate(&env);
+    let cap = ProposerCap { token: capped.clone(), amount: 1000, window_seconds: 86_400 };
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::SetProposerCap(cap)), 2);
+
+    let proposer = signers.get_unchecked(0);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    assert_eq!(client.get_proposer_capacity(&proposer, &capped), Some(1000));
+    assert_eq!(client.get_proposer_capacity(&proposer, &uncapped), None);
+
+    client.create_proposal(&proposer, &capped, &recipient, &600, &reason, &3600);
+    assert_eq!(client.get_proposer_capacity(&proposer, &capped), Some(400));
+
+    // Over the cap for this proposer, but not for other signers or tokens
+    let result = client.try_create_proposal(&proposer, &capped, &recipient, &401, &reason, &3600);
+    assert_eq!(result, Err(Ok(MultisigError::ProposerCapExceeded.into())));
+    client.create_proposal(&signers.get_unchecked(1), &capped, &recipient, &1000, &reason, &3600);
+    client.create_proposal(&proposer, &uncapped, &recipient, &1_000_000, &reason, &3600);
+    client.create_proposal(&proposer, &capped, &recipient, &400, &reason, &3600);
+    assert_eq!(client.get_proposer_capacity(&proposer, &capped), Some(0));
+
+    // The accumulation resets once the window has passed
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    assert_eq!(client.get_proposer_capacity(&proposer, &capped), Some(1000));
+    client.create_proposal(&proposer, &capped, &recipient, &1000, &reason, &3600);
+
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::RemoveProposerCap(capped.clone())), 2);
+    assert_eq!(client.get_proposer_capacity(&proposer, &capped), None);
+}
+
+#[test]
+fn test_amount_ceiling() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let proposer = signers.get_unchecked(0);
+    let token = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+
+    for amount in [i128::MAX, i128::MAX - 1, validation::MAX_AMOUNT + 1] {
+        let result = client.try_create_proposal(&proposer, &token, &recipient, &amount, &reason, &3600);
+        assert_eq!(result, Err(Ok(MultisigError::AmountTooLarge.into())));
+        assert_eq!(
+            client.validate_draft(&proposer, &token, &recipient, &amount, &3600),
+            vec![&env, Symbol::new(&env, "amount_too_large")]
+        );
+    }
+
+    let allowance = ProposalAction::Approve(AllowanceAction {
+        token_address: token.clone(),
+        spender: recipient.clone(),
+        amount: i128::MAX,
+        expiration_ledger: 1000,
+    });
+    let result = client.try_create_proposal_v2(&proposer, &allowance, &reason, &3600);
+    assert_eq!(result, Err(Ok(MultisigError::AmountTooLarge.into())));
+
+    // The ceiling itself is fine, and executes end to end
+    let funded = create_funded_token(&env, &contract_id, validation::MAX_AMOUNT);
+    let proposal_id = client.create_proposal(&proposer, &funded, &recipient, &validation::MAX_AMOUNT, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    client.execute_proposal(&proposal_id);
+    assert_eq!(soroban_sdk::token::Client::new(&env, &funded).balance(&recipient), validation::MAX_AMOUNT);
+}
+
+#[test]
+fn test_large_amounts_accumulate_without_overflow() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    // Running proposer totals near the top of the range
+    let token = Address::generate(&env);
+    let cap = ProposerCap { token: token.clone(), amount: i128::MAX, window_seconds: 86_400 };
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::SetProposerCap(cap)), 2);
+
+    let proposer = signers.get_unchecked(0);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    for _ in 0..3 {
+        client.create_proposal(&proposer, &token, &recipient, &validation::MAX_AMOUNT, &reason, &3600);
+    }
+    assert_eq!(client.get_proposer_capacity(&proposer, &token), Some(i128::MAX - 3 * validation::MAX_AMOUNT));
+
+    // Valuing a huge balance at a huge price is an error, not a trap
+    let funded = create_funded_token(&env, &contract_id, validation::MAX_AMOUNT);
+    let targets = vec![&env, AllocationTarget { token: funded, weight: 1, price_hint: Some(i128::MAX) }];
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::SetAllocationTargets(targets)), 2);
+    assert_eq!(client.try_get_allocation_report(), Err(Ok(MultisigError::ArithmeticOverflow.into())));
+}
+
+#[test]
+fn test_initialize_funded_pulls_seed_funds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let funder = Address::generate(&env);
+    let token = create_funded_token(&env, &funder, 10_000);
+    let token_client = soroban_sdk::token::Client::new(&env, &token);
+    token_client.approve(&funder, &contract_id, &2500, &1000);
+
+    let signers = create_test_signers(&env, 3);
+    let funding = InitialFunding { funder: funder.clone(), token: token.clone(), amount: 2500 };
+    client.initialize_funded(&signers, &2, &false, &funding);
+
+    assert_eq!(client.threshold(), 2);
+    assert_eq!(token_client.balance(&contract_id), 2500);
+    assert_eq!(token_client.balance(&funder), 7500);
+    assert_eq!(
+        client.get_initial_deposit(),
+        Some(Deposit { funder, token, amount: 2500, deposited_at: env.ledger().timestamp() })
+    );
+}
+
+#[test]
+fn test_initialize_funded_is_atomic() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    // The funder approved less than requested
+    let funder = Address::generate(&env);
+    let token = create_funded_token(&env, &funder, 10_000);
+    soroban_sdk::token::Client::new(&env, &token).approve(&funder, &contract_id, &1000, &1000);
+
+    let signers = create_test_signers(&env, 3);
+    let funding = InitialFunding { funder, token: token.clone(), amount: 2500 };
+    assert!(client.try_initialize_funded(&signers, &2, &false, &funding).is_err());
+    assert_eq!(client.try_threshold(), Err(Ok(MultisigError::NotInitialized.into())));
+
+    // Plain initialization is still possible afterwards
+    client.initialize(&signers, &2, &false);
+    assert_eq!(client.get_initial_deposit(), None);
+}
+
+fn run_signer_change(client: &MultiSigContractClient, signers: &Vec<BytesN<32>>, change: SignerChangeKind, target: &BytesN<32>) {
+    let proposal_id = client.propose_signer_change(&signers.get_unchecked(0), &change, target, &3600);
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(0));
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(1));
+    client.execute_signer_change(&proposal_id);
+}
+
+#[test]
+fn test_signer_history_replay() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let newcomer = BytesN::from_array(&env, &[99u8; 32]);
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    run_signer_change(&client, &signers, SignerChangeKind::Add, &newcomer);
+    env.ledger().with_mut(|li| li.timestamp = 3_000);
+    run_signer_change(&client, &signers, SignerChangeKind::Remove, &signers.get_unchecked(2));
+
+    let history = client.get_signer_history(&0, &10);
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.get_unchecked(0).change, SignerSetChange::Initialized(signers.clone(), 2));
+    assert_eq!(
+        history.get_unchecked(2),
+        SignerHistoryEntry { seq: 3, timestamp: 3_000, change: SignerSetChange::Removed(signers.get_unchecked(2)) }
+    );
+    assert_eq!(client.get_signer_history(&3, &10).len(), 1);
+
+    assert_eq!(client.get_signer_set_at(&999).signers, Vec::new(&env));
+    assert_eq!(client.get_signer_set_at(&1_500), SignerSetSnapshot { signers: signers.clone(), threshold: 2 });
+
+    let mut after_add = signers.clone();
+    after_add.push_back(newcomer.clone());
+    assert_eq!(client.get_signer_set_at(&2_999).signers, after_add);
+
+    let now = client.get_signer_set_at(&3_000);
+    assert_eq!(
+        now.signers,
+        vec![&env, signers.get_unchecked(0), signers.get_unchecked(1), newcomer]
+    );
+    assert_eq!(now.threshold, 2);
+}
+
+#[test]
+fn test_signer_set_at_resumes_from_checkpoints() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    // Two and a half intervals of additions, one second apart
+    let interval = history::CHECKPOINT_INTERVAL;
+    let joiner = |n: u64| BytesN::from_array(&env, &[n as u8 + 100; 32]);
+    env.as_contract(&contract_id, || {
+        for n in 2..=interval * 5 / 2 {
+            env.ledger().with_mut(|li| li.timestamp = 1_000 + n);
+            history::record(&env, SignerSetChange::Added(joiner(n)));
+        }
+    });
+    env.as_contract(&contract_id, || {
+        assert!(storage::read_signer_set_checkpoint(&env, interval).is_some());
+        assert!(storage::read_signer_set_checkpoint(&env, interval * 2).is_some());
+        assert!(storage::read_signer_set_checkpoint(&env, interval * 2 + 1).is_none());
+    });
+
+    // Every point in time sees exactly the entries up to it
+    for seq in [1, interval - 1, interval, interval + 1, interval * 2, interval * 5 / 2] {
+        let mut expected = signers.clone();
+        for n in 2..=seq {
+            expected.push_back(joiner(n));
+        }
+        assert_eq!(client.get_signer_set_at(&(1_000 + seq)), SignerSetSnapshot { signers: expected, threshold: 2 });
+    }
+    assert_eq!(client.get_signer_set_at(&999).signers, Vec::new(&env));
+}
+
+#[test]
+fn test_signer_history_attributes_entries_to_proposals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let newcomer = BytesN::from_array(&env, &[99u8; 32]);
+
+    let mut proposal_ids = Vec::<u64>::new(&env);
+    for (at, change) in [(2_000, SignerChangeKind::Add), (3_000, SignerChangeKind::Remove), (4_000, SignerChangeKind::Add)] {
+        env.ledger().with_mut(|li| li.timestamp = at);
+        run_signer_change(&client, &signers, change, &newcomer);
+        proposal_ids.push_back(client.get_last_proposal_id());
+    }
+    let threshold_change = pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::SetThreshold(3)), 2);
+
+    let history = client.get_signer_history(&2, &10);
+    let expected = [
+        SignerSetChange::Added(newcomer.clone()),
+        SignerSetChange::Removed(newcomer.clone()),
+        SignerSetChange::Added(newcomer.clone()),
+        SignerSetChange::ThresholdChanged(3),
+    ];
+    assert_eq!(history.len(), 4);
+    for (entry, change) in history.iter().zip(expected) {
+        assert_eq!(entry.change, change);
+    }
+    assert_eq!(history.get_unchecked(1).timestamp, 3_000);
+
+    // Initialization was not a proposal; every later entry was
+    assert_eq!(client.get_signer_history_proposal(&1), None);
+    for (seq, proposal_id) in (2..).zip(proposal_ids.iter()) {
+        assert_eq!(client.get_signer_history_proposal(&seq), Some(proposal_id));
+    }
+    assert_eq!(client.get_signer_history_proposal(&5), Some(threshold_change));
+}
+
+#[test]
+fn test_instances_share_a_signer_registry() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let signers = create_test_signers(&env, 3);
+
+    let registry_id = env.register(MultiSigContract, ());
+    let registry = MultiSigContractClient::new(&env, &registry_id);
+    registry.initialize(&signers, &2, &false);
+
+    let first = MultiSigContractClient::new(&env, &env.register(MultiSigContract, ()));
+    let second = MultiSigContractClient::new(&env, &env.register(MultiSigContract, ()));
+    first.initialize_with_registry(&registry_id);
+    second.initialize_with_registry(&registry_id);
+
+    assert_eq!(first.get_signer_registry(), Some(registry_id.clone()));
+    assert_eq!(first.threshold(), 2);
+    assert_eq!(second.signer_count(), 3);
+
+    // One membership change on the registry applies to both instances
+    let newcomer = BytesN::from_array(&env, &[99u8; 32]);
+    run_signer_change(&registry, &signers, SignerChangeKind::Add, &newcomer);
+    assert!(first.is_signer(&newcomer));
+    assert!(second.is_signer(&newcomer));
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = second.create_proposal(&newcomer, &token_address, &recipient, &1000, &reason, &3600);
+    second.approve_proposal(&proposal_id, &newcomer);
+    second.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    assert!(second.get_approval_progress(&proposal_id).ready);
+
+    // Membership is managed on the registry only
+    let add_type = SignerChangeKind::Add;
+    let result = first.try_propose_signer_change(&signers.get_unchecked(0), &add_type, &newcomer, &3600);
+    assert_eq!(result, Err(Ok(SignerError::RegistryManaged.into())));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #45)")]
+fn test_unreachable_signer_registry() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    // An uninitialized instance cannot answer registry reads
+    let registry_id = env.register(MultiSigContract, ());
+    client.initialize_with_registry(&registry_id);
+}
+
+fn ordinals(client: &MultiSigContractClient, proposal_id: u64) -> Vec<(BytesN<32>, u32)> {
+    let mut out = Vec::new(&client.env);
+    for approval in client.get_proposal_approvals(&proposal_id).iter() {
+        out.push_back((approval.signer, approval.ordinal));
+    }
+    out
+}
+
+#[test]
+fn test_approval_ordinals_across_revocation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 4);
+    client.initialize(&signers, &2, &false);
+    let [a, b, c, d] = [0, 1, 2, 3].map(|i| signers.get_unchecked(i));
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&a, &token_address, &recipient, &1000, &reason, &3600);
+
+    client.approve_proposal(&proposal_id, &a);
+    client.approve_proposal(&proposal_id, &b);
+    client.approve_proposal(&proposal_id, &c);
+    assert_eq!(ordinals(&client, proposal_id), vec![&env, (a.clone(), 1), (b.clone(), 2), (c.clone(), 3)]);
+
+    // Revoking from the middle closes the gap
+    client.revoke_approval(&proposal_id, &b);
+    assert_eq!(ordinals(&client, proposal_id), vec![&env, (a.clone(), 1), (c.clone(), 2)]);
+
+    // Re-approving goes to the back of the line
+    client.approve_proposal(&proposal_id, &d);
+    client.approve_proposal(&proposal_id, &b);
+    assert_eq!(ordinals(&client, proposal_id), vec![&env, (a.clone(), 1), (c.clone(), 2), (d.clone(), 3), (b.clone(), 4)]);
+
+    // Revoking the first and the last
+    client.revoke_approval(&proposal_id, &a);
+    client.revoke_approval(&proposal_id, &b);
+    assert_eq!(ordinals(&client, proposal_id), vec![&env, (c, 1), (d, 2)]);
+}
+
+#[test]
+fn test_final_approver_must_approve_last() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let treasurer = signers.get_unchecked(2);
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::SetFinalApprover(treasurer.clone())), 2);
+    assert_eq!(client.get_final_approver(), Some(treasurer.clone()));
+
+    let token_address = create_funded_token(&env, &contract_id, 5000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+
+    // Threshold met without the treasurer
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    assert!(!client.get_approval_progress(&proposal_id).ready);
+    assert_eq!(client.try_execute_proposal(&proposal_id), Err(Ok(MultisigError::FinalApproverRequired.into())));
+
+    // Treasurer approving early does not count as final once someone follows
+    client.revoke_approval(&proposal_id, &signers.get_unchecked(1));
+    client.approve_proposal(&proposal_id, &treasurer);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    assert_eq!(client.try_execute_proposal(&proposal_id), Err(Ok(MultisigError::FinalApproverRequired.into())));
+
+    client.revoke_approval(&proposal_id, &treasurer);
+    client.approve_proposal(&proposal_id, &treasurer);
+    assert!(client.get_approval_progress(&proposal_id).ready);
+    client.execute_proposal(&proposal_id);
+    assert!(client.is_proposal_executed(&proposal_id));
+}
+
+#[test]
+fn test_allocation_report_against_targets() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    // 900 units of a stablecoin at 1.00 and 50 of a token at 2.00: value 900 vs 100
+    let stable = create_funded_token(&env, &contract_id, 900);
+    let volatile = create_funded_token(&env, &contract_id, 50);
+    let targets = vec![
+        &env,
+        AllocationTarget { token: stable.clone(), weight: 60, price_hint: None },
+        AllocationTarget { token: volatile.clone(), weight: 40, price_hint: Some(20_000_000) },
+    ];
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::SetAllocationTargets(targets.clone())), 2);
+    assert_eq!(client.get_allocation_targets(), targets);
+
+    let report = client.get_allocation_report();
+    assert_eq!(report.total_value, 1000);
+    assert_eq!(
+        report.entries,
+        vec![
+            &env,
+            AllocationEntry { token: stable, balance: 900, value: 900, target_bps: 6000, actual_bps: 9000, drift_bps: 3000 },
+            AllocationEntry { token: volatile, balance: 50, value: 100, target_bps: 4000, actual_bps: 1000, drift_bps: -3000 },
+        ]
+    );
+}
+
+#[test]
+fn test_allocation_report_rounds_down() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let tokens = [1, 1, 1].map(|amount| create_funded_token(&env, &contract_id, amount));
+    let mut targets = Vec::new(&env);
+    for token in tokens.iter() {
+        targets.push_back(AllocationTarget { token: token.clone(), weight: 1, price_hint: None });
+    }
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::SetAllocationTargets(targets)), 2);
+
+    // Thirds come out as 3333 bps each
+    for entry in client.get_allocation_report().entries.iter() {
+        assert_eq!(entry.target_bps, 3333);
+        assert_eq!(entry.actual_bps, 3333);
+        assert_eq!(entry.drift_bps, 0);
+    }
+}
+
+#[test]
+fn test_allocation_report_empty_treasury() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    let targets = vec![&env, AllocationTarget { token, weight: 1, price_hint: None }];
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::SetAllocationTargets(targets)), 2);
+
+    let report = client.get_allocation_report();
+    assert_eq!(report.total_value, 0);
+    assert_eq!(report.entries.get_unchecked(0).actual_bps, 0);
+    assert_eq!(report.entries.get_unchecked(0).drift_bps, -10_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn test_allocation_targets_reject_duplicate_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    let targets = vec![
+        &env,
+        AllocationTarget { token: token.clone(), weight: 1, price_hint: None },
+        AllocationTarget { token, weight: 2, price_hint: None },
+    ];
+    client.propose_governance(&signers.get_unchecked(0), &GovernanceAction::Treasury(TreasuryAction::SetAllocationTargets(targets)), &3600);
+}
+
+#[test]
+fn test_proposal_from_template() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token = create_funded_token(&env, &contract_id, 10_000);
+    let vendor = Address::generate(&env);
+    let draft = TemplateDraft { name: String::from_str(&env, "Hosting"), token: token.clone(), recipient: vendor.clone() };
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::CreateTemplate(draft)), 2);
+
+    let template = ProposalTemplate {
+        id: 1,
+        name: String::from_str(&env, "Hosting"),
+        token: token.clone(),
+        recipient: vendor.clone(),
+        active: true,
+    };
+    assert_eq!(client.get_templates(), vec![&env, template]);
+
+    let reason = String::from_str(&env, "March invoice");
+    let proposal_id = client.create_proposal_from_template(&signers.get_unchecked(0), &1, &1200, &reason, &3600);
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!(
+        proposal.action,
+        ProposalAction::Transfer(TransferAction { token_address: token.clone(), recipient: vendor.clone(), amount: 1200 })
+    );
+    assert_eq!(client.get_proposal_template(&proposal_id), Some(1));
+
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    client.execute_proposal(&proposal_id);
+    assert_eq!(soroban_sdk::token::Client::new(&env, &token).balance(&vendor), 1200);
+
+    // Ordinary proposals carry no template
+    let other = client.create_proposal(&signers.get_unchecked(0), &token, &vendor, &5, &reason, &3600);
+    assert_eq!(client.get_proposal_template(&other), None);
+}
+
+#[test]
+fn test_deactivated_template_rejects_proposals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let draft = TemplateDraft {
+        name: String::from_str(&env, "Payroll"),
+        token: Address::generate(&env),
+        recipient: Address::generate(&env),
+    };
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::CreateTemplate(draft)), 2);
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::DeactivateTemplate(1)), 2);
+    assert!(!client.get_templates().get_unchecked(0).active);
+
+    let proposer = signers.get_unchecked(0);
+    let reason = String::from_str(&env, "Payroll");
+    let result = client.try_create_proposal_from_template(&proposer, &1, &100, &reason, &3600);
+    assert_eq!(result, Err(Ok(GovernanceError::TemplateInactive.into())));
+    let result = client.try_create_proposal_from_template(&proposer, &2, &100, &reason, &3600);
+    assert_eq!(result, Err(Ok(GovernanceError::TemplateNotFound.into())));
+
+    // Unknown ids are refused before a vote is wasted on them
+    let result = client.try_propose_governance(&proposer, &GovernanceAction::Treasury(TreasuryAction::DeactivateTemplate(2)), &3600);
+    assert_eq!(result, Err(Ok(GovernanceError::TemplateNotFound.into())));
+}
+
+#[test]
+fn test_template_limits() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let proposer = signers.get_unchecked(0);
+    let draft = |name: &str| TemplateDraft {
+        name: String::from_str(&env, name),
+        token: Address::generate(&env),
+        recipient: Address::generate(&env),
+    };
+
+    for name in ["", "a name well past the thirty-two byte limit"] {
+        let result = client.try_propose_governance(&proposer, &GovernanceAction::Treasury(TreasuryAction::CreateTemplate(draft(name))), &3600);
+        assert_eq!(result, Err(Ok(MultisigError::InvalidProposal.into())));
+    }
+
+    for _ in 0..templates::MAX_TEMPLATES {
+        pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::CreateTemplate(draft("Vendor"))), 2);
+    }
+    let result = client.try_propose_governance(&proposer, &GovernanceAction::Treasury(TreasuryAction::CreateTemplate(draft("Vendor"))), &3600);
+    assert_eq!(result, Err(Ok(GovernanceError::TooManyTemplates.into())));
+}
+
+#[test]
+fn test_address_book_entries_and_labels() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let acme = Address::generate(&env);
+    let globex = Address::generate(&env);
+    for (label, address) in [("acme", &acme), ("globex", &globex)] {
+        let entry = AddressLabel { label: Symbol::new(&env, label), address: address.clone() };
+        pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::AddAddressBookEntry(entry)), 2);
+    }
+    assert_eq!(
+        client.get_address_book(),
+        vec![
+            &env,
+            AddressBookEntry { id: 1, label: Symbol::new(&env, "acme"), address: acme.clone() },
+            AddressBookEntry { id: 2, label: Symbol::new(&env, "globex"), address: globex.clone() },
+        ]
+    );
+
+    let token = Address::generate(&env);
+    let reason = String::from_str(&env, "Invoice");
+    let proposal_id = client.create_proposal_to_entry(&signers.get_unchecked(0), &token, &2, &700, &reason, &3600);
+    assert_eq!(
+        client.get_proposal(&proposal_id).action,
+        ProposalAction::Transfer(TransferAction { token_address: token.clone(), recipient: globex.clone(), amount: 700 })
+    );
+    assert_eq!(client.get_recipient_label(&proposal_id), Some(Symbol::new(&env, "globex")));
+
+    // Removing the entry keeps the label already recorded on the proposal
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::RemoveAddressBookEntry(2)), 2);
+    assert_eq!(client.get_address_book().len(), 1);
+    assert_eq!(client.get_recipient_label(&proposal_id), Some(Symbol::new(&env, "globex")));
+    let result = client.try_create_proposal_to_entry(&signers.get_unchecked(0), &token, &2, &700, &reason, &3600);
+    assert_eq!(result, Err(Ok(GovernanceError::AddressBookEntryNotFound.into())));
+
+    // Labels and addresses are unique
+    let proposer = signers.get_unchecked(0);
+    for entry in [
+        AddressLabel { label: Symbol::new(&env, "acme"), address: Address::generate(&env) },
+        AddressLabel { label: Symbol::new(&env, "acme_two"), address: acme },
+    ] {
+        let result = client.try_propose_governance(&proposer, &GovernanceAction::Treasury(TreasuryAction::AddAddressBookEntry(entry)), &3600);
+        assert_eq!(result, Err(Ok(MultisigError::InvalidProposal.into())));
+    }
+}
+
+#[test]
+fn test_address_book_only_restricts_recipients() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let listed = Address::generate(&env);
+    let entry = AddressLabel { label: Symbol::new(&env, "payroll"), address: listed.clone() };
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::AddAddressBookEntry(entry)), 2);
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::EnableFeature(Symbol::new(&env, "book_only"))), 2);
+
+    let proposer = signers.get_unchecked(0);
+    let token = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+
+    client.create_proposal(&proposer, &token, &listed, &100, &reason, &3600);
+    let result = client.try_create_proposal(&proposer, &token, &stranger, &100, &reason, &3600);
+    assert_eq!(result, Err(Ok(MultisigError::RecipientNotInAddressBook.into())));
+    assert_eq!(
+        client.validate_draft(&proposer, &token, &stranger, &100, &3600),
+        vec![&env, Symbol::new(&env, "unlisted_recipient")]
+    );
+
+    // Allowances are held to the same rule through their spender
+    let allowance = ProposalAction::Approve(AllowanceAction {
+        token_address: token.clone(),
+        spender: stranger,
+        amount: 100,
+        expiration_ledger: 1000,
+    });
+    let result = client.try_create_proposal_v2(&proposer, &allowance, &reason, &3600);
+    assert_eq!(result, Err(Ok(MultisigError::RecipientNotInAddressBook.into())));
+}
+
+#[test]
+fn test_forwarding_token_needs_governed_implementation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    // Balances live in the implementation; the multisig only ever talks to the proxy
+    let implementation = create_funded_token(&env, &contract_id, 5000);
+    let proxy = env.register(ProxyToken, (&implementation,));
+    let proxy_client = ProxyTokenClient::new(&env, &proxy);
+
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &proxy, &recipient, &1200, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+
+    // From here on only real authorization counts
+    env.set_auths(&[]);
+    assert!(client.try_execute_proposal(&proposal_id).is_err());
+    assert_eq!(proxy_client.balance(&contract_id), 5000);
+
+    env.mock_all_auths();
+    let entry = TokenImplementation { token: proxy.clone(), implementation: implementation.clone() };
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::SetTokenImplementation(entry)), 2);
+    assert_eq!(client.get_token_implementation(&proxy), Some(implementation));
+
+    env.set_auths(&[]);
+    client.execute_proposal(&proposal_id);
+    assert_eq!(proxy_client.balance(&contract_id), 3800);
+    assert_eq!(proxy_client.balance(&recipient), 1200);
+}
+
+#[test]
+fn test_forwarding_token_allowance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let implementation = create_funded_token(&env, &contract_id, 5000);
+    let proxy = env.register(ProxyToken, (&implementation,));
+    let entry = TokenImplementation { token: proxy.clone(), implementation: implementation.clone() };
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::SetTokenImplementation(entry)), 2);
+
+    let spender = Address::generate(&env);
+    let action = ProposalAction::Approve(AllowanceAction {
+        token_address: proxy,
+        spender: spender.clone(),
+        amount: 300,
+        expiration_ledger: 1000,
+    });
+    let reason = String::from_str(&env, "Allowance");
+    let proposal_id = client.create_proposal_v2(&signers.get_unchecked(0), &action, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+
+    env.set_auths(&[]);
+    client.execute_proposal(&proposal_id);
+    assert_eq!(soroban_sdk::token::Client::new(&env, &implementation).allowance(&contract_id, &spender), 300);
+
+    // Pointing the token at itself pre-authorizes nothing useful
+    env.mock_all_auths();
+    let entry = TokenImplementation { token: implementation.clone(), implementation };
+    let result = client.try_propose_governance(&signers.get_unchecked(0), &GovernanceAction::Treasury(TreasuryAction::SetTokenImplementation(entry)), &3600);
+    assert_eq!(result, Err(Ok(MultisigError::InvalidProposal.into())));
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum StubTokenError {
+    InsufficientBalance = 10,
+}
+
+// Token whose `transfer` and `transfer_from` behave as configured: succeed,
+// fail with a contract error, or trap outright.
+#[contract]
+pub struct StubToken;
+
+const STUB_OK: u32 = 0;
+const STUB_ERROR: u32 = 1;
+const STUB_TRAP: u32 = 2;
+
+#[contractimpl]
+impl StubToken {
+    pub fn set_mode(env: Env, mode: u32) {
+        env.storage().instance().set(&symbol_short!("mode"), &mode);
+    }
+
+    pub fn transfer(env: Env, _from: Address, _to: Address, amount: i128) {
+        match env.storage().instance().get(&symbol_short!("mode")).unwrap_or(STUB_OK) {
+            STUB_ERROR => panic_with_error!(&env, StubTokenError::InsufficientBalance),
+            STUB_TRAP => panic!("token trapped"),
+            _ => env.storage().instance().set(&symbol_short!("moved"), &amount),
+        }
+    }
+
+    pub fn transfer_from(env: Env, _spender: Address, from: Address, to: Address, amount: i128) {
+        Self::transfer(env, from, to, amount)
+    }
+}
+
+#[test]
+fn test_pulled_funds_are_announced_and_failures_mapped() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let token = env.register(StubToken, ());
+    let stub = StubTokenClient::new(&env, &token);
+    let funder = Address::generate(&env);
+    let signers = create_test_signers(&env, 3);
+    let funding = InitialFunding { funder: funder.clone(), token: token.clone(), amount: 2500 };
+
+    for mode in [STUB_ERROR, STUB_TRAP] {
+        stub.set_mode(&mode);
+        assert_eq!(
+            client.try_initialize_funded(&signers, &2, &false, &funding),
+            Err(Ok(MultisigError::TransferFailed.into()))
+        );
+    }
+
+    stub.set_mode(&STUB_OK);
+    client.initialize_funded(&signers, &2, &false, &funding);
+
+    let data: Map<Symbol, Val> = map![
+        &env,
+        (Symbol::new(&env, "amount"), 2500i128.into_val(&env)),
+        (Symbol::new(&env, "from"), funder.into_val(&env)),
+    ];
+    let pulled = (
+        contract_id.clone(),
+        (Symbol::new(&env, "funds_pulled"), symbol_short!("init_fund"), token.clone()).into_val(&env),
+        data.into_val(&env),
+    );
+    let data: Map<Symbol, Val> = map![
+        &env,
+        (Symbol::new(&env, "amount"), 2500i128.into_val(&env)),
+        (Symbol::new(&env, "funder"), funder.into_val(&env)),
+    ];
+    let funded = (contract_id.clone(), (Symbol::new(&env, "funded"), token).into_val(&env), data.into_val(&env));
+    let events = contract_events(&env, &contract_id);
+    let fingerprint = fingerprint_event(&env, &contract_id, &client.get_config_fingerprint());
+    assert_eq!(events, vec![&env, fingerprint, pulled, funded]);
+}
+
+#[test]
+fn test_failed_token_transfer_leaves_proposal_open() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token = env.register(StubToken, ());
+    let stub = StubTokenClient::new(&env, &token);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &100, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+
+    for mode in [STUB_ERROR, STUB_TRAP] {
+        stub.set_mode(&mode);
+        let result = client.try_execute_proposal(&proposal_id);
+        assert_eq!(result, Err(Ok(MultisigError::TransferFailed.into())));
+        assert!(!client.is_proposal_executed(&proposal_id));
+        assert_eq!(client.get_proposal_outcome(&proposal_id), None);
+        assert_eq!(client.nonce(), 0);
+    }
+
+    // Once the token recovers the same proposal goes through
+    stub.set_mode(&STUB_OK);
+    client.execute_proposal(&proposal_id);
+    assert!(client.is_proposal_executed(&proposal_id));
+    assert_eq!(client.get_open_proposals(&0, &10).proposals.len(), 0);
+}
+
+#[test]
+fn test_token_error_code_extraction() {
+    let contract_error = soroban_sdk::Error::from_contract_error(10);
+    assert_eq!(payments::token_error_code(Ok(contract_error)), Some(10));
+    assert_eq!(payments::token_error_code(Err(soroban_sdk::InvokeError::Contract(7))), Some(7));
+    assert_eq!(payments::token_error_code(Err(soroban_sdk::InvokeError::Abort)), None);
+    let host_error = soroban_sdk::Error::from_type_and_code(
+        soroban_sdk::xdr::ScErrorType::Budget,
+        soroban_sdk::xdr::ScErrorCode::ExceededLimit,
+    );
+    assert_eq!(payments::token_error_code(Ok(host_error)), None);
+}
+
+#[test]
+fn test_scheduled_signer_change_windows() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 4);
+    client.initialize(&signers, &2, &false);
+
+    let day = 86_400u64;
+    let newcomer = BytesN::from_array(&env, &[42u8; 32]);
+    let add = SignerChangeKind::Add;
+    let proposal_id =
+        client.propose_scheduled_signer_change(&signers.get_unchecked(0), &add, &newcomer, &day, &Some(10 * day), &(20 * day));
+
+    let proposal = client.get_signer_change_proposal(&proposal_id);
+    assert_eq!(proposal.approval_deadline, day);
+    assert_eq!(proposal.execute_not_before, Some(10 * day));
+    assert_eq!(proposal.expires_at, 20 * day);
+
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(0));
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(1));
+
+    // Approvals close after a day, well before the proposal expires
+    env.ledger().with_mut(|li| li.timestamp = day + 1);
+    let result = client.try_approve_signer_change(&proposal_id, &signers.get_unchecked(2));
+    assert_eq!(result, Err(Ok(SignerError::SignerChangeApprovalClosed.into())));
+
+    // Fully approved, but not yet effective
+    let result = client.try_execute_signer_change(&proposal_id);
+    assert_eq!(result, Err(Ok(SignerError::SignerChangeNotYetEffective.into())));
+    assert!(!client.is_signer(&newcomer));
+
+    env.ledger().with_mut(|li| li.timestamp = 10 * day);
+    client.execute_signer_change(&proposal_id);
+    assert!(client.is_signer(&newcomer));
+}
+
+#[test]
+fn test_scheduled_signer_change_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let proposer = signers.get_unchecked(0);
+    let newcomer = BytesN::from_array(&env, &[42u8; 32]);
+    let add = SignerChangeKind::Add;
+    let day = 86_400u64;
+
+    // Window longer than the proposal, shorter than the minimum lifetime, and
+    // an effective date after expiry
+    for (window, effective) in [(3 * day, None), (60, None), (day, Some(3 * day))] {
+        let result = client.try_propose_scheduled_signer_change(&proposer, &add, &newcomer, &window, &effective, &(2 * day));
+        assert_eq!(result, Err(Ok(MultisigError::InvalidExpiryTime.into())));
+    }
+
+    // The plain entry point keeps approvals open until expiry
+    let proposal_id = client.propose_signer_change(&proposer, &add, &newcomer, &day);
+    let proposal = client.get_signer_change_proposal(&proposal_id);
+    assert_eq!(proposal.approval_deadline, proposal.expires_at);
+    assert_eq!(proposal.execute_not_before, None);
+}
+
+#[test]
+fn test_legacy_signer_change_reads_with_open_schedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let newcomer = BytesN::from_array(&env, &[42u8; 32]);
+    let legacy = LegacySignerChangeProposal {
+        id: 1,
+        proposer: signers.get_unchecked(0),
+        change_type: String::from_str(&env, "add"),
+        signer: newcomer.clone(),
+        created_at: 0,
+        expires_at: 3600,
+        executed: false,
+    };
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&DataKey::SignerChangeProposal(1), &legacy);
+        env.storage().instance().set(&DataKey::ProposalCount, &1u64);
+    });
+
+    let proposal = client.get_signer_change_proposal(&1);
+    assert_eq!(proposal.change_type, SignerChangeKind::Add);
+    assert_eq!(proposal.approval_deadline, 3600);
+    assert_eq!(proposal.execute_not_before, None);
+
+    client.approve_signer_change(&1, &signers.get_unchecked(0));
+    client.approve_signer_change(&1, &signers.get_unchecked(1));
+    client.execute_signer_change(&1);
+    assert!(client.is_signer(&newcomer));
+}
+
+#[test]
+fn test_signer_change_stored_with_string_type_reads_as_kind() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let removed = signers.get_unchecked(2);
+    let proposal_id = client.propose_signer_change(&signers.get_unchecked(0), &SignerChangeKind::Remove, &removed, &3600);
+
+    // Rewrite the change type the way it was stored before the enum
+    env.as_contract(&contract_id, || {
+        let key = DataKey::SignerChangeProposal(proposal_id);
+        let mut stored: Map<Symbol, Val> = env.storage().instance().get(&key).unwrap();
+        stored.set(Symbol::new(&env, "change_type"), String::from_str(&env, "remove").into_val(&env));
+        env.storage().instance().set(&key, &stored);
+    });
+
+    assert_eq!(client.get_signer_change_proposal(&proposal_id).change_type, SignerChangeKind::Remove);
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(0));
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(1));
+    client.execute_signer_change(&proposal_id);
+    assert!(!client.is_signer(&removed));
+
+    // A name no release ever stored is reported, not trapped on
+    env.as_contract(&contract_id, || {
+        let key = DataKey::SignerChangeProposal(proposal_id);
+        let mut stored: Map<Symbol, Val> = env.storage().instance().get(&key).unwrap();
+        stored.set(Symbol::new(&env, "change_type"), String::from_str(&env, "promote").into_val(&env));
+        env.storage().instance().set(&key, &stored);
+    });
+    assert_eq!(client.try_get_signer_change_proposal(&proposal_id), Err(Ok(MultisigError::InvalidProposal.into())));
+}
+
+#[test]
+fn test_upcoming_obligations_projection() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let usdc = create_funded_token(&env, &contract_id, 10_000);
+    let eurc = create_funded_token(&env, &contract_id, 10_000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let day = 86_400u64;
+    let approve = |proposal_id: u64| {
+        client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+        client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    };
+
+    let late = client.create_proposal(&signers.get_unchecked(0), &usdc, &recipient, &300, &reason, &(5 * day));
+    let soon = client.create_proposal(&signers.get_unchecked(0), &eurc, &recipient, &200, &reason, &day);
+    let paid = client.create_proposal(&signers.get_unchecked(0), &usdc, &recipient, &100, &reason, &(2 * day));
+    let withdrawn = client.create_proposal(&signers.get_unchecked(0), &usdc, &recipient, &400, &reason, &(3 * day));
+    let pending = client.create_proposal(&signers.get_unchecked(0), &usdc, &recipient, &500, &reason, &(4 * day));
+    let allowance = ProposalAction::Approve(AllowanceAction {
+        token_address: usdc.clone(),
+        spender: recipient.clone(),
+        amount: 50,
+        expiration_ledger: 1000,
+    });
+    let not_outflow = client.create_proposal_v2(&signers.get_unchecked(0), &allowance, &reason, &(2 * day));
+
+    for proposal_id in [late, soon, paid, withdrawn, not_outflow] {
+        approve(proposal_id);
+    }
+    client.approve_proposal(&pending, &signers.get_unchecked(0));
+    client.revoke_approval(&withdrawn, &signers.get_unchecked(1));
+    client.execute_proposal(&paid);
+
+    assert_eq!(
+        client.get_upcoming_obligations(&0, &(10 * day), &10),
+        vec![
+            &env,
+            Obligation { timestamp: day, token: eurc, amount: 200, proposal_id: soon },
+            Obligation { timestamp: 5 * day, token: usdc.clone(), amount: 300, proposal_id: late },
+        ]
+    );
+    assert_eq!(client.get_upcoming_obligations(&0, &(10 * day), &1).len(), 1);
+    assert_eq!(client.get_upcoming_obligations(&(2 * day), &(10 * day), &10).get_unchecked(0).proposal_id, late);
+
+    // Reaching the requirement later puts it on the calendar; expiry takes it off
+    client.approve_proposal(&pending, &signers.get_unchecked(1));
+    assert_eq!(client.get_upcoming_obligations(&(4 * day), &(4 * day), &10).get_unchecked(0).proposal_id, pending);
+    env.ledger().with_mut(|li| li.timestamp = day + 1);
+    client.expire_proposal(&soon);
+    assert_eq!(client.get_upcoming_obligations(&0, &(10 * day), &10).len(), 2);
+}
+
+#[test]
+fn test_reserve_floor_boundary() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token = create_funded_token(&env, &contract_id, 8000);
+    let floor = ReserveFloor { token: token.clone(), amount: 5000 };
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::SetReserveFloor(floor)), 2);
+    assert_eq!(client.get_reserve_floor(&token), 5000);
+    assert_eq!(client.get_free_balance(&token), 3000);
+
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let approved = |amount: i128| {
+        let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &amount, &reason, &3600);
+        client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+        client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+        proposal_id
+    };
+
+    // One unit over the free balance is refused; exactly the free balance is not
+    let over = approved(3001);
+    assert_eq!(client.try_execute_proposal(&over), Err(Ok(MultisigError::ReserveBreached.into())));
+    client.execute_proposal(&approved(3000));
+    assert_eq!(client.get_free_balance(&token), 0);
+
+    let result = client.try_execute_proposal(&approved(1));
+    assert_eq!(result, Err(Ok(MultisigError::ReserveBreached.into())));
+
+    // Lifting the floor frees the reserve again
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::ClearReserveFloor(token.clone())), 2);
+    assert_eq!(client.get_free_balance(&token), 5000);
+    client.execute_proposal(&over);
+}
+
+#[test]
+fn test_reserve_floor_limits_allowances() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token = create_funded_token(&env, &contract_id, 8000);
+    let floor = ReserveFloor { token: token.clone(), amount: 5000 };
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::SetReserveFloor(floor)), 2);
+
+    let reason = String::from_str(&env, "Allowance");
+    let spender = Address::generate(&env);
+    for (amount, allowed) in [(3001, false), (3000, true), (0, true)] {
+        let action = ProposalAction::Approve(AllowanceAction {
+            token_address: token.clone(),
+            spender: spender.clone(),
+            amount,
+            expiration_ledger: 1000,
+        });
+        let proposal_id = client.create_proposal_v2(&signers.get_unchecked(0), &action, &reason, &3600);
+        client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+        client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+        let result = client.try_execute_proposal(&proposal_id);
+        if allowed {
+            assert_eq!(result, Ok(Ok(())));
+        } else {
+            assert_eq!(result, Err(Ok(MultisigError::ReserveBreached.into())));
+        }
+    }
+
+    let result = client.try_propose_governance(
+        &signers.get_unchecked(0),
+        &GovernanceAction::Treasury(TreasuryAction::SetReserveFloor(ReserveFloor { token, amount: 0 })),
+        &3600,
+    );
+    assert_eq!(result, Err(Ok(MultisigError::InvalidProposal.into())));
+}
+
+#[test]
+fn test_all_open_items_interleaves_families() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let proposer = signers.get_unchecked(0);
+    let token = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let add = SignerChangeKind::Add;
+    let newcomer = |seed: u8| BytesN::from_array(&env, &[seed; 32]);
+
+    let pay_1 = client.create_proposal(&proposer, &token, &recipient, &100, &reason, &3600);
+    let change_1 = client.propose_signer_change(&proposer, &add, &newcomer(41), &3600);
+    let change_2 = client.propose_signer_change(&proposer, &add, &newcomer(42), &3600);
+    let pay_2 = client.create_proposal(&proposer, &token, &recipient, &200, &reason, &3600);
+    let change_3 = client.propose_signer_change(&proposer, &add, &newcomer(43), &3600);
+
+    let ids = |page: &UnifiedProposalPage| {
+        let mut out = Vec::new(&env);
+        for item in page.items.iter() {
+            out.push_back(item.id());
+        }
+        out
+    };
+
+    let page = client.get_all_open_items(&0, &10);
+    assert_eq!(ids(&page), vec![&env, pay_1, change_1, change_2, pay_2, change_3]);
+    assert_eq!(page.items.get_unchecked(1), UnifiedProposal::SignerChange(client.get_signer_change_proposal(&change_1)));
+    assert_eq!(page.items.get_unchecked(3), UnifiedProposal::Payment(client.get_proposal(&pay_2)));
+
+    // Executed signer changes drop out, and paging resumes after the last id
+    client.approve_signer_change(&change_2, &signers.get_unchecked(0));
+    client.approve_signer_change(&change_2, &signers.get_unchecked(1));
+    client.execute_signer_change(&change_2);
+
+    let first = client.get_all_open_items(&0, &2);
+    assert_eq!(ids(&first), vec![&env, pay_1, change_1]);
+    assert!(first.has_more);
+    let second = client.get_all_open_items(&first.next_cursor, &2);
+    assert_eq!(ids(&second), vec![&env, pay_2, change_3]);
+    assert!(!second.has_more);
+}
+
+#[test]
+fn test_create_proposal_with_key_is_idempotent() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let action = ProposalAction::Transfer(TransferAction {
+        token_address: Address::generate(&env),
+        recipient: Address::generate(&env),
+        amount: 100,
+    });
+    let reason = String::from_str(&env, "Invoice 42");
+    let key = BytesN::from_array(&env, &[7u8; 32]);
+
+    let first = client.create_proposal_with_key(&signers.get_unchecked(0), &action, &reason, &3600, &key);
+    let retried = client.create_proposal_with_key(&signers.get_unchecked(0), &action, &reason, &3600, &key);
+    assert_eq!(retried, first);
+    assert_eq!(client.get_proposal_count(), 1);
+
+    // Keys are scoped per proposer
+    let other = client.create_proposal_with_key(&signers.get_unchecked(1), &action, &reason, &3600, &key);
+    assert_ne!(other, first);
+
+    // Once the original has closed, the key starts a new proposal
+    env.ledger().with_mut(|li| li.timestamp = 3601);
+    client.expire_proposal(&first);
+    let reused = client.create_proposal_with_key(&signers.get_unchecked(0), &action, &reason, &3600, &key);
+    assert_ne!(reused, first);
+    assert_eq!(client.create_proposal_with_key(&signers.get_unchecked(0), &action, &reason, &3600, &key), reused);
+}
+
+#[test]
+fn test_amended_proposal_discards_earlier_approvals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+
+    // Change the amount behind the approver's back
+    env.as_contract(&contract_id, || {
+        let mut proposal = storage::read_proposal(&env, proposal_id).unwrap();
+        proposal.action = ProposalAction::Transfer(TransferAction {
+            token_address: token_address.clone(),
+            recipient: recipient.clone(),
+            amount: 1_000_000,
+        });
+        storage::write_proposal(&env, &proposal);
+    });
+
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    assert_eq!(
+        client.get_approval_progress(&proposal_id),
+        ApprovalProgress { approvals: 1, required: 2, roles: Vec::new(&env), ready: false, stale_approvals: 1 }
+    );
+    assert_eq!(client.try_execute_proposal(&proposal_id), Err(Ok(MultisigError::InsufficientApprovals.into())));
+
+    // Approving again replaces the stale approval
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    let progress = client.get_approval_progress(&proposal_id);
+    assert_eq!((progress.approvals, progress.stale_approvals, progress.ready), (2, 0, true));
+    assert_eq!(client.get_proposal_approvals(&proposal_id).len(), 2);
+}
+
+#[test]
+fn test_execution_grace_after_late_approval() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::SetExecutionGrace(600)), 2);
+    assert_eq!(client.get_execution_grace(), 600);
+
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let on_time = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &100, &reason, &3600);
+    let too_late = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &100, &reason, &3600);
+    let half_approved = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &100, &reason, &3600);
+
+    // Threshold reached 30 seconds before expiry
+    env.ledger().with_mut(|li| li.timestamp = 3570);
+    for proposal_id in [on_time, too_late] {
+        client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+        client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    }
+    client.approve_proposal(&half_approved, &signers.get_unchecked(0));
+    assert_eq!(client.get_fully_approved_at(&on_time), Some(3570));
+    assert_eq!(client.get_fully_approved_at(&half_approved), None);
+
+    // Approvals still close at expiry
+    env.ledger().with_mut(|li| li.timestamp = 3700);
+    assert_eq!(
+        client.try_approve_proposal(&half_approved, &signers.get_unchecked(1)),
+        Err(Ok(MultisigError::ProposalExpired.into()))
+    );
+    client.expire_proposal(&half_approved);
+
+    // Within the grace window a fully approved proposal still executes
+    client.execute_proposal(&on_time);
+    assert_eq!(soroban_sdk::token::Client::new(&env, &token).balance(&recipient), 100);
+    assert_eq!(client.try_expire_proposal(&too_late), Err(Ok(MultisigError::ProposalNotExpired.into())));
+
+    // Beyond it, the proposal is expired like any other
+    env.ledger().with_mut(|li| li.timestamp = 3600 + 601);
+    assert_eq!(client.try_execute_proposal(&too_late), Err(Ok(MultisigError::ProposalExpired.into())));
+    client.expire_proposal(&too_late);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #24)")]
+fn test_execution_grace_rejects_over_one_day() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    client.propose_governance(&signers.get_unchecked(0), &GovernanceAction::Policy(PolicyAction::SetExecutionGrace(86_401)), &3600);
+}
+
+#[test]
+fn test_separate_execution_requires_later_ledger() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::EnableFeature(symbol_short!("sep_exec"))), 2);
+
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &100, &reason, &3600);
+
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    assert_eq!(client.try_execute_proposal(&proposal_id), Err(Ok(MultisigError::SameLedgerExecution.into())));
+
+    // Approvals past the requirement do not move the recorded ledger
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(2));
+    client.execute_proposal(&proposal_id);
+    assert_eq!(soroban_sdk::token::Client::new(&env, &token).balance(&recipient), 100);
+}
+
+#[test]
+fn test_proposal_notes_thread() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+
+    let text = String::from_str(&env, "Agreed on the call, invoice attached off-chain");
+    assert_eq!(client.add_note(&proposal_id, &signers.get_unchecked(1), &text), 0);
+    let data: Map<Symbol, Val> = map![
+        &env,
+        (Symbol::new(&env, "author"), signers.get_unchecked(1).into_val(&env)),
+        (Symbol::new(&env, "index"), 0u32.into_val(&env)),
+        (Symbol::new(&env, "text"), text.into_val(&env)),
+    ];
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (contract_id.clone(), (Symbol::new(&env, "note_added"), proposal_id).into_val(&env), data.into_val(&env))]
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 10);
+    let reply = String::from_str(&env, "Confirmed");
+    assert_eq!(client.add_note(&proposal_id, &signers.get_unchecked(2), &reply), 1);
+
+    let notes = client.get_notes(&signers.get_unchecked(0), &proposal_id, &1, &10);
+    assert_eq!(notes, vec![&env, ProposalNote { author: signers.get_unchecked(2), text: reply, added_at: 10 }]);
+    assert_eq!(client.get_notes(&signers.get_unchecked(0), &proposal_id, &0, &1).get_unchecked(0).text, text);
+    assert_eq!(client.get_notes(&signers.get_unchecked(0), &proposal_id, &5, &10).len(), 0);
+
+    assert_eq!(
+        client.try_add_note(&proposal_id, &BytesN::from_array(&env, &[9u8; 32]), &text),
+        Err(Ok(MultisigError::UnknownSigner.into()))
+    );
+    assert_eq!(client.try_add_note(&99, &signers.get_unchecked(0), &text), Err(Ok(MultisigError::ProposalNotFound.into())));
+    assert_eq!(
+        client.try_add_note(&proposal_id, &signers.get_unchecked(0), &String::from_str(&env, "")),
+        Err(Ok(SignerError::InvalidNote.into()))
+    );
+}
+
+#[test]
+fn test_proposal_notes_are_capped() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let proposal_id = client.propose_governance(&signers.get_unchecked(0), &GovernanceAction::Signers(SignerAction::ClearFinalApprover), &3600);
+
+    let too_long = String::from_bytes(&env, &[b'a'; 281]);
+    assert_eq!(client.try_add_note(&proposal_id, &signers.get_unchecked(0), &too_long), Err(Ok(SignerError::InvalidNote.into())));
+
+    let text = String::from_bytes(&env, &[b'a'; 280]);
+    for _ in 0..50 {
+        client.add_note(&proposal_id, &signers.get_unchecked(0), &text);
+    }
+    assert_eq!(client.try_add_note(&proposal_id, &signers.get_unchecked(0), &text), Err(Ok(SignerError::TooManyNotes.into())));
+    assert_eq!(client.get_notes(&signers.get_unchecked(0), &proposal_id, &0, &50).len(), 50);
+}
+
+#[test]
+fn test_observers_can_read_but_not_vote() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let observer = BytesN::from_array(&env, &[42u8; 32]);
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::AddObserver(observer.clone())), 2);
+    assert_eq!(client.get_observers(), vec![&env, observer.clone()]);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+    client.add_note(&proposal_id, &signers.get_unchecked(0), &String::from_str(&env, "Quarterly payout"));
+
+    assert_eq!(client.get_notes(&observer, &proposal_id, &0, &10).len(), 1);
+    let outsider = BytesN::from_array(&env, &[43u8; 32]);
+    assert_eq!(
+        client.try_get_notes(&outsider, &proposal_id, &0, &10),
+        Err(Ok(GovernanceError::ReadAccessDenied.into()))
+    );
+
+    assert_eq!(client.try_approve_proposal(&proposal_id, &observer), Err(Ok(MultisigError::UnknownSigner.into())));
+    assert_eq!(
+        client.try_create_proposal(&observer, &token_address, &recipient, &1000, &reason, &3600),
+        Err(Ok(MultisigError::UnknownSigner.into()))
+    );
+    assert_eq!(
+        client.try_propose_governance(&observer, &GovernanceAction::Signers(SignerAction::ClearFinalApprover), &3600),
+        Err(Ok(MultisigError::UnknownSigner.into()))
+    );
+
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::RemoveObserver(observer.clone())), 2);
+    assert_eq!(client.get_observers(), Vec::new(&env));
+    assert_eq!(
+        client.try_get_notes(&observer, &proposal_id, &0, &10),
+        Err(Ok(GovernanceError::ReadAccessDenied.into()))
+    );
+    assert_eq!(
+        client.try_propose_governance(&signers.get_unchecked(0), &GovernanceAction::Signers(SignerAction::RemoveObserver(observer)), &3600),
+        Err(Ok(GovernanceError::ObserverNotFound.into()))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn test_signer_cannot_be_observer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    client.propose_governance(&signers.get_unchecked(0), &GovernanceAction::Signers(SignerAction::AddObserver(signers.get_unchecked(1))), &3600);
+}
+
+#[test]
+fn test_fifo_per_recipient_orders_execution() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::EnableFeature(symbol_short!("fifo_rcpt"))), 2);
+
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Invoice");
+    let first = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &100, &reason, &3600);
+    let unapproved = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &100, &reason, &3600);
+    let second = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &100, &reason, &7200);
+    let elsewhere = client.create_proposal(&signers.get_unchecked(0), &token, &Address::generate(&env), &100, &reason, &3600);
+
+    for proposal_id in [first, second, elsewhere] {
+        client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+        client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    }
+
+    // The older ready proposal holds up the newer one; the unapproved one does not
+    assert_eq!(client.get_fifo_blocker(&second), Some(first));
+    assert_eq!(client.get_fifo_blocker(&unapproved), Some(first));
+    assert_eq!(client.try_execute_proposal(&second), Err(Ok(MultisigError::OlderProposalPending.into())));
+
+    // Other recipients are unaffected
+    assert_eq!(client.get_fifo_blocker(&elsewhere), None);
+    client.execute_proposal(&elsewhere);
+
+    client.execute_proposal(&first);
+    assert_eq!(client.get_fifo_blocker(&second), None);
+    client.execute_proposal(&second);
+}
+
+#[test]
+fn test_fifo_per_recipient_unblocks_on_expiry() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::EnableFeature(symbol_short!("fifo_rcpt"))), 2);
+
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Invoice");
+    let first = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &100, &reason, &3600);
+    let second = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &100, &reason, &7200);
+    for proposal_id in [first, second] {
+        client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+        client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    }
+    assert_eq!(client.try_execute_proposal(&second), Err(Ok(MultisigError::OlderProposalPending.into())));
+
+    // Past its expiry the older proposal stops blocking, expired or not
+    env.ledger().with_mut(|li| li.timestamp = 3601);
+    assert_eq!(client.get_fifo_blocker(&second), None);
+    client.expire_proposal(&first);
+    client.execute_proposal(&second);
+    assert_eq!(soroban_sdk::token::Client::new(&env, &token).balance(&recipient), 100);
+}
+
+#[test]
+fn test_schema_is_stamped_on_initialize() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let schema = client.get_storage_schema();
+    assert_eq!(schema.version, schema::SCHEMA_VERSION);
+    assert!(schema.families.contains(Symbol::new(&env, "Proposal")));
+    assert!(schema.families.contains(Symbol::new(&env, "SchemaVersion")));
+}
+
+#[test]
+fn test_newer_schema_blocks_every_entry_point() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    // As if a newer release had written storage before being rolled back
+    env.as_contract(&contract_id, || storage::write_schema_version(&env, schema::SCHEMA_VERSION + 1));
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    assert_eq!(
+        client.try_create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600),
+        Err(Ok(GovernanceError::SchemaMismatch.into()))
+    );
+    assert_eq!(client.try_threshold(), Err(Ok(GovernanceError::SchemaMismatch.into())));
+    assert_eq!(client.try_migrate_proposals(&0, &10), Err(Ok(GovernanceError::SchemaMismatch.into())));
+}
+
+#[test]
+fn test_migration_restamps_older_schema() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    // As if written by the release before this one
+    env.as_contract(&contract_id, || storage::write_schema_version(&env, schema::SCHEMA_VERSION - 1));
+    assert_eq!(client.try_threshold(), Err(Ok(GovernanceError::SchemaMismatch.into())));
+
+    client.migrate_proposals(&0, &10);
+    assert_eq!(client.threshold(), 2);
+    assert_eq!(client.get_storage_schema().version, schema::SCHEMA_VERSION);
+}
+
+#[test]
+fn test_unstamped_deployment_runs_until_migrated() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    // Deployments from before the schema record have none at all
+    env.as_contract(&contract_id, || {
+        env.storage().instance().remove(&DataKey::Policy(PolicyKey::SchemaVersion));
+        env.storage().instance().remove(&DataKey::Policy(PolicyKey::SchemaFamilies));
+    });
+    assert_eq!(client.threshold(), 2);
+    assert_eq!(client.get_storage_schema().version, 0);
+
+    client.migrate_proposals(&0, &10);
+    assert_eq!(client.get_storage_schema().version, schema::SCHEMA_VERSION);
+}
+
+#[test]
+fn test_migrate_packs_legacy_executed_flags() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let executed = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+    let open = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+    let change = client.propose_signer_change(&signers.get_unchecked(0), &SignerChangeKind::Add, &BytesN::from_array(&env, &[9u8; 32]), &3600);
+
+    // Lay the flags out as a deployment from before they were packed
+    env.as_contract(&contract_id, || {
+        for id in [executed, open, change] {
+            env.storage().instance().remove(&DataKey::Proposals(ProposalKey::ProposalFlags(id)));
+        }
+        env.storage().instance().set(&DataKey::ProposalExecuted(executed), &true);
+        env.storage().instance().set(&DataKey::SignerChangeExecuted(change), &true);
+        env.storage().instance().remove(&DataKey::Policy(PolicyKey::SchemaVersion));
+    });
+
+    // Unmigrated proposals still read their old flags
+    assert!(client.is_proposal_executed(&executed));
+    assert!(client.is_signer_change_executed(&change));
+    assert!(!client.is_proposal_executed(&open));
+
+    client.migrate_proposals(&0, &10);
+
+    assert!(client.is_proposal_executed(&executed));
+    assert!(client.is_signer_change_executed(&change));
+    assert!(!client.is_proposal_executed(&open));
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&DataKey::ProposalExecuted(executed)));
+        assert!(!env.storage().instance().has(&DataKey::SignerChangeExecuted(change)));
+        assert_eq!(storage::read_proposal_flags(&env, executed), storage::FLAG_EXECUTED);
+        assert_eq!(storage::read_proposal_flags(&env, open), 0);
+    });
+}
+
+fn signing_key(seed: u8) -> SigningKey {
+    SigningKey::from_bytes(&[seed; 32])
+}
+
+// Signers whose keys are real ed25519 public keys, for signature-based entry points
+fn create_signing_signers(env: &Env, count: u8) -> Vec<BytesN<32>> {
+    let mut signers = Vec::new(env);
+    for seed in 1..=count {
+        signers.push_back(BytesN::from_array(env, &signing_key(seed).verifying_key().to_bytes()));
+    }
+    signers
+}
+
+// Signs the session as the signer with key `seed`, at their next nonce
+fn sign_session(env: &Env, client: &MultiSigContractClient, session_id: u64, seed: u8) -> (u64, BytesN<64>) {
+    let signer = BytesN::from_array(env, &signing_key(seed).verifying_key().to_bytes());
+    let payload = client.get_session_payload(&session_id, &signer);
+    (client.signer_nonce(&signer), BytesN::from_array(env, &signing_key(seed).sign(&payload.to_array()).to_bytes()))
+}
+
+#[test]
+fn test_signing_session_approves_every_proposal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let reason = String::from_str(&env, "Quarterly payout");
+    let mut proposal_ids = Vec::new(&env);
+    for _ in 0..5 {
+        let recipient = Address::generate(&env);
+        proposal_ids.push_back(client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &100, &reason, &7200));
+    }
+
+    let session_id = client.open_signing_session(&signers.get_unchecked(0), &proposal_ids, &3600);
+    let session = client.get_signing_session(&session_id);
+    assert_eq!(session.proposal_ids, proposal_ids);
+    assert_eq!(session.expires_at, 3600);
+
+    let first = sign_session(&env, &client, session_id, 1);
+    for seed in 1..=2u8 {
+        let (nonce, signature) = if seed == 1 { first.clone() } else { sign_session(&env, &client, session_id, seed) };
+        let report = client.approve_session(&session_id, &signers.get_unchecked(seed as u32 - 1), &nonce, &signature);
+        assert_eq!(report.approved, proposal_ids);
+        assert_eq!(report.already_approved.len() + report.amended.len() + report.unavailable.len(), 0);
+    }
+    for proposal_id in proposal_ids.iter() {
+        assert!(client.get_approval_progress(&proposal_id).ready);
+    }
+
+    // The accepted signature cannot be replayed, and signing again changes nothing
+    assert_eq!(
+        client.try_approve_session(&session_id, &signers.get_unchecked(0), &first.0, &first.1),
+        Err(Ok(MultisigError::InvalidNonce.into()))
+    );
+    let (nonce, signature) = sign_session(&env, &client, session_id, 1);
+    let report = client.approve_session(&session_id, &signers.get_unchecked(0), &nonce, &signature);
+    assert_eq!(report.already_approved, proposal_ids);
+    assert_eq!(client.get_proposal_approvals(&proposal_ids.get_unchecked(0)).len(), 2);
+}
+
+#[test]
+fn test_signing_session_skips_with_report() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let fresh = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &100, &reason, &7200);
+    let approved = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &100, &reason, &7200);
+    let amended = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &100, &reason, &7200);
+    let short_lived = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &100, &reason, &3600);
+    let ids = vec![&env, fresh, approved, amended, short_lived];
+    let session_id = client.open_signing_session(&signers.get_unchecked(1), &ids, &7200);
+
+    client.approve_proposal(&approved, &signers.get_unchecked(0));
+    env.as_contract(&contract_id, || {
+        let mut proposal = storage::read_proposal(&env, amended).unwrap();
+        proposal.reason = String::from_str(&env, "Payment, revised");
+        storage::write_proposal(&env, &proposal);
+    });
+    env.ledger().with_mut(|li| li.timestamp = 3601);
+
+    let (nonce, signature) = sign_session(&env, &client, session_id, 1);
+    let report = client.approve_session(&session_id, &signers.get_unchecked(0), &nonce, &signature);
+    assert_eq!(
+        report,
+        SessionApprovalReport {
+            approved: vec![&env, fresh],
+            already_approved: vec![&env, approved],
+            amended: vec![&env, amended],
+            unavailable: vec![&env, short_lived],
+        }
+    );
+    assert_eq!(client.get_approval_progress(&amended).approvals, 0);
+}
+
+#[test]
+fn test_signing_session_rejects_bad_signature_and_expiry() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &100, &reason, &7200);
+    let session_id = client.open_signing_session(&signers.get_unchecked(0), &vec![&env, proposal_id], &3600);
+
+    // Signed by someone else
+    let (nonce, signature) = sign_session(&env, &client, session_id, 2);
+    assert!(client.try_approve_session(&session_id, &signers.get_unchecked(0), &nonce, &signature).is_err());
+    assert_eq!(client.get_proposal_approvals(&proposal_id).len(), 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 3601);
+    let (nonce, signature) = sign_session(&env, &client, session_id, 1);
+    assert_eq!(
+        client.try_approve_session(&session_id, &signers.get_unchecked(0), &nonce, &signature),
+        Err(Ok(SignerError::SessionExpired.into()))
+    );
+    assert_eq!(
+        client.try_approve_session(&99, &signers.get_unchecked(0), &nonce, &signature),
+        Err(Ok(SignerError::SessionNotFound.into()))
+    );
+}
+
+#[test]
+fn test_signing_session_validates_proposals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &100, &reason, &3600);
+    let opener = signers.get_unchecked(0);
+
+    assert_eq!(
+        client.try_open_signing_session(&opener, &vec![&env, proposal_id, proposal_id], &3600),
+        Err(Ok(MultisigError::InvalidProposal.into()))
+    );
+    assert_eq!(client.try_open_signing_session(&opener, &Vec::new(&env), &3600), Err(Ok(MultisigError::InvalidProposal.into())));
+    assert_eq!(
+        client.try_open_signing_session(&opener, &vec![&env, proposal_id, 42], &3600),
+        Err(Ok(MultisigError::ProposalNotFound.into()))
+    );
+}
+
+#[test]
+fn test_destination_memo_is_stored_and_published() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let exchange = Address::generate(&env);
+    let action = ProposalAction::Transfer(TransferAction { token_address: token.clone(), recipient: exchange.clone(), amount: 250 });
+    let memo = String::from_str(&env, "104729");
+    let proposal_id = client.create_proposal_with_memo(&signers.get_unchecked(0), &action, &String::from_str(&env, "Deposit"), &memo, &3600);
+    assert_eq!(client.get_proposal(&proposal_id).destination_memo, Some(memo.clone()));
+
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    client.execute_proposal(&proposal_id);
+
+    let data: Map<Symbol, Val> = map![
+        &env,
+        (Symbol::new(&env, "amount"), 250i128.into_val(&env)),
+        (Symbol::new(&env, "destination_memo"), Some(memo).into_val(&env)),
+        (Symbol::new(&env, "document_hash"), None::<BytesN<32>>.into_val(&env)),
+        (Symbol::new(&env, "token"), token.into_val(&env)),
+    ];
+    let expected = (
+        contract_id.clone(),
+        (Symbol::new(&env, "payment_executed"), proposal_id, exchange).into_val(&env),
+        data.into_val(&env),
+    );
+    assert_eq!(contract_events(&env, &contract_id), vec![&env, expected]);
+}
+
+#[test]
+fn test_memo_required_recipients() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token = Address::generate(&env);
+    let exchange = Address::generate(&env);
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::RequireMemo(exchange.clone())), 2);
+    assert!(client.is_memo_required(&exchange));
+
+    let proposer = signers.get_unchecked(0);
+    let reason = String::from_str(&env, "Deposit");
+    let action = ProposalAction::Transfer(TransferAction { token_address: token.clone(), recipient: exchange.clone(), amount: 100 });
+    assert_eq!(
+        client.try_create_proposal(&proposer, &token, &exchange, &100, &reason, &3600),
+        Err(Ok(MultisigError::MemoRequired.into()))
+    );
+    assert_eq!(
+        client.try_create_proposal_with_memo(&proposer, &action, &reason, &String::from_str(&env, ""), &3600),
+        Err(Ok(MultisigError::MemoRequired.into()))
+    );
+    assert_eq!(client.validate_draft(&proposer, &token, &exchange, &100, &3600), vec![&env, Symbol::new(&env, "memo_required")]);
+    client.create_proposal_with_memo(&proposer, &action, &reason, &String::from_str(&env, "tag-7"), &3600);
+
+    // Memos belong to transfers only, and stay short
+    let allowance = ProposalAction::Approve(AllowanceAction {
+        token_address: token.clone(),
+        spender: Address::generate(&env),
+        amount: 100,
+        expiration_ledger: 1000,
+    });
+    assert_eq!(
+        client.try_create_proposal_with_memo(&proposer, &allowance, &reason, &String::from_str(&env, "tag-7"), &3600),
+        Err(Ok(MultisigError::InvalidProposal.into()))
+    );
+    assert_eq!(
+        client.try_create_proposal_with_memo(&proposer, &action, &reason, &String::from_bytes(&env, &[b'7'; 65]), &3600),
+        Err(Ok(MultisigError::InvalidProposal.into()))
+    );
+
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::ClearMemoRequirement(exchange.clone())), 2);
+    client.create_proposal(&proposer, &token, &exchange, &100, &reason, &3600);
+}
+
+#[test]
+fn test_proposal_stored_without_memo_or_document_still_reads() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+
+    env.as_contract(&contract_id, || {
+        let mut fields = storage::read_proposal_fields(&env, proposal_id).unwrap();
+        fields.remove(Symbol::new(&env, "destination_memo"));
+        fields.remove(Symbol::new(&env, "document_hash"));
+        env.storage().instance().set(&DataKey::Proposal(proposal_id), &fields);
+    });
+
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!(proposal.destination_memo, None);
+    assert_eq!(proposal.document_hash, None);
+    assert_eq!(proposal.reason, reason);
+}
+
+#[test]
+fn test_document_hash_required_above_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let threshold = DocumentThreshold { token: token.clone(), amount: 1000 };
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::SetDocumentThreshold(threshold)), 2);
+    assert_eq!(client.get_document_threshold(&token), Some(1000));
+
+    let proposer = signers.get_unchecked(0);
+    let reason = String::from_str(&env, "Invoice 17");
+    let invoice = BytesN::from_array(&env, &[17; 32]);
+    let action = ProposalAction::Transfer(TransferAction { token_address: token.clone(), recipient: recipient.clone(), amount: 1001 });
+
+    // At the threshold no document is needed; above it one is
+    client.create_proposal(&proposer, &token, &recipient, &1000, &reason, &3600);
+    assert_eq!(
+        client.try_create_proposal_v2(&proposer, &action, &reason, &3600),
+        Err(Ok(MultisigError::DocumentHashRequired.into()))
+    );
+    assert_eq!(client.validate_draft(&proposer, &token, &recipient, &1001, &3600), vec![&env, Symbol::new(&env, "document_required")]);
+
+    let first = client.create_proposal_with_document(&proposer, &action, &reason, &None, &invoice, &3600);
+    assert_eq!(client.get_proposal(&first).document_hash, Some(invoice.clone()));
+    assert_eq!(client.find_proposal_by_document(&invoice), vec![&env, first]);
+
+    // Approvals commit to the document along with everything else
+    env.as_contract(&contract_id, || {
+        let mut proposal = storage::read_proposal(&env, first).unwrap();
+        let signed = proposal.content_hash(&env);
+        proposal.document_hash = Some(BytesN::from_array(&env, &[18; 32]));
+        assert_ne!(proposal.content_hash(&env), signed);
+    });
+
+    // A second proposal against the same invoice shows up next to the first
+    let second = client.create_proposal_with_document(&proposer, &action, &reason, &None, &invoice, &3600);
+    assert_eq!(client.find_proposal_by_document(&invoice), vec![&env, first, second]);
+    assert_eq!(client.find_proposal_by_document(&BytesN::from_array(&env, &[18; 32])).len(), 0);
+
+    let result = client.try_propose_governance(
+        &proposer,
+        &GovernanceAction::Treasury(TreasuryAction::SetDocumentThreshold(DocumentThreshold { token: token.clone(), amount: -1 })),
+        &3600,
+    );
+    assert_eq!(result, Err(Ok(MultisigError::InvalidProposal.into())));
+
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::ClearDocumentThreshold(token.clone())), 2);
+    assert_eq!(client.get_document_threshold(&token), None);
+    client.create_proposal_v2(&proposer, &action, &reason, &3600);
+}
+
+#[test]
+fn test_document_hash_published_on_execution() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let recipient = Address::generate(&env);
+    let invoice = BytesN::from_array(&env, &[17; 32]);
+    let action = ProposalAction::Transfer(TransferAction { token_address: token.clone(), recipient: recipient.clone(), amount: 250 });
+    let proposal_id = client.create_proposal_with_document(&signers.get_unchecked(0), &action, &String::from_str(&env, "Invoice 17"), &None, &invoice, &3600);
+
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    client.execute_proposal(&proposal_id);
+
+    let data: Map<Symbol, Val> = map![
+        &env,
+        (Symbol::new(&env, "amount"), 250i128.into_val(&env)),
+        (Symbol::new(&env, "destination_memo"), None::<String>.into_val(&env)),
+        (Symbol::new(&env, "document_hash"), Some(invoice.clone()).into_val(&env)),
+        (Symbol::new(&env, "token"), token.into_val(&env)),
+    ];
+    let expected = (
+        contract_id.clone(),
+        (Symbol::new(&env, "payment_executed"), proposal_id, recipient).into_val(&env),
+        data.into_val(&env),
+    );
+    assert_eq!(contract_events(&env, &contract_id), vec![&env, expected]);
+
+    // Executed proposals stay findable by their document
+    assert_eq!(client.find_proposal_by_document(&invoice), vec![&env, proposal_id]);
+}
+
+fn sign_approval(env: &Env, client: &MultiSigContractClient, proposal_id: u64, seed: u8) -> BytesN<64> {
+    let signer = BytesN::from_array(env, &signing_key(seed).verifying_key().to_bytes());
+    let payload = client.get_approval_payload(&proposal_id, &signer);
+    BytesN::from_array(env, &signing_key(seed).sign(&payload.to_array()).to_bytes())
+}
+
+#[test]
+fn test_signed_approval_advances_nonce_and_cannot_be_replayed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let first = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &100, &reason, &3600);
+    let second = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &200, &reason, &3600);
+    let approver = signers.get_unchecked(1);
+
+    let signature = sign_approval(&env, &client, first, 2);
+    client.approve_proposal_signed(&first, &approver, &0, &signature);
+    assert_eq!(client.get_proposal_approvals(&first).get_unchecked(0).signer, approver);
+    assert_eq!(client.signer_nonce(&approver), 1);
+
+    // Replaying the accepted signature is refused on its spent nonce
+    client.revoke_approval(&first, &approver);
+    assert_eq!(
+        client.try_approve_proposal_signed(&first, &approver, &0, &signature),
+        Err(Ok(MultisigError::InvalidNonce.into()))
+    );
+    // and does not verify against the current one
+    assert!(client.try_approve_proposal_signed(&first, &approver, &1, &signature).is_err());
+    assert_eq!(client.get_proposal_approvals(&first).len(), 0);
+
+    // Nor does it carry over to another proposal
+    assert!(client.try_approve_proposal_signed(&second, &approver, &1, &signature).is_err());
+
+    let signature = sign_approval(&env, &client, second, 2);
+    client.approve_proposal_signed(&second, &approver, &1, &signature);
+    assert_eq!(client.signer_nonce(&approver), 2);
+}
+
+#[test]
+fn test_signed_approval_rejects_wrong_signer_and_proposal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let first = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &100, &reason, &3600);
+    let second = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &200, &reason, &3600);
+    let approver = signers.get_unchecked(1);
+
+    // Signed by a different key than the one it claims to come from
+    let signature = sign_approval(&env, &client, first, 3);
+    assert!(client.try_approve_proposal_signed(&first, &approver, &0, &signature).is_err());
+
+    // Signed by the approver, but over another proposal
+    let signature = sign_approval(&env, &client, second, 2);
+    assert!(client.try_approve_proposal_signed(&first, &approver, &0, &signature).is_err());
+
+    assert_eq!(client.get_proposal_approvals(&first).len(), 0);
+    assert_eq!(client.signer_nonce(&approver), 0);
+
+    // Signing does not get around the usual approval rules
+    let outsider = BytesN::from_array(&env, &signing_key(9).verifying_key().to_bytes());
+    assert_eq!(
+        client.try_approve_proposal_signed(&first, &outsider, &0, &sign_approval(&env, &client, first, 9)),
+        Err(Ok(MultisigError::UnknownSigner.into()))
+    );
+}
+
+#[test]
+fn test_payment_actions_need_signer_accounts_under_plain_initialize() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    env.mock_all_auths();
+    let token_address = create_funded_token(&env, &contract_id, 5000);
+    env.set_auths(&[]);
+
+    // Nobody can act for a signer by naming its public key
+    let thief = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposer = signers.get_unchecked(0);
+    assert!(client.try_create_proposal(&proposer, &token_address, &thief, &5000, &reason, &3600).is_err());
+
+    env.mock_all_auths();
+    let proposal_id = client.create_proposal(&proposer, &token_address, &thief, &5000, &reason, &3600);
+    client.approve_proposal(&proposal_id, &proposer);
+    client.delegate_vote(&proposal_id, &signers.get_unchecked(1), &signers.get_unchecked(2));
+    env.set_auths(&[]);
+
+    assert!(client.try_approve_proposal(&proposal_id, &signers.get_unchecked(2)).is_err());
+    assert!(client.try_delegate_vote(&proposal_id, &signers.get_unchecked(2), &proposer).is_err());
+    assert!(client.try_revoke_vote_delegation(&proposal_id, &signers.get_unchecked(1)).is_err());
+    assert!(client.try_revoke_approval(&proposal_id, &proposer).is_err());
+    assert!(client.try_cancel_proposal(&proposal_id, &proposer).is_err());
+    assert_eq!(client.get_proposal_approvals(&proposal_id).len(), 1);
+    assert_eq!(client.try_execute_proposal(&proposal_id), Err(Ok(MultisigError::InsufficientApprovals.into())));
+    assert_eq!(soroban_sdk::token::Client::new(&env, &token_address).balance(&thief), 0);
+}
+
+#[test]
+fn test_bound_signers_need_their_address_to_authorize() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let bindings = vec![&env, (signers.get_unchecked(0), alice.clone()), (signers.get_unchecked(1), bob.clone())];
+    client.initialize_with_addresses(&signers, &2, &false, &bindings);
+    assert_eq!(client.get_signer_address(&signers.get_unchecked(0)), Some(alice.clone()));
+    assert_eq!(client.get_signer_address(&signers.get_unchecked(2)), None);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposer = signers.get_unchecked(0);
+    let args = (proposer.clone(), token_address.clone(), recipient.clone(), 100i128, reason.clone(), 3600u64).into_val(&env);
+
+    // Knowing the key is not enough, and neither is another signer's address
+    assert!(client.try_create_proposal(&proposer, &token_address, &recipient, &100, &reason, &3600).is_err());
+    let invoke = MockAuthInvoke { contract: &contract_id, fn_name: "create_proposal", args, sub_invokes: &[] };
+    assert!(client
+        .mock_auths(&[MockAuth { address: &bob, invoke: &invoke }])
+        .try_create_proposal(&proposer, &token_address, &recipient, &100, &reason, &3600)
+        .is_err());
+
+    let proposal_id = client
+        .mock_auths(&[MockAuth { address: &alice, invoke: &invoke }])
+        .create_proposal(&proposer, &token_address, &recipient, &100, &reason, &3600);
+
+    // An unbound signer acts through its own account, not the key alone
+    assert!(client.try_approve_proposal(&proposal_id, &signers.get_unchecked(2)).is_err());
+    assert!(client.try_revoke_approval(&proposal_id, &signers.get_unchecked(1)).is_err());
+
+    env.mock_all_auths();
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, bob);
+    assert_eq!(
+        auths[0].1.function,
+        AuthorizedFunction::Contract((
+            contract_id.clone(),
+            Symbol::new(&env, "approve_proposal"),
+            (proposal_id, signers.get_unchecked(1)).into_val(&env),
+        ))
+    );
+    client.revoke_approval(&proposal_id, &signers.get_unchecked(1));
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(env.auths()[0].0, bob);
+}
+
+#[test]
+fn test_signer_address_bindings() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    let alice = Address::generate(&env);
+    let stranger = BytesN::from_array(&env, &[42u8; 32]);
+    assert_eq!(
+        client.try_initialize_with_addresses(&signers, &2, &false, &vec![&env, (stranger, alice.clone())]),
+        Err(Ok(MultisigError::UnknownSigner.into()))
+    );
+    let twice = vec![&env, (signers.get_unchecked(0), alice.clone()), (signers.get_unchecked(0), Address::generate(&env))];
+    assert_eq!(
+        client.try_initialize_with_addresses(&signers, &2, &false, &twice),
+        Err(Ok(MultisigError::DuplicateSigner.into()))
+    );
+
+    let removed = signers.get_unchecked(2);
+    let bindings = vec![&env, (signers.get_unchecked(0), alice.clone()), (removed.clone(), Address::generate(&env))];
+    client.initialize_with_addresses(&signers, &2, &false, &bindings);
+    let remove = SignerChangeKind::Remove;
+
+    // Signer changes are covered too
+    assert!(client.try_propose_signer_change(&signers.get_unchecked(0), &remove, &removed, &3600).is_err());
+    env.mock_all_auths();
+    let proposal_id = client.propose_signer_change(&signers.get_unchecked(1), &remove, &removed, &3600);
+    env.set_auths(&[]);
+    assert!(client.try_approve_signer_change(&proposal_id, &signers.get_unchecked(0)).is_err());
+
+    env.mock_all_auths();
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(0));
+    assert_eq!(env.auths()[0].0, alice);
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(1));
+    client.execute_signer_change(&proposal_id);
+
+    // The binding leaves with the signer
+    assert_eq!(client.get_signer_address(&removed), None);
+}
+
+#[test]
+fn test_payment_proof_round_trip() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = create_funded_token(&env, &contract_id, 5000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Invoice 42");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(2));
+
+    assert_eq!(client.try_get_payment_proof(&proposal_id), Err(Ok(MultisigError::ProposalNotExecuted.into())));
+    assert_eq!(client.try_get_payment_proof(&99), Err(Ok(MultisigError::ProposalNotFound.into())));
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    client.execute_proposal(&proposal_id);
+
+    let proof = client.get_payment_proof(&proposal_id);
+    assert_eq!(proof.proposal, client.get_proposal(&proposal_id));
+    assert_eq!(proof.receipt.executed_at, 500);
+    assert_eq!(proof.receipt.approvers, vec![&env, signers.get_unchecked(0), signers.get_unchecked(2)]);
+    assert!(client.verify_payment_proof(&proof));
+
+    // Any edit to the payment details breaks the proof
+    let mut inflated = proof.clone();
+    if let ProposalAction::Transfer(transfer) = &mut inflated.proposal.action {
+        transfer.amount = 10_000;
+    }
+    assert!(!client.verify_payment_proof(&inflated));
+
+    // So does rehashing the edit, since the contract kept its own hash
+    inflated.content_hash = env.as_contract(&contract_id, || inflated.proposal.content_hash(&env));
+    assert!(!client.verify_payment_proof(&inflated));
+
+    // Or claiming a different set of approvers
+    let mut forged = proof.clone();
+    forged.receipt.approvers = vec![&env, signers.get_unchecked(1), signers.get_unchecked(2)];
+    assert!(!client.verify_payment_proof(&forged));
+
+    let mut elsewhere = proof;
+    elsewhere.proposal.id = 99;
+    assert!(!client.verify_payment_proof(&elsewhere));
+}
+
+fn sign_execution(env: &Env, client: &MultiSigContractClient, proposal_id: u64, seed: u8) -> (BytesN<32>, u64, BytesN<64>) {
+    let signer = BytesN::from_array(env, &signing_key(seed).verifying_key().to_bytes());
+    let payload = client.get_approval_payload(&proposal_id, &signer);
+    let signature = BytesN::from_array(env, &signing_key(seed).sign(&payload.to_array()).to_bytes());
+    (signer.clone(), client.signer_nonce(&signer), signature)
+}
+
+#[test]
+fn test_execute_with_offline_signatures() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = create_funded_token(&env, &contract_id, 5000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+
+    // One signature is not enough on its own
+    let first = sign_execution(&env, &client, proposal_id, 1);
+    assert_eq!(
+        client.try_execute_with_signatures(&proposal_id, &vec![&env, first.clone()]),
+        Err(Ok(MultisigError::InsufficientApprovals.into()))
+    );
+
+    let signatures = vec![&env, first, sign_execution(&env, &client, proposal_id, 3)];
+    client.execute_with_signatures(&proposal_id, &signatures);
+
+    assert!(client.is_proposal_executed(&proposal_id));
+    assert_eq!(client.nonce(), 1);
+    assert_eq!(soroban_sdk::token::Client::new(&env, &token_address).balance(&recipient), 1000);
+    assert_eq!(
+        client.get_execution_receipt(&proposal_id).unwrap().approvers,
+        vec![&env, signers.get_unchecked(0), signers.get_unchecked(2)]
+    );
+    assert_eq!(
+        client.try_execute_with_signatures(&proposal_id, &signatures),
+        Err(Ok(MultisigError::ProposalAlreadyExecuted.into()))
+    );
+}
+
+#[test]
+fn test_execute_with_signatures_rejects_bad_sets() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+    let first = sign_execution(&env, &client, proposal_id, 1);
+
+    assert_eq!(
+        client.try_execute_with_signatures(&proposal_id, &vec![&env, first.clone(), first.clone()]),
+        Err(Ok(MultisigError::DuplicateSigner.into()))
+    );
+    assert_eq!(
+        client.try_execute_with_signatures(&proposal_id, &vec![&env, first.clone(), sign_execution(&env, &client, proposal_id, 9)]),
+        Err(Ok(MultisigError::UnknownSigner.into()))
+    );
+
+    // A signature from one signer presented under another's key
+    let (_, nonce, stolen) = sign_execution(&env, &client, proposal_id, 1);
+    assert!(client
+        .try_execute_with_signatures(&proposal_id, &vec![&env, first.clone(), (signers.get_unchecked(1), nonce, stolen)])
+        .is_err());
+    assert_eq!(client.get_proposal_approvals(&proposal_id).len(), 0);
+
+    // A signature offered at a nonce other than the signer's next one
+    let (key, nonce, signature) = first.clone();
+    assert_eq!(
+        client.try_execute_with_signatures(&proposal_id, &vec![&env, (key, nonce + 1, signature)]),
+        Err(Ok(MultisigError::InvalidNonce.into()))
+    );
+
+    let second = sign_execution(&env, &client, proposal_id, 2);
+    env.ledger().with_mut(|li| li.timestamp = 3601);
+    assert_eq!(
+        client.try_execute_with_signatures(&proposal_id, &vec![&env, first, second]),
+        Err(Ok(MultisigError::ProposalExpired.into()))
+    );
+}
+
+#[test]
+fn test_execute_with_signatures_needs_a_quorum_of_signatures() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 3);
+    client.initialize_with_signers(&as_signers(&env, &signers), &2, &false, &None, &Vec::new(&env), &true);
+    let token_address = create_funded_token(&env, &contract_id, 5000);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &Address::generate(&env), &1000, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+
+    // Approvals already on-chain do not let an outsider execute without signatures
+    assert_eq!(client.try_execute_proposal(&proposal_id), Err(Ok(MultisigError::ExecutionTierClosed.into())));
+    assert_eq!(
+        client.try_execute_with_signatures(&proposal_id, &Vec::new(&env)),
+        Err(Ok(MultisigError::InsufficientApprovals.into()))
+    );
+    let first = sign_execution(&env, &client, proposal_id, 1);
+    assert_eq!(
+        client.try_execute_with_signatures(&proposal_id, &vec![&env, first.clone()]),
+        Err(Ok(MultisigError::InsufficientApprovals.into()))
+    );
+
+    client.execute_with_signatures(&proposal_id, &vec![&env, first, sign_execution(&env, &client, proposal_id, 2)]);
+    assert!(client.is_proposal_executed(&proposal_id));
+}
+
+#[test]
+fn test_redundancy_lost_and_restored() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    assert_eq!(
+        client.get_config(),
+        MultisigConfig { signer_count: 3, threshold: 2, redundancy_policy: RedundancyPolicy::Warn, no_redundancy: false }
+    );
+
+    // Warn is the default: the removal goes through but raises the flag
+    let removed = signers.get_unchecked(2);
+    run_signer_change(&client, &signers, SignerChangeKind::Remove, &removed);
+    let data: Map<Symbol, Val> = map![
+        &env,
+        (Symbol::new(&env, "signer_count"), 2u32.into_val(&env)),
+        (Symbol::new(&env, "threshold"), 2u32.into_val(&env)),
+    ];
+    let lost = (contract_id.clone(), (Symbol::new(&env, "redundancy_lost"),).into_val(&env), data.into_val(&env));
+    let events = contract_events(&env, &contract_id);
+    let fingerprint = fingerprint_event(&env, &contract_id, &client.get_config_fingerprint());
+    assert_eq!(events, vec![&env, lost, fingerprint]);
+    assert!(client.get_config().no_redundancy);
+
+    run_signer_change(&client, &signers, SignerChangeKind::Add, &removed);
+    let data: Map<Symbol, Val> = map![
+        &env,
+        (Symbol::new(&env, "signer_count"), 3u32.into_val(&env)),
+        (Symbol::new(&env, "threshold"), 2u32.into_val(&env)),
+    ];
+    let restored = (contract_id.clone(), (Symbol::new(&env, "redundancy_restored"),).into_val(&env), data.into_val(&env));
+    let events = contract_events(&env, &contract_id);
+    let fingerprint = fingerprint_event(&env, &contract_id, &client.get_config_fingerprint());
+    assert_eq!(events, vec![&env, restored, fingerprint]);
+    assert!(!client.get_config().no_redundancy);
+
+    // A signer set with no spare keys is flagged from the start
+    let tight_id = env.register(MultiSigContract, ());
+    let tight = MultiSigContractClient::new(&env, &tight_id);
+    tight.initialize(&signers, &3, &false);
+    assert!(tight.get_config().no_redundancy);
+}
+
+#[test]
+fn test_block_policy_requires_accepting_removal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let remove = SignerChangeKind::Remove;
+    let removed = signers.get_unchecked(2);
+
+    // Proposed under Warn, so only the execution-time check catches it
+    let earlier = client.propose_signer_change(&signers.get_unchecked(0), &remove, &removed, &3600);
+    client.approve_signer_change(&earlier, &signers.get_unchecked(0));
+    client.approve_signer_change(&earlier, &signers.get_unchecked(1));
+
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::SetRedundancyPolicy(RedundancyPolicy::Block)), 2);
+    assert_eq!(client.get_config().redundancy_policy, RedundancyPolicy::Block);
+
+    assert_eq!(client.try_execute_signer_change(&earlier), Err(Ok(SignerError::RedundancyRequired.into())));
+    assert_eq!(
+        client.try_propose_signer_change(&signers.get_unchecked(0), &remove, &removed, &3600),
+        Err(Ok(SignerError::RedundancyRequired.into()))
+    );
+    assert_eq!(
+        client.try_propose_signer_removal(&signers.get_unchecked(0), &removed, &3600, &false),
+        Err(Ok(SignerError::RedundancyRequired.into()))
+    );
+
+    let accepted = client.propose_signer_removal(&signers.get_unchecked(0), &removed, &3600, &true);
+    assert!(client.get_signer_change_proposal(&accepted).accept_no_redundancy);
+    client.approve_signer_change(&accepted, &signers.get_unchecked(0));
+    client.approve_signer_change(&accepted, &signers.get_unchecked(1));
+    client.execute_signer_change(&accepted);
+
+    assert_eq!(client.signer_count(), 2);
+    assert!(client.get_config().no_redundancy);
+}
+
+#[test]
+fn test_proposal_hash_matches_raw_fields() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposer = signers.get_unchecked(0);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let proposal_id = client.create_proposal(&proposer, &token_address, &recipient, &250, &reason, &3600);
+
+    let action = ProposalAction::Transfer(TransferAction { token_address, recipient, amount: 250 });
+    let fields = (proposal_id, proposer, action, reason, 1_000u64, 4_600u64, None::<String>, None::<BytesN<32>>);
+    let content_hash: BytesN<32> = env.crypto().sha256(&fields.to_xdr(&env)).into();
+    let expected: BytesN<32> = env.crypto().sha256(&(contract_id.clone(), content_hash.clone(), 0u64).to_xdr(&env)).into();
+
+    assert_eq!(client.get_proposal_hash(&proposal_id), expected);
+    assert_eq!(client.try_get_proposal_hash(&99), Err(Ok(MultisigError::ProposalNotFound.into())));
+
+    // secp256k1 signers sign the same contents hashed with keccak256
+    let expected: BytesN<32> = env.crypto().keccak256(&(contract_id.clone(), content_hash, 0u64).to_xdr(&env)).into();
+    assert_eq!(client.get_proposal_hash_keccak(&proposal_id), expected);
+}
+
+#[test]
+fn test_proposal_hash_changes_with_every_field() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = create_funded_token(&env, &contract_id, 5000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &250, &reason, &3600);
+    let original = client.get_proposal(&proposal_id);
+    let original_hash = client.get_proposal_hash(&proposal_id);
+
+    let hash_of = |proposal: &Proposal| {
+        env.as_contract(&contract_id, || storage::write_proposal(&env, proposal));
+        client.get_proposal_hash(&proposal.id)
+    };
+    let transfer_edits: [fn(&Env, &mut TransferAction); 3] = [
+        |env, transfer| transfer.token_address = Address::generate(env),
+        |env, transfer| transfer.recipient = Address::generate(env),
+        |_, transfer| transfer.amount = 251,
+    ];
+    for edit in transfer_edits {
+        let mut proposal = original.clone();
+        let ProposalAction::Transfer(mut transfer) = proposal.action.clone() else { unreachable!() };
+        edit(&env, &mut transfer);
+        proposal.action = ProposalAction::Transfer(transfer);
+        assert_ne!(hash_of(&proposal), original_hash);
+    }
+    let mut later = original.clone();
+    later.expires_at += 1;
+    assert_ne!(hash_of(&later), original_hash);
+    let mut renumbered = original.clone();
+    renumbered.id = 99;
+    assert_ne!(hash_of(&renumbered), original_hash);
+
+    assert_eq!(hash_of(&original), original_hash);
+
+    // Executing any payment moves the nonce on
+    let other = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &100, &reason, &3600);
+    client.approve_proposal(&other, &signers.get_unchecked(0));
+    client.approve_proposal(&other, &signers.get_unchecked(1));
+    client.execute_proposal(&other);
+    assert_ne!(client.get_proposal_hash(&proposal_id), original_hash);
+
+    // The same proposal held by another contract hashes differently
+    let twin_id = env.register(MultiSigContract, ());
+    let twin = MultiSigContractClient::new(&env, &twin_id);
+    twin.initialize(&signers, &2, &false);
+    env.as_contract(&twin_id, || storage::write_proposal(&env, &original));
+    assert_ne!(twin.get_proposal_hash(&proposal_id), original_hash);
+}
+
+#[test]
+fn test_bulk_approvals_skip_missing_ids() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let first = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &100, &reason, &3600);
+    let second = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &200, &reason, &3600);
+    client.approve_proposal(&first, &signers.get_unchecked(1));
+    let change = client.propose_signer_change(&signers.get_unchecked(0), &SignerChangeKind::Remove, &signers.get_unchecked(2), &3600);
+    client.approve_signer_change(&change, &signers.get_unchecked(0));
+
+    let bulk = client.get_approvals_bulk(&vec![&env, second, 99, first, change]);
+    assert_eq!(
+        bulk,
+        vec![
+            &env,
+            (second, Vec::new(&env)),
+            (first, client.get_proposal_approvals(&first)),
+        ]
+    );
+    assert_eq!(bulk.get_unchecked(1).1.get_unchecked(0).signer, signers.get_unchecked(1));
+
+    let bulk = client.get_signer_change_approvals_bulk(&vec![&env, first, change, 99]);
+    assert_eq!(bulk, vec![&env, (change, client.get_signer_change_approvals(&change))]);
+    assert_eq!(bulk.get_unchecked(0).1.len(), 1);
+
+    assert_eq!(client.get_approvals_bulk(&Vec::new(&env)).len(), 0);
+    let mut too_many = Vec::new(&env);
+    for id in 0..=pagination::MAX_PAGE_SIZE as u64 {
+        too_many.push_back(id);
+    }
+    assert_eq!(client.try_get_approvals_bulk(&too_many), Err(Ok(MultisigError::PageTooLarge.into())));
+    assert_eq!(client.try_get_signer_change_approvals_bulk(&too_many), Err(Ok(MultisigError::PageTooLarge.into())));
+}
+
+#[contract]
+pub struct OwnedVault;
+
+#[contractimpl]
+impl OwnedVault {
+    pub fn withdraw(env: Env, owner: Address, amount: i128) {
+        owner.require_auth_for_args(vec![&env, amount.into_val(&env)]);
+    }
+}
+
+// An authorization entry for `invoke` on behalf of the multisig, signed by the
+// signers with keys `seeds`
+fn multisig_auth(
+    env: &Env,
+    multisig: &Address,
+    invoke: &MockAuthInvoke,
+    seeds: &[u8],
+) -> soroban_sdk::xdr::SorobanAuthorizationEntry {
+    use soroban_sdk::xdr::{Hash, HashIdPreimage, HashIdPreimageSorobanAuthorization, Limits, ScVal, SorobanCredentials, WriteXdr};
+    use soroban_sdk::TryFromVal;
+
+    let mut entry: soroban_sdk::xdr::SorobanAuthorizationEntry = (&MockAuth { address: multisig, invoke }).into();
+    let SorobanCredentials::Address(credentials) = &mut entry.credentials else { unreachable!() };
+    let preimage = HashIdPreimage::SorobanAuthorization(HashIdPreimageSorobanAuthorization {
+        network_id: Hash(env.ledger().network_id().to_array()),
+        nonce: credentials.nonce,
+        signature_expiration_ledger: credentials.signature_expiration_ledger,
+        invocation: entry.root_invocation.clone(),
+    });
+    let payload = env.crypto().sha256(&soroban_sdk::Bytes::from_slice(env, &preimage.to_xdr(Limits::none()).unwrap()));
+
+    let mut signatures: Vec<(BytesN<32>, BytesN<64>)> = Vec::new(env);
+    for seed in seeds {
+        let key = signing_key(*seed);
+        signatures.push_back((
+            BytesN::from_array(env, &key.verifying_key().to_bytes()),
+            BytesN::from_array(env, &key.sign(&payload.to_array()).to_bytes()),
+        ));
+    }
+    credentials.signature = ScVal::try_from_val(env, &signatures.to_val()).unwrap();
+    entry
+}
+
+#[test]
+fn test_multisig_authorizes_as_account() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+    client.initialize(&create_signing_signers(&env, 3), &2, &false);
+
+    let vault_id = env.register(OwnedVault, ());
+    let vault = OwnedVaultClient::new(&env, &vault_id);
+    let invoke = MockAuthInvoke { contract: &vault_id, fn_name: "withdraw", args: vec![&env, 500i128.into_val(&env)], sub_invokes: &[] };
+
+    // Two of three signers meet the threshold
+    vault.set_auths(&[multisig_auth(&env, &contract_id, &invoke, &[1, 3])]).withdraw(&contract_id, &500);
+
+    // One signer, or the same signer twice, does not
+    assert!(vault.set_auths(&[multisig_auth(&env, &contract_id, &invoke, &[2])]).try_withdraw(&contract_id, &500).is_err());
+    assert!(vault.set_auths(&[multisig_auth(&env, &contract_id, &invoke, &[2, 2])]).try_withdraw(&contract_id, &500).is_err());
+
+    // Nor do keys outside the signer set
+    assert!(vault.set_auths(&[multisig_auth(&env, &contract_id, &invoke, &[1, 9])]).try_withdraw(&contract_id, &500).is_err());
+
+    // Signatures only cover the invocation they were made for
+    let entry = multisig_auth(&env, &contract_id, &invoke, &[1, 2]);
+    assert!(vault.set_auths(&[entry]).try_withdraw(&contract_id, &600).is_err());
+}
+
+#[test]
+fn test_check_auth_reports_errors() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+    client.initialize(&create_signing_signers(&env, 3), &2, &false);
+
+    let payload = BytesN::from_array(&env, &[7; 32]);
+    let sign = |seed: u8| {
+        let key = signing_key(seed);
+        (
+            BytesN::from_array(&env, &key.verifying_key().to_bytes()),
+            BytesN::from_array(&env, &key.sign(&payload.to_array()).to_bytes()),
+        )
+    };
+    let check = |signatures: Vec<(BytesN<32>, BytesN<64>)>| {
+        env.try_invoke_contract_check_auth::<MultisigError>(&contract_id, &payload, signatures.into_val(&env), &vec![&env])
+    };
+
+    assert_eq!(check(vec![&env, sign(1), sign(2)]), Ok(()));
+    assert_eq!(check(vec![&env, sign(1)]), Err(Ok(MultisigError::InsufficientApprovals)));
+    assert_eq!(check(vec![&env, sign(1), sign(1)]), Err(Ok(MultisigError::DuplicateSigner)));
+    assert_eq!(check(vec![&env, sign(1), sign(9)]), Err(Ok(MultisigError::UnknownSigner)));
+}
+
+#[test]
+fn test_check_auth_keeps_treasury_controls() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let payload = BytesN::from_array(&env, &[7; 32]);
+    let mut signatures: Vec<(BytesN<32>, BytesN<64>)> = Vec::new(&env);
+    for seed in [1, 2] {
+        let key = signing_key(seed);
+        signatures.push_back((
+            BytesN::from_array(&env, &key.verifying_key().to_bytes()),
+            BytesN::from_array(&env, &key.sign(&payload.to_array()).to_bytes()),
+        ));
+    }
+    let call = |contract: &Address, function: &str, args: Vec<Val>| {
+        soroban_sdk::auth::Context::Contract(soroban_sdk::auth::ContractContext { contract: contract.clone(), fn_name: Symbol::new(&env, function), args })
+    };
+    let check = |contexts: Vec<soroban_sdk::auth::Context>| {
+        env.try_invoke_contract_check_auth::<GovernanceError>(&contract_id, &payload, signatures.clone().into_val(&env), &contexts)
+    };
+
+    // Threshold signatures cannot move the treasury around its proposals
+    let token = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let transfer = call(&token, "transfer", (contract_id.clone(), recipient.clone(), 1000i128).into_val(&env));
+    assert_eq!(check(vec![&env, transfer]), Err(Ok(GovernanceError::AccountCallForbidden)));
+    let allowance = call(&token, "approve", (contract_id.clone(), recipient, 1000i128, 100u32).into_val(&env));
+    assert_eq!(check(vec![&env, allowance]), Err(Ok(GovernanceError::AccountCallForbidden)));
+
+    // Other calls are authorized, until payments are paused
+    let vault = Address::generate(&env);
+    let withdraw = call(&vault, "withdraw", vec![&env, 500i128.into_val(&env)]);
+    assert_eq!(check(vec![&env, withdraw.clone()]), Ok(()));
+    env.mock_all_auths();
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::Pause), 2);
+    assert_eq!(check(vec![&env, withdraw]), Err(Ok(GovernanceError::ContractPaused)));
+}
+
+#[test]
+fn test_scheduled_execution_tiers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token = create_funded_token(&env, &contract_id, 3000);
+    let recipient = Address::generate(&env);
+    let executor = Address::generate(&env);
+    let schedule = ExecutionSchedule { executors: vec![&env, Address::generate(&env), executor.clone()], fallback_at: 1000, public_at: 2000 };
+    let action = ProposalAction::Transfer(TransferAction { token_address: token.clone(), recipient: recipient.clone(), amount: 1000 });
+    let reason = String::from_str(&env, "Payroll");
+
+    let mut proposal_ids = Vec::new(&env);
+    for _ in 0..3 {
+        let proposal_id = client.create_proposal_with_schedule(&signers.get_unchecked(0), &action, &reason, &3600, &schedule);
+        client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+        client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+        proposal_ids.push_back(proposal_id);
+    }
+    assert_eq!(client.get_execution_schedule(&proposal_ids.get_unchecked(0)), Some(schedule));
+
+    let executed_by = |proposal_id: u64, tier: ExecutionTier| {
+        let tier_data: Map<Symbol, Val> = map![&env, (Symbol::new(&env, "tier"), tier.into_val(&env))];
+        let payment_data: Map<Symbol, Val> = map![
+            &env,
+            (Symbol::new(&env, "amount"), 1000i128.into_val(&env)),
+            (Symbol::new(&env, "destination_memo"), None::<String>.into_val(&env)),
+            (Symbol::new(&env, "document_hash"), None::<BytesN<32>>.into_val(&env)),
+            (Symbol::new(&env, "token"), token.into_val(&env)),
+        ];
+        vec![
+            &env,
+            (contract_id.clone(), (Symbol::new(&env, "scheduled_execution"), proposal_id).into_val(&env), tier_data.into_val(&env)),
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "payment_executed"), proposal_id, recipient.clone()).into_val(&env),
+                payment_data.into_val(&env),
+            ),
+        ]
+    };
+
+    // Before the fallback only the committee may execute
+    env.ledger().with_mut(|li| li.timestamp = 999);
+    let first = proposal_ids.get_unchecked(0);
+    assert_eq!(client.try_execute_proposal(&first), Err(Ok(MultisigError::ExecutionTierClosed.into())));
+    assert_eq!(client.try_execute_as_executor(&first, &executor), Err(Ok(MultisigError::ExecutionTierClosed.into())));
+    client.execute_as_signer(&first, &signers.get_unchecked(2));
+    assert_eq!(contract_events(&env, &contract_id), executed_by(first, ExecutionTier::Committee));
+
+    // From the fallback the named executors may too, but nobody else
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let second = proposal_ids.get_unchecked(1);
+    assert_eq!(client.try_execute_proposal(&second), Err(Ok(MultisigError::ExecutionTierClosed.into())));
+    assert_eq!(
+        client.try_execute_as_executor(&second, &Address::generate(&env)),
+        Err(Ok(MultisigError::ExecutionTierClosed.into()))
+    );
+    client.execute_as_executor(&second, &executor);
+    assert_eq!(contract_events(&env, &contract_id), executed_by(second, ExecutionTier::Executor));
+
+    // From the second deadline anyone may
+    env.ledger().with_mut(|li| li.timestamp = 1999);
+    let third = proposal_ids.get_unchecked(2);
+    assert_eq!(client.try_execute_proposal(&third), Err(Ok(MultisigError::ExecutionTierClosed.into())));
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    client.execute_proposal(&third);
+    assert_eq!(contract_events(&env, &contract_id), executed_by(third, ExecutionTier::Public));
+
+    assert_eq!(soroban_sdk::token::Client::new(&env, &token).balance(&recipient), 3000);
+}
+
+#[test]
+fn test_execution_schedule_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let proposer = signers.get_unchecked(0);
+    let executor = Address::generate(&env);
+    let action = ProposalAction::Transfer(TransferAction { token_address: Address::generate(&env), recipient: Address::generate(&env), amount: 100 });
+    let reason = String::from_str(&env, "Payroll");
+
+    let invalid = [
+        ExecutionSchedule { executors: Vec::new(&env), fallback_at: 1000, public_at: 2000 },
+        ExecutionSchedule { executors: vec![&env, executor.clone()], fallback_at: 2000, public_at: 1000 },
+        ExecutionSchedule { executors: vec![&env, executor.clone(), executor.clone()], fallback_at: 1000, public_at: 2000 },
+    ];
+    for schedule in invalid {
+        assert_eq!(
+            client.try_create_proposal_with_schedule(&proposer, &action, &reason, &3600, &schedule),
+            Err(Ok(MultisigError::InvalidProposal.into()))
+        );
+    }
+
+    // Proposals without a schedule have no executors, and stay open to anyone
+    let proposal_id = client.create_proposal_v2(&proposer, &action, &reason, &3600);
+    assert_eq!(client.get_execution_schedule(&proposal_id), None);
+    assert_eq!(
+        client.try_execute_as_executor(&proposal_id, &executor),
+        Err(Ok(MultisigError::ExecutionTierClosed.into()))
+    );
+    assert_eq!(client.try_execute_proposal(&proposal_id), Err(Ok(MultisigError::InsufficientApprovals.into())));
+}
+
+#[test]
+fn test_contract_signer_approves_transfer() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let keys = create_signing_signers(&env, 2);
+    let dao = Address::generate(&env);
+    let signers = vec![&env, Signer::Ed25519(keys.get_unchecked(0)), Signer::Ed25519(keys.get_unchecked(1)), Signer::Contract(dao.clone())];
+    client.initialize_with_signers(&signers, &2, &false, &None, &Vec::new(&env), &false);
+
+    let dao_id = client.signer_id(&Signer::Contract(dao.clone()));
+    assert_eq!(client.signer_count(), 3);
+    assert!(client.is_signer_v2(&Signer::Contract(dao.clone())));
+    assert!(client.is_signer_v2(&Signer::Ed25519(keys.get_unchecked(0))));
+    assert!(client.is_signer(&dao_id));
+    assert_eq!(client.get_signer_address(&dao_id), Some(dao.clone()));
+
+    env.mock_all_auths();
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&keys.get_unchecked(0), &token, &recipient, &400, &reason, &3600);
+    env.set_auths(&[]);
+    client.approve_proposal_signed(&proposal_id, &keys.get_unchecked(0), &0, &sign_approval(&env, &client, proposal_id, 1));
+
+    // The contract approves through its own authorization, not a signature
+    assert!(client.try_approve_proposal_as_contract(&proposal_id, &dao).is_err());
+    client
+        .mock_auths(&[MockAuth {
+            address: &dao,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "approve_proposal_as_contract",
+                args: (proposal_id, dao.clone()).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .approve_proposal_as_contract(&proposal_id, &dao);
+    assert_eq!(client.get_proposal_approvals(&proposal_id).get_unchecked(1).signer, dao_id);
+
+    client.execute_proposal(&proposal_id);
+    assert_eq!(soroban_sdk::token::Client::new(&env, &token).balance(&recipient), 400);
+
+    // Contracts outside the signer set cannot approve
+    env.mock_all_auths();
+    let other = client.create_proposal(&keys.get_unchecked(0), &token, &recipient, &100, &reason, &3600);
+    assert_eq!(
+        client.try_approve_proposal_as_contract(&other, &Address::generate(&env)),
+        Err(Ok(MultisigError::UnknownSigner.into()))
+    );
+}
+
+#[test]
+fn test_contract_signer_added_and_removed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let dao = Address::generate(&env);
+    let dao_signer = Signer::Contract(dao.clone());
+    let dao_id = client.signer_id(&dao_signer);
+    let add = SignerChangeKind::Add;
+    let proposal_id = client.propose_signer_change_v2(&signers.get_unchecked(0), &add, &dao_signer, &3600);
+
+    // Nothing is bound to the id until the addition executes
+    assert_eq!(client.get_signer_address(&dao_id), None);
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(0));
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(1));
+    client.execute_signer_change(&proposal_id);
+    assert!(client.is_signer_v2(&dao_signer));
+    assert_eq!(client.get_signer_address(&dao_id), Some(dao.clone()));
+
+    // Under its id the contract acts like any bound signer
+    let remove = SignerChangeKind::Remove;
+    let proposal_id = client.propose_signer_change_v2(&signers.get_unchecked(0), &remove, &dao_signer, &3600);
+    client.approve_signer_change(&proposal_id, &dao_id);
+    assert_eq!(env.auths()[0].0, dao);
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(0));
+    client.execute_signer_change(&proposal_id);
+
+    assert!(!client.is_signer_v2(&dao_signer));
+    assert_eq!(client.get_signer_address(&dao_id), None);
+}
+
+// Applies pages of `get_changes_since` from `cursor` until caught up
+fn sync_changes(client: &MultiSigContractClient, state: &mut Map<u64, ProposalState>, mut cursor: u64) -> u64 {
+    loop {
+        let page = client.get_changes_since(&cursor, &3);
+        for proposal in page.proposals.iter() {
+            state.set(proposal.proposal.id, proposal);
+        }
+        cursor = page.next_seq;
+        if !page.has_more {
+            return cursor;
+        }
+    }
+}
+
+#[test]
+fn test_changes_since_matches_full_read() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token = create_funded_token(&env, &contract_id, 5000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let mut ids = Vec::new(&env);
+    for amount in [100i128, 200, 300, 400] {
+        ids.push_back(client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &amount, &reason, &3600));
+    }
+
+    client.approve_proposal(&ids.get_unchecked(0), &signers.get_unchecked(0));
+    client.approve_proposal(&ids.get_unchecked(0), &signers.get_unchecked(1));
+    client.approve_proposal(&ids.get_unchecked(1), &signers.get_unchecked(2));
+
+    let mut state: Map<u64, ProposalState> = Map::new(&env);
+    let cursor = sync_changes(&client, &mut state, 0);
+    assert_eq!(cursor, 8);
+    let first = client.get_changes_since(&1, &3);
+    assert_eq!(first.entries.get_unchecked(2).action, symbol_short!("created"));
+    assert_eq!(first.entries.get_unchecked(2).seq, 3);
+    assert_eq!(first.next_seq, 4);
+    assert!(first.has_more);
+
+    // A second batch touches some proposals again and leaves others alone
+    client.execute_proposal(&ids.get_unchecked(0));
+    client.revoke_approval(&ids.get_unchecked(1), &signers.get_unchecked(2));
+    client.delegate_vote(&ids.get_unchecked(2), &signers.get_unchecked(0), &signers.get_unchecked(1));
+    client.approve_proposal(&ids.get_unchecked(2), &signers.get_unchecked(1));
+    env.ledger().with_mut(|li| li.timestamp = 3601);
+    client.expire_proposal(&ids.get_unchecked(3));
+
+    let page = client.get_changes_since(&cursor, &50);
+    assert_eq!(page.entries.len(), 5);
+    assert_eq!(page.proposals.len(), 4);
+    assert!(!page.has_more);
+    assert_eq!(sync_changes(&client, &mut state, cursor), cursor + 5);
+    assert_eq!(client.get_changes_since(&(cursor + 5), &3).entries.len(), 0);
+
+    let mut full: Map<u64, ProposalState> = Map::new(&env);
+    for id in ids.iter() {
+        let status = match client.get_proposal_outcome(&id).map(|record| record.outcome) {
+            Some(ProposalOutcome::Executed) => ProposalStatus::Executed,
+            Some(ProposalOutcome::Expired) => ProposalStatus::Expired,
+            Some(ProposalOutcome::Rejected) => ProposalStatus::Rejected,
+            Some(ProposalOutcome::Canceled) => ProposalStatus::Canceled,
+            Some(ProposalOutcome::Vetoed) => ProposalStatus::Vetoed,
+            None if client.get_pending_cancellation(&id).is_some() => ProposalStatus::CancelPending,
+            None if client.get_approval_progress(&id).ready => ProposalStatus::Ready,
+            None => ProposalStatus::Open,
+        };
+        full.set(id, ProposalState { proposal: client.get_proposal(&id), approvals: client.get_proposal_approvals(&id), status });
+    }
+    assert_eq!(state, full);
+    assert_eq!(state.get_unchecked(ids.get_unchecked(0)).status, ProposalStatus::Executed);
+    assert_eq!(state.get_unchecked(ids.get_unchecked(2)).status, ProposalStatus::Ready);
+    assert_eq!(state.get_unchecked(ids.get_unchecked(3)).status, ProposalStatus::Expired);
+}
+
+// Authenticator data as a platform authenticator returns it: rp id hash, then
+// the user-present and user-verified flags and a zero sign counter
+const AUTHENTICATOR_DATA: [u8; 37] = {
+    let mut data = [0u8; 37];
+    let mut i = 0;
+    while i < 32 {
+        data[i] = 0x49;
+        i += 1;
+    }
+    data[32] = 0x05;
+    data
+};
+
+fn passkey_signing_key(seed: u8) -> p256::ecdsa::SigningKey {
+    p256::ecdsa::SigningKey::from_slice(&[seed; 32]).unwrap()
+}
+
+fn passkey(env: &Env, seed: u8) -> Secp256r1Key {
+    let point = passkey_signing_key(seed).verifying_key().to_encoded_point(false);
+    Secp256r1Key {
+        key_id: Bytes::from_array(env, &[seed; 16]),
+        public_key: BytesN::from_array(env, point.as_bytes().try_into().unwrap()),
+    }
+}
+
+// Client data JSON of the given ceremony type carrying `challenge`
+fn client_data(env: &Env, kind: &str, challenge: &BytesN<32>) -> Bytes {
+    let mut json = Bytes::from_slice(env, br#"{"type":""#);
+    json.extend_from_slice(kind.as_bytes());
+    json.extend_from_slice(br#"","challenge":""#);
+    json.extend_from_slice(&webauthn::base64url(&challenge.to_array()));
+    json.extend_from_slice(br#"","origin":"https://wallet.example","crossOrigin":false}"#);
+    json
+}
+
+// Signs as the authenticator holding passkey `seed` would: over the
+// authenticator data followed by the sha256 of the client data, low-S
+fn sign_webauthn(env: &Env, seed: u8, client_data_json: &Bytes) -> BytesN<64> {
+    let mut message = [0u8; 69];
+    message[..37].copy_from_slice(&AUTHENTICATOR_DATA);
+    message[37..].copy_from_slice(&env.crypto().sha256(client_data_json).to_array());
+    let signature: p256::ecdsa::Signature = passkey_signing_key(seed).sign(&message);
+    let signature = signature.normalize_s().unwrap_or(signature);
+    BytesN::from_array(env, &signature.to_bytes().into())
+}
+
+#[test]
+fn test_base64url_challenge_encoding() {
+    let env = Env::default();
+    let digest: BytesN<32> = env.crypto().sha256(&Bytes::from_slice(&env, b"abc")).into();
+    assert_eq!(&webauthn::base64url(&digest.to_array()), b"ungWv48Bz-pBQUDeXa4iI7ADYaOWF3qctBD_YfIAFa0");
+}
+
+#[test]
+fn test_passkey_signer_approves_with_webauthn() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let keys = create_signing_signers(&env, 2);
+    let passkey = passkey(&env, 7);
+    let signers = vec![&env, Signer::Ed25519(keys.get_unchecked(0)), Signer::Ed25519(keys.get_unchecked(1)), Signer::Secp256r1(passkey.clone())];
+    client.initialize_with_signers(&signers, &2, &false, &None, &Vec::new(&env), &false);
+
+    let passkey_id = client.signer_id(&Signer::Secp256r1(passkey.clone()));
+    assert!(client.is_signer_v2(&Signer::Secp256r1(passkey.clone())));
+    assert_eq!(client.get_passkey(&passkey_id), Some(passkey));
+    assert_eq!(client.get_passkey(&keys.get_unchecked(0)), None);
+
+    env.mock_all_auths();
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&keys.get_unchecked(0), &token, &recipient, &400, &reason, &3600);
+    env.set_auths(&[]);
+    client.approve_proposal_signed(&proposal_id, &keys.get_unchecked(0), &0, &sign_approval(&env, &client, proposal_id, 1));
+
+    let authenticator_data = Bytes::from_array(&env, &AUTHENTICATOR_DATA);
+    let client_data_json = client_data(&env, "webauthn.get", &client.get_approval_payload(&proposal_id, &passkey_id));
+    let signature = sign_webauthn(&env, 7, &client_data_json);
+    client.approve_proposal_webauthn(&proposal_id, &passkey_id, &0, &authenticator_data, &client_data_json, &signature);
+    assert_eq!(client.get_proposal_approvals(&proposal_id).get_unchecked(1).signer, passkey_id);
+    assert_eq!(client.signer_nonce(&passkey_id), 1);
+
+    // The same assertion cannot be replayed once the nonce has moved on
+    assert_eq!(
+        client.try_approve_proposal_webauthn(&proposal_id, &passkey_id, &0, &authenticator_data, &client_data_json, &signature),
+        Err(Ok(MultisigError::InvalidNonce.into()))
+    );
+
+    client.execute_proposal(&proposal_id);
+    assert_eq!(soroban_sdk::token::Client::new(&env, &token).balance(&recipient), 400);
+}
+
+#[test]
+fn test_webauthn_rejects_bad_assertions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let keys = create_signing_signers(&env, 2);
+    let passkey = Signer::Secp256r1(passkey(&env, 7));
+    client.initialize_with_signers(&vec![&env, Signer::Ed25519(keys.get_unchecked(0)), Signer::Ed25519(keys.get_unchecked(1)), passkey.clone()], &2, &false, &None, &Vec::new(&env), &false);
+    let passkey_id = client.signer_id(&passkey);
+
+    let token = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&keys.get_unchecked(0), &token, &Address::generate(&env), &400, &reason, &3600);
+    let authenticator_data = Bytes::from_array(&env, &AUTHENTICATOR_DATA);
+    let payload = client.get_approval_payload(&proposal_id, &passkey_id);
+    let approve = |client_data_json: &Bytes| {
+        let signature = sign_webauthn(&env, 7, client_data_json);
+        client.try_approve_proposal_webauthn(&proposal_id, &passkey_id, &0, &authenticator_data, client_data_json, &signature)
+    };
+
+    // A challenge for anything other than this approval at this nonce
+    let stale = client_data(&env, "webauthn.get", &client.get_proposal_hash(&proposal_id));
+    assert_eq!(approve(&stale), Err(Ok(SignerError::ChallengeMismatch.into())));
+
+    // Registration responses and client data without a challenge
+    assert_eq!(approve(&client_data(&env, "webauthn.create", &payload)), Err(Ok(SignerError::MalformedClientData.into())));
+    let no_challenge = Bytes::from_slice(&env, br#"{"type":"webauthn.get","origin":"https://wallet.example"}"#);
+    assert_eq!(approve(&no_challenge), Err(Ok(SignerError::MalformedClientData.into())));
+    let mut unterminated = Bytes::from_slice(&env, br#"{"type":"webauthn.get","challenge":""#);
+    unterminated.extend_from_slice(&webauthn::base64url(&payload.to_array()));
+    assert_eq!(approve(&unterminated), Err(Ok(SignerError::MalformedClientData.into())));
+    let mut oversized = client_data(&env, "webauthn.get", &payload);
+    oversized.extend_from_array(&[b' '; webauthn::MAX_CLIENT_DATA_LEN as usize]);
+    assert_eq!(approve(&oversized), Err(Ok(SignerError::MalformedClientData.into())));
+
+    // Valid client data with a signature over something else
+    let valid = client_data(&env, "webauthn.get", &payload);
+    let mut tampered = authenticator_data.clone();
+    tampered.set(32, 0x01);
+    assert!(client
+        .try_approve_proposal_webauthn(&proposal_id, &passkey_id, &0, &tampered, &valid, &sign_webauthn(&env, 7, &valid))
+        .is_err());
+    assert!(client
+        .try_approve_proposal_webauthn(&proposal_id, &passkey_id, &0, &authenticator_data, &valid, &sign_webauthn(&env, 8, &valid))
+        .is_err());
+
+    // Ed25519 signers have no passkey to check against
+    assert_eq!(
+        client.try_approve_proposal_webauthn(&proposal_id, &keys.get_unchecked(0), &0, &authenticator_data, &valid, &sign_webauthn(&env, 7, &valid)),
+        Err(Ok(MultisigError::UnknownSigner.into()))
+    );
+
+    assert_eq!(approve(&valid), Ok(Ok(())));
+    assert_eq!(client.signer_nonce(&passkey_id), 1);
+}
+
+fn sign_rejection(env: &Env, client: &MultiSigContractClient, proposal_id: u64, seed: u8) -> BytesN<64> {
+    let signer = BytesN::from_array(env, &signing_key(seed).verifying_key().to_bytes());
+    let payload = client.get_rejection_payload(&proposal_id, &signer);
+    BytesN::from_array(env, &signing_key(seed).sign(&payload.to_array()).to_bytes())
+}
+
+fn reject(env: &Env, client: &MultiSigContractClient, signers: &Vec<BytesN<32>>, proposal_id: u64, seed: u8) {
+    let rejecter = signers.get_unchecked(seed as u32 - 1);
+    let nonce = client.signer_nonce(&rejecter);
+    client.reject_proposal_signed(&proposal_id, &rejecter, &nonce, &sign_rejection(env, client, proposal_id, seed));
+}
+
+#[test]
+fn test_rejections_are_recorded_without_strict_mode() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &Address::generate(&env), &Address::generate(&env), &100, &reason, &3600);
+    client.approve_proposal_signed(&proposal_id, &signers.get_unchecked(0), &0, &sign_approval(&env, &client, proposal_id, 1));
+
+    reject(&env, &client, &signers, proposal_id, 2);
+    reject(&env, &client, &signers, proposal_id, 3);
+    assert_eq!(client.get_proposal_rejections(&proposal_id), vec![&env, signers.get_unchecked(1), signers.get_unchecked(2)]);
+    assert_eq!(client.get_proposal_outcome(&proposal_id), None);
+
+    // Approvers cannot also reject, nor rejecters approve or reject twice
+    let nonce = client.signer_nonce(&signers.get_unchecked(0));
+    assert_eq!(
+        client.try_reject_proposal_signed(&proposal_id, &signers.get_unchecked(0), &nonce, &sign_rejection(&env, &client, proposal_id, 1)),
+        Err(Ok(MultisigError::AlreadyApproved.into()))
+    );
+    let nonce = client.signer_nonce(&signers.get_unchecked(1));
+    assert_eq!(
+        client.try_reject_proposal_signed(&proposal_id, &signers.get_unchecked(1), &nonce, &sign_rejection(&env, &client, proposal_id, 2)),
+        Err(Ok(MultisigError::AlreadyRejected.into()))
+    );
+    assert_eq!(
+        client.try_approve_proposal_signed(&proposal_id, &signers.get_unchecked(1), &nonce, &sign_approval(&env, &client, proposal_id, 2)),
+        Err(Ok(MultisigError::AlreadyRejected.into()))
+    );
+
+    // A rejection signature does not double as an approval
+    let nonce = client.signer_nonce(&signers.get_unchecked(2));
+    assert!(client
+        .try_approve_proposal_signed(&proposal_id, &signers.get_unchecked(2), &nonce, &sign_rejection(&env, &client, proposal_id, 3))
+        .is_err());
+}
+
+#[test]
+fn test_strict_rejection_closes_proposal_at_tipping_point() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 5);
+    client.initialize(&signers, &3, &false);
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::EnableFeature(Symbol::new(&env, "strict_rj"))), 3);
+
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &Address::generate(&env), &Address::generate(&env), &100, &reason, &3600);
+    client.approve_proposal_signed(&proposal_id, &signers.get_unchecked(0), &0, &sign_approval(&env, &client, proposal_id, 1));
+
+    // Three signers could still approve after two rejections
+    reject(&env, &client, &signers, proposal_id, 4);
+    reject(&env, &client, &signers, proposal_id, 5);
+    assert_eq!(client.get_proposal_outcome(&proposal_id), None);
+
+    // The third leaves only two, short of the threshold of three
+    reject(&env, &client, &signers, proposal_id, 3);
+    let rejected: Map<Symbol, Val> = map![&env, (Symbol::new(&env, "rejections"), 3u32.into_val(&env))];
+    assert_eq!(
+        contract_events(&env, &contract_id),
+        vec![&env, (contract_id.clone(), (Symbol::new(&env, "proposal_rejected"), proposal_id).into_val(&env), rejected.into_val(&env))]
+    );
+
+    let outcome = client.get_proposal_outcome(&proposal_id).unwrap();
+    assert_eq!(outcome.outcome, ProposalOutcome::Rejected);
+    assert_eq!(outcome.actor, Some(signers.get_unchecked(2)));
+    assert_eq!(client.get_open_proposals(&0, &10).proposals.len(), 0);
+    assert_eq!(client.get_changes_since(&0, &10).proposals.get_unchecked(0).status, ProposalStatus::Rejected);
+
+    assert_eq!(
+        client.try_approve_proposal_signed(&proposal_id, &signers.get_unchecked(1), &0, &BytesN::from_array(&env, &[0; 64])),
+        Err(Ok(MultisigError::ProposalClosed.into()))
+    );
+    assert_eq!(client.try_execute_proposal(&proposal_id), Err(Ok(MultisigError::ProposalClosed.into())));
+}
+
+#[test]
+fn test_strict_rejection_accounts_for_role_requirements() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    // Signer 3 is the only executive, and payments need an executive approval
+    let signers = create_signing_signers(&env, 5);
+    client.initialize(&signers, &2, &false);
+    let assignment = SignerRoleAssignment { signer: signers.get_unchecked(2), role: Symbol::new(&env, "exec") };
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::SetSignerRole(assignment)), 2);
+    let requirements = vec![&env, RoleRequirement { role: Symbol::new(&env, "exec"), count: 1 }];
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::SetRoleRequirements(requirements)), 2);
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::EnableFeature(Symbol::new(&env, "staged"))), 2);
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::EnableFeature(Symbol::new(&env, "strict_rj"))), 2);
+
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &Address::generate(&env), &Address::generate(&env), &100, &reason, &3600);
+    reject(&env, &client, &signers, proposal_id, 4);
+    assert_eq!(client.get_proposal_outcome(&proposal_id), None);
+
+    // Four signers remain for a threshold of two, but no executive
+    reject(&env, &client, &signers, proposal_id, 3);
+    assert_eq!(client.get_proposal_outcome(&proposal_id).unwrap().outcome, ProposalOutcome::Rejected);
+}
+
+fn evm_signing_key(seed: u8) -> k256::ecdsa::SigningKey {
+    k256::ecdsa::SigningKey::from_slice(&[seed; 32]).unwrap()
+}
+
+fn evm_key(env: &Env, seed: u8) -> BytesN<65> {
+    let point = evm_signing_key(seed).verifying_key().to_encoded_point(false);
+    BytesN::from_array(env, point.as_bytes().try_into().unwrap())
+}
+
+// Signs `approver`'s next approval payload for `proposal_id` with the
+// secp256k1 key `seed`, returning the signature and its recovery id
+fn sign_secp256k1(client: &MultiSigContractClient, proposal_id: u64, approver: &BytesN<32>, seed: u8) -> (k256::ecdsa::Signature, u32) {
+    let payload = client.get_approval_payload(&proposal_id, approver);
+    let (signature, recovery_id) = evm_signing_key(seed).sign_prehash_recoverable(&payload.to_array()).unwrap();
+    (signature, recovery_id.to_byte() as u32)
+}
+
+#[test]
+fn test_secp256k1_signer_approves_with_recoverable_signature() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let keys = create_signing_signers(&env, 2);
+    let evm_signer = Signer::Secp256k1(evm_key(&env, 7));
+    client.initialize_with_signers(&vec![&env, Signer::Ed25519(keys.get_unchecked(0)), Signer::Ed25519(keys.get_unchecked(1)), evm_signer.clone()], &2, &false, &None, &Vec::new(&env), &false);
+    let evm_id = client.signer_id(&evm_signer);
+    assert!(client.is_signer(&evm_id));
+
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&keys.get_unchecked(0), &Address::generate(&env), &Address::generate(&env), &100, &reason, &3600);
+
+    // The payload is the ed25519 one with keccak256 in place of sha256
+    let expected: BytesN<32> = env.crypto().keccak256(&(client.get_proposal_hash_keccak(&proposal_id), 0u64).to_xdr(&env)).into();
+    assert_eq!(client.get_approval_payload(&proposal_id, &evm_id), expected);
+
+    let (signature, recovery_id) = sign_secp256k1(&client, proposal_id, &evm_id, 7);
+    let signature = BytesN::from_array(&env, &signature.to_bytes().into());
+    client.approve_proposal_secp256k1(&proposal_id, &evm_id, &0, &signature, &recovery_id);
+    assert_eq!(client.get_proposal_approvals(&proposal_id).get_unchecked(0).signer, evm_id);
+    assert_eq!(client.signer_nonce(&evm_id), 1);
+
+    assert_eq!(
+        client.try_approve_proposal_secp256k1(&proposal_id, &evm_id, &0, &signature, &recovery_id),
+        Err(Ok(MultisigError::InvalidNonce.into()))
+    );
+}
+
+#[test]
+fn test_secp256k1_rejects_foreign_and_malleated_signatures() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let keys = create_signing_signers(&env, 2);
+    let evm_signer = Signer::Secp256k1(evm_key(&env, 7));
+    client.initialize_with_signers(&vec![&env, Signer::Ed25519(keys.get_unchecked(0)), Signer::Ed25519(keys.get_unchecked(1)), evm_signer.clone()], &2, &false, &None, &Vec::new(&env), &false);
+    let evm_id = client.signer_id(&evm_signer);
+
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&keys.get_unchecked(0), &Address::generate(&env), &Address::generate(&env), &100, &reason, &3600);
+
+    // A key outside the signer set signing in the signer's name
+    let (foreign, recovery_id) = sign_secp256k1(&client, proposal_id, &evm_id, 9);
+    let foreign = BytesN::from_array(&env, &foreign.to_bytes().into());
+    assert_eq!(
+        client.try_approve_proposal_secp256k1(&proposal_id, &evm_id, &0, &foreign, &recovery_id),
+        Err(Ok(SignerError::RecoveredKeyMismatch.into()))
+    );
+    let foreign_id = client.signer_id(&Signer::Secp256k1(evm_key(&env, 9)));
+    assert_eq!(
+        client.try_approve_proposal_secp256k1(&proposal_id, &foreign_id, &0, &foreign, &recovery_id),
+        Err(Ok(MultisigError::UnknownSigner.into()))
+    );
+
+    // The high-S twin of a valid signature is refused, as is the valid one
+    // with the wrong recovery id
+    let (signature, recovery_id) = sign_secp256k1(&client, proposal_id, &evm_id, 7);
+    let (r, s) = signature.split_scalars();
+    let malleated = k256::ecdsa::Signature::from_scalars(r, -s).unwrap();
+    assert!(client
+        .try_approve_proposal_secp256k1(&proposal_id, &evm_id, &0, &BytesN::from_array(&env, &malleated.to_bytes().into()), &(recovery_id ^ 1))
+        .is_err());
+    let signature = BytesN::from_array(&env, &signature.to_bytes().into());
+    assert_eq!(
+        client.try_approve_proposal_secp256k1(&proposal_id, &evm_id, &0, &signature, &(recovery_id ^ 1)),
+        Err(Ok(SignerError::RecoveredKeyMismatch.into()))
+    );
+
+    client.approve_proposal_secp256k1(&proposal_id, &evm_id, &0, &signature, &recovery_id);
+    assert_eq!(client.get_approval_progress(&proposal_id).approvals, 1);
+}
+
+#[test]
+fn test_config_fingerprint_pins_canonical_encoding() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let key = |byte: u8| BytesN::from_array(&env, &[byte; 32]);
+    client.initialize(&vec![&env, key(3), key(1), key(2)], &2, &false);
+
+    let signer = |byte: u8| ConfigSigner { id: key(byte), address: None, role: None, protected: false, suspended: false };
+    let expected = ConfigSnapshot {
+        version: 3,
+        signers: vec![&env, signer(1), signer(2), signer(3)],
+        threshold: 2,
+        signer_registry: None,
+        allow_low_security: false,
+        protected_quorum: 3,
+        final_approver: None,
+        role_requirements: Vec::new(&env),
+        features: vec![&env, symbol_short!("delegate")],
+        execution_grace: 0,
+        cancel_grace: 86_400,
+        redundancy_policy: RedundancyPolicy::Warn,
+    };
+    assert_eq!(client.get_config_snapshot(), expected);
+
+    // Off-chain tooling reproduces the fingerprint from the XDR encoding
+    let fingerprint = client.get_config_fingerprint();
+    assert_eq!(fingerprint, env.crypto().sha256(&expected.to_xdr(&env)).to_bytes());
+    assert_eq!(
+        fingerprint.to_array(),
+        [
+            0x4d, 0x1b, 0x44, 0xe9, 0x6c, 0x1e, 0xed, 0x5d, 0x20, 0x58, 0xcd, 0x48, 0x0e, 0x30, 0xe6, 0x5b,
+            0xc5, 0x85, 0x8b, 0xf3, 0x2f, 0x47, 0xe5, 0x9e, 0x83, 0x80, 0x28, 0x0f, 0x61, 0x4e, 0xba, 0x8d,
+        ]
+    );
+
+    // The order signers were listed in does not matter
+    let other_id = env.register(MultiSigContract, ());
+    let other = MultiSigContractClient::new(&env, &other_id);
+    other.initialize(&vec![&env, key(1), key(2), key(3)], &2, &false);
+    assert_eq!(other.get_config_fingerprint(), fingerprint);
+}
+
+#[test]
+fn test_config_fingerprint_announced_when_it_moves() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let initial = client.get_config_fingerprint();
+
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::SetFinalApprover(signers.get_unchecked(1))), 2);
+    let events = contract_events(&env, &contract_id);
+    let moved = client.get_config_fingerprint();
+    assert_ne!(moved, initial);
+    assert_eq!(events, vec![&env, fingerprint_event(&env, &contract_id, &moved)]);
+    assert_eq!(client.get_config_snapshot().final_approver, Some(signers.get_unchecked(1)));
+
+    // Actions outside the fingerprinted settings stay quiet
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::FreezeToken(Address::generate(&env))), 2);
+    assert_eq!(contract_events(&env, &contract_id), Vec::new(&env));
+    assert_eq!(client.get_config_fingerprint(), moved);
+
+    run_signer_change(&client, &signers, SignerChangeKind::Add, &BytesN::from_array(&env, &[9; 32]));
+    let events = contract_events(&env, &contract_id);
+    let grown = client.get_config_fingerprint();
+    assert_eq!(events, vec![&env, fingerprint_event(&env, &contract_id, &grown)]);
+    assert_eq!(client.get_config_snapshot().signers.len(), 4);
+}
+
+fn bls_secret(env: &Env, seed: u32) -> Fr {
+    Fr::from_u256(U256::from_u32(env, seed * 7_919 + 13))
+}
+
+fn bls_key(env: &Env, seed: u32) -> BytesN<96> {
+    let generator = G1Affine::from_bytes(bytesn!(
+        env,
+        0x17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1
+    ));
+    env.crypto().bls12_381().g1_mul(&generator, &bls_secret(env, seed)).to_bytes()
+}
+
+fn bls_sign(env: &Env, seed: u32, message: &Bytes, dst: &[u8]) -> G2Affine {
+    let bls = env.crypto().bls12_381();
+    bls.g2_mul(&bls.hash_to_g2(message, &Bytes::from_slice(env, dst)), &bls_secret(env, seed))
+}
+
+// Registers BLS key `seed` for the ed25519 signer with key `seed`
+fn register_bls(env: &Env, client: &MultiSigContractClient, seed: u8) {
+    let signer = BytesN::from_array(env, &signing_key(seed).verifying_key().to_bytes());
+    let key = bls_key(env, seed as u32);
+    let proof = bls_sign(env, seed as u32, &key.clone().into(), bls::POP_DST).to_bytes();
+    let payload = client.get_bls_registration_payload(&signer, &key);
+    let signature = BytesN::from_array(env, &signing_key(seed).sign(&payload.to_array()).to_bytes());
+    client.register_bls_key(&signer, &key, &proof, &client.signer_nonce(&signer), &signature);
+}
+
+// Aggregates the execution signatures of BLS keys `seeds` over `proposal_id`
+fn bls_aggregate(env: &Env, client: &MultiSigContractClient, proposal_id: u64, seeds: &[u32]) -> BytesN<192> {
+    let message: Bytes = client.get_proposal_hash(&proposal_id).into();
+    let bls = env.crypto().bls12_381();
+    let mut aggregate = bls_sign(env, seeds[0], &message, bls::SIGNATURE_DST);
+    for seed in &seeds[1..] {
+        aggregate = bls.g2_add(&aggregate, &bls_sign(env, *seed, &message, bls::SIGNATURE_DST));
+    }
+    aggregate.to_bytes()
+}
+
+#[test]
+fn test_aggregate_signature_executes_proposal() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 4);
+    client.initialize(&signers, &3, &false);
+    for seed in 1..=4 {
+        register_bls(&env, &client, seed);
+    }
+    assert_eq!(client.get_bls_signers(), signers);
+    assert_eq!(client.get_bls_key(&signers.get_unchecked(1)), Some(bls_key(&env, 2)));
+
+    env.mock_all_auths();
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &400, &reason, &3600);
+    env.set_auths(&[]);
+
+    // Signers one, two and four: bits 0, 1 and 3
+    let aggregate = bls_aggregate(&env, &client, proposal_id, &[1, 2, 4]);
+    client.execute_with_aggregate_signature(&proposal_id, &aggregate, &Bytes::from_array(&env, &[0b1011]));
+
+    assert!(client.is_proposal_executed(&proposal_id));
+    assert_eq!(soroban_sdk::token::Client::new(&env, &token).balance(&recipient), 400);
+    let approvals = client.get_proposal_approvals(&proposal_id);
+    assert_eq!(approvals.len(), 3);
+    assert_eq!(approvals.get_unchecked(2).signer, signers.get_unchecked(3));
+}
+
+#[test]
+fn test_aggregate_signature_rejects_bad_selections() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 4);
+    client.initialize(&signers, &3, &false);
+    for seed in 1..=3 {
+        register_bls(&env, &client, seed);
+    }
+
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &Address::generate(&env), &Address::generate(&env), &400, &reason, &3600);
+
+    // The fourth signer never registered a key, so bit 3 selects nobody
+    let aggregate = bls_aggregate(&env, &client, proposal_id, &[1, 2, 4]);
+    assert_eq!(
+        client.try_execute_with_aggregate_signature(&proposal_id, &aggregate, &Bytes::from_array(&env, &[0b1011])),
+        Err(Ok(SignerError::BlsKeyNotRegistered.into()))
+    );
+
+    let aggregate = bls_aggregate(&env, &client, proposal_id, &[1, 2]);
+    assert_eq!(
+        client.try_execute_with_aggregate_signature(&proposal_id, &aggregate, &Bytes::from_array(&env, &[0b0011])),
+        Err(Ok(MultisigError::InsufficientApprovals.into()))
+    );
+
+    // Selected signers that did not all sign
+    assert_eq!(
+        client.try_execute_with_aggregate_signature(&proposal_id, &aggregate, &Bytes::from_array(&env, &[0b0111])),
+        Err(Ok(SignerError::InvalidBlsSignature.into()))
+    );
+    assert!(client.get_proposal_approvals(&proposal_id).is_empty());
+
+    // A key is only accepted with proof its holder can sign with it
+    let signer = signers.get_unchecked(3);
+    let key = bls_key(&env, 4);
+    let borrowed_proof = bls_sign(&env, 1, &bls_key(&env, 1).into(), bls::POP_DST).to_bytes();
+    let payload = client.get_bls_registration_payload(&signer, &key);
+    let signature = BytesN::from_array(&env, &signing_key(4).sign(&payload.to_array()).to_bytes());
+    assert_eq!(
+        client.try_register_bls_key(&signer, &key, &borrowed_proof, &0, &signature),
+        Err(Ok(SignerError::InvalidBlsSignature.into()))
+    );
+}
+
+fn sign_relay(env: &Env, client: &MultiSigContractClient, proposal_id: u64, seed: u8, valid_until: u64) -> BytesN<64> {
+    let signer = BytesN::from_array(env, &signing_key(seed).verifying_key().to_bytes());
+    let payload = client.get_relay_payload(&proposal_id, &signer, &valid_until);
+    BytesN::from_array(env, &signing_key(seed).sign(&payload.to_array()).to_bytes())
+}
+
+#[test]
+fn test_relayed_approval_records_relayer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &100, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+
+    let relayer = Address::generate(&env);
+    let approver = signers.get_unchecked(1);
+    let valid_until = env.ledger().timestamp() + 600;
+    let signature = sign_relay(&env, &client, proposal_id, 2, valid_until);
+    client.relay_approval(&proposal_id, &approver, &0, &signature, &valid_until, &relayer);
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(env.auths()[0].0, relayer);
+
+    let approvals = client.get_proposal_approvals(&proposal_id);
+    assert_eq!(approvals.len(), 2);
+    assert_eq!(approvals.get_unchecked(0).relayer, None);
+    let relayed = approvals.get_unchecked(1);
+    assert_eq!(relayed.signer, approver);
+    assert_eq!(relayed.ordinal, 2);
+    assert_eq!(relayed.relayer, Some(relayer.clone()));
+    assert!(client.get_approval_progress(&proposal_id).ready);
+
+    // The same signature cannot be relayed twice
+    client.revoke_approval(&proposal_id, &approver);
+    assert_eq!(
+        client.try_relay_approval(&proposal_id, &approver, &0, &signature, &valid_until, &relayer),
+        Err(Ok(MultisigError::InvalidNonce.into()))
+    );
+    assert_eq!(client.get_proposal_approvals(&proposal_id).len(), 1);
+
+    // Nor passed off as a plain signed approval
+    assert!(client.try_approve_proposal_signed(&proposal_id, &approver, &1, &signature).is_err());
+}
+
+#[test]
+fn test_relayed_approval_refused_after_valid_until() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &100, &reason, &3600);
+
+    let relayer = Address::generate(&env);
+    let approver = signers.get_unchecked(1);
+    let valid_until = env.ledger().timestamp() + 600;
+    let signature = sign_relay(&env, &client, proposal_id, 2, valid_until);
+
+    env.ledger().with_mut(|li| li.timestamp += 601);
+    assert_eq!(
+        client.try_relay_approval(&proposal_id, &approver, &0, &signature, &valid_until, &relayer),
+        Err(Ok(SignerError::RelayExpired.into()))
+    );
+
+    // Claiming a later deadline than was signed does not verify
+    let extended = valid_until + 600;
+    assert!(client.try_relay_approval(&proposal_id, &approver, &0, &signature, &extended, &relayer).is_err());
+    assert_eq!(client.get_proposal_approvals(&proposal_id).len(), 0);
+    assert_eq!(client.signer_nonce(&approver), 0);
+}
+
+#[test]
+fn test_approvals_stored_without_relayer_still_read() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+
+    env.as_contract(&contract_id, || {
+        let stored: Vec<Map<Symbol, Val>> = env.storage().instance().get(&DataKey::ProposalApprovals(proposal_id)).unwrap();
+        let mut fields = stored.get_unchecked(0);
+        fields.remove(Symbol::new(&env, "relayer"));
+        env.storage().instance().set(&DataKey::ProposalApprovals(proposal_id), &vec![&env, fields]);
+    });
+
+    let approvals = client.get_proposal_approvals(&proposal_id);
+    assert_eq!(approvals.len(), 1);
+    assert_eq!(approvals.get_unchecked(0).signer, signers.get_unchecked(0));
+    assert_eq!(approvals.get_unchecked(0).relayer, None);
+}
+
+#[test]
+fn test_canceled_proposal_restored_with_approvals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = create_funded_token(&env, &contract_id, 5000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposer = signers.get_unchecked(0);
+    let proposal_id = client.create_proposal(&proposer, &token_address, &recipient, &1000, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+
+    assert_eq!(
+        client.try_cancel_proposal(&proposal_id, &signers.get_unchecked(1)),
+        Err(Ok(MultisigError::NotProposer.into()))
+    );
+    client.cancel_proposal(&proposal_id, &proposer);
+    let events = env.events().all();
+    let restorable_until = env.ledger().timestamp() + client.get_cancel_grace();
+    let data: Map<Symbol, Val> = map![&env, (Symbol::new(&env, "restorable_until"), restorable_until.into_val(&env))];
+    assert_eq!(
+        events,
+        vec![&env, (contract_id.clone(), (Symbol::new(&env, "proposal_canceled"), proposal_id).into_val(&env), data.into_val(&env))]
+    );
+
+    // Out of play while canceled, but nothing is cleaned up yet
+    assert_eq!(client.try_execute_proposal(&proposal_id), Err(Ok(MultisigError::ProposalCanceled.into())));
+    assert_eq!(
+        client.try_approve_proposal(&proposal_id, &signers.get_unchecked(2)),
+        Err(Ok(MultisigError::ProposalCanceled.into()))
+    );
+    assert_eq!(client.try_cancel_proposal(&proposal_id, &proposer), Err(Ok(MultisigError::ProposalCanceled.into())));
+    assert_eq!(client.get_open_proposals(&0, &10).proposals.len(), 1);
+    assert_eq!(client.get_proposal_outcome(&proposal_id), None);
+
+    // Co-signing takes the signer's own account, not just its key
+    env.set_auths(&[]);
+    assert!(client.try_restore_proposal(&proposal_id, &signers.get_unchecked(1)).is_err());
+    assert_eq!(client.get_pending_cancellation(&proposal_id).unwrap().restore_votes.len(), 0);
+    env.mock_all_auths();
+
+    // One co-signer is not enough
+    client.restore_proposal(&proposal_id, &signers.get_unchecked(1));
+    assert_eq!(
+        client.try_restore_proposal(&proposal_id, &signers.get_unchecked(1)),
+        Err(Ok(MultisigError::AlreadyApproved.into()))
+    );
+    assert_eq!(client.get_pending_cancellation(&proposal_id).unwrap().restore_votes, vec![&env, signers.get_unchecked(1)]);
+
+    client.restore_proposal(&proposal_id, &signers.get_unchecked(2));
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (contract_id.clone(), (Symbol::new(&env, "proposal_restored"), proposal_id).into_val(&env), Map::<Symbol, Val>::new(&env).into_val(&env))
+        ]
+    );
+    assert_eq!(client.get_pending_cancellation(&proposal_id), None);
+    assert_eq!(
+        client.try_restore_proposal(&proposal_id, &signers.get_unchecked(0)),
+        Err(Ok(MultisigError::ProposalNotCanceled.into()))
+    );
+
+    // Back as it was, with both approvals
+    assert_eq!(client.get_proposal_approvals(&proposal_id).len(), 2);
+    client.execute_proposal(&proposal_id);
+    let token_client = soroban_sdk::token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&recipient), 1000);
+}
+
+#[test]
+fn test_canceled_proposal_becomes_final_after_grace() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposer = signers.get_unchecked(0);
+    let proposal_id = client.create_proposal(&proposer, &token_address, &recipient, &1000, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+
+    assert_eq!(client.get_cancel_grace(), validation::DEFAULT_CANCEL_GRACE_SECONDS);
+    client.cancel_proposal(&proposal_id, &proposer);
+    assert_eq!(client.try_finalize_cancellation(&proposal_id), Err(Ok(MultisigError::RestoreWindowOpen.into())));
+
+    env.ledger().with_mut(|li| li.timestamp += validation::DEFAULT_CANCEL_GRACE_SECONDS + 1);
+    assert_eq!(
+        client.try_restore_proposal(&proposal_id, &signers.get_unchecked(1)),
+        Err(Ok(MultisigError::RestoreWindowClosed.into()))
+    );
+
+    client.finalize_cancellation(&proposal_id);
+    let record = client.get_proposal_outcome(&proposal_id).unwrap();
+    assert_eq!(record.outcome, ProposalOutcome::Canceled);
+    assert_eq!(record.actor, Some(proposer));
+    assert_eq!(client.get_pending_cancellation(&proposal_id), None);
+    assert_eq!(client.get_open_proposals(&0, &10).proposals.len(), 0);
+
+    assert_eq!(client.try_finalize_cancellation(&proposal_id), Err(Ok(MultisigError::ProposalClosed.into())));
+    assert_eq!(
+        client.try_restore_proposal(&proposal_id, &signers.get_unchecked(1)),
+        Err(Ok(MultisigError::ProposalClosed.into()))
+    );
+}
+
+#[test]
+fn test_committee_and_graceless_cancellations_are_final() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposer = signers.get_unchecked(0);
+    let first = client.create_proposal(&proposer, &token_address, &recipient, &1000, &reason, &3600);
+    let second = client.create_proposal(&proposer, &token_address, &recipient, &2000, &reason, &3600);
+
+    // The committee overrides a cancellation still pending from the proposer
+    client.cancel_proposal(&first, &proposer);
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::CancelProposal(first)), 2);
+    let record = client.get_proposal_outcome(&first).unwrap();
+    assert_eq!(record.outcome, ProposalOutcome::Canceled);
+    assert_eq!(record.actor, None);
+    assert_eq!(client.get_pending_cancellation(&first), None);
+    assert!(client.try_propose_governance(&proposer, &GovernanceAction::Policy(PolicyAction::CancelProposal(first)), &3600).is_err());
+
+    // Without a grace, the proposer's own cancellation is final at once
+    assert!(client.try_propose_governance(&proposer, &GovernanceAction::Policy(PolicyAction::SetCancelGrace(validation::MAX_CANCEL_GRACE_SECONDS + 1)), &3600).is_err());
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::SetCancelGrace(0)), 2);
+    assert_eq!(client.get_cancel_grace(), 0);
+    client.cancel_proposal(&second, &proposer);
+    assert_eq!(client.get_proposal_outcome(&second).unwrap().outcome, ProposalOutcome::Canceled);
+    assert_eq!(client.get_pending_cancellation(&second), None);
+    assert_eq!(client.get_open_proposals(&0, &10).proposals.len(), 0);
+}
+
+#[cfg(not(feature = "testing-hooks"))]
+#[test]
+fn test_testing_hooks_absent_by_default() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    assert!(!client.testing_hooks_enabled());
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+
+    for (name, args) in [
+        ("force_expire", vec![&env, proposal_id.into_val(&env)]),
+        ("set_timestamp_offset", vec![&env, 3601u64.into_val(&env)]),
+        ("reset_rate_limits", Vec::new(&env)),
+    ] {
+        let result = env.try_invoke_contract::<(), soroban_sdk::Error>(&contract_id, &Symbol::new(&env, name), args);
+        assert!(result.is_err());
+    }
+    assert_eq!(client.get_proposal_outcome(&proposal_id), None);
+}
+
+#[cfg(feature = "testing-hooks")]
+#[test]
+fn test_testing_hooks_force_expire_and_shift_time() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    assert!(client.testing_hooks_enabled());
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let first = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+    let second = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &1000, &reason, &3600);
+
+    client.force_expire(&first);
+    let record = client.get_proposal_outcome(&first).unwrap();
+    assert_eq!(record.outcome, ProposalOutcome::Expired);
+    assert_eq!(record.timestamp, env.ledger().timestamp());
+    assert_eq!(client.try_force_expire(&first), Err(Ok(MultisigError::ProposalClosed.into())));
+
+    // The offset moves every deadline the contract checks, with the ledger untouched
+    assert_eq!(client.try_expire_proposal(&second), Err(Ok(MultisigError::ProposalNotExpired.into())));
+    client.set_timestamp_offset(&3601);
+    assert_eq!(
+        client.try_approve_proposal(&second, &signers.get_unchecked(1)),
+        Err(Ok(MultisigError::ProposalExpired.into()))
+    );
+    client.expire_proposal(&second);
+    assert_eq!(client.get_proposal_outcome(&second).unwrap().timestamp, env.ledger().timestamp() + 3601);
+}
+
+#[cfg(feature = "testing-hooks")]
+#[test]
+fn test_testing_hooks_reset_rate_limits() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token = Address::generate(&env);
+    let cap = ProposerCap { token: token.clone(), amount: 1000, window_seconds: 86_400 };
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::SetProposerCap(cap)), 2);
+
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    for i in 0..2 {
+        client.create_proposal(&signers.get_unchecked(i), &token, &recipient, &1000, &reason, &3600);
+        assert_eq!(client.get_proposer_capacity(&signers.get_unchecked(i), &token), Some(0));
+    }
+
+    client.reset_rate_limits();
+    for i in 0..2 {
+        assert_eq!(client.get_proposer_capacity(&signers.get_unchecked(i), &token), Some(1000));
+    }
+    client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &1000, &reason, &3600);
+    let result = client.try_create_proposal(&signers.get_unchecked(0), &token, &recipient, &1, &reason, &3600);
+    assert_eq!(result, Err(Ok(MultisigError::ProposerCapExceeded.into())));
+}
+
+// Delegates the approvals of the signer with key `seed` to `delegate`
+fn delegate_approvals(env: &Env, client: &MultiSigContractClient, seed: u8, delegate: &BytesN<32>, expires_at: u64) {
+    let signer = BytesN::from_array(env, &signing_key(seed).verifying_key().to_bytes());
+    let payload = client.get_delegation_payload(&signer, delegate, &expires_at);
+    let signature = BytesN::from_array(env, &signing_key(seed).sign(&payload.to_array()).to_bytes());
+    client.delegate_approval(&signer, delegate, &expires_at, &client.signer_nonce(&signer), &signature);
+}
+
+#[test]
+fn test_delegate_approves_for_signer_until_expiry() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let first = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &100, &reason, &3600);
+    let second = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &200, &reason, &3600);
+
+    let away = signers.get_unchecked(0);
+    let delegate = BytesN::from_array(&env, &signing_key(9).verifying_key().to_bytes());
+    let expires_at = env.ledger().timestamp() + 1000;
+    delegate_approvals(&env, &client, 1, &delegate, expires_at);
+    assert_eq!(client.get_delegation(&away).unwrap().delegate, delegate);
+
+    // The delegate signs with their own key and nonce; the approval is the signer's
+    let payload = client.get_approval_payload(&first, &delegate);
+    let signature = BytesN::from_array(&env, &signing_key(9).sign(&payload.to_array()).to_bytes());
+    client.approve_proposal_signed(&first, &delegate, &0, &signature);
+    let approvals = client.get_proposal_approvals(&first);
+    assert_eq!(approvals.len(), 1);
+    assert_eq!(approvals.get_unchecked(0).signer, away);
+    assert_eq!(client.signer_nonce(&delegate), 1);
+
+    // The delegator and delegate share that one approval
+    assert_eq!(client.try_approve_proposal(&first, &away), Err(Ok(MultisigError::AlreadyApproved.into())));
+    assert_eq!(client.try_approve_proposal(&first, &delegate), Err(Ok(MultisigError::AlreadyApproved.into())));
+
+    env.ledger().with_mut(|li| li.timestamp = expires_at + 1);
+    assert_eq!(client.get_delegation(&away), None);
+    assert_eq!(client.try_approve_proposal(&second, &delegate), Err(Ok(MultisigError::UnknownSigner.into())));
+    client.approve_proposal(&second, &away);
+
+    // Delegations are bounded and cannot point back at the signer
+    let now = env.ledger().timestamp();
+    assert!(client.try_delegate_approval(&away, &delegate, &now, &client.signer_nonce(&away), &signature).is_err());
+    let result = client.try_delegate_approval(&away, &away, &(now + 10), &client.signer_nonce(&away), &signature);
+    assert_eq!(result, Err(Ok(MultisigError::InvalidDelegation.into())));
+}
+
+#[test]
+fn test_delegation_ends_on_revocation_and_signer_removal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &100, &reason, &3600);
+
+    let away = signers.get_unchecked(2);
+    let delegate = BytesN::from_array(&env, &signing_key(9).verifying_key().to_bytes());
+    let expires_at = env.ledger().timestamp() + 86_400;
+    delegate_approvals(&env, &client, 3, &delegate, expires_at);
+
+    // A delegate acts for one signer at a time
+    let other = signers.get_unchecked(1);
+    let payload = client.get_delegation_payload(&other, &delegate, &expires_at);
+    let signature = BytesN::from_array(&env, &signing_key(2).sign(&payload.to_array()).to_bytes());
+    assert_eq!(
+        client.try_delegate_approval(&other, &delegate, &expires_at, &0, &signature),
+        Err(Ok(MultisigError::InvalidDelegation.into()))
+    );
+
+    let payload = client.get_revocation_payload(&away);
+    let signature = BytesN::from_array(&env, &signing_key(3).sign(&payload.to_array()).to_bytes());
+    client.revoke_delegation(&away, &client.signer_nonce(&away), &signature);
+    assert_eq!(client.get_delegation(&away), None);
+    assert_eq!(client.try_approve_proposal(&proposal_id, &delegate), Err(Ok(MultisigError::UnknownSigner.into())));
+    assert_eq!(
+        client.try_revoke_delegation(&away, &client.signer_nonce(&away), &signature),
+        Err(Ok(MultisigError::InvalidDelegation.into()))
+    );
+
+    // Removing the delegator drops the delegation for good
+    delegate_approvals(&env, &client, 3, &delegate, expires_at);
+    run_signer_change(&client, &signers, SignerChangeKind::Remove, &away);
+    assert_eq!(client.get_delegation(&away), None);
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::read_delegation(&env, &away), None);
+        assert_eq!(storage::read_delegator_of(&env, &delegate), None);
+    });
+    run_signer_change(&client, &signers, SignerChangeKind::Add, &away);
+    assert_eq!(client.get_delegation(&away), None);
+    assert_eq!(client.try_approve_proposal(&proposal_id, &delegate), Err(Ok(MultisigError::UnknownSigner.into())));
+}
+
+#[test]
+fn test_delegate_who_is_a_signer_cannot_double_count() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let first = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &100, &reason, &3600);
+    let second = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &200, &reason, &3600);
+
+    // Approved in their own right before the delegation started
+    let (away, delegate) = (signers.get_unchecked(0), signers.get_unchecked(1));
+    client.approve_proposal(&second, &delegate);
+    delegate_approvals(&env, &client, 1, &delegate, env.ledger().timestamp() + 1000);
+
+    // One approval with the delegate's key fills the delegator's slot only
+    client.approve_proposal(&first, &delegate);
+    let approvals = client.get_proposal_approvals(&first);
+    assert_eq!(approvals.len(), 1);
+    assert_eq!(approvals.get_unchecked(0).signer, away);
+    assert_eq!(client.try_approve_proposal(&first, &delegate), Err(Ok(MultisigError::AlreadyApproved.into())));
+    assert!(!client.get_approval_progress(&first).ready);
+
+    // Nor can the pair add a second approval where the delegate already has one
+    assert_eq!(client.try_approve_proposal(&second, &delegate), Err(Ok(MultisigError::AlreadyApproved.into())));
+    assert_eq!(client.try_approve_proposal(&second, &away), Err(Ok(MultisigError::AlreadyApproved.into())));
+    assert_eq!(client.get_proposal_approvals(&second).len(), 1);
+
+    client.approve_proposal(&first, &signers.get_unchecked(2));
+    assert!(client.get_approval_progress(&first).ready);
+}
+
+fn sign_rotation(env: &Env, client: &MultiSigContractClient, seed: u8, new_key: &BytesN<32>) -> BytesN<64> {
+    let old_key = BytesN::from_array(env, &signing_key(seed).verifying_key().to_bytes());
+    let payload = client.get_rotation_payload(&old_key, new_key);
+    BytesN::from_array(env, &signing_key(seed).sign(&payload.to_array()).to_bytes())
+}
+
+#[test]
+fn test_rotate_own_key_moves_seat_and_drops_old_approvals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let token_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token_address, &recipient, &100, &reason, &3600);
+
+    let old_key = signers.get_unchecked(0);
+    let new_key = BytesN::from_array(&env, &signing_key(9).verifying_key().to_bytes());
+    client.approve_proposal(&proposal_id, &old_key);
+    env.as_contract(&contract_id, || storage::set_protected_signer(&env, &old_key, true));
+
+    let signature = sign_rotation(&env, &client, 1, &new_key);
+    client.rotate_own_key(&old_key, &new_key, &signature);
+    let events = env.events().all();
+    let data: Map<Symbol, Val> = map![&env, (Symbol::new(&env, "new_key"), new_key.into_val(&env))];
+    let rotated = (contract_id.clone(), (Symbol::new(&env, "signer_key_rotated"), old_key.clone()).into_val(&env), data.into_val(&env));
+    assert_eq!(events, vec![&env, rotated, fingerprint_event(&env, &contract_id, &client.get_config_fingerprint())]);
+
+    assert!(!client.is_signer(&old_key));
+    assert!(client.is_signer(&new_key));
+    assert_eq!(client.signer_count(), 3);
+    assert!(!client.is_protected_signer(&old_key));
+    assert!(client.is_protected_signer(&new_key));
+    assert_eq!(client.get_proposal_approvals(&proposal_id).len(), 0);
+
+    // Re-approving under the new key is the seat's only approval
+    client.approve_proposal(&proposal_id, &new_key);
+    assert!(!client.get_approval_progress(&proposal_id).ready);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    assert!(client.get_approval_progress(&proposal_id).ready);
+
+    // The old key has left the set, so its signature cannot rotate again
+    let other = BytesN::from_array(&env, &signing_key(10).verifying_key().to_bytes());
+    assert_eq!(
+        client.try_rotate_own_key(&old_key, &other, &sign_rotation(&env, &client, 1, &other)),
+        Err(Ok(MultisigError::UnknownSigner.into()))
+    );
+}
+
+#[test]
+fn test_rotate_own_key_rejects_key_already_a_signer() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let (old_key, taken) = (signers.get_unchecked(0), signers.get_unchecked(1));
+    let signature = sign_rotation(&env, &client, 1, &taken);
+    assert_eq!(client.try_rotate_own_key(&old_key, &taken, &signature), Err(Ok(MultisigError::DuplicateSigner.into())));
+    assert_eq!(client.try_rotate_own_key(&old_key, &old_key, &signature), Err(Ok(MultisigError::DuplicateSigner.into())));
+    assert!(client.is_signer(&old_key));
+    assert!(client.is_signer(&taken));
+    assert_eq!(client.signer_count(), 3);
+    assert_eq!(client.signer_nonce(&old_key), 0);
+}
+
+fn set_rate(client: &MultiSigContractClient, signers: &Vec<BytesN<32>>, token: &Address, numerator: i128, denominator: i128, as_of: u64) {
+    let rate = ExchangeRate { token: token.clone(), numerator, denominator, as_of };
+    pass_governance(client, signers, &GovernanceAction::Treasury(TreasuryAction::SetExchangeRate(rate)), 2);
+}
+
+#[test]
+fn test_value_cap_accumulates_across_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    // One unit of `dollars` is worth one cent, one of `euros` a cent and a half
+    let dollars = Address::generate(&env);
+    let euros = Address::generate(&env);
+    set_rate(&client, &signers, &dollars, 1, 1, 0);
+    set_rate(&client, &signers, &euros, 3, 2, 0);
+    let cap = ValueCap { amount: 1000, window_seconds: 86_400 };
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::SetValueCap(cap.clone())), 2);
+    assert_eq!(client.get_value_cap(), Some(cap));
+
+    let proposer = signers.get_unchecked(0);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    assert_eq!(client.get_proposer_value_capacity(&proposer), Some(1000));
+
+    client.create_proposal(&proposer, &dollars, &recipient, &400, &reason, &3600);
+    client.create_proposal(&proposer, &euros, &recipient, &300, &reason, &3600);
+    assert_eq!(client.get_proposer_value_capacity(&proposer), Some(150));
+
+    // 101 units come to 151.5 cents, rounded up past what is left
+    assert_eq!(client.quote_value(&euros, &101), 152);
+    let result = client.try_create_proposal(&proposer, &euros, &recipient, &101, &reason, &3600);
+    assert_eq!(result, Err(Ok(MultisigError::ProposerCapExceeded.into())));
+    assert_eq!(client.validate_draft(&proposer, &euros, &recipient, &101, &3600), vec![&env, Symbol::new(&env, "proposer_cap")]);
+    client.create_proposal(&proposer, &euros, &recipient, &100, &reason, &3600);
+    assert_eq!(client.get_proposer_value_capacity(&proposer), Some(0));
+
+    // Other proposers have their own allowance, and the window resets
+    client.create_proposal(&signers.get_unchecked(1), &dollars, &recipient, &1000, &reason, &3600);
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    set_rate(&client, &signers, &dollars, 1, 1, env.ledger().timestamp());
+    env.ledger().with_mut(|li| li.timestamp += 86_400 - 3600);
+    assert_eq!(client.get_proposer_value_capacity(&proposer), Some(1000));
+    client.create_proposal(&proposer, &dollars, &recipient, &1000, &reason, &3600);
+
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::RemoveValueCap), 2);
+    assert_eq!(client.get_proposer_value_capacity(&proposer), None);
+}
+
+#[test]
+fn test_stale_or_missing_rates_fail_value_checks_closed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    env.ledger().with_mut(|li| li.timestamp = 10_000);
+
+    let token = Address::generate(&env);
+    let unrated = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let proposer = signers.get_unchecked(0);
+    let reason = String::from_str(&env, "Payment");
+
+    // Rates must be positive and cannot be quoted from the future
+    for (numerator, denominator, as_of) in [(0, 1, 10_000), (1, 0, 10_000), (1, 1, 10_001)] {
+        let rate = ExchangeRate { token: token.clone(), numerator, denominator, as_of };
+        let result = client.try_propose_governance(&proposer, &GovernanceAction::Treasury(TreasuryAction::SetExchangeRate(rate)), &3600);
+        assert_eq!(result, Err(Ok(MultisigError::InvalidProposal.into())));
+    }
+
+    // Without value limits, rates are never consulted
+    client.create_proposal(&proposer, &unrated, &recipient, &500, &reason, &3600);
+
+    set_rate(&client, &signers, &token, 1, 3, 9_000);
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::SetMaxRateAge(3600)), 2);
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::SetDocumentValueThreshold(100)), 2);
+    assert_eq!(client.get_max_rate_age(), 3600);
+    assert_eq!(client.get_document_value_threshold(), Some(100));
+    assert_eq!(client.quote_value(&token, &1), 1);
+
+    // 300 units are worth 100 cents, at the threshold; 301 are over it
+    client.create_proposal(&proposer, &token, &recipient, &300, &reason, &3600);
+    let action = ProposalAction::Transfer(TransferAction { token_address: token.clone(), recipient: recipient.clone(), amount: 301 });
+    assert_eq!(client.try_create_proposal_v2(&proposer, &action, &reason, &3600), Err(Ok(MultisigError::DocumentHashRequired.into())));
+    let invoice = BytesN::from_array(&env, &[7; 32]);
+    client.create_proposal_with_document(&proposer, &action, &reason, &None, &invoice, &3600);
+
+    // A token without a rate is as stale as one whose rate has aged out
+    let result = client.try_create_proposal(&proposer, &unrated, &recipient, &1, &reason, &3600);
+    assert_eq!(result, Err(Ok(MultisigError::StaleRate.into())));
+    env.ledger().with_mut(|li| li.timestamp = 9_000 + 3601);
+    let result = client.try_create_proposal(&proposer, &token, &recipient, &1, &reason, &3600);
+    assert_eq!(result, Err(Ok(MultisigError::StaleRate.into())));
+    assert_eq!(client.try_quote_value(&token, &1), Err(Ok(MultisigError::StaleRate.into())));
+
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::SetValueCap(ValueCap { amount: 1000, window_seconds: 86_400 })), 2);
+    assert_eq!(client.validate_draft(&proposer, &token, &recipient, &1, &3600), vec![&env, Symbol::new(&env, "stale_rate")]);
+
+    // A fresh quote reopens the checks
+    set_rate(&client, &signers, &token, 1, 3, env.ledger().timestamp());
+    client.create_proposal(&proposer, &token, &recipient, &3, &reason, &3600);
+    assert_eq!(client.get_proposer_value_capacity(&proposer), Some(999));
+}
+
+fn legacy_proposal(env: &Env, external_ref: u64, proposer: &BytesN<32>, token: &Address, approvals: Vec<BytesN<32>>) -> ImportedProposal {
+    let now = env.ledger().timestamp();
+    ImportedProposal {
+        external_ref,
+        proposer: proposer.clone(),
+        action: ProposalAction::Transfer(TransferAction { token_address: token.clone(), recipient: Address::generate(env), amount: 100 }),
+        reason: String::from_str(env, "Legacy payment"),
+        created_at: now.saturating_sub(3600),
+        expires_at: now + 86_400,
+        destination_memo: None,
+        document_hash: None,
+        approvals,
+    }
+}
+
+#[test]
+fn test_import_carries_over_signers_and_open_proposals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 100_000);
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 4);
+    client.initialize(&vec![&env, signers.get_unchecked(0), signers.get_unchecked(1), signers.get_unchecked(2)], &2, &false);
+    assert!(client.import_open());
+
+    client.import_signers(&vec![&env, signers.get_unchecked(3)]);
+    let events = env.events().all();
+    let data: Map<Symbol, Val> = Map::new(&env);
+    let imported = (contract_id.clone(), (Symbol::new(&env, "signer_imported"), signers.get_unchecked(3)).into_val(&env), data.into_val(&env));
+    let fingerprint = fingerprint_event(&env, &contract_id, &client.get_config_fingerprint());
+    assert_eq!(events, vec![&env, imported, fingerprint]);
+    assert_eq!(client.signer_count(), 4);
+    assert!(client.is_signer(&signers.get_unchecked(3)));
+    let result = client.try_import_signers(&vec![&env, signers.get_unchecked(0)]);
+    assert_eq!(result, Err(Ok(MultisigError::DuplicateSigner.into())));
+
+    // Imported with the approvals it had, so it can execute without anyone re-approving
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let approvals = vec![&env, signers.get_unchecked(2), signers.get_unchecked(3)];
+    let legacy = legacy_proposal(&env, 41, &signers.get_unchecked(1), &token, approvals);
+    let proposal_id = client.import_proposal(&legacy);
+    let data: Map<Symbol, Val> = map![
+        &env,
+        (Symbol::new(&env, "approvals"), 2u32.into_val(&env)),
+        (Symbol::new(&env, "external_ref"), 41u64.into_val(&env))
+    ];
+    let imported = (contract_id.clone(), (Symbol::new(&env, "proposal_imported"), proposal_id).into_val(&env), data.into_val(&env));
+    let ready = readiness_event(&env, &contract_id, "proposal_ready", proposal_id, 2, 2);
+    assert_eq!(contract_events(&env, &contract_id), vec![&env, ready, imported]);
+
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!(proposal.proposer, signers.get_unchecked(1));
+    assert_eq!(proposal.created_at, 100_000 - 3600);
+    assert_eq!(client.get_import_ref(&proposal_id), Some(41));
+    assert_eq!(client.find_imported_proposal(&41), Some(proposal_id));
+    assert_eq!(client.get_changes_since(&0, &10).entries.get_unchecked(0).action, Symbol::new(&env, "imported"));
+    assert!(client.get_approval_progress(&proposal_id).ready);
+    client.execute_proposal(&proposal_id);
+    assert_eq!(soroban_sdk::token::TokenClient::new(&env, &token).balance(&contract_id), 900);
+
+    // Each legacy id once, and only current signers' approvals
+    let legacy = legacy_proposal(&env, 41, &signers.get_unchecked(1), &token, Vec::new(&env));
+    assert_eq!(client.try_import_proposal(&legacy), Err(Ok(GovernanceError::AlreadyImported.into())));
+    let stranger = BytesN::from_array(&env, &[99; 32]);
+    let legacy = legacy_proposal(&env, 42, &signers.get_unchecked(1), &token, vec![&env, stranger]);
+    assert_eq!(client.try_import_proposal(&legacy), Err(Ok(MultisigError::UnknownSigner.into())));
+    let mut legacy = legacy_proposal(&env, 42, &signers.get_unchecked(1), &token, Vec::new(&env));
+    legacy.expires_at = legacy.created_at;
+    assert_eq!(client.try_import_proposal(&legacy), Err(Ok(MultisigError::InvalidExpiryTime.into())));
+
+    let recipient = Address::generate(&env);
+    let created = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &1, &String::from_str(&env, "New"), &3600);
+    assert_eq!(client.get_import_ref(&created), None);
+}
+
+#[test]
+fn test_finished_import_stays_closed_and_leaves_normal_operation_alone() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 4);
+    client.initialize(&vec![&env, signers.get_unchecked(0), signers.get_unchecked(1), signers.get_unchecked(2)], &2, &false);
+    let token = create_funded_token(&env, &contract_id, 1000);
+    client.import_proposal(&legacy_proposal(&env, 7, &signers.get_unchecked(0), &token, vec![&env, signers.get_unchecked(1)]));
+
+    client.finish_import();
+    assert!(!client.import_open());
+    let closed = GovernanceError::ImportClosed.into();
+    assert_eq!(client.try_import_signers(&vec![&env, signers.get_unchecked(3)]), Err(Ok(closed)));
+    assert_eq!(client.try_import_proposal(&legacy_proposal(&env, 8, &signers.get_unchecked(0), &token, Vec::new(&env))), Err(Ok(closed)));
+    assert_eq!(client.try_finish_import(), Err(Ok(closed)));
+
+    // Proposals, imported or not, and signer changes work as before
+    let recipient = Address::generate(&env);
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &300, &String::from_str(&env, "After import"), &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    client.execute_proposal(&proposal_id);
+    let imported = client.find_imported_proposal(&7).unwrap();
+    client.approve_proposal(&imported, &signers.get_unchecked(2));
+    client.execute_proposal(&imported);
+    run_signer_change(&client, &signers, SignerChangeKind::Add, &signers.get_unchecked(3));
+    assert_eq!(client.signer_count(), 4);
+}
+
+#[test]
+fn test_import_phase_is_bounded_and_needs_contract_auth() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let newcomer = BytesN::from_array(&env, &[42; 32]);
+
+    // Without threshold authorization by the contract's own account
+    assert!(client.try_import_signers(&vec![&env, newcomer.clone()]).is_err());
+    assert!(client.try_finish_import().is_err());
+    assert!(client.import_open());
+
+    env.ledger().with_mut(|li| li.timestamp += import::IMPORT_WINDOW_SECONDS + 1);
+    assert!(!client.import_open());
+    env.mock_all_auths();
+    assert_eq!(client.try_import_signers(&vec![&env, newcomer]), Err(Ok(GovernanceError::ImportClosed.into())));
+}
+
+#[test]
+fn test_removed_signer_approvals_stop_counting() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 4);
+    client.initialize(&signers, &2, &false);
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let removed = signers.get_unchecked(3);
+
+    let transfer = client.create_proposal(&signers.get_unchecked(2), &token, &recipient, &100, &reason, &3600);
+    client.approve_proposal(&transfer, &signers.get_unchecked(2));
+    client.approve_proposal(&transfer, &removed);
+    let newcomer = BytesN::from_array(&env, &[42; 32]);
+    let addition = client.propose_signer_change(&signers.get_unchecked(2), &SignerChangeKind::Add, &newcomer, &3600);
+    client.approve_signer_change(&addition, &signers.get_unchecked(2));
+    client.approve_signer_change(&addition, &removed);
+
+    run_signer_change(&client, &signers, SignerChangeKind::Remove, &removed);
+
+    // The removed signer's approvals are purged from every open proposal
+    assert_eq!(ordinals(&client, transfer), vec![&env, (signers.get_unchecked(2), 1)]);
+    assert_eq!(client.get_signer_change_approvals(&addition).len(), 1);
+    assert_eq!(client.get_approval_progress(&transfer).approvals, 1);
+    assert_eq!(client.try_execute_proposal(&transfer), Err(Ok(MultisigError::InsufficientApprovals.into())));
+    assert_eq!(client.try_execute_signer_change(&addition), Err(Ok(SignerError::InsufficientSignerChangeApprovals.into())));
+
+    client.approve_proposal(&transfer, &signers.get_unchecked(0));
+    client.execute_proposal(&transfer);
+    client.approve_signer_change(&addition, &signers.get_unchecked(0));
+    client.execute_signer_change(&addition);
+    assert!(client.is_signer(&newcomer));
+}
+
+#[test]
+fn test_threshold_is_frozen_at_creation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 4);
+    client.initialize(&signers, &3, &false);
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let reason = String::from_str(&env, "Payment");
+    let transfer = client.create_proposal(&signers.get_unchecked(0), &token, &Address::generate(&env), &100, &reason, &3600);
+    let newcomer = BytesN::from_array(&env, &[42; 32]);
+    let addition = client.propose_signer_change(&signers.get_unchecked(0), &SignerChangeKind::Add, &newcomer, &3600);
+
+    // Lowering the threshold leaves what pending proposals need alone
+    env.as_contract(&contract_id, || storage::write_threshold(&env, 2));
+    for i in 0..2 {
+        client.approve_proposal(&transfer, &signers.get_unchecked(i));
+        client.approve_signer_change(&addition, &signers.get_unchecked(i));
+    }
+    let progress = client.get_approval_progress(&transfer);
+    assert_eq!((progress.approvals, progress.required, progress.ready), (2, 3, false));
+    assert_eq!(client.get_signer_change_quorum(&addition), 3);
+    assert_eq!(client.try_execute_proposal(&transfer), Err(Ok(MultisigError::InsufficientApprovals.into())));
+    assert_eq!(client.try_execute_signer_change(&addition), Err(Ok(SignerError::InsufficientSignerChangeApprovals.into())));
+
+    // Proposals created since follow the new threshold
+    let later = client.create_proposal(&signers.get_unchecked(0), &token, &Address::generate(&env), &100, &reason, &3600);
+    assert_eq!(client.get_approval_progress(&later).required, 2);
+
+    client.approve_proposal(&transfer, &signers.get_unchecked(2));
+    client.execute_proposal(&transfer);
+    client.approve_signer_change(&addition, &signers.get_unchecked(2));
+    client.execute_signer_change(&addition);
+    assert!(client.is_signer(&newcomer));
+}
+
+#[test]
+fn test_purge_stale_approvals_after_registry_removal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let signers = create_test_signers(&env, 4);
+
+    let registry_id = env.register(MultiSigContract, ());
+    let registry = MultiSigContractClient::new(&env, &registry_id);
+    registry.initialize(&signers, &2, &false);
+    let client = MultiSigContractClient::new(&env, &env.register(MultiSigContract, ()));
+    client.initialize_with_registry(&registry_id);
+
+    let removed = signers.get_unchecked(3);
+    let reason = String::from_str(&env, "Payment");
+    let transfer = client.create_proposal(&signers.get_unchecked(2), &Address::generate(&env), &Address::generate(&env), &100, &reason, &3600);
+    client.approve_proposal(&transfer, &removed);
+    client.approve_proposal(&transfer, &signers.get_unchecked(2));
+    assert!(client.get_approval_progress(&transfer).ready);
+
+    // The registry cannot reach this instance's proposals, so the approval lingers until purged
+    run_signer_change(&registry, &signers, SignerChangeKind::Remove, &removed);
+    assert_eq!(client.get_proposal_approvals(&transfer).len(), 2);
+    assert!(!client.get_approval_progress(&transfer).ready);
+
+    assert_eq!(client.purge_stale_approvals(&transfer), 1);
+    assert_eq!(ordinals(&client, transfer), vec![&env, (signers.get_unchecked(2), 1)]);
+    assert_eq!(client.purge_stale_approvals(&transfer), 0);
+
+    client.approve_proposal(&transfer, &signers.get_unchecked(0));
+    let progress = client.get_approval_progress(&transfer);
+    assert_eq!((progress.approvals, progress.ready), (2, true));
+}
+
+#[test]
+fn test_later_signers_approve_old_proposals_without_snapshots() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let reason = String::from_str(&env, "Payment");
+    let transfer = client.create_proposal(&signers.get_unchecked(0), &Address::generate(&env), &Address::generate(&env), &100, &reason, &3600);
+    assert_eq!(client.get_proposal_signer_set_version(&transfer), Some(1));
+
+    let newcomer = BytesN::from_array(&env, &[42; 32]);
+    run_signer_change(&client, &signers, SignerChangeKind::Add, &newcomer);
+    assert_eq!(client.get_signer_set_version(), 2);
+
+    client.approve_proposal(&transfer, &newcomer);
+    assert_eq!(client.get_approval_progress(&transfer).approvals, 1);
+}
+
+#[test]
+fn test_snapshot_signers_decide_their_own_proposals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::EnableFeature(Symbol::new(&env, "snapshot"))), 2);
+    let reason = String::from_str(&env, "Payment");
+    let old = client.create_proposal(&signers.get_unchecked(0), &Address::generate(&env), &Address::generate(&env), &100, &reason, &3600);
+
+    let newcomer = BytesN::from_array(&env, &[42; 32]);
+    run_signer_change(&client, &signers, SignerChangeKind::Add, &newcomer);
+
+    // Neither an approval nor a delegated vote from the newcomer reaches the old proposal
+    let not_in_snapshot = SignerError::SignerNotInSnapshot.into();
+    assert_eq!(client.try_approve_proposal(&old, &newcomer), Err(Ok(not_in_snapshot)));
+    assert_eq!(client.try_delegate_vote(&old, &newcomer, &signers.get_unchecked(0)), Err(Ok(not_in_snapshot)));
+    client.approve_proposal(&old, &signers.get_unchecked(0));
+    client.approve_proposal(&old, &signers.get_unchecked(1));
+    assert!(client.get_approval_progress(&old).ready);
+
+    // Proposals created since include the newcomer
+    let new = client.create_proposal(&signers.get_unchecked(0), &Address::generate(&env), &Address::generate(&env), &100, &reason, &3600);
+    client.approve_proposal(&new, &newcomer);
+
+    // A rotated seat keeps the version it joined at
+    let rotated = BytesN::from_array(&env, &[43; 32]);
+    env.as_contract(&contract_id, || signers::swap_key(&env, &signers.get_unchecked(2), &rotated));
+    client.approve_proposal(&old, &rotated);
+
+    // Join versions live with the registry's own signer set, out of reach of its members
+    let member = MultiSigContractClient::new(&env, &env.register(MultiSigContract, ()));
+    member.initialize_with_registry(&contract_id);
+    let action = GovernanceAction::Policy(PolicyAction::EnableFeature(Symbol::new(&env, "snapshot")));
+    let result = member.try_propose_governance(&signers.get_unchecked(0), &action, &3600);
+    assert_eq!(result, Err(Ok(SignerError::RegistryManaged.into())));
+}
+
+#[test]
+fn test_open_execution_is_recorded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token, &Address::generate(&env), &100, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    assert_eq!(client.get_execution_record(&proposal_id), None);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_500);
+    client.execute_proposal(&proposal_id);
+    let record = ExecutionRecord { executed_at: 1_500, tier: ExecutionTier::Public, executed_by: None, executor: None };
+    assert_eq!(client.get_execution_record(&proposal_id), Some(record));
+}
+
+#[test]
+fn test_signers_execute_closes_public_execution() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::EnableFeature(Symbol::new(&env, "signer_ex"))), 2);
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token, &Address::generate(&env), &100, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+
+    assert_eq!(client.try_execute_proposal(&proposal_id), Err(Ok(MultisigError::ExecutionTierClosed.into())));
+    let outsider = BytesN::from_array(&env, &[42; 32]);
+    assert_eq!(client.try_execute_as_signer(&proposal_id, &outsider), Err(Ok(MultisigError::UnknownSigner.into())));
+
+    // A signer who did not approve may still pull the trigger
+    client.execute_as_signer(&proposal_id, &signers.get_unchecked(2));
+    let record = ExecutionRecord { executed_at: 1_000, tier: ExecutionTier::Committee, executed_by: Some(signers.get_unchecked(2)), executor: None };
+    assert_eq!(client.get_execution_record(&proposal_id), Some(record));
+}
+
+#[test]
+fn test_only_signers_execute_is_set_at_initialize() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    // Open execution: anyone may execute, and no signer is recorded
+    let signers = create_test_signers(&env, 3);
+    let open_id = env.register(MultiSigContract, ());
+    let open = MultiSigContractClient::new(&env, &open_id);
+    open.initialize_with_signers(&as_signers(&env, &signers), &2, &false, &None, &Vec::new(&env), &false);
+    let token = create_funded_token(&env, &open_id, 1000);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = open.create_proposal(&signers.get_unchecked(0), &token, &Address::generate(&env), &100, &reason, &3600);
+    open.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    open.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    assert_eq!(open.get_proposal(&proposal_id).executed_at, None);
+    open.execute_proposal(&proposal_id);
+    let proposal = open.get_proposal(&proposal_id);
+    assert_eq!((proposal.executed_by, proposal.executed_at), (None, Some(1_000)));
+
+    // Signers only: the public call is closed and the executing signer recorded
+    let closed_id = env.register(MultiSigContract, ());
+    let closed = MultiSigContractClient::new(&env, &closed_id);
+    closed.initialize_with_signers(&as_signers(&env, &signers), &2, &false, &None, &Vec::new(&env), &true);
+    assert!(closed.get_enabled_features().contains(Symbol::new(&env, "signer_ex")));
+    let token = create_funded_token(&env, &closed_id, 1000);
+    let proposal_id = closed.create_proposal(&signers.get_unchecked(0), &token, &Address::generate(&env), &100, &reason, &3600);
+    closed.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    closed.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    assert_eq!(closed.try_execute_proposal(&proposal_id), Err(Ok(MultisigError::ExecutionTierClosed.into())));
+    closed.execute_as_signer(&proposal_id, &signers.get_unchecked(2));
+    let proposal = closed.get_proposal(&proposal_id);
+    assert_eq!((proposal.executed_by, proposal.executed_at), (Some(signers.get_unchecked(2)), Some(1_000)));
+}
+
+#[test]
+fn test_execute_as_signer_needs_authorization() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let keys = create_test_signers(&env, 2);
+    let passkey = Signer::Secp256r1(passkey(&env, 7));
+    let signers = vec![&env, Signer::Ed25519(keys.get_unchecked(0)), Signer::Ed25519(keys.get_unchecked(1)), passkey.clone()];
+    client.initialize_with_signers(&signers, &2, &false, &None, &Vec::new(&env), &true);
+    env.mock_all_auths();
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&keys.get_unchecked(0), &token, &Address::generate(&env), &100, &reason, &3600);
+    client.approve_proposal(&proposal_id, &keys.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &keys.get_unchecked(1));
+
+    // Knowing an unbound key is not enough to execute as it
+    env.set_auths(&[]);
+    assert!(client.try_execute_as_signer(&proposal_id, &keys.get_unchecked(0)).is_err());
+    assert_eq!(
+        client.try_execute_as_signer(&proposal_id, &client.signer_id(&passkey)),
+        Err(Ok(SignerError::SignerUnbound.into()))
+    );
+
+    // An unbound ed25519 key executes through the account it is the master key of
+    env.mock_all_auths();
+    client.execute_as_signer(&proposal_id, &keys.get_unchecked(0));
+    assert_eq!(env.auths()[0].0, signer_account(&env, &keys.get_unchecked(0)));
+}
+
+fn policy_check(env: &Env, verdict: &ExecutionVerdict, rule: &str) -> PolicyCheck {
+    verdict.checks.iter().find(|check| check.rule == Symbol::new(env, rule)).unwrap()
+}
+
+#[test]
+fn test_simulated_execution_matches_real_execution() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let token = create_funded_token(&env, &contract_id, 1000);
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::SetReserveFloor(ReserveFloor { token: token.clone(), amount: 800 })), 2);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let approved = |amount: i128| {
+        let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &amount, &reason, &3600);
+        client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+        client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+        proposal_id
+    };
+
+    // Above the reserve floor's headroom: the simulation and execution both refuse
+    let verdict = client.simulate_draft_execution(&token, &recipient, &300, &2_000);
+    assert!(!verdict.passed);
+    assert_eq!(verdict.checks.len(), 8);
+    let reserve = PolicyCheck { rule: Symbol::new(&env, "reserve_floor"), passed: false, actual: Some(300), limit: Some(200) };
+    assert_eq!(policy_check(&env, &verdict, "reserve_floor"), reserve);
+    assert!(policy_check(&env, &verdict, "balance").passed);
+    let too_much = approved(300);
+    assert_eq!(client.try_execute_proposal(&too_much), Err(Ok(MultisigError::ReserveBreached.into())));
+
+    // Within it, both go through
+    let verdict = client.simulate_draft_execution(&token, &recipient, &150, &2_000);
+    assert!(verdict.passed);
+    client.execute_proposal(&approved(150));
+
+    // A frozen token fails both, as does an execution time already past
+    let pending = approved(10);
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::FreezeToken(token.clone())), 2);
+    let verdict = client.simulate_draft_execution(&token, &recipient, &10, &500);
+    assert!(!policy_check(&env, &verdict, "token_frozen").passed);
+    assert!(!policy_check(&env, &verdict, "execute_at").passed);
+    assert_eq!(client.try_execute_proposal(&pending), Err(Ok(MultisigError::TokenFrozen.into())));
+}
+
+#[test]
+fn test_simulation_judges_rates_at_execution_time() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let token = create_funded_token(&env, &contract_id, 1000);
+    set_rate(&client, &signers, &token, 1, 1, 1_000);
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::SetDocumentValueThreshold(500)), 2);
+    let recipient = Address::generate(&env);
+
+    assert!(client.simulate_draft_execution(&token, &recipient, &100, &2_000).passed);
+    let verdict = client.simulate_draft_execution(&token, &recipient, &600, &2_000);
+    assert!(!policy_check(&env, &verdict, "document_required").passed);
+
+    // By then the rate is too old to value the payment at all
+    let later = 1_000 + rates::DEFAULT_MAX_RATE_AGE_SECONDS + 1;
+    let verdict = client.simulate_draft_execution(&token, &recipient, &100, &later);
+    assert!(!verdict.passed);
+    assert!(!policy_check(&env, &verdict, "document_required").passed);
+    assert_eq!(client.validate_draft(&signers.get_unchecked(0), &token, &recipient, &100, &3600), Vec::new(&env));
+}
+
+fn sign_ring_activation(env: &Env, client: &MultiSigContractClient, seed: u8, new_key: &BytesN<32>) -> BytesN<64> {
+    let active_key = BytesN::from_array(env, &signing_key(seed).verifying_key().to_bytes());
+    let payload = client.get_ring_activation_payload(&active_key, new_key);
+    BytesN::from_array(env, &signing_key(seed).sign(&payload.to_array()).to_bytes())
+}
+
+#[test]
+fn test_ring_key_activation_is_self_service_and_rate_limited() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let ledger_user = signers.get_unchecked(2);
+    let (second_path, third_path) = (
+        BytesN::from_array(&env, &signing_key(9).verifying_key().to_bytes()),
+        BytesN::from_array(&env, &signing_key(10).verifying_key().to_bytes()),
+    );
+    let ring = KeyRing { signer: ledger_user.clone(), keys: vec![&env, second_path.clone(), third_path.clone()] };
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::SetKeyRing(ring)), 2);
+    assert_eq!(client.get_key_ring(&ledger_user), (vec![&env, second_path.clone(), third_path.clone()], 0));
+
+    client.activate_ring_key(&ledger_user, &second_path, &sign_ring_activation(&env, &client, 3, &second_path));
+    let events = env.events().all();
+    let data: Map<Symbol, Val> = map![
+        &env,
+        (Symbol::new(&env, "new_key"), second_path.into_val(&env)),
+        (Symbol::new(&env, "remaining"), 1u32.into_val(&env))
+    ];
+    let activated = (contract_id.clone(), (Symbol::new(&env, "ring_key_activated"), ledger_user.clone()).into_val(&env), data.into_val(&env));
+    assert_eq!(events, vec![&env, activated, fingerprint_event(&env, &contract_id, &client.get_config_fingerprint())]);
+
+    // The seat moved, and the rest of the ring went with it
+    assert!(!client.is_signer(&ledger_user));
+    assert!(client.is_signer(&second_path));
+    assert_eq!(client.signer_count(), 3);
+    assert_eq!(client.get_key_ring(&ledger_user), (Vec::new(&env), 0));
+    assert_eq!(client.get_key_ring(&second_path), (vec![&env, third_path.clone()], 1_000 + keyring::MIN_ACTIVATION_INTERVAL_SECONDS));
+
+    // One switch per day
+    let signature = sign_ring_activation(&env, &client, 9, &third_path);
+    assert_eq!(client.try_activate_ring_key(&second_path, &third_path, &signature), Err(Ok(SignerError::RotationTooSoon.into())));
+    env.ledger().with_mut(|li| li.timestamp += keyring::MIN_ACTIVATION_INTERVAL_SECONDS);
+    client.activate_ring_key(&second_path, &third_path, &signature);
+    assert!(client.is_signer(&third_path));
+    assert_eq!(client.get_key_ring(&third_path).0, Vec::new(&env));
+}
+
+#[test]
+fn test_ring_key_activation_rejects_keys_outside_the_ring() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+
+    let active = signers.get_unchecked(0);
+    let ring_key = BytesN::from_array(&env, &signing_key(9).verifying_key().to_bytes());
+    let outsider = BytesN::from_array(&env, &signing_key(10).verifying_key().to_bytes());
+    let ring = KeyRing { signer: active.clone(), keys: vec![&env, ring_key.clone()] };
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::SetKeyRing(ring)), 2);
+
+    // Even with a valid signature from the active key
+    let signature = sign_ring_activation(&env, &client, 1, &outsider);
+    assert_eq!(client.try_activate_ring_key(&active, &outsider, &signature), Err(Ok(SignerError::KeyNotInRing.into())));
+    let signature = sign_ring_activation(&env, &client, 2, &ring_key);
+    assert_eq!(client.try_activate_ring_key(&signers.get_unchecked(1), &ring_key, &signature), Err(Ok(SignerError::KeyNotInRing.into())));
+    assert!(client.is_signer(&active));
+    assert_eq!(client.signer_nonce(&active), 0);
+
+    // Rings only hold distinct keys that are not signers yet, and only so many
+    let taken = KeyRing { signer: active.clone(), keys: vec![&env, signers.get_unchecked(1)] };
+    let result = client.try_propose_governance(&active, &GovernanceAction::Signers(SignerAction::SetKeyRing(taken)), &3600);
+    assert_eq!(result, Err(Ok(MultisigError::DuplicateSigner.into())));
+    let mut keys = Vec::new(&env);
+    for seed in 20..=20 + keyring::MAX_RING_KEYS as u8 {
+        keys.push_back(BytesN::from_array(&env, &[seed; 32]));
+    }
+    let result = client.try_propose_governance(&active, &GovernanceAction::Signers(SignerAction::SetKeyRing(KeyRing { signer: active.clone(), keys })), &3600);
+    assert_eq!(result, Err(Ok(MultisigError::InvalidProposal.into())));
+
+    // Removing the signer drops their ring
+    run_signer_change(&client, &signers, SignerChangeKind::Remove, &active);
+    assert_eq!(client.get_key_ring(&active).0, Vec::new(&env));
+}
+
+#[test]
+fn test_pause_blocks_payments_but_not_key_rotation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &100, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    assert!(!client.is_paused());
+
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::Pause), 2);
+    assert!(client.is_paused());
+
+    // Payments stop at every step
+    let result = client.try_create_proposal(&signers.get_unchecked(0), &token, &recipient, &100, &reason, &3600);
+    assert_eq!(result, Err(Ok(GovernanceError::ContractPaused.into())));
+    assert_eq!(client.try_approve_proposal(&proposal_id, &signers.get_unchecked(1)), Err(Ok(GovernanceError::ContractPaused.into())));
+    assert_eq!(client.try_execute_proposal(&proposal_id), Err(Ok(GovernanceError::ContractPaused.into())));
+
+    // Rotating the compromised key out still works
+    let compromised = signers.get_unchecked(2);
+    let replacement = BytesN::from_array(&env, &[77; 32]);
+    run_signer_change(&client, &signers, SignerChangeKind::Add, &replacement);
+    run_signer_change(&client, &signers, SignerChangeKind::Remove, &compromised);
+    assert!(client.is_signer(&replacement));
+    assert!(!client.is_signer(&compromised));
+
+    // Unpausing lets payments through again
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::Unpause), 2);
+    assert!(!client.is_paused());
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    client.execute_proposal(&proposal_id);
+    assert!(client.get_proposal(&proposal_id).executed);
+}
+
+#[test]
+fn test_retirement_sweeps_funds_to_successor_for_good() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let usdc = create_funded_token(&env, &contract_id, 1000);
+    let eurc = create_funded_token(&env, &contract_id, 250);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let open = client.create_proposal(&signers.get_unchecked(0), &usdc, &recipient, &100, &reason, &3600);
+    client.approve_proposal(&open, &signers.get_unchecked(0));
+    assert_eq!(client.get_successor(), None);
+
+    let successor = Address::generate(&env);
+    let retirement = Retirement { successor: successor.clone(), tokens: vec![&env, usdc.clone(), eurc.clone()] };
+    let retire_id = pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::Retire(retirement)), 2);
+    assert!(client.is_governance_executed(&retire_id));
+    assert_eq!(client.get_successor(), Some(successor.clone()));
+
+    // Every listed balance is now the successor's
+    let usdc_client = soroban_sdk::token::Client::new(&env, &usdc);
+    let eurc_client = soroban_sdk::token::Client::new(&env, &eurc);
+    assert_eq!((usdc_client.balance(&successor), eurc_client.balance(&successor)), (1000, 250));
+    assert_eq!((usdc_client.balance(&contract_id), eurc_client.balance(&contract_id)), (0, 0));
+
+    // Nothing that changes state goes through any more
+    let retired = Err(Ok(GovernanceError::ContractRetired.into()));
+    let result = client.try_create_proposal(&signers.get_unchecked(0), &usdc, &recipient, &100, &reason, &3600);
+    assert_eq!(result, Err(Ok(GovernanceError::ContractRetired.into())));
+    assert_eq!(client.try_approve_proposal(&open, &signers.get_unchecked(1)), retired);
+    assert_eq!(client.try_execute_proposal(&open), retired);
+    assert_eq!(client.try_cancel_proposal(&open, &signers.get_unchecked(0)), retired);
+    let result = client.try_propose_governance(&signers.get_unchecked(0), &GovernanceAction::Policy(PolicyAction::Unpause), &3600);
+    assert_eq!(result, Err(Ok(GovernanceError::ContractRetired.into())));
+    let result = client.try_propose_signer_change(&signers.get_unchecked(0), &SignerChangeKind::Add, &BytesN::from_array(&env, &[5; 32]), &3600);
+    assert_eq!(result, Err(Ok(GovernanceError::ContractRetired.into())));
+
+    // Not even with threshold signatures for the contract's own address
+    let payload = BytesN::from_array(&env, &[7; 32]);
+    let mut signatures: Vec<(BytesN<32>, BytesN<64>)> = Vec::new(&env);
+    for seed in [1, 2] {
+        let key = signing_key(seed);
+        signatures.push_back((
+            BytesN::from_array(&env, &key.verifying_key().to_bytes()),
+            BytesN::from_array(&env, &key.sign(&payload.to_array()).to_bytes()),
+        ));
+    }
+    let result = env.try_invoke_contract_check_auth::<GovernanceError>(&contract_id, &payload, signatures.into_val(&env), &vec![&env]);
+    assert_eq!(result, Err(Ok(GovernanceError::ContractRetired)));
+
+    // Views keep working
+    assert_eq!(client.get_proposal(&open).reason, reason);
+    assert_eq!(client.threshold(), 2);
+}
+
+#[test]
+fn test_retirement_rejects_invalid_handover() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let token = Address::generate(&env);
+    let successor = Address::generate(&env);
+    let propose = |successor: &Address, tokens: Vec<Address>| {
+        let retirement = Retirement { successor: successor.clone(), tokens };
+        client.try_propose_governance(&signers.get_unchecked(0), &GovernanceAction::Policy(PolicyAction::Retire(retirement)), &3600)
+    };
+
+    assert_eq!(propose(&contract_id, vec![&env, token.clone()]), Err(Ok(MultisigError::InvalidProposal.into())));
+    assert_eq!(propose(&successor, vec![&env, token.clone(), token.clone()]), Err(Ok(MultisigError::InvalidProposal.into())));
+
+    // A frozen token has to be unfrozen before it can be swept
+    pass_governance(&client, &signers, &GovernanceAction::Treasury(TreasuryAction::FreezeToken(token.clone())), 2);
+    assert_eq!(propose(&successor, vec![&env, token.clone()]), Err(Ok(MultisigError::TokenFrozen.into())));
+    assert_eq!(client.get_successor(), None);
+}
+
+#[test]
+fn test_retirement_needs_authorized_signers() {
+    let env = Env::default();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    let mut bindings = Vec::new(&env);
+    for signer in signers.iter() {
+        bindings.push_back((signer, Address::generate(&env)));
+    }
+    client.initialize_with_addresses(&signers, &2, &false, &bindings);
+    env.mock_all_auths();
+    let usdc = create_funded_token(&env, &contract_id, 1000);
+    env.set_auths(&[]);
+
+    // Someone who only knows the signer keys cannot hand themselves the treasury
+    let attacker = Address::generate(&env);
+    let retire = GovernanceAction::Policy(PolicyAction::Retire(Retirement { successor: attacker.clone(), tokens: vec![&env, usdc.clone()] }));
+    assert!(client.try_propose_governance(&signers.get_unchecked(0), &retire, &3600).is_err());
+
+    env.mock_all_auths();
+    let proposal_id = client.propose_governance(&signers.get_unchecked(0), &retire, &3600);
+    env.set_auths(&[]);
+    for i in 0..2 {
+        assert!(client.try_approve_governance(&proposal_id, &signers.get_unchecked(i)).is_err());
+    }
+    assert_eq!(
+        client.try_execute_governance(&proposal_id),
+        Err(Ok(GovernanceError::InsufficientGovernanceApprovals.into()))
+    );
+    assert_eq!(client.get_successor(), None);
+    assert_eq!(soroban_sdk::token::Client::new(&env, &usdc).balance(&contract_id), 1000);
+}
+
+#[test]
+fn test_guardian_vetoes_payment_proposals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let early = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &100, &reason, &3600);
+    let late = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &200, &reason, &3600);
+    assert_eq!(client.try_veto_proposal(&early), Err(Ok(GovernanceError::NoGuardian.into())));
+
+    let guardian = Address::generate(&env);
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::SetGuardian(guardian.clone())), 2);
+    assert_eq!(client.get_guardian(), Some(guardian.clone()));
+
+    // Vetoed before anyone approved it
+    client.veto_proposal(&early);
+    assert_eq!(env.auths()[0].0, guardian);
+    assert_eq!(client.get_proposal_status(&early), ProposalStatus::Vetoed);
+    assert_eq!(client.try_approve_proposal(&early, &signers.get_unchecked(0)), Err(Ok(MultisigError::ProposalVetoed.into())));
+
+    // Vetoed with every approval it needed, before it executed
+    client.approve_proposal(&late, &signers.get_unchecked(0));
+    client.approve_proposal(&late, &signers.get_unchecked(1));
+    assert_eq!(client.get_proposal_status(&late), ProposalStatus::Ready);
+    client.veto_proposal(&late);
+    assert_eq!(client.try_execute_proposal(&late), Err(Ok(MultisigError::ProposalVetoed.into())));
+    assert_eq!(client.get_proposal_status(&late), ProposalStatus::Vetoed);
+    assert_eq!(client.get_proposal_outcome(&late).unwrap().outcome, ProposalOutcome::Vetoed);
+    assert_eq!(soroban_sdk::token::Client::new(&env, &token).balance(&contract_id), 1000);
+    assert!(client.get_open_proposals(&0, &10).proposals.is_empty());
+    assert_eq!(client.try_veto_proposal(&late), Err(Ok(MultisigError::ProposalClosed.into())));
+}
+
+#[test]
+fn test_guardian_cannot_veto_signer_changes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let guardian = Address::generate(&env);
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::SetGuardian(guardian)), 2);
+
+    let newcomer = BytesN::from_array(&env, &[42; 32]);
+    let change = client.propose_signer_change(&signers.get_unchecked(0), &SignerChangeKind::Add, &newcomer, &3600);
+    assert_eq!(client.try_veto_proposal(&change), Err(Ok(MultisigError::ProposalNotFound.into())));
+    client.approve_signer_change(&change, &signers.get_unchecked(0));
+    client.approve_signer_change(&change, &signers.get_unchecked(1));
+    client.execute_signer_change(&change);
+    assert!(client.is_signer(&newcomer));
+
+    // Nor can it stop the signers from replacing it
+    pass_governance(&client, &signers, &GovernanceAction::Policy(PolicyAction::ClearGuardian), 2);
+    assert_eq!(client.get_guardian(), None);
+}
+
+fn sign_recovery(env: &Env, client: &MultiSigContractClient, seed: u8, signers: &Vec<BytesN<32>>, threshold: u32) -> BytesN<64> {
+    let payload = client.get_recovery_payload(signers, &threshold);
+    BytesN::from_array(env, &signing_key(seed).sign(&payload.to_array()).to_bytes())
+}
+
+#[test]
+fn test_recovery_key_takes_over_after_inactivity() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let recipient = Address::generate(&env);
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &100, &String::from_str(&env, "Payment"), &(30 * 86_400));
+
+    let recovery_key = BytesN::from_array(&env, &signing_key(20).verifying_key().to_bytes());
+    let config = RecoveryConfig { recovery_key, inactivity_seconds: 10 * 86_400 };
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::SetRecovery(config.clone())), 2);
+    assert_eq!(client.get_recovery(), Some((config.clone(), 1_000 + 10 * 86_400)));
+
+    // An approval five days in restarts the timer
+    env.ledger().with_mut(|li| li.timestamp = 1_000 + 5 * 86_400);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    let deadline = 1_000 + 15 * 86_400;
+    assert_eq!(client.get_recovery(), Some((config, deadline)));
+
+    let successors = vec![
+        &env,
+        BytesN::from_array(&env, &signing_key(21).verifying_key().to_bytes()),
+        BytesN::from_array(&env, &signing_key(22).verifying_key().to_bytes()),
+        BytesN::from_array(&env, &signing_key(23).verifying_key().to_bytes()),
+    ];
+    env.ledger().with_mut(|li| li.timestamp = deadline);
+    let signature = sign_recovery(&env, &client, 20, &successors, 2);
+    assert_eq!(client.try_recovery_claim(&successors, &2, &signature), Err(Ok(SignerError::RecoveryNotDue.into())));
+
+    // One second later the recovery key may take over, with a valid set
+    env.ledger().with_mut(|li| li.timestamp = deadline + 1);
+    assert_eq!(client.try_recovery_claim(&successors, &4, &signature), Err(Ok(MultisigError::ThresholdExceedsSigners.into())));
+    client.recovery_claim(&successors, &2, &signature);
+    for signer in signers.iter() {
+        assert!(!client.is_signer(&signer));
+    }
+    for signer in successors.iter() {
+        assert!(client.is_signer(&signer));
+    }
+    assert_eq!((client.signer_count(), client.threshold()), (3, 2));
+    assert_eq!(client.get_signer_set_at(&(deadline + 1)), SignerSetSnapshot { signers: successors.clone(), threshold: 2 });
+
+    // The old committee's approvals are gone, and the new one carries on
+    assert_eq!(client.get_proposal_approvals(&proposal_id).len(), 0);
+    client.approve_proposal(&proposal_id, &successors.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &successors.get_unchecked(1));
+    client.execute_proposal(&proposal_id);
+    assert_eq!(soroban_sdk::token::Client::new(&env, &token).balance(&recipient), 100);
+}
+
+#[test]
+fn test_recovery_is_disabled_without_configuration() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_signing_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let successors = vec![&env, BytesN::from_array(&env, &[7; 32]), BytesN::from_array(&env, &[8; 32])];
+    let signature = BytesN::from_array(&env, &[0; 64]);
+    assert_eq!(client.get_recovery(), None);
+    assert_eq!(client.try_recovery_claim(&successors, &2, &signature), Err(Ok(SignerError::RecoveryDisabled.into())));
+
+    // Clearing the configuration turns the path off again, however long the
+    // committee then stays idle
+    let recovery_key = BytesN::from_array(&env, &signing_key(20).verifying_key().to_bytes());
+    let config = RecoveryConfig { recovery_key, inactivity_seconds: 86_400 };
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::SetRecovery(config)), 2);
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::ClearRecovery), 2);
+    env.ledger().with_mut(|li| li.timestamp += 365 * 86_400);
+    assert_eq!(client.try_recovery_claim(&successors, &2, &signature), Err(Ok(SignerError::RecoveryDisabled.into())));
+    assert!(client.is_signer(&signers.get_unchecked(0)));
+}
+
+#[test]
+fn test_suspended_signer_is_blocked_until_reinstated() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 4);
+    client.initialize(&signers, &2, &false);
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let suspended = signers.get_unchecked(3);
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &100, &reason, &3600);
+    client.approve_proposal(&proposal_id, &suspended);
+
+    run_signer_change(&client, &signers, SignerChangeKind::Suspend, &suspended);
+    assert!(client.is_signer(&suspended));
+    assert!(!client.is_signer_active(&suspended));
+    assert_eq!(client.signer_count(), 4);
+    assert!(client.get_signers(&0, &10).iter().any(|signer| signer.id == suspended && signer.suspended));
+
+    // Blocked from approving, proposing and voting on signer changes, and
+    // the approval given before no longer counts
+    assert_eq!(client.get_proposal_approvals(&proposal_id).len(), 0);
+    let suspended_error = Err(Ok(MultisigError::SignerSuspended.into()));
+    assert_eq!(client.try_approve_proposal(&proposal_id, &suspended), suspended_error);
+    let result = client.try_create_proposal(&suspended, &token, &recipient, &100, &reason, &3600);
+    assert_eq!(result, Err(Ok(MultisigError::SignerSuspended.into())));
+    let newcomer = BytesN::from_array(&env, &[42; 32]);
+    let result = client.try_propose_signer_change(&suspended, &SignerChangeKind::Add, &newcomer, &3600);
+    assert_eq!(result, Err(Ok(MultisigError::SignerSuspended.into())));
+
+    // Reinstated, they approve like anyone else
+    run_signer_change(&client, &signers, SignerChangeKind::Reinstate, &suspended);
+    assert!(client.is_signer_active(&suspended));
+    client.approve_proposal(&proposal_id, &suspended);
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.execute_proposal(&proposal_id);
+}
+
+#[test]
+fn test_suspensions_keep_enough_active_signers_for_the_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let suspend = SignerChangeKind::Suspend;
+    let proposer = signers.get_unchecked(0);
+
+    run_signer_change(&client, &signers, SignerChangeKind::Suspend, &signers.get_unchecked(2));
+    let result = client.try_propose_signer_change(&proposer, &suspend, &signers.get_unchecked(1), &3600);
+    assert_eq!(result, Err(Ok(MultisigError::ThresholdExceedsSigners.into())));
+    let result = client.try_propose_signer_removal(&proposer, &signers.get_unchecked(1), &3600, &true);
+    assert_eq!(result, Err(Ok(MultisigError::ThresholdExceedsSigners.into())));
+
+    // Already suspended signers cannot be suspended again, nor active ones
+    // reinstated
+    let result = client.try_propose_signer_change(&proposer, &suspend, &signers.get_unchecked(2), &3600);
+    assert_eq!(result, Err(Ok(MultisigError::InvalidProposal.into())));
+    let result = client.try_propose_signer_change(&proposer, &SignerChangeKind::Reinstate, &proposer, &3600);
+    assert_eq!(result, Err(Ok(MultisigError::InvalidProposal.into())));
+}
+
+#[test]
+fn test_threshold_change_leaves_open_proposals_at_their_requirement() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 4);
+    client.initialize(&signers, &2, &false);
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let open = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &100, &reason, &3600);
+    client.approve_proposal(&open, &signers.get_unchecked(0));
+    client.approve_proposal(&open, &signers.get_unchecked(1));
+
+    // Raising
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::SetThreshold(3)), 2);
+    assert_eq!(client.get_config().threshold, 3);
+    assert_eq!(client.get_signer_set_at(&env.ledger().timestamp()).threshold, 3);
+
+    // The open proposal still needs the two approvals it was created with
+    assert_eq!(client.get_approval_progress(&open).required, 2);
+    client.execute_proposal(&open);
+
+    let later = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &100, &reason, &3600);
+    client.approve_proposal(&later, &signers.get_unchecked(0));
+    client.approve_proposal(&later, &signers.get_unchecked(1));
+    assert_eq!(client.try_execute_proposal(&later), Err(Ok(MultisigError::InsufficientApprovals.into())));
+    client.approve_proposal(&later, &signers.get_unchecked(2));
+    client.execute_proposal(&later);
+
+    // Lowering takes the current threshold's three approvals
+    let lower = client.propose_governance(&signers.get_unchecked(0), &GovernanceAction::Signers(SignerAction::SetThreshold(2)), &3600);
+    client.approve_governance(&lower, &signers.get_unchecked(0));
+    client.approve_governance(&lower, &signers.get_unchecked(1));
+    assert_eq!(client.try_execute_governance(&lower), Err(Ok(GovernanceError::InsufficientGovernanceApprovals.into())));
+    client.approve_governance(&lower, &signers.get_unchecked(2));
+    client.execute_governance(&lower);
+    assert_eq!(client.get_config().threshold, 2);
+}
+
+#[test]
+fn test_threshold_change_rejects_thresholds_the_signers_cannot_meet() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let proposer = signers.get_unchecked(0);
+
+    let result = client.try_propose_governance(&proposer, &GovernanceAction::Signers(SignerAction::SetThreshold(4)), &3600);
+    assert_eq!(result, Err(Ok(MultisigError::ThresholdExceedsSigners.into())));
+    let result = client.try_propose_governance(&proposer, &GovernanceAction::Signers(SignerAction::SetThreshold(0)), &3600);
+    assert_eq!(result, Err(Ok(MultisigError::InvalidThreshold.into())));
+    let result = client.try_propose_governance(&proposer, &GovernanceAction::Signers(SignerAction::SetThreshold(1)), &3600);
+    assert_eq!(result, Err(Ok(MultisigError::InsecureConfiguration.into())));
+
+    // Checked again at execution against the signers active by then
+    let raise = client.propose_governance(&proposer, &GovernanceAction::Signers(SignerAction::SetThreshold(3)), &3600);
+    client.approve_governance(&raise, &signers.get_unchecked(0));
+    client.approve_governance(&raise, &signers.get_unchecked(1));
+    run_signer_change(&client, &signers, SignerChangeKind::Suspend, &signers.get_unchecked(2));
+    assert_eq!(client.try_execute_governance(&raise), Err(Ok(MultisigError::ThresholdExceedsSigners.into())));
+    assert_eq!(client.get_config().threshold, 2);
+}
+
+#[test]
+fn test_signer_policy_bounds_the_signer_count() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    // The initial set must already be within the policy
+    let policy = SignerPolicy { min_signers: 3, max_signers: 4 };
+    let result = client.try_initialize_with_signers(&as_signers(&env, &create_test_signers(&env, 2)), &2, &true, &Some(policy.clone()), &Vec::new(&env), &false);
+    assert_eq!(result, Err(Ok(SignerError::SignerPolicyViolation.into())));
+    let inverted = SignerPolicy { min_signers: 4, max_signers: 3 };
+    let result = client.try_initialize_with_signers(&as_signers(&env, &create_test_signers(&env, 3)), &2, &false, &Some(inverted), &Vec::new(&env), &false);
+    assert_eq!(result, Err(Ok(MultisigError::InvalidProposal.into())));
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize_with_signers(&as_signers(&env, &signers), &2, &false, &Some(policy.clone()), &Vec::new(&env), &false);
+    assert_eq!(client.get_signer_policy(), Some(policy));
+    let proposer = signers.get_unchecked(0);
+
+    // Up to the maximum and no further
+    run_signer_change(&client, &signers, SignerChangeKind::Add, &BytesN::from_array(&env, &[71; 32]));
+    let add_type = SignerChangeKind::Add;
+    let addition = client.propose_signer_change(&proposer, &add_type, &BytesN::from_array(&env, &[72; 32]), &3600);
+    client.approve_signer_change(&addition, &signers.get_unchecked(0));
+    client.approve_signer_change(&addition, &signers.get_unchecked(1));
+    assert_eq!(client.try_execute_signer_change(&addition), Err(Ok(SignerError::SignerPolicyViolation.into())));
+
+    // Down to the minimum and no further
+    run_signer_change(&client, &signers, SignerChangeKind::Remove, &signers.get_unchecked(2));
+    let removal = client.propose_signer_removal(&proposer, &signers.get_unchecked(1), &3600, &false);
+    client.approve_signer_change(&removal, &signers.get_unchecked(0));
+    client.approve_signer_change(&removal, &signers.get_unchecked(1));
+    assert_eq!(client.try_execute_signer_change(&removal), Err(Ok(SignerError::SignerPolicyViolation.into())));
+    assert_eq!(client.get_config().signer_count, 3);
+}
+
+#[test]
+fn test_signer_policy_is_changed_by_governance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    // Without a policy the count is unbounded
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    assert_eq!(client.get_signer_policy(), None);
+    run_signer_change(&client, &signers, SignerChangeKind::Add, &BytesN::from_array(&env, &[71; 32]));
+    let proposer = signers.get_unchecked(0);
+
+    let result = client.try_propose_governance(&proposer, &GovernanceAction::Signers(SignerAction::SetSignerPolicy(SignerPolicy { min_signers: 5, max_signers: 9 })), &3600);
+    assert_eq!(result, Err(Ok(SignerError::SignerPolicyViolation.into())));
+    let result = client.try_propose_governance(&proposer, &GovernanceAction::Signers(SignerAction::SetSignerPolicy(SignerPolicy { min_signers: 0, max_signers: 9 })), &3600);
+    assert_eq!(result, Err(Ok(MultisigError::InvalidProposal.into())));
+
+    let policy = SignerPolicy { min_signers: 3, max_signers: 4 };
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::SetSignerPolicy(policy.clone())), 2);
+    assert_eq!(client.get_signer_policy(), Some(policy));
+
+    // Batches are held to the policy by their final count
+    let additions = vec![&env, BytesN::from_array(&env, &[72; 32])];
+    let result = client.try_propose_signer_batch(&proposer, &additions, &Vec::new(&env), &None, &3600);
+    assert_eq!(result, Err(Ok(SignerError::SignerPolicyViolation.into())));
+
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::ClearSignerPolicy), 2);
+    assert_eq!(client.get_signer_policy(), None);
+    client.propose_signer_batch(&proposer, &additions, &Vec::new(&env), &None, &3600);
+}
+
+#[test]
+fn test_initialize_with_signers_applies_policy_and_permissions_together() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let keys = create_test_signers(&env, 2);
+    let dao = Signer::Contract(Address::generate(&env));
+    let signers = vec![&env, Signer::Ed25519(keys.get_unchecked(0)), Signer::Ed25519(keys.get_unchecked(1)), dao.clone()];
+    let dao_id = client.signer_id(&dao);
+    let policy = SignerPolicy { min_signers: 2, max_signers: 3 };
+    let proposer_only = SignerPermissions { can_propose: true, can_approve: false };
+    client.initialize_with_signers(&signers, &2, &false, &Some(policy.clone()), &vec![&env, (dao_id.clone(), proposer_only)], &false);
+
+    assert_eq!(client.get_signer_policy(), Some(policy));
+    assert_eq!(client.get_signer_permissions(&dao_id), proposer_only);
+    assert!(client.get_signer_address(&dao_id).is_some());
+    let addition = client.propose_signer_change(&dao_id, &SignerChangeKind::Add, &BytesN::from_array(&env, &[71; 32]), &3600);
+    assert_eq!(client.try_approve_signer_change(&addition, &dao_id), Err(Ok(MultisigError::RoleNotPermitted.into())));
+    client.approve_signer_change(&addition, &keys.get_unchecked(0));
+    client.approve_signer_change(&addition, &keys.get_unchecked(1));
+    assert_eq!(client.try_execute_signer_change(&addition), Err(Ok(SignerError::SignerPolicyViolation.into())));
+}
+
+#[test]
+fn test_proposer_only_signer_drafts_but_cannot_approve() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 4);
+    let analyst = signers.get_unchecked(3);
+    let proposer_only = SignerPermissions { can_propose: true, can_approve: false };
+
+    // Only signers who can approve count towards the threshold
+    let result = client.try_initialize_with_signers(&as_signers(&env, &create_test_signers(&env, 3)), &3, &false, &None, &vec![&env, (signers.get_unchecked(2), proposer_only)], &false);
+    assert_eq!(result, Err(Ok(MultisigError::ThresholdExceedsSigners.into())));
+    client.initialize_with_signers(&as_signers(&env, &signers), &2, &false, &None, &vec![&env, (analyst.clone(), proposer_only)], &false);
+    assert_eq!(client.get_signer_permissions(&analyst), proposer_only);
+    assert_eq!(client.get_signer_permissions(&signers.get_unchecked(0)), SignerPermissions { can_propose: true, can_approve: true });
+
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Invoice 42");
+    let proposal_id = client.create_proposal(&analyst, &token, &recipient, &100, &reason, &3600);
+    assert_eq!(client.try_approve_proposal(&proposal_id, &analyst), Err(Ok(MultisigError::RoleNotPermitted.into())));
+
+    let add_type = SignerChangeKind::Add;
+    let addition = client.propose_signer_change(&analyst, &add_type, &BytesN::from_array(&env, &[71; 32]), &3600);
+    assert_eq!(client.try_approve_signer_change(&addition, &analyst), Err(Ok(MultisigError::RoleNotPermitted.into())));
+    let raise = client.propose_governance(&analyst, &GovernanceAction::Signers(SignerAction::SetThreshold(3)), &3600);
+    assert_eq!(client.try_approve_governance(&raise, &analyst), Err(Ok(MultisigError::RoleNotPermitted.into())));
+
+    // Four signers, but only three the threshold can rely on
+    let result = client.try_propose_governance(&analyst, &GovernanceAction::Signers(SignerAction::SetThreshold(4)), &3600);
+    assert_eq!(result, Err(Ok(MultisigError::ThresholdExceedsSigners.into())));
+
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(0));
+    client.approve_proposal(&proposal_id, &signers.get_unchecked(1));
+    client.execute_proposal(&proposal_id);
+    assert_eq!(soroban_sdk::token::Client::new(&env, &token).balance(&recipient), 100);
+}
+
+#[test]
+fn test_signer_permissions_changed_by_governance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let signer = signers.get_unchecked(2);
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let proposal_id = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &100, &reason, &3600);
+    client.approve_proposal(&proposal_id, &signer);
+
+    let neither = SignerPermissions { can_propose: false, can_approve: false };
+    let result = client.try_propose_governance(&signers.get_unchecked(0), &GovernanceAction::Signers(SignerAction::SetSignerPermissions(signer.clone(), neither)), &3600);
+    assert_eq!(result, Err(Ok(MultisigError::InvalidProposal.into())));
+
+    // Losing the right to approve drops the approvals already given
+    let approver_only = SignerPermissions { can_propose: false, can_approve: true };
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::SetSignerPermissions(signer.clone(), approver_only)), 2);
+    let result = client.try_create_proposal(&signer, &token, &recipient, &100, &reason, &3600);
+    assert_eq!(result, Err(Ok(MultisigError::RoleNotPermitted.into())));
+    let proposer_only = SignerPermissions { can_propose: true, can_approve: false };
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::SetSignerPermissions(signer.clone(), proposer_only)), 2);
+    assert_eq!(client.get_approval_progress(&proposal_id).approvals, 0);
+
+    // With one of three unable to approve, no other may be suspended
+    let suspend_type = SignerChangeKind::Suspend;
+    let result = client.try_propose_signer_change(&signers.get_unchecked(0), &suspend_type, &signers.get_unchecked(1), &3600);
+    assert_eq!(result, Err(Ok(MultisigError::ThresholdExceedsSigners.into())));
+    let result = client.try_propose_governance(
+        &signers.get_unchecked(0),
+        &GovernanceAction::Signers(SignerAction::SetSignerPermissions(signers.get_unchecked(1), proposer_only)),
+        &3600,
+    );
+    assert_eq!(result, Err(Ok(MultisigError::ThresholdExceedsSigners.into())));
+}
+
+#[test]
+fn test_inactive_signer_removed_with_reduced_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 5);
+    client.initialize(&signers, &3, &false);
+    let proposer = signers.get_unchecked(0);
+    let (idle, returning) = (signers.get_unchecked(4), signers.get_unchecked(3));
+
+    let result = client.try_propose_governance(&proposer, &GovernanceAction::Signers(SignerAction::SetInactivityRemoval(InactivityRemoval { inactivity_seconds: 1_000, threshold: 4 })), &3600);
+    assert_eq!(result, Err(Ok(MultisigError::InvalidThreshold.into())));
+    let config = InactivityRemoval { inactivity_seconds: 1_000, threshold: 2 };
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::SetInactivityRemoval(config.clone())), 3);
+    assert_eq!(client.get_inactivity_removal(), Some(config));
+
+    let configured_at = env.ledger().timestamp();
+    let removal = client.propose_signer_removal(&proposer, &idle, &7200, &false);
+    let recall = client.propose_signer_removal(&proposer, &returning, &7200, &false);
+    for proposal_id in [removal, recall] {
+        client.approve_signer_change(&proposal_id, &signers.get_unchecked(0));
+        client.approve_signer_change(&proposal_id, &signers.get_unchecked(1));
+    }
+
+    // Not inactive for long enough yet
+    assert_eq!(client.get_signer_change_quorum(&removal), 3);
+    assert_eq!(client.try_execute_signer_change(&removal), Err(Ok(SignerError::InsufficientSignerChangeApprovals.into())));
+
+    env.ledger().with_mut(|li| li.timestamp += 1_001);
+    assert_eq!(client.get_signer_last_active(&idle), configured_at);
+    assert_eq!(client.get_signer_change_quorum(&removal), 2);
+    client.execute_signer_change(&removal);
+    assert!(!client.is_signer(&idle));
+
+    // Acting again mid-vote brings back the ordinary threshold
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let reason = String::from_str(&env, "Payment");
+    let payment = client.create_proposal(&proposer, &token, &Address::generate(&env), &100, &reason, &3600);
+    client.approve_proposal(&payment, &returning);
+    assert_eq!(client.get_signer_last_active(&returning), env.ledger().timestamp());
+    assert_eq!(client.try_execute_signer_change(&recall), Err(Ok(SignerError::InsufficientSignerChangeApprovals.into())));
+    assert!(client.is_signer(&returning));
+}
+
+#[test]
+fn test_inactivity_removal_off_by_default_and_when_zero() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 5);
+    client.initialize(&signers, &3, &false);
+    let proposer = signers.get_unchecked(0);
+    assert_eq!(client.get_inactivity_removal(), None);
+
+    let removal = client.propose_signer_removal(&proposer, &signers.get_unchecked(4), &7200, &false);
+    client.approve_signer_change(&removal, &signers.get_unchecked(0));
+    client.approve_signer_change(&removal, &signers.get_unchecked(1));
+    env.ledger().with_mut(|li| li.timestamp += 5_000);
+    assert_eq!(client.get_signer_change_quorum(&removal), 3);
+
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::SetInactivityRemoval(InactivityRemoval { inactivity_seconds: 1_000, threshold: 2 })), 3);
+    pass_governance(&client, &signers, &GovernanceAction::Signers(SignerAction::SetInactivityRemoval(InactivityRemoval { inactivity_seconds: 0, threshold: 0 })), 3);
+    assert_eq!(client.get_inactivity_removal(), None);
+    env.ledger().with_mut(|li| li.timestamp += 1_001);
+    assert_eq!(client.try_execute_signer_change(&removal), Err(Ok(SignerError::InsufficientSignerChangeApprovals.into())));
+}
+
+#[test]
+fn test_replace_signer_swaps_keys_in_one_vote() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let old_signer = signers.get_unchecked(2);
+    let new_signer = BytesN::from_array(&env, &[77; 32]);
+
+    // The outgoing signer has approvals pending on both kinds of proposal
+    let payment = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &100, &reason, &3600);
+    client.approve_proposal(&payment, &old_signer);
+    let add_type = SignerChangeKind::Add;
+    let addition = client.propose_signer_change(&signers.get_unchecked(0), &add_type, &BytesN::from_array(&env, &[88; 32]), &3600);
+    client.approve_signer_change(&addition, &old_signer);
+
+    let proposal_id = client.propose_signer_replacement(&signers.get_unchecked(0), &old_signer, &new_signer, &3600);
+    assert_eq!(client.get_signer_change_proposal(&proposal_id).change_type, SignerChangeKind::Replace);
+    assert_eq!(client.get_signer_replacement(&proposal_id), Some(new_signer.clone()));
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(0));
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(1));
+    client.execute_signer_change(&proposal_id);
+
+    assert!(!client.is_signer(&old_signer));
+    assert!(client.is_signer(&new_signer));
+    assert_eq!(client.get_config().signer_count, 3);
+
+    // None of the old key's approvals carry over to the new one
+    assert_eq!(client.get_approval_progress(&payment).approvals, 0);
+    assert!(client.get_signer_change_approvals(&addition).is_empty());
+    client.approve_proposal(&payment, &new_signer);
+    assert_eq!(client.get_approval_progress(&payment).approvals, 1);
+
+    let history = client.get_signer_history(&0, &10);
+    assert_eq!(history.get_unchecked(history.len() - 2).change, SignerSetChange::Removed(old_signer));
+    assert_eq!(history.get_unchecked(history.len() - 1).change, SignerSetChange::Added(new_signer));
+}
+
+#[test]
+fn test_replace_signer_rejects_keys_in_the_wrong_place() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let proposer = signers.get_unchecked(0);
+    let outsider = BytesN::from_array(&env, &[77; 32]);
+
+    let result = client.try_propose_signer_replacement(&proposer, &outsider, &BytesN::from_array(&env, &[78; 32]), &3600);
+    assert_eq!(result, Err(Ok(MultisigError::SignerNotFound.into())));
+    let result = client.try_propose_signer_replacement(&proposer, &signers.get_unchecked(2), &signers.get_unchecked(1), &3600);
+    assert_eq!(result, Err(Ok(MultisigError::DuplicateSigner.into())));
+    let replace_type = SignerChangeKind::Replace;
+    let result = client.try_propose_signer_change(&proposer, &replace_type, &signers.get_unchecked(2), &3600);
+    assert_eq!(result, Err(Ok(MultisigError::InvalidProposal.into())));
+
+    // Checked again at execution: the old key may be gone by then
+    let first = client.propose_signer_replacement(&proposer, &signers.get_unchecked(2), &outsider, &3600);
+    let second = client.propose_signer_replacement(&proposer, &signers.get_unchecked(2), &BytesN::from_array(&env, &[78; 32]), &3600);
+    for proposal_id in [first, second] {
+        client.approve_signer_change(&proposal_id, &signers.get_unchecked(0));
+        client.approve_signer_change(&proposal_id, &signers.get_unchecked(1));
+    }
+    client.execute_signer_change(&first);
+    assert_eq!(client.try_execute_signer_change(&second), Err(Ok(MultisigError::SignerNotFound.into())));
+}
+
+#[test]
+fn test_signer_batch_onboards_and_offboards_in_one_vote() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let additions = vec![&env, BytesN::from_array(&env, &[71; 32]), BytesN::from_array(&env, &[72; 32]), BytesN::from_array(&env, &[73; 32])];
+    let removals = vec![&env, signers.get_unchecked(2)];
+
+    let proposal_id = client.propose_signer_batch(&signers.get_unchecked(0), &additions, &removals, &Some(3), &3600);
+    let batch = SignerBatch { additions: additions.clone(), removals: removals.clone(), threshold: Some(3) };
+    assert_eq!(client.get_signer_batch(&proposal_id), Some(batch));
+    assert_eq!(client.get_signer_change_proposal(&proposal_id).change_type, SignerChangeKind::Batch);
+
+    // One vote at the current threshold covers every change in it
+    assert_eq!(client.get_signer_change_quorum(&proposal_id), 2);
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(0));
+    assert_eq!(client.try_execute_signer_change(&proposal_id), Err(Ok(SignerError::InsufficientSignerChangeApprovals.into())));
+    client.approve_signer_change(&proposal_id, &signers.get_unchecked(1));
+    client.execute_signer_change(&proposal_id);
+
+    assert_eq!(client.get_config().signer_count, 5);
+    assert_eq!(client.get_config().threshold, 3);
+    assert!(!client.is_signer(&signers.get_unchecked(2)));
+    for signer in additions.iter() {
+        assert!(client.is_signer(&signer));
+    }
+    let snapshot = client.get_signer_set_at(&env.ledger().timestamp());
+    assert_eq!(snapshot.signers.len(), 5);
+    assert_eq!(snapshot.threshold, 3);
+
+    // Later signer changes need the new threshold
+    let add_type = SignerChangeKind::Add;
+    let next = client.propose_signer_change(&signers.get_unchecked(0), &add_type, &BytesN::from_array(&env, &[74; 32]), &3600);
+    assert_eq!(client.get_signer_change_quorum(&next), 3);
+}
+
+#[test]
+fn test_signer_batch_checks_the_final_set_before_applying_anything() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let proposer = signers.get_unchecked(0);
+    let none = Vec::<BytesN<32>>::new(&env);
+    let outsider = BytesN::from_array(&env, &[71; 32]);
+
+    let result = client.try_propose_signer_batch(&proposer, &none, &vec![&env, outsider.clone()], &None, &3600);
+    assert_eq!(result, Err(Ok(MultisigError::SignerNotFound.into())));
+    let result = client.try_propose_signer_batch(&proposer, &vec![&env, outsider.clone(), outsider.clone()], &none, &None, &3600);
+    assert_eq!(result, Err(Ok(MultisigError::DuplicateSigner.into())));
+    let result = client.try_propose_signer_batch(&proposer, &vec![&env, signers.get_unchecked(1)], &none, &None, &3600);
+    assert_eq!(result, Err(Ok(MultisigError::DuplicateSigner.into())));
+    let result = client.try_propose_signer_batch(&proposer, &vec![&env, outsider.clone()], &none, &Some(0), &3600);
+    assert_eq!(result, Err(Ok(MultisigError::InvalidThreshold.into())));
+    let result = client.try_propose_signer_batch(&proposer, &none, &none, &None, &3600);
+    assert_eq!(result, Err(Ok(MultisigError::InvalidProposal.into())));
+    let batch_type = SignerChangeKind::Batch;
+    let result = client.try_propose_signer_change(&proposer, &batch_type, &outsider, &3600);
+    assert_eq!(result, Err(Ok(MultisigError::InvalidProposal.into())));
+
+    // Valid when proposed, but a removal executed in between leaves too few
+    // signers for the batch's threshold
+    let removal = client.propose_signer_batch(&proposer, &none, &vec![&env, signers.get_unchecked(2)], &None, &3600);
+    let raise = client.propose_signer_batch(&proposer, &vec![&env, outsider.clone()], &none, &Some(4), &3600);
+    for proposal_id in [removal, raise] {
+        client.approve_signer_change(&proposal_id, &signers.get_unchecked(0));
+        client.approve_signer_change(&proposal_id, &signers.get_unchecked(1));
+    }
+    client.execute_signer_change(&removal);
+    assert_eq!(client.try_execute_signer_change(&raise), Err(Ok(MultisigError::ThresholdExceedsSigners.into())));
+
+    // Nothing from the rejected batch applied
+    assert!(!client.is_signer(&outsider));
+    assert_eq!(client.get_config().signer_count, 2);
+    assert_eq!(client.get_config().threshold, 2);
+}
+
+// The signer list, the per-key entries and the count must always agree.
+fn assert_signer_keys(client: &MultiSigContractClient, expected: &Vec<BytesN<32>>, gone: &Vec<BytesN<32>>) {
+    let keys = client.get_signer_keys(&0, &pagination::MAX_PAGE_SIZE);
+    assert_eq!(keys, expected.clone());
+    assert_eq!(keys.len(), client.get_config().signer_count);
+    for key in keys.iter() {
+        assert!(client.is_signer(&key));
+        assert_eq!(keys.first_index_of(&key), keys.last_index_of(&key));
+    }
+    for key in gone.iter() {
+        assert!(!client.is_signer(&key));
+    }
+}
+
+#[test]
+fn test_signer_keys_follow_every_signer_change() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let (s0, s1, s2) = (signers.get_unchecked(0), signers.get_unchecked(1), signers.get_unchecked(2));
+    let a = BytesN::from_array(&env, &[71; 32]);
+    let b = BytesN::from_array(&env, &[72; 32]);
+    let c = BytesN::from_array(&env, &[73; 32]);
+    let none = Vec::<BytesN<32>>::new(&env);
+    assert_signer_keys(&client, &signers, &none);
+
+    run_signer_change(&client, &signers, SignerChangeKind::Add, &a);
+    assert_signer_keys(&client, &vec![&env, s0.clone(), s1.clone(), s2.clone(), a.clone()], &none);
+
+    run_signer_change(&client, &signers, SignerChangeKind::Remove, &s1);
+    assert_signer_keys(&client, &vec![&env, s0.clone(), s2.clone(), a.clone()], &vec![&env, s1.clone()]);
+
+    // Removed signers can no longer approve, so the rest is voted by s0 and a
+    let voters = vec![&env, s0.clone(), a.clone()];
+    let replacement = client.propose_signer_replacement(&s0, &s2, &b, &3600);
+    client.approve_signer_change(&replacement, &s0);
+    client.approve_signer_change(&replacement, &a);
+    client.execute_signer_change(&replacement);
+    assert_signer_keys(&client, &vec![&env, s0.clone(), a.clone(), b.clone()], &vec![&env, s1.clone(), s2.clone()]);
+
+    run_signer_change(&client, &voters, SignerChangeKind::Add, &s1);
+    assert_signer_keys(&client, &vec![&env, s0.clone(), a.clone(), b.clone(), s1.clone()], &vec![&env, s2.clone()]);
+
+    let batch = client.propose_signer_batch(&s0, &vec![&env, c.clone()], &vec![&env, a.clone()], &None, &3600);
+    client.approve_signer_change(&batch, &s0);
+    client.approve_signer_change(&batch, &b);
+    client.execute_signer_change(&batch);
+    assert_signer_keys(&client, &vec![&env, s0.clone(), b.clone(), s1.clone(), c.clone()], &vec![&env, s2, a]);
+
+    // The listing views page like every other
+    assert_eq!(client.get_signer_keys(&1, &2), vec![&env, b, s1]);
+    assert_eq!(client.get_signer_keys(&3, &10), vec![&env, c]);
+    assert_eq!(client.get_signer_keys(&4, &10).len(), 0);
+    assert_eq!(client.get_signers(&0, &2).len(), 2);
+    assert_eq!(client.get_signers(&2, &10).get_unchecked(0).id, client.get_signers(&0, &10).get_unchecked(2).id);
+    let too_many = pagination::MAX_PAGE_SIZE + 1;
+    assert_eq!(client.try_get_signer_keys(&0, &too_many), Err(Ok(MultisigError::PageTooLarge.into())));
+    assert_eq!(client.try_get_signers(&0, &too_many), Err(Ok(MultisigError::PageTooLarge.into())));
+    assert_eq!(client.try_get_signers_detailed(&0, &too_many), Err(Ok(MultisigError::PageTooLarge.into())));
+}
+
+#[test]
+fn test_signer_labels_name_seats_until_removal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    let bob = Address::generate(&env);
+    client.initialize_with_addresses(&signers, &2, &false, &vec![&env, (signers.get_unchecked(1), bob.clone())]);
+    let initialized_at = env.ledger().timestamp();
+    let (s0, s1) = (signers.get_unchecked(0), signers.get_unchecked(1));
+
+    let alice = String::from_str(&env, "Alice (treasury)");
+    env.set_auths(&[]);
+    assert!(client.try_set_signer_label(&s0, &alice).is_err());
+    env.mock_all_auths();
+    client.set_signer_label(&s0, &alice);
+    assert_eq!(env.auths()[0].0, signer_account(&env, &s0));
+    client.set_signer_label(&s1, &String::from_str(&env, "Bob"));
+    assert_eq!(env.auths()[0].0, bob);
+    assert_eq!(client.get_signer_label(&s0), Some(alice.clone()));
+
+    let too_long = String::from_str(&env, "a label well over the sixty-four bytes a signer label may take up");
+    assert_eq!(client.try_set_signer_label(&s0, &too_long), Err(Ok(SignerError::InvalidSignerLabel.into())));
+    let outsider = BytesN::from_array(&env, &[71; 32]);
+    assert!(client.try_set_signer_label(&outsider, &alice).is_err());
+
+    env.ledger().with_mut(|li| li.timestamp += 100);
+    run_signer_change(&client, &signers, SignerChangeKind::Add, &outsider);
+    let detailed = client.get_signers_detailed(&0, &10);
+    assert_eq!(detailed.len(), 4);
+    assert_eq!(detailed.get_unchecked(0), SignerInfo { key: s0.clone(), label: Some(alice), added_at: initialized_at });
+    assert_eq!(detailed.get_unchecked(1).label, Some(String::from_str(&env, "Bob")));
+    assert_eq!(detailed.get_unchecked(2).label, None);
+    assert_eq!(detailed.get_unchecked(3), SignerInfo { key: outsider, label: None, added_at: initialized_at + 100 });
+    assert_eq!(client.get_signers_detailed(&3, &10), detailed.slice(3..4));
+
+    // An empty label clears it, and removal drops it with the seat
+    client.set_signer_label(&s0, &String::from_str(&env, ""));
+    assert_eq!(client.get_signer_label(&s0), None);
+    run_signer_change(&client, &signers, SignerChangeKind::Remove, &s1);
+    assert_eq!(client.get_signer_label(&s1), None);
+}
+
+#[test]
+fn test_proposal_counts_are_kept_per_family() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+    let newcomer = BytesN::from_array(&env, &[77; 32]);
+
+    let payment = client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &100, &reason, &3600);
+    let change = client.propose_signer_change(&signers.get_unchecked(0), &SignerChangeKind::Add, &newcomer, &3600);
+    assert_eq!(client.get_proposal_count(), 1);
+    assert_eq!(client.get_signer_change_count(), 1);
+
+    // Ids stay shared, so each family is fetched under the id it was given
+    assert_eq!(client.get_last_proposal_id(), 2);
+    assert_eq!(client.get_proposal(&payment).id, payment);
+    assert_eq!(client.get_signer_change_proposal(&change).signer, newcomer);
+    assert_eq!(client.get_proposals(&0, &10).proposals.len(), 1);
+
+    // Governance takes an id but counts toward neither
+    client.propose_governance(&signers.get_unchecked(0), &GovernanceAction::Policy(PolicyAction::Pause), &3600);
+    assert_eq!(client.get_last_proposal_id(), 3);
+    assert_eq!(client.get_proposal_count(), 1);
+    assert_eq!(client.get_signer_change_count(), 1);
+}
+
+#[test]
+fn test_proposal_counts_backfill_for_older_deployments() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = create_test_signers(&env, 3);
+    client.initialize(&signers, &2, &false);
+    let token = create_funded_token(&env, &contract_id, 1000);
+    let recipient = Address::generate(&env);
+    let reason = String::from_str(&env, "Payment");
+
+    // A deployment from before the counts carries none
+    env.as_contract(&contract_id, || {
+        env.storage().instance().remove(&DataKey::Proposals(ProposalKey::PaymentProposalCount));
+        env.storage().instance().remove(&DataKey::Signers(SignerKey::SignerChangeCount));
+    });
+    client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &100, &reason, &3600);
+    client.propose_signer_change(&signers.get_unchecked(0), &SignerChangeKind::Add, &BytesN::from_array(&env, &[77; 32]), &3600);
+    client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &200, &reason, &3600);
+    assert_eq!(client.get_proposal_count(), 0);
+
+    assert_eq!(client.migrate_proposal_counts(&2), 3);
+    // Proposals created mid-way are picked up by the walk, not counted twice
+    client.create_proposal(&signers.get_unchecked(0), &token, &recipient, &300, &reason, &3600);
+    assert_eq!(client.migrate_proposal_counts(&2), 5);
+    assert_eq!(client.get_proposal_count(), 3);
+    assert_eq!(client.get_signer_change_count(), 1);
+
+    // From here on the counts are live, and further calls change nothing
+    client.propose_signer_change(&signers.get_unchecked(0), &SignerChangeKind::Add, &BytesN::from_array(&env, &[78; 32]), &3600);
+    assert_eq!(client.get_signer_change_count(), 2);
+    assert_eq!(client.migrate_proposal_counts(&2), 6);
+    assert_eq!(client.get_proposal_count(), 3);
+    assert_eq!(client.get_signer_change_count(), 2);
+}
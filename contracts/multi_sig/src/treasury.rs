@@ -0,0 +1,106 @@
+use soroban_sdk::{panic_with_error, token, Address, Env, Vec};
+
+use crate::errors::MultisigError;
+use crate::storage;
+use crate::types::{AllocationEntry, AllocationReport, AllocationTarget, ReserveFloor};
+use crate::validation;
+
+/// Fixed-point scale of `AllocationTarget::price_hint`: a hint of
+/// `PRICE_SCALE` values one raw unit of the token at 1.
+pub const PRICE_SCALE: i128 = 10_000_000;
+/// Allocation shares are reported in basis points.
+pub const BPS: i128 = 10_000;
+
+pub fn validate_targets(env: &Env, targets: &Vec<AllocationTarget>) {
+    let mut total_weight: u64 = 0;
+    for i in 0..targets.len() {
+        let target = targets.get_unchecked(i);
+        if target.price_hint.is_some_and(|price| price <= 0) {
+            panic_with_error!(env, MultisigError::InvalidProposal);
+        }
+        for j in (i + 1)..targets.len() {
+            if targets.get_unchecked(j).token == target.token {
+                panic_with_error!(env, MultisigError::InvalidProposal);
+            }
+        }
+        total_weight += target.weight as u64;
+    }
+    if !targets.is_empty() && total_weight == 0 {
+        panic_with_error!(env, MultisigError::InvalidProposal);
+    }
+}
+
+/// Compares current holdings with the governed target weights. Every division
+/// rounds toward zero, so shares may sum to slightly under 10 000 bps.
+pub fn allocation_report(env: &Env) -> AllocationReport {
+    let targets = storage::read_allocation_targets(env);
+    let holder = env.current_contract_address();
+
+    let mut total_weight: i128 = 0;
+    let mut total_value: i128 = 0;
+    let mut values: Vec<(i128, i128)> = Vec::new(env);
+    for target in targets.iter() {
+        let balance = token::Client::new(env, &target.token).balance(&holder);
+        let value = match target.price_hint {
+            Some(price) => checked(env, balance.checked_mul(price)) / PRICE_SCALE,
+            None => balance,
+        };
+        total_weight += target.weight as i128;
+        total_value = checked(env, total_value.checked_add(value));
+        values.push_back((balance, value));
+    }
+
+    let mut entries = Vec::new(env);
+    for (i, target) in targets.iter().enumerate() {
+        let (balance, value) = values.get_unchecked(i as u32);
+        let target_bps = (target.weight as i128 * BPS / total_weight) as u32;
+        let actual_bps = if total_value == 0 {
+            0
+        } else {
+            (checked(env, value.checked_mul(BPS)) / total_value) as u32
+        };
+        entries.push_back(AllocationEntry {
+            token: target.token,
+            balance,
+            value,
+            target_bps,
+            actual_bps,
+            drift_bps: actual_bps as i32 - target_bps as i32,
+        });
+    }
+
+    AllocationReport { total_value, entries }
+}
+
+pub fn validate_reserve_floor(env: &Env, floor: &ReserveFloor) {
+    validation::raise(env, validation::check_amount(floor.amount));
+}
+
+/// Balance of `token` above its reserve floor, never negative.
+pub fn free_balance(env: &Env, token: &Address) -> i128 {
+    let balance = token::Client::new(env, token).balance(&env.current_contract_address());
+    (balance - storage::read_reserve_floor(env, token)).max(0)
+}
+
+/// `ReserveBreached` unless `amount` can leave without taking the balance
+/// below the token's reserve floor.
+pub fn check_within_reserve(env: &Env, token: &Address, amount: i128) -> Result<(), MultisigError> {
+    if storage::read_reserve_floor(env, token) == 0 {
+        return Ok(());
+    }
+    if amount > free_balance(env, token) {
+        return Err(MultisigError::ReserveBreached);
+    }
+    Ok(())
+}
+
+pub fn require_within_reserve(env: &Env, token: &Address, amount: i128) {
+    validation::raise(env, check_within_reserve(env, token, amount));
+}
+
+fn checked(env: &Env, value: Option<i128>) -> i128 {
+    match value {
+        Some(value) => value,
+        None => panic_with_error!(env, MultisigError::ArithmeticOverflow),
+    }
+}
@@ -1,4 +1,5 @@
-use soroban_sdk::{contracttype, Address, BytesN, String};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Env, String, Symbol, Val, Vec};
 
 #[contracttype]
 #[derive(Clone)]
@@ -11,15 +12,295 @@ pub enum DataKey {
     ProposalCount,
     Proposal(u64),
     ProposalApprovals(u64),
+    ProposalContentHash(u64),
+    /// Replaced by `ProposalKey::ProposalFlags`; only read for proposals not yet migrated.
     ProposalExecuted(u64),
     SignerChangeProposal(u64),
     SignerChangeApprovals(u64),
+    /// Replaced by `ProposalKey::ProposalFlags`; only read for proposals not yet migrated.
     SignerChangeExecuted(u64),
+    VoteDelegations(u64),
+    GovernanceProposal(u64),
+    GovernanceApprovals(u64),
+    /// Replaced by `ProposalKey::ProposalFlags`; only read for proposals not yet migrated.
+    GovernanceExecuted(u64),
+    FrozenToken(Address),
+    FeatureFlags,
+    AllowLowSecurity,
+    TokenWatchers(Address),
+    SignerRole(BytesN<32>),
+    RoleRequirements,
+    ApprovalRoles(u64),
+    ProtectedSigner(BytesN<32>),
+    ProtectedQuorum,
+    Outcome(u64),
+    SignerRegistry,
+    FinalApprover,
+    AllocationTargets,
+    InitialDeposit,
+    TemplateCount,
+    Template(u32),
+    ProposalTemplate(u64),
+    AddressBookNextId,
+    AddressBookIds,
+    AddressBookEntry(u32),
+    AddressBookId(Address),
+    RecipientLabel(u64),
+    TokenImplementation(Address),
+    ReserveFloor(Address),
+    IdempotencyKey(BytesN<32>),
+    IndexChunkCount(IndexKey),
+    IndexChunk(IndexKey, u32),
+    ExecutionReceipt(u64),
+    /// Per-signer state and signer-set bookkeeping.
+    Signers(SignerKey),
+    /// Per-proposal state added alongside the core proposal records.
+    Proposals(ProposalKey),
+    /// Contract-wide policy and configuration.
+    Policy(PolicyKey),
+}
+
+/// Storage keys for signer state, nested under `DataKey::Signers`.
+#[contracttype]
+#[derive(Clone)]
+pub enum SignerKey {
+    SignerHistoryCount,
+    SignerHistory(u64),
+    SignerNonce(BytesN<32>),
+    SignerAddress(BytesN<32>),
+    RedundancyPolicy,
+    NoRedundancy,
+    Passkey(BytesN<32>),
+    Secp256k1Key(BytesN<32>),
+    BlsKey(BytesN<32>),
+    BlsSigners,
+    Delegation(BytesN<32>),
+    DelegatorOf(BytesN<32>),
+    KeyRing(BytesN<32>),
+    LastRingActivation(BytesN<32>),
+    SignerJoinedAt(BytesN<32>),
+    Recovery,
+    LastActivity,
+    SuspendedSigners,
+    SignerReplacement(u64),
+    SignerBatch(u64),
+    SignerChangeCount,
+    SignerList,
+    SignerLabel(BytesN<32>),
+    SignerHistoryProposal(u64),
+    SignerPolicy,
+    SignerPermissions(BytesN<32>),
+    SignerLastActive(BytesN<32>),
+    InactivityRemoval,
+    SignerSetCheckpoint(u64),
+    SignerAddition(u64),
+}
+
+/// Storage keys for per-proposal state, nested under `DataKey::Proposals`.
+#[contracttype]
+#[derive(Clone)]
+pub enum ProposalKey {
+    ProposalFlags(u64),
+    FullyApprovedAt(u64),
+    FullyApprovedLedger(u64),
+    ProposalNotes(u64),
+    SessionCount,
+    SigningSession(u64),
+    ExecutionSchedule(u64),
+    ProposalRejections(u64),
+    PendingCancellation(u64),
+    ImportDeadline,
+    ImportRef(u64),
+    ImportedProposal(u64),
+    ProposalSignerSetVersion(u64),
+    RequiredApprovals(u64),
+    ExecutionRecord(u64),
+    ProposalVetoed(u64),
+    PaymentProposalCount,
+    CountBackfill,
+}
+
+/// Storage keys for contract-wide policy, nested under `DataKey::Policy`.
+#[contracttype]
+#[derive(Clone)]
+pub enum PolicyKey {
+    ProposerCap(Address),
+    ProposerUsage(BytesN<32>, Address),
+    ExecutionGrace,
+    Observers,
+    SchemaVersion,
+    SchemaFamilies,
+    MemoRequired(Address),
+    DocumentThreshold(Address),
+    AuditCount,
+    AuditEntry(u64),
+    ConfigFingerprint,
+    CancelGrace,
+    ExchangeRate(Address),
+    MaxRateAge,
+    ValueCap,
+    ProposerValueUsage(BytesN<32>),
+    DocumentValueThreshold,
+    Paused,
+    Retired,
+    Guardian,
+}
+
+/// Seed funds pulled from `funder` while initializing. `funder` must have
+/// approved the multisig as spender of at least `amount` beforehand.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitialFunding {
+    pub funder: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Deposit {
+    pub funder: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub deposited_at: u64,
+}
+
+/// Names one chunked id index; see `index`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum IndexKey {
+    /// Payment proposals created during one `INDEX_BUCKET_SECONDS` bucket.
+    ProposalsByDay(u64),
+    /// Payment proposals that are neither executed nor expired.
+    OpenProposals,
+    /// Proposals behind committed outflows; see `obligations`.
+    Obligations,
+    /// Signer-change proposals not yet executed.
+    OpenSignerChanges,
+    /// Open transfer proposals paying one recipient in one token.
+    RecipientQueue(Address, Address),
+    /// Payment proposals, open or closed, that reference one document hash.
+    ProposalsByDocument(BytesN<32>),
+}
+
+/// One committed outflow: `amount` of `token` expected to leave by `timestamp`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Obligation {
+    pub timestamp: u64,
+    pub token: Address,
+    pub amount: i128,
+    pub proposal_id: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferAction {
+    pub token_address: Address,
+    pub recipient: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowanceAction {
+    pub token_address: Address,
+    pub spender: Address,
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvokeAction {
+    pub contract: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+}
+
+/// What a payment-side proposal does once executed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProposalAction {
+    Transfer(TransferAction),
+    Approve(AllowanceAction),
+    Invoke(InvokeAction),
+}
+
+impl ProposalAction {
+    /// Token moved or exposed by this action, if any.
+    pub fn token(&self) -> Option<Address> {
+        match self {
+            ProposalAction::Transfer(transfer) => Some(transfer.token_address.clone()),
+            ProposalAction::Approve(allowance) => Some(allowance.token_address.clone()),
+            ProposalAction::Invoke(_) => None,
+        }
+    }
+
+    /// Address that receives funds or spending rights through this action.
+    pub fn beneficiary(&self) -> Option<Address> {
+        match self {
+            ProposalAction::Transfer(transfer) => Some(transfer.recipient.clone()),
+            ProposalAction::Approve(allowance) => Some(allowance.spender.clone()),
+            ProposalAction::Invoke(_) => None,
+        }
+    }
+
+    /// Token and amount this action puts in motion, if it can be measured.
+    pub fn value(&self) -> Option<(Address, i128)> {
+        match self {
+            ProposalAction::Transfer(transfer) => Some((transfer.token_address.clone(), transfer.amount)),
+            ProposalAction::Approve(allowance) => Some((allowance.token_address.clone(), allowance.amount)),
+            ProposalAction::Invoke(_) => None,
+        }
+    }
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Proposal {
+    pub id: u64,
+    pub proposer: BytesN<32>,
+    pub action: ProposalAction,
+    pub reason: String,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub executed: bool,
+    /// Memo the recipient needs to attribute a transfer, such as an exchange
+    /// deposit tag. Only transfers carry one.
+    pub destination_memo: Option<String>,
+    /// Hash of the document backing the payment, such as a signed invoice.
+    pub document_hash: Option<BytesN<32>>,
+    /// Signer who executed the proposal, when a signer did.
+    pub executed_by: Option<BytesN<32>>,
+    /// When the proposal executed.
+    pub executed_at: Option<u64>,
+}
+
+impl Proposal {
+    /// Digest of everything a signer agrees to when approving: who proposed
+    /// what, why, against which document, and for how long. Execution status
+    /// is left out.
+    pub fn content_hash(&self, env: &Env) -> BytesN<32> {
+        let contents = (
+            self.id,
+            self.proposer.clone(),
+            self.action.clone(),
+            self.reason.clone(),
+            self.created_at,
+            self.expires_at,
+            self.destination_memo.clone(),
+            self.document_hash.clone(),
+        );
+        env.crypto().sha256(&contents.to_xdr(env)).into()
+    }
+}
+
+/// Storage layout of payment proposals written before proposals carried an
+/// action. Only read by `migrate_proposals`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyProposal {
     pub id: u64,
     pub proposer: BytesN<32>,
     pub token_address: Address,
@@ -36,6 +317,406 @@ pub struct Proposal {
 pub struct ProposalApproval {
     pub signer: BytesN<32>,
     pub approved_at: u64,
+    /// Position among the proposal's current approvals, starting at 1.
+    /// Later approvals move up when an earlier one is revoked.
+    pub ordinal: u32,
+    /// `Proposal::content_hash` at the time of approving. The approval only
+    /// counts while the proposal still hashes to this.
+    pub content_hash: BytesN<32>,
+    /// Account that submitted the approval on the signer's behalf through
+    /// `relay_approval`, if any.
+    pub relayer: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalProgress {
+    pub approvals: u32,
+    pub required: u32,
+    pub roles: Vec<RoleProgress>,
+    pub ready: bool,
+    /// Approvals given to an earlier version of the proposal. They do not
+    /// count until their signers approve again.
+    pub stale_approvals: u32,
+}
+
+/// One rule `simulate_draft_execution` checked. Where a rule compares
+/// numbers, it passes while `actual` is at most `limit`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PolicyCheck {
+    pub rule: Symbol,
+    pub passed: bool,
+    pub actual: Option<i128>,
+    pub limit: Option<i128>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecutionVerdict {
+    /// Whether every check passed.
+    pub passed: bool,
+    pub checks: Vec<PolicyCheck>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleProgress {
+    pub role: Symbol,
+    pub approvals: u32,
+    pub required: u32,
+}
+
+/// A fixed set of open payment proposals that signers approve together by
+/// signing `session_hash` once.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SigningSession {
+    pub id: u64,
+    pub opened_by: BytesN<32>,
+    pub proposal_ids: Vec<u64>,
+    /// `Proposal::content_hash` of each proposal when the session was opened,
+    /// in the same order as `proposal_ids`.
+    pub content_hashes: Vec<BytesN<32>>,
+    pub session_hash: BytesN<32>,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+/// What `approve_session` did with each proposal in the session.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionApprovalReport {
+    pub approved: Vec<u64>,
+    pub already_approved: Vec<u64>,
+    /// Changed since the session was opened; the signer has to approve the
+    /// new contents separately.
+    pub amended: Vec<u64>,
+    /// Executed, expired, or voted through a delegate.
+    pub unavailable: Vec<u64>,
+}
+
+/// Storage layout this deployment was last written under; see `schema`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StorageSchema {
+    pub version: u32,
+    /// Every storage key variant in use, by name.
+    pub families: Vec<Symbol>,
+}
+
+/// One entry in a proposal's notes thread. Never changed once written.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalNote {
+    pub author: BytesN<32>,
+    pub text: String,
+    pub added_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalPage {
+    pub proposals: Vec<Proposal>,
+    pub next_cursor: u64,
+    pub has_more: bool,
+}
+
+/// Either family of proposal that waits on signer approvals.
+// Contract values are host handles that are never boxed, so the native size
+// gap between the variants does not matter.
+#[allow(clippy::large_enum_variant)]
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UnifiedProposal {
+    Payment(Proposal),
+    SignerChange(SignerChangeProposal),
+}
+
+impl UnifiedProposal {
+    pub fn id(&self) -> u64 {
+        match self {
+            UnifiedProposal::Payment(proposal) => proposal.id,
+            UnifiedProposal::SignerChange(proposal) => proposal.id,
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnifiedProposalPage {
+    pub items: Vec<UnifiedProposal>,
+    pub next_cursor: u64,
+    pub has_more: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecutionReceipt {
+    pub proposal_id: u64,
+    pub executed_at: u64,
+    pub approvals_at_execution: u32,
+    pub approvers: Vec<BytesN<32>>,
+}
+
+/// Who executed a payment proposal, and when. `executed_by` is the signer who
+/// called `execute_as_signer`, and `executor` the caller of
+/// `execute_as_executor`. Both are `None` for public executions and for
+/// those carried by collected signatures.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecutionRecord {
+    pub executed_at: u64,
+    pub tier: ExecutionTier,
+    pub executed_by: Option<BytesN<32>>,
+    pub executor: Option<Address>,
+}
+
+/// Self-contained evidence that a payment proposal was executed as approved:
+/// what was proposed, the digest signers approved, and who was counted when it
+/// executed. Check it with `verify_payment_proof` or against ledger history.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentProof {
+    pub proposal: Proposal,
+    pub content_hash: BytesN<32>,
+    pub receipt: ExecutionReceipt,
+}
+
+/// A member of the signer set. Ed25519, secp256r1 and secp256k1 keys prove
+/// themselves with signatures; contracts, such as another multisig or a DAO,
+/// through `require_auth` on their address.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Signer {
+    Ed25519(BytesN<32>),
+    Contract(Address),
+    Secp256r1(Secp256r1Key),
+    /// SEC-1 uncompressed public key, as EVM tooling holds it.
+    Secp256k1(BytesN<65>),
+}
+
+/// A passkey: a secp256r1 key held by a WebAuthn authenticator.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Secp256r1Key {
+    /// Credential id clients pass to the authenticator to pick this key.
+    pub key_id: Bytes,
+    /// SEC-1 uncompressed public key.
+    pub public_key: BytesN<65>,
+}
+
+impl Signer {
+    /// Id the signer is stored and referred to by wherever a signer key is
+    /// expected: the key itself, the sha256 of a contract's address, or the
+    /// sha256 of a secp256r1 or secp256k1 public key.
+    pub fn id(&self, env: &Env) -> BytesN<32> {
+        match self {
+            Signer::Ed25519(key) => key.clone(),
+            Signer::Contract(address) => env.crypto().sha256(&address.clone().to_xdr(env)).into(),
+            Signer::Secp256r1(key) => env.crypto().sha256(&key.public_key.clone().into()).into(),
+            Signer::Secp256k1(key) => env.crypto().sha256(&key.clone().into()).into(),
+        }
+    }
+}
+
+/// What happens when the signer set shrinks to exactly the threshold, where
+/// one lost key would leave the multisig unable to act.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RedundancyPolicy {
+    /// Allow it, but raise the `no_redundancy` flag and publish
+    /// `redundancy_lost`.
+    Warn,
+    /// Refuse removals that would cause it unless the removal proposal
+    /// explicitly accepts it.
+    Block,
+}
+
+/// The multisig's current signer configuration.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MultisigConfig {
+    pub signer_count: u32,
+    pub threshold: u32,
+    pub redundancy_policy: RedundancyPolicy,
+    /// Set while every signer is needed to reach the threshold.
+    pub no_redundancy: bool,
+}
+
+/// One signer as `ConfigSnapshot` lists it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigSigner {
+    pub id: BytesN<32>,
+    /// Address whose authorization the signer's calls need, if bound.
+    pub address: Option<Address>,
+    pub role: Option<Symbol>,
+    pub protected: bool,
+    /// Registered but barred from proposing and approving until reinstated.
+    pub suspended: bool,
+}
+
+/// One signer as `get_signers_detailed` lists it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerInfo {
+    pub key: BytesN<32>,
+    /// The signer's own name for their seat, if they set one.
+    pub label: Option<String>,
+    /// When the seat was added; signers present at initialization carry the
+    /// initialization time.
+    pub added_at: u64,
+}
+
+/// Everything `get_config_fingerprint` commits to. The fingerprint is the
+/// sha256 of this struct's XDR encoding, which is its canonical form: fields
+/// are encoded as a map keyed by field name, signers sorted by id, and
+/// `version` changes whenever a field is added or its meaning changes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigSnapshot {
+    pub version: u32,
+    /// Empty when a signer registry holds the signer set.
+    pub signers: Vec<ConfigSigner>,
+    pub threshold: u32,
+    pub signer_registry: Option<Address>,
+    pub allow_low_security: bool,
+    /// Approvals needed to change signer protection or remove a protected
+    /// signer.
+    pub protected_quorum: u32,
+    pub final_approver: Option<BytesN<32>>,
+    /// In the order governance set them.
+    pub role_requirements: Vec<RoleRequirement>,
+    /// Enabled features, sorted.
+    pub features: Vec<Symbol>,
+    pub execution_grace: u64,
+    pub cancel_grace: u64,
+    pub redundancy_policy: RedundancyPolicy,
+}
+
+/// How a proposal ended.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProposalOutcome {
+    Executed,
+    Expired,
+    /// Closed by strict rejection once enough signers rejected it that it
+    /// could no longer pass.
+    Rejected,
+    /// Canceled by governance, or by its proposer once the cancel grace ran
+    /// out without a restore.
+    Canceled,
+    /// Blocked by the guardian.
+    Vetoed,
+}
+
+/// Who may execute a proposal that has an `ExecutionSchedule`, in the order the
+/// tiers open.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExecutionTier {
+    /// Any signer, at any time.
+    Committee,
+    /// The schedule's named executors, from `fallback_at`.
+    Executor,
+    /// Anyone, from `public_at`.
+    Public,
+}
+
+/// Failover for a time-critical payment proposal: if the committee has not
+/// executed it by `fallback_at`, any of `executors` may, and from `public_at`
+/// anyone may. Timestamps are absolute ledger times.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecutionSchedule {
+    pub executors: Vec<Address>,
+    pub fallback_at: u64,
+    pub public_at: u64,
+}
+
+/// One change to a payment proposal: `created`, `approved`, `revoked`,
+/// `delegated`, `executed` or `expired`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditEntry {
+    pub seq: u64,
+    pub proposal_id: u64,
+    pub action: Symbol,
+    pub timestamp: u64,
+}
+
+/// Where a payment proposal stands.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProposalStatus {
+    /// Open and still short of its approval requirement.
+    Open,
+    /// Open with every approval it needs.
+    Ready,
+    Executed,
+    Expired,
+    Rejected,
+    /// Canceled by its proposer and not yet final.
+    CancelPending,
+    Canceled,
+    Vetoed,
+}
+
+/// A payment proposal as it stands now, with its approvals.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalState {
+    pub proposal: Proposal,
+    pub approvals: Vec<ProposalApproval>,
+    pub status: ProposalStatus,
+}
+
+/// Audit entries from a sequence number on and the current state of every
+/// proposal they touch. Pass `next_seq` back to continue.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChangesPage {
+    pub entries: Vec<AuditEntry>,
+    pub proposals: Vec<ProposalState>,
+    pub next_seq: u64,
+    pub has_more: bool,
+}
+
+/// A payment proposal its proposer canceled, which the committee may still
+/// restore until `restorable_until`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingCancellation {
+    pub canceled_by: BytesN<32>,
+    pub canceled_at: u64,
+    pub restorable_until: u64,
+    /// Signers who have co-signed restoring the proposal so far.
+    pub restore_votes: Vec<BytesN<32>>,
+}
+
+/// Written once, when a proposal of any family reaches a terminal state.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TerminalRecord {
+    pub outcome: ProposalOutcome,
+    pub actor: Option<BytesN<32>>,
+    pub timestamp: u64,
+}
+
+/// What a signer change does to the signer set.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SignerChangeKind {
+    Add,
+    Remove,
+    /// Keeps the signer's seat but stops them proposing or approving.
+    Suspend,
+    Reinstate,
+    /// Swaps `signer` for the key in `get_signer_replacement`.
+    Replace,
+    /// Applies the changes in `get_signer_batch` together.
+    Batch,
 }
 
 #[contracttype]
@@ -43,7 +724,57 @@ pub struct ProposalApproval {
 pub struct SignerChangeProposal {
     pub id: u64,
     pub proposer: BytesN<32>,
-    pub change_type: String, // "add" or "remove"
+    pub change_type: SignerChangeKind,
+    /// The signer changed. A batch names its proposer here instead.
+    pub signer: BytesN<32>,
+    pub created_at: u64,
+    pub expires_at: u64,
+    /// Last moment approvals are accepted; never later than `expires_at`.
+    pub approval_deadline: u64,
+    /// Earliest moment the change may execute, if it is scheduled.
+    pub execute_not_before: Option<u64>,
+    pub executed: bool,
+    /// Set on a removal that may leave exactly as many signers as the
+    /// threshold, which `RedundancyPolicy::Block` otherwise refuses.
+    pub accept_no_redundancy: bool,
+}
+
+/// Changes a "batch" signer change applies together: removals first, then
+/// additions, then the threshold, if it sets one.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerBatch {
+    pub additions: Vec<BytesN<32>>,
+    pub removals: Vec<BytesN<32>>,
+    pub threshold: Option<u32>,
+}
+
+/// Fewest and most signers the set may hold. Signer changes that would take
+/// the count outside these bounds fail to execute.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerPolicy {
+    pub min_signers: u32,
+    pub max_signers: u32,
+}
+
+/// What a signer may do. Signers without an entry may do both; one who may
+/// only propose does not count towards what the threshold can rely on.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SignerPermissions {
+    pub can_propose: bool,
+    pub can_approve: bool,
+}
+
+/// Signer-change layout from before approval deadlines and effective dates.
+/// Read back as a proposal whose deadline is its expiry.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacySignerChangeProposal {
+    pub id: u64,
+    pub proposer: BytesN<32>,
+    pub change_type: String,
     pub signer: BytesN<32>,
     pub created_at: u64,
     pub expires_at: u64,
@@ -56,3 +787,371 @@ pub struct SignerChangeApproval {
     pub signer: BytesN<32>,
     pub approved_at: u64,
 }
+
+/// One mutation of the signer set or threshold.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SignerSetChange {
+    Initialized(Vec<BytesN<32>>, u32),
+    Added(BytesN<32>),
+    Removed(BytesN<32>),
+    ThresholdChanged(u32),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerHistoryEntry {
+    pub seq: u64,
+    pub timestamp: u64,
+    pub change: SignerSetChange,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerSetSnapshot {
+    pub signers: Vec<BytesN<32>>,
+    pub threshold: u32,
+}
+
+/// A signer's standing hand-over of their approvals to `delegate`, for
+/// example while they are away. Approvals made with the delegate's key count
+/// as the signer's until `expires_at`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Delegation {
+    pub delegate: BytesN<32>,
+    pub delegated_at: u64,
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteDelegation {
+    pub delegator: BytesN<32>,
+    pub delegate: BytesN<32>,
+    pub delegated_at: u64,
+}
+
+/// What a governance proposal changes once executed, grouped by area.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GovernanceAction {
+    Signers(SignerAction),
+    Treasury(TreasuryAction),
+    Policy(PolicyAction),
+}
+
+/// Signer-set rules: roles, protections, keys, recovery and the threshold.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SignerAction {
+    SetSignerRole(SignerRoleAssignment),
+    ClearSignerRole(BytesN<32>),
+    SetRoleRequirements(Vec<RoleRequirement>),
+    ProtectSigner(BytesN<32>),
+    UnprotectSigner(BytesN<32>),
+    SetProtectedQuorum(u32),
+    SetFinalApprover(BytesN<32>),
+    ClearFinalApprover,
+    AddObserver(BytesN<32>),
+    RemoveObserver(BytesN<32>),
+    SetRedundancyPolicy(RedundancyPolicy),
+    /// Replaces a signer's key ring. An empty ring clears it.
+    SetKeyRing(KeyRing),
+    /// Lets `recovery_key` replace the signer set after a period without any
+    /// approval or execution.
+    SetRecovery(RecoveryConfig),
+    ClearRecovery,
+    /// Changes the threshold. Proposals already open keep the requirement
+    /// they were created with.
+    SetThreshold(u32),
+    /// Bounds the signer count. The current set must already be within it.
+    SetSignerPolicy(SignerPolicy),
+    ClearSignerPolicy,
+    /// Changes what a signer may do. A signer who can no longer approve
+    /// loses the approvals they had given.
+    SetSignerPermissions(BytesN<32>, SignerPermissions),
+    /// Lowers the approvals needed to remove a long-inactive signer.
+    SetInactivityRemoval(InactivityRemoval),
+}
+
+/// Rules on the funds: tokens, caps, reserves, recipients and rates.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TreasuryAction {
+    FreezeToken(Address),
+    UnfreezeToken(Address),
+    AddWatcher(WatcherRegistration),
+    RemoveWatcher(WatcherRegistration),
+    SetProposerCap(ProposerCap),
+    RemoveProposerCap(Address),
+    SetAllocationTargets(Vec<AllocationTarget>),
+    CreateTemplate(TemplateDraft),
+    DeactivateTemplate(u32),
+    AddAddressBookEntry(AddressLabel),
+    RemoveAddressBookEntry(u32),
+    SetTokenImplementation(TokenImplementation),
+    ClearTokenImplementation(Address),
+    SetReserveFloor(ReserveFloor),
+    ClearReserveFloor(Address),
+    RequireMemo(Address),
+    ClearMemoRequirement(Address),
+    SetDocumentThreshold(DocumentThreshold),
+    ClearDocumentThreshold(Address),
+    SetExchangeRate(ExchangeRate),
+    ClearExchangeRate(Address),
+    SetMaxRateAge(u64),
+    SetValueCap(ValueCap),
+    RemoveValueCap,
+    /// Reference-unit value above which a payment in any token must reference
+    /// a document hash.
+    SetDocumentValueThreshold(i128),
+    ClearDocumentValueThreshold,
+}
+
+/// Contract-wide switches: features, grace periods, pausing, retirement and the guardian.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PolicyAction {
+    EnableFeature(Symbol),
+    DisableFeature(Symbol),
+    SetExecutionGrace(u64),
+    SetCancelGrace(u64),
+    /// Closes an open payment proposal as `Canceled` at once, with no grace.
+    CancelProposal(u64),
+    /// Stops payment proposals from being created, approved or executed, for
+    /// example while a compromised key is rotated out. Signer changes and
+    /// governance carry on.
+    Pause,
+    Unpause,
+    /// Sweeps the listed tokens to the successor and retires the contract
+    /// for good.
+    Retire(Retirement),
+    /// Names the address that may veto payment proposals with
+    /// `veto_proposal`. It cannot approve or move anything.
+    SetGuardian(Address),
+    ClearGuardian,
+}
+
+/// Balance of `token` that no outflow may touch.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReserveFloor {
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// Amount of `token` above which a payment proposal must reference a
+/// document hash.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DocumentThreshold {
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// Contract that actually authenticates holders behind `token`, for tokens
+/// that forward their entry points to another contract.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenImplementation {
+    pub token: Address,
+    pub implementation: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddressLabel {
+    pub label: Symbol,
+    pub address: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddressBookEntry {
+    pub id: u32,
+    pub label: Symbol,
+    pub address: Address,
+}
+
+/// Fields of a template before governance assigns it an id.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TemplateDraft {
+    pub name: String,
+    pub token: Address,
+    pub recipient: Address,
+}
+
+/// A stored recurring payment: everything but the amount is fixed, so
+/// proposals created from it cannot mistype the recipient.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalTemplate {
+    pub id: u32,
+    pub name: String,
+    pub token: Address,
+    pub recipient: Address,
+    pub active: bool,
+}
+
+/// Desired share of the treasury held in `token`, as a weight relative to the
+/// other targets. `price_hint` converts raw units into a common value (scaled
+/// by `PRICE_SCALE`); without one, raw units are compared directly.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllocationTarget {
+    pub token: Address,
+    pub weight: u32,
+    pub price_hint: Option<i128>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllocationEntry {
+    pub token: Address,
+    pub balance: i128,
+    pub value: i128,
+    pub target_bps: u32,
+    pub actual_bps: u32,
+    pub drift_bps: i32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllocationReport {
+    pub total_value: i128,
+    pub entries: Vec<AllocationEntry>,
+}
+
+/// Most of `token` any one signer may propose moving per `window_seconds`,
+/// however the proposals are later voted on.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposerCap {
+    pub token: Address,
+    pub amount: i128,
+    pub window_seconds: u64,
+}
+
+/// Future keys `signer` may switch to on their own with `activate_ring_key`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeyRing {
+    pub signer: BytesN<32>,
+    pub keys: Vec<BytesN<32>>,
+}
+
+/// Where a retiring multisig hands its treasury: the whole balance of each of
+/// `tokens` goes to `successor`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Retirement {
+    pub successor: Address,
+    pub tokens: Vec<Address>,
+}
+
+/// A key that may replace the whole signer set with `recovery_claim` once
+/// more than `inactivity_seconds` pass without any approval or execution.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryConfig {
+    pub recovery_key: BytesN<32>,
+    pub inactivity_seconds: u64,
+}
+
+/// Removing a signer who has neither proposed nor approved anything for more
+/// than `inactivity_seconds` needs only `threshold` approvals. Zero
+/// `inactivity_seconds` turns this off.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InactivityRemoval {
+    pub inactivity_seconds: u64,
+    pub threshold: u32,
+}
+
+/// An open proposal carried over from a legacy multisig by `import_proposal`.
+/// `external_ref` is its id there; `approvals` are the signers who had
+/// approved it, all of whom must be signers here.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ImportedProposal {
+    pub external_ref: u64,
+    pub proposer: BytesN<32>,
+    pub action: ProposalAction,
+    pub reason: String,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub destination_memo: Option<String>,
+    pub document_hash: Option<BytesN<32>>,
+    pub approvals: Vec<BytesN<32>>,
+}
+
+/// Most any one signer may propose moving per `window_seconds` across every
+/// token, in the reference unit exchange rates are quoted against.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValueCap {
+    pub amount: i128,
+    pub window_seconds: u64,
+}
+
+/// What one base unit of `token` is worth in the reference unit, as
+/// `numerator / denominator`, quoted at `as_of`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExchangeRate {
+    pub token: Address,
+    pub numerator: i128,
+    pub denominator: i128,
+    pub as_of: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposerUsage {
+    pub window_start: u64,
+    pub proposed: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerRoleAssignment {
+    pub signer: BytesN<32>,
+    pub role: Symbol,
+}
+
+/// In staged-approval mode, at least `count` approvals must come from signers
+/// holding `role`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleRequirement {
+    pub role: Symbol,
+    pub count: u32,
+}
+
+/// A contract to notify whenever a payment in `token` executes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WatcherRegistration {
+    pub token: Address,
+    pub watcher: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceProposal {
+    pub id: u64,
+    pub proposer: BytesN<32>,
+    pub action: GovernanceAction,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub executed: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceApproval {
+    pub signer: BytesN<32>,
+    pub approved_at: u64,
+}
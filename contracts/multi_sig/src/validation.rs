@@ -0,0 +1,128 @@
+use soroban_sdk::{panic_with_error, BytesN, Env};
+
+use crate::errors::{GovernanceError, MultisigError};
+use crate::schema;
+use crate::storage;
+
+/// Shortest lifetime a proposal of any kind may be given (1 hour).
+pub const MIN_EXPIRY_SECONDS: u64 = 3600;
+/// Longest lifetime a proposal of any kind may be given (30 days).
+pub const MAX_EXPIRY_SECONDS: u64 = 2_592_000;
+/// Longest a fully approved proposal may stay executable past its expiry (1 day).
+pub const MAX_EXECUTION_GRACE_SECONDS: u64 = 86_400;
+/// How long a proposer's cancellation stays restorable unless governance
+/// sets another grace (1 day).
+pub const DEFAULT_CANCEL_GRACE_SECONDS: u64 = 86_400;
+/// Longest cancel grace governance may set (7 days).
+pub const MAX_CANCEL_GRACE_SECONDS: u64 = 604_800;
+/// Co-signers restoring a canceled proposal takes, or the threshold if lower.
+pub const RESTORE_COSIGNERS: u32 = 2;
+
+/// Largest amount a single proposal may move or approve. Far above any real
+/// token supply, and low enough that 65 536 such amounts can be summed in an
+/// `i128` without overflowing.
+pub const MAX_AMOUNT: i128 = i128::MAX >> 16;
+
+/// Longest destination memo a transfer proposal may carry, in bytes.
+pub const MAX_MEMO_LEN: u32 = 64;
+
+/// Fewest signers a deployment may have unless it opted out at initialize.
+pub const MIN_SECURE_SIGNERS: u32 = 2;
+/// Lowest threshold a deployment may have unless it opted out at initialize.
+pub const MIN_SECURE_THRESHOLD: u32 = 2;
+
+// The `check_*` functions report a broken rule without panicking, for views
+// that collect every violation; the `validate_*` / `require_*` forms raise it.
+
+pub fn check_expiry(expires_in_seconds: u64) -> Result<(), MultisigError> {
+    if !(MIN_EXPIRY_SECONDS..=MAX_EXPIRY_SECONDS).contains(&expires_in_seconds) {
+        return Err(MultisigError::InvalidExpiryTime);
+    }
+    Ok(())
+}
+
+pub fn validate_expiry(env: &Env, expires_in_seconds: u64) {
+    raise(env, check_expiry(expires_in_seconds));
+}
+
+pub fn validate_execution_grace(env: &Env, grace_seconds: u64) {
+    if grace_seconds > MAX_EXECUTION_GRACE_SECONDS {
+        panic_with_error!(env, MultisigError::InvalidExpiryTime);
+    }
+}
+
+pub fn validate_cancel_grace(env: &Env, grace_seconds: u64) {
+    if grace_seconds > MAX_CANCEL_GRACE_SECONDS {
+        panic_with_error!(env, MultisigError::InvalidExpiryTime);
+    }
+}
+
+pub fn require_initialized(env: &Env) {
+    if !storage::is_initialized(env) {
+        panic_with_error!(env, MultisigError::NotInitialized);
+    }
+    schema::require_schema(env);
+}
+
+/// `require_initialized` for calls that change state, which a retired
+/// contract refuses with `ContractRetired`.
+pub fn require_active(env: &Env) {
+    require_initialized(env);
+    if storage::read_successor(env).is_some() {
+        panic_with_error!(env, GovernanceError::ContractRetired);
+    }
+}
+
+/// Fails with `ContractPaused` while governance has payments paused.
+pub fn require_not_paused(env: &Env) {
+    if storage::is_paused(env) {
+        panic_with_error!(env, GovernanceError::ContractPaused);
+    }
+}
+
+pub fn check_signer(env: &Env, key: &BytesN<32>) -> Result<(), MultisigError> {
+    if !storage::is_signer(env, key) {
+        return Err(MultisigError::UnknownSigner);
+    }
+    Ok(())
+}
+
+pub fn require_signer(env: &Env, key: &BytesN<32>) {
+    raise(env, check_signer(env, key));
+}
+
+pub fn check_amount(amount: i128) -> Result<(), MultisigError> {
+    if amount <= 0 {
+        return Err(MultisigError::InvalidProposal);
+    }
+    check_amount_ceiling(amount)
+}
+
+pub fn check_amount_ceiling(amount: i128) -> Result<(), MultisigError> {
+    if amount > MAX_AMOUNT {
+        return Err(MultisigError::AmountTooLarge);
+    }
+    Ok(())
+}
+
+/// Raises the error of a failed check.
+pub fn raise<E: Into<soroban_sdk::Error>>(env: &Env, check: Result<(), E>) {
+    if let Err(error) = check {
+        panic_with_error!(env, error.into());
+    }
+}
+
+pub fn validate_security_floor(env: &Env, signer_count: u32, threshold: u32, allow_low_security: bool) {
+    if allow_low_security {
+        return;
+    }
+    if signer_count < MIN_SECURE_SIGNERS || threshold < MIN_SECURE_THRESHOLD {
+        panic_with_error!(env, MultisigError::InsecureConfiguration);
+    }
+}
+
+/// Checks a prospective signer count and threshold against the floor the
+/// deployment was initialized with.
+pub fn require_security_floor(env: &Env, signer_count: u32, threshold: u32) {
+    validate_security_floor(env, signer_count, threshold, storage::allows_low_security(env));
+}
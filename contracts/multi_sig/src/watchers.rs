@@ -0,0 +1,56 @@
+use soroban_sdk::{panic_with_error, Env, IntoVal, InvokeError, Symbol, Val, Vec};
+
+use crate::errors::GovernanceError;
+use crate::events::WatcherFailed;
+use crate::storage;
+use crate::types::{TransferAction, WatcherRegistration};
+
+/// Most watchers a single token may have, bounding the work added to every
+/// execution in that token.
+pub const MAX_WATCHERS_PER_TOKEN: u32 = 5;
+
+pub fn add_watcher(env: &Env, registration: &WatcherRegistration) {
+    let mut watchers = storage::read_token_watchers(env, &registration.token);
+
+    if watchers.contains(&registration.watcher) {
+        panic_with_error!(env, GovernanceError::WatcherAlreadyRegistered);
+    }
+    if watchers.len() >= MAX_WATCHERS_PER_TOKEN {
+        panic_with_error!(env, GovernanceError::TooManyWatchers);
+    }
+
+    watchers.push_back(registration.watcher.clone());
+    storage::write_token_watchers(env, &registration.token, &watchers);
+}
+
+pub fn remove_watcher(env: &Env, registration: &WatcherRegistration) {
+    let mut watchers = storage::read_token_watchers(env, &registration.token);
+
+    let Some(index) = watchers.first_index_of(&registration.watcher) else {
+        panic_with_error!(env, GovernanceError::WatcherNotFound);
+    };
+
+    watchers.remove(index);
+    storage::write_token_watchers(env, &registration.token, &watchers);
+}
+
+/// Tells every watcher of the transferred token that `proposal_id` executed.
+/// Each call is isolated: a watcher that traps or returns an error is reported
+/// through a `watcher_failed` event and never reverts the payment.
+pub fn notify_executed(env: &Env, proposal_id: u64, transfer: &TransferAction) {
+    let function = Symbol::new(env, "on_proposal_executed");
+    let args: Vec<Val> = (
+        proposal_id,
+        transfer.token_address.clone(),
+        transfer.recipient.clone(),
+        transfer.amount,
+    )
+        .into_val(env);
+
+    for watcher in storage::read_token_watchers(env, &transfer.token_address).iter() {
+        let result = env.try_invoke_contract::<Val, InvokeError>(&watcher, &function, args.clone());
+        if result.is_err() {
+            WatcherFailed { proposal_id, watcher }.publish(env);
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use soroban_sdk::{panic_with_error, Bytes, BytesN, Env};
+
+use crate::errors::SignerError;
+use crate::types::Secp256r1Key;
+
+/// Longest client data JSON an assertion may carry.
+pub const MAX_CLIENT_DATA_LEN: u32 = 1024;
+
+const TYPE_GET: &[u8] = br#""type":"webauthn.get""#;
+const CHALLENGE_KEY: &[u8] = br#""challenge":""#;
+// Unpadded base64url of a 32-byte challenge
+const CHALLENGE_LEN: usize = 43;
+const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+// A WebAuthn authenticator never signs the challenge directly. It signs its
+// authenticator data followed by the sha256 of a client data JSON, which holds
+// the challenge base64url-encoded, so both have to be relayed alongside the
+// signature and the challenge found in the JSON.
+
+/// Checks that `signature` is `passkey`'s assertion over `challenge`. Client
+/// data that is too long, is not a `webauthn.get` or has no challenge fails
+/// with `MalformedClientData`; a challenge other than `challenge` fails with
+/// `ChallengeMismatch`. A signature that does not verify aborts in the host.
+pub fn verify(
+    env: &Env,
+    passkey: &Secp256r1Key,
+    challenge: &BytesN<32>,
+    authenticator_data: &Bytes,
+    client_data_json: &Bytes,
+    signature: &BytesN<64>,
+) {
+    let len = client_data_json.len();
+    if len > MAX_CLIENT_DATA_LEN {
+        panic_with_error!(env, SignerError::MalformedClientData);
+    }
+    let mut buffer = [0u8; MAX_CLIENT_DATA_LEN as usize];
+    let json = &mut buffer[..len as usize];
+    client_data_json.copy_into_slice(json);
+
+    if find(json, TYPE_GET).is_none() {
+        panic_with_error!(env, SignerError::MalformedClientData);
+    }
+    let Some(start) = find(json, CHALLENGE_KEY).map(|at| at + CHALLENGE_KEY.len()) else {
+        panic_with_error!(env, SignerError::MalformedClientData);
+    };
+    let Some(end) = json[start..].iter().position(|&byte| byte == b'"') else {
+        panic_with_error!(env, SignerError::MalformedClientData);
+    };
+    if json[start..start + end] != base64url(&challenge.to_array()) {
+        panic_with_error!(env, SignerError::ChallengeMismatch);
+    }
+
+    let mut signed = authenticator_data.clone();
+    signed.extend_from_array(&env.crypto().sha256(client_data_json).to_array());
+    env.crypto().secp256r1_verify(&passkey.public_key, &env.crypto().sha256(&signed), signature);
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+/// `bytes` base64url-encoded without padding, as WebAuthn embeds challenges.
+pub fn base64url(bytes: &[u8; 32]) -> [u8; CHALLENGE_LEN] {
+    let mut encoded = [0u8; CHALLENGE_LEN];
+    let (mut buffered, mut bits, mut next) = (0u32, 0u32, 0usize);
+    for &byte in bytes {
+        buffered = (buffered << 8) | byte as u32;
+        bits += 8;
+        while bits >= 6 {
+            bits -= 6;
+            encoded[next] = BASE64URL[((buffered >> bits) & 0x3f) as usize];
+            next += 1;
+        }
+    }
+    if bits > 0 {
+        encoded[next] = BASE64URL[((buffered << (6 - bits)) & 0x3f) as usize];
+    }
+    encoded
+}
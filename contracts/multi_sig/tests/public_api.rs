@@ -0,0 +1,61 @@
+// Compiles only while every item embedders rely on stays reachable through
+// its public path. A failure here is a breaking change to the crate's API.
+
+use multi_sig::events::{
+    ConfigFingerprintChanged, ExecutionBlocked, Funded, FundsPulled, NoteAdded, PauseChanged, PaymentExecuted, ProposalCanceled, ProposalReady, ProposalRejected, ProposalRestored, ProposalUnready, ProposalVetoed, RecoveryClaimed, RedundancyLost, RedundancyRestored, Retired,
+    ProposalImported, RingKeyActivated, ScheduledExecution, SignerChangeCanceled, SignerImported, SignerKeyRotated, SignerLabelChanged, ThresholdChanged, TokenTransferFailed, WatcherFailed,
+};
+use multi_sig::prelude::*;
+use multi_sig::{
+    AddressBookEntry, AddressLabel, AllocationEntry, AllocationReport, AllocationTarget, AllowanceAction, AuditEntry, ChangesPage, ConfigSigner, ConfigSnapshot, Delegation, Deposit, DocumentThreshold, ExchangeRate, ExecutionReceipt, ExecutionRecord, ExecutionSchedule, ExecutionTier, ExecutionVerdict,
+    GovernanceApproval, GovernanceProposal, ImportedProposal, InactivityRemoval, InitialFunding, InvokeAction, KeyRing, MultisigConfig, Obligation, PaymentProof, PendingCancellation, PolicyAction, PolicyCheck, ProposalApproval, ProposalNote, ProposalPage, ProposalState, ProposalStatus,
+    ProposalTemplate, ProposerCap, RecoveryConfig, RedundancyPolicy, ReserveFloor, Retirement, RoleProgress, RoleRequirement, Secp256r1Key, SessionApprovalReport, Signer, SignerChangeApproval, SignerHistoryEntry,
+    SignerAction, SignerBatch, SignerChangeKind, SignerInfo, SignerPermissions, SignerPolicy, SignerRegistry, SignerRegistryClient, SignerRoleAssignment, SigningSession, SignerSetChange, SignerSetSnapshot, StorageSchema, TemplateDraft,
+    TerminalRecord, TokenImplementation, TreasuryAction, UnifiedProposal, UnifiedProposalPage, ValueCap, VoteDelegation, WatcherRegistration,
+};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Vec};
+
+#[allow(dead_code)]
+type Types = (
+    (AddressBookEntry, AddressLabel, AllocationEntry, AllocationReport, AllocationTarget, AllowanceAction, AuditEntry, ChangesPage, Deposit, ExecutionReceipt),
+    (GovernanceApproval, GovernanceProposal, InitialFunding, InvokeAction, MultisigConfig, Obligation, PaymentProof, ProposalApproval, ProposalNote, ProposalPage, ProposalState, ProposalStatus),
+    (ProposalTemplate, ProposerCap, RedundancyPolicy, ReserveFloor, RoleProgress, RoleRequirement, SignerChangeApproval, SignerHistoryEntry),
+    (Secp256r1Key, SessionApprovalReport, Signer, SigningSession, SignerRoleAssignment, SignerSetChange, SignerSetSnapshot, StorageSchema, TemplateDraft, TerminalRecord),
+    (ConfigSigner, ConfigSnapshot, DocumentThreshold, ExecutionSchedule, ExecutionTier, TokenImplementation, UnifiedProposal, UnifiedProposalPage, VoteDelegation, WatcherRegistration),
+    (ExecutionBlocked, Funded, FundsPulled, NoteAdded, PaymentExecuted, ProposalReady, ProposalUnready, RedundancyLost, RedundancyRestored, ScheduledExecution, TokenTransferFailed, WatcherFailed),
+    (ApprovalProgress, GovernanceAction, SignerChangeProposal, ProposalOutcome, ProposalRejected, ConfigFingerprintChanged, PendingCancellation, ProposalCanceled, ProposalRestored, Delegation, SignerKeyRotated),
+    (ExchangeRate, ValueCap, ImportedProposal, ProposalImported, SignerImported, KeyRing, RingKeyActivated, PolicyCheck, ExecutionVerdict, ExecutionRecord, PauseChanged),
+    (Retirement, Retired, ProposalVetoed, RecoveryConfig, RecoveryClaimed, ThresholdChanged, SignerBatch, SignerChangeKind, SignerChangeCanceled, SignerInfo, SignerLabelChanged),
+    (SignerPolicy, SignerPermissions, InactivityRemoval),
+    (SignerError, GovernanceError, SignerAction, TreasuryAction, PolicyAction),
+);
+
+#[allow(dead_code)]
+fn registry_client<'a>(env: &'a Env, id: &'a Address) -> SignerRegistryClient<'a> {
+    SignerRegistryClient::new(env, id)
+}
+
+#[allow(dead_code)]
+fn registry_interface<T: SignerRegistry>() {}
+
+#[test]
+fn test_public_api_drives_the_contract() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MultiSigContract, ());
+    let client = MultiSigContractClient::new(&env, &contract_id);
+
+    let signers = Vec::from_array(&env, [BytesN::from_array(&env, &[1u8; 32]), BytesN::from_array(&env, &[2u8; 32])]);
+    client.initialize(&signers, &2, &false);
+
+    let action = ProposalAction::Transfer(TransferAction {
+        token_address: Address::generate(&env),
+        recipient: Address::generate(&env),
+        amount: 100,
+    });
+    let proposal_id = client.create_proposal_v2(&signers.get_unchecked(0), &action, &String::from_str(&env, "Payment"), &3600);
+
+    let proposal: Proposal = client.get_proposal(&proposal_id);
+    assert_eq!(proposal.action, action);
+    assert_eq!(client.try_execute_proposal(&proposal_id), Err(Ok(MultisigError::InsufficientApprovals.into())));
+}
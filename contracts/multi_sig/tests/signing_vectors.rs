@@ -0,0 +1,93 @@
+// Canonical vectors for the signing scheme, for wallets and SDKs building
+// approvals off-chain. Everything below is fixed: the contract and token
+// addresses, the ledger time, the proposal and the ed25519 seeds. With
+//
+//   proposal_hash = sha256(xdr((contract, content_hash, nonce)))
+//   payload       = sha256(xdr((proposal_hash, signer_nonce)))
+//
+// the hashes and signatures must come out exactly as listed. A change to any
+// of them changes the wire format, so it has to be deliberate.
+
+use ed25519_dalek::{Signer as _, SigningKey};
+use multi_sig::prelude::*;
+use soroban_sdk::{testutils::Ledger as _, Address, BytesN, Env, String, Vec};
+
+const CONTRACT: &str = "CAAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQC526";
+const TOKEN: &str = "CABAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAFNSZ";
+const RECIPIENT: &str = "GABQGAYDAMBQGAYDAMBQGAYDAMBQGAYDAMBQGAYDAMBQGAYDAMBQHGPC";
+const NOW: u64 = 1_700_000_000;
+
+const CONTENT_HASH: &str = "5ece4e9dc74e20f21b4dc59392e30cc0f5518e7cffa341ff459acc1837025f13";
+const PROPOSAL_HASH: &str = "dadaeff00e710972f45b305e3ff3ecd27a418e6c26541619e2a275d8bd4010fe";
+const PAYLOAD_NONCE_0: &str = "6213db3687ae0c078c9f3d61dc890e63ab65b80ac222abb6aaf5aa27933cc748";
+const PAYLOAD_NONCE_1: &str = "994e10dc3bcea5c43ab248478df38ddfb65e5b133b6a56f00ac04e760249194f";
+const SIGNATURE_SEED_1: &str = "78d97aba18a2fc398f78ec6f87214b654644eb5742410fea758451b4b9bcb7018ce78de2db624db1fdea4a128afe70ff6c14c9f3d90f908b998d0debacafb305";
+const SIGNATURE_SEED_2: &str = "06a71b9dc7ccf7e0628603d946a51acf380822aa99a7fba550e5affb195402584470e9cc13f95a8c128fb36f8220f9df73f5464d788775b095f0dd4312e45b09";
+
+fn hex(bytes: &[u8]) -> std::string::String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn signing_key(seed: u8) -> SigningKey {
+    SigningKey::from_bytes(&[seed; 32])
+}
+
+fn public_key(env: &Env, key: &SigningKey) -> BytesN<32> {
+    BytesN::from_array(env, &key.verifying_key().to_bytes())
+}
+
+fn setup(env: &Env) -> (MultiSigContractClient<'_>, u64) {
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = NOW);
+    let contract_id = env.register_at(&Address::from_str(env, CONTRACT), MultiSigContract, ());
+    let client = MultiSigContractClient::new(env, &contract_id);
+
+    let signers = Vec::from_array(env, [public_key(env, &signing_key(1)), public_key(env, &signing_key(2))]);
+    client.initialize(&signers, &2, &false);
+
+    let action = ProposalAction::Transfer(TransferAction {
+        token_address: Address::from_str(env, TOKEN),
+        recipient: Address::from_str(env, RECIPIENT),
+        amount: 10_000_000_000,
+    });
+    let proposal_id = client.create_proposal_v2(&signers.get_unchecked(0), &action, &String::from_str(env, "Invoice 42"), &86_400);
+    (client, proposal_id)
+}
+
+#[test]
+fn test_proposal_hash_vector() {
+    let env = Env::default();
+    let (client, proposal_id) = setup(&env);
+
+    assert_eq!(hex(&client.get_proposal(&proposal_id).content_hash(&env).to_array()), CONTENT_HASH);
+    assert_eq!(hex(&client.get_proposal_hash(&proposal_id).to_array()), PROPOSAL_HASH);
+}
+
+#[test]
+fn test_approval_signature_vectors() {
+    let env = Env::default();
+    let (client, proposal_id) = setup(&env);
+    let (first, second) = (signing_key(1), signing_key(2));
+
+    let payload = client.get_approval_payload(&proposal_id, &public_key(&env, &first)).to_array();
+    assert_eq!(hex(&payload), PAYLOAD_NONCE_0);
+    let signature = first.sign(&payload).to_bytes();
+    assert_eq!(hex(&signature), SIGNATURE_SEED_1);
+
+    // The contract accepts the vector signature once, at the nonce it was made at
+    let signature = BytesN::from_array(&env, &signature);
+    client.approve_proposal_signed(&proposal_id, &public_key(&env, &first), &0, &signature);
+    assert_eq!(
+        client.try_approve_proposal_signed(&proposal_id, &public_key(&env, &first), &0, &signature),
+        Err(Ok(MultisigError::InvalidNonce.into()))
+    );
+    assert_eq!(hex(&client.get_approval_payload(&proposal_id, &public_key(&env, &first)).to_array()), PAYLOAD_NONCE_1);
+
+    // The payload depends on the signer only through their nonce
+    let payload = client.get_approval_payload(&proposal_id, &public_key(&env, &second)).to_array();
+    assert_eq!(hex(&payload), PAYLOAD_NONCE_0);
+    let signature = second.sign(&payload).to_bytes();
+    assert_eq!(hex(&signature), SIGNATURE_SEED_2);
+    client.approve_proposal_signed(&proposal_id, &public_key(&env, &second), &0, &BytesN::from_array(&env, &signature));
+    assert!(client.get_approval_progress(&proposal_id).ready);
+}
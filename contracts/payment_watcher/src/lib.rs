@@ -0,0 +1,54 @@
+#![no_std]
+//! Sample watcher for the multisig. Register it for a token through an
+//! `AddWatcher` governance proposal and it will keep a log of every payment the
+//! multisig executes in that token.
+
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecutedPayment {
+    pub proposal_id: u64,
+    pub token: Address,
+    pub recipient: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    Multisig,
+    Payments,
+}
+
+#[contract]
+pub struct PaymentWatcher;
+
+#[contractimpl]
+impl PaymentWatcher {
+    pub fn __constructor(env: Env, multisig: Address) {
+        env.storage().instance().set(&DataKey::Multisig, &multisig);
+    }
+
+    /// Called by the multisig after it executes a payment. Only the multisig
+    /// this watcher was deployed for may report payments.
+    pub fn on_proposal_executed(env: Env, proposal_id: u64, token: Address, recipient: Address, amount: i128) {
+        let multisig: Address = env.storage().instance().get(&DataKey::Multisig).unwrap();
+        multisig.require_auth();
+
+        let mut payments = Self::payments(env.clone());
+        payments.push_back(ExecutedPayment {
+            proposal_id,
+            token,
+            recipient,
+            amount,
+        });
+        env.storage().instance().set(&DataKey::Payments, &payments);
+    }
+
+    pub fn payments(env: Env) -> Vec<ExecutedPayment> {
+        env.storage().instance().get(&DataKey::Payments).unwrap_or(Vec::new(&env))
+    }
+}
+
+mod test;
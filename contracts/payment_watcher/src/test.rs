@@ -0,0 +1,35 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+#[test]
+fn test_records_payment_reported_by_multisig() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let multisig = Address::generate(&env);
+    let contract_id = env.register(PaymentWatcher, (&multisig,));
+    let client = PaymentWatcherClient::new(&env, &contract_id);
+
+    let token = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    client.on_proposal_executed(&7, &token, &recipient, &500);
+
+    let payments = client.payments();
+    assert_eq!(payments.len(), 1);
+    assert_eq!(
+        payments.get_unchecked(0),
+        ExecutedPayment { proposal_id: 7, token, recipient, amount: 500 }
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_rejects_reports_from_others() {
+    let env = Env::default();
+    let multisig = Address::generate(&env);
+    let contract_id = env.register(PaymentWatcher, (&multisig,));
+    let client = PaymentWatcherClient::new(&env, &contract_id);
+
+    client.on_proposal_executed(&7, &Address::generate(&env), &Address::generate(&env), &500);
+}
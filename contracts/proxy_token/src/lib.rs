@@ -0,0 +1,45 @@
+#![no_std]
+//! Sample token that forwards every call to an implementation contract, the
+//! way upgradeable token proxies do. Holders are authenticated by the
+//! implementation, one call below the one they make, so a contract holding
+//! this token must pre-authorize the forwarded call. For the multisig, that is
+//! a `SetTokenImplementation` governance proposal naming the implementation.
+
+use soroban_sdk::{contract, contractimpl, contracttype, token, Address, Env};
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    Implementation,
+}
+
+#[contract]
+pub struct ProxyToken;
+
+#[contractimpl]
+impl ProxyToken {
+    pub fn __constructor(env: Env, implementation: Address) {
+        env.storage().instance().set(&DataKey::Implementation, &implementation);
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        Self::implementation(&env).transfer(&from, &to, &amount);
+    }
+
+    pub fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        Self::implementation(&env).approve(&from, &spender, &amount, &expiration_ledger);
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        Self::implementation(&env).balance(&id)
+    }
+}
+
+impl ProxyToken {
+    fn implementation(env: &Env) -> token::Client<'_> {
+        let implementation: Address = env.storage().instance().get(&DataKey::Implementation).unwrap();
+        token::Client::new(env, &implementation)
+    }
+}
+
+mod test;
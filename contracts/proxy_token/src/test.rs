@@ -0,0 +1,22 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, Address, Env};
+
+#[test]
+fn test_forwards_to_implementation() {
+    let env = Env::default();
+    // The holder is authenticated by the implementation, below the root call
+    env.mock_all_auths_allowing_non_root_auth();
+    let implementation = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    let contract_id = env.register(ProxyToken, (&implementation,));
+    let client = ProxyTokenClient::new(&env, &contract_id);
+
+    let holder = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    StellarAssetClient::new(&env, &implementation).mint(&holder, &1000);
+
+    client.transfer(&holder, &recipient, &400);
+    assert_eq!(client.balance(&holder), 600);
+    assert_eq!(client.balance(&recipient), 400);
+}